@@ -1,42 +1,109 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::model::{CacheStats, LoadedImage};
+
+/// Values stored in an [`LruCache`] report their own size for budgeting.
+pub(super) trait CacheWeight {
+    fn weight(&self) -> usize;
+}
+
+impl CacheWeight for Vec<u8> {
+    fn weight(&self) -> usize {
+        self.len()
+    }
+}
+
+impl CacheWeight for LoadedImage {
+    fn weight(&self) -> usize {
+        self.pixels.len()
+    }
+}
+
+impl<T: CacheWeight> CacheWeight for Arc<T> {
+    fn weight(&self) -> usize {
+        T::weight(self)
+    }
+}
 
 #[derive(Debug)]
-pub(super) struct CachedBytes {
-    pub data: Vec<u8>,
+pub(super) struct CachedEntry<T> {
+    pub data: T,
     pub bytes: usize,
     pub last_used: u64,
 }
 
 #[derive(Debug)]
-pub(super) struct ByteCache {
-    entries: HashMap<String, CachedBytes>,
+pub(super) struct LruCache<T> {
+    entries: HashMap<String, CachedEntry<T>>,
     usage_counter: u64,
     current_bytes: usize,
     max_bytes: usize,
+    hits: u64,
+    misses: u64,
 }
 
-impl ByteCache {
+pub(super) type ByteCache = LruCache<Vec<u8>>;
+/// Decoded images are shared, so a cache hit never copies the pixel buffer.
+pub(super) type ImageCache = LruCache<Arc<LoadedImage>>;
+
+impl<T: Clone + CacheWeight> LruCache<T> {
     pub(super) fn new(max_bytes: usize) -> Self {
         Self {
             entries: HashMap::new(),
             usage_counter: 0,
             current_bytes: 0,
             max_bytes,
+            hits: 0,
+            misses: 0,
         }
     }
 
-    pub(super) fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+    pub(super) fn get(&mut self, key: &str) -> Option<T> {
         self.usage_counter = self.usage_counter.wrapping_add(1);
-        self.entries.get_mut(key).map(|entry| {
+        let found = self.entries.get_mut(key).map(|entry| {
             entry.last_used = self.usage_counter;
             entry.data.clone()
-        })
+        });
+        if found.is_some() {
+            self.hits = self.hits.saturating_add(1);
+        } else {
+            self.misses = self.misses.saturating_add(1);
+        }
+        found
+    }
+
+    pub(super) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            current_bytes: self.current_bytes,
+            max_bytes: self.max_bytes,
+            entries: self.entries.len(),
+        }
+    }
+
+    /// Drops `key` if cached and returns its size.
+    pub(super) fn remove(&mut self, key: &str) -> Option<usize> {
+        let entry = self.entries.remove(key)?;
+        self.current_bytes = self.current_bytes.saturating_sub(entry.bytes);
+        Some(entry.bytes)
+    }
+
+    /// Zeroes the hit/miss counters; cached entries are kept.
+    pub(super) fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
     }
 
-    pub(super) fn insert(&mut self, key: String, data: Vec<u8>) {
-        let bytes = data.len();
+    /// Inserts `data`, evicting least-recently-used entries to fit the
+    /// budget. Returns the `(key, bytes)` of each entry evicted; replacing an
+    /// existing key does not count as an eviction.
+    pub(super) fn insert(&mut self, key: String, data: T) -> Vec<(String, usize)> {
+        let mut evicted = Vec::new();
+        let bytes = data.weight();
         if bytes > self.max_bytes {
-            return;
+            return evicted;
         }
 
         self.usage_counter = self.usage_counter.wrapping_add(1);
@@ -56,16 +123,18 @@ impl ByteCache {
             };
             self.entries.remove(&evict_key);
             self.current_bytes = self.current_bytes.saturating_sub(evict_bytes);
+            evicted.push((evict_key, evict_bytes));
         }
 
         self.entries.insert(
             key,
-            CachedBytes {
+            CachedEntry {
                 data,
                 bytes,
                 last_used: self.usage_counter,
             },
         );
         self.current_bytes = self.current_bytes.saturating_add(bytes);
+        evicted
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::helpers::{
     infer_asset_kind, is_allowed_by_extension, normalize_asset_key, sha256_file_and_size,
@@ -18,49 +18,51 @@ pub struct AssetFingerprintCatalog {
 
 impl AssetFingerprintCatalog {
     pub fn build(root: &Path, allowed_extensions: &[&str]) -> Result<Self, AssetError> {
-        let mut entries = BTreeMap::new();
-        let mut dedup_groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
-        let allowed: HashSet<String> = allowed_extensions
-            .iter()
-            .map(|value| value.to_ascii_lowercase())
-            .collect();
-        let mut stack = vec![root.to_path_buf()];
-
-        while let Some(dir) = stack.pop() {
-            for entry in fs::read_dir(&dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.is_dir() {
-                    stack.push(path);
-                    continue;
-                }
+        let mut hashed = Vec::new();
+        for (rel, path) in collect_candidates(root, allowed_extensions)? {
+            let (sha256, size) = sha256_file_and_size(&path)?;
+            hashed.push((rel, sha256, size));
+        }
+        Ok(Self::from_hashed(hashed))
+    }
 
-                if !is_allowed_by_extension(&path, &allowed) {
-                    continue;
-                }
+    /// Same result as [`AssetFingerprintCatalog::build`], hashing files on the
+    /// rayon thread pool. The directory walk stays serial and candidates are
+    /// sorted first, so output does not depend on scheduling.
+    #[cfg(feature = "parallel")]
+    pub fn build_parallel(root: &Path, allowed_extensions: &[&str]) -> Result<Self, AssetError> {
+        use rayon::prelude::*;
 
-                let rel = path
-                    .strip_prefix(root)
-                    .map_err(|_| AssetError::Traversal)?
-                    .to_path_buf();
-                let rel = normalize_asset_key(&rel);
+        let hashed = collect_candidates(root, allowed_extensions)?
+            .into_par_iter()
+            .map(|(rel, path)| {
                 let (sha256, size) = sha256_file_and_size(&path)?;
-                entries.insert(
-                    rel.clone(),
-                    AssetFingerprintEntry {
-                        rel_path: rel.clone(),
-                        sha256: sha256.clone(),
-                        size,
-                    },
-                );
-                dedup_groups.entry(sha256).or_default().push(rel);
-            }
-        }
+                Ok((rel, sha256, size))
+            })
+            .collect::<Result<Vec<_>, AssetError>>()?;
+        Ok(Self::from_hashed(hashed))
+    }
 
-        Ok(Self {
+    /// Builds the catalog from `(rel_path, sha256, size)` rows sorted by path,
+    /// so dedup groups list their members in path order.
+    fn from_hashed(hashed: Vec<(String, String, u64)>) -> Self {
+        let mut entries = BTreeMap::new();
+        let mut dedup_groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (rel, sha256, size) in hashed {
+            entries.insert(
+                rel.clone(),
+                AssetFingerprintEntry {
+                    rel_path: rel.clone(),
+                    sha256: sha256.clone(),
+                    size,
+                },
+            );
+            dedup_groups.entry(sha256).or_default().push(rel);
+        }
+        Self {
             entries,
             dedup_groups,
-        })
+        }
     }
 
     pub fn unique_blob_count(&self) -> usize {
@@ -172,3 +174,41 @@ impl AssetFingerprintCatalog {
         }
     }
 }
+
+/// Walks `root` and returns `(normalized key, path)` for every allowed file,
+/// sorted by key.
+fn collect_candidates(
+    root: &Path,
+    allowed_extensions: &[&str],
+) -> Result<Vec<(String, PathBuf)>, AssetError> {
+    let allowed: HashSet<String> = allowed_extensions
+        .iter()
+        .map(|value| value.to_ascii_lowercase())
+        .collect();
+    let mut candidates = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+
+            if !is_allowed_by_extension(&path, &allowed) {
+                continue;
+            }
+
+            let rel = path
+                .strip_prefix(root)
+                .map_err(|_| AssetError::Traversal)?
+                .to_path_buf();
+            candidates.push((normalize_asset_key(&rel), path));
+        }
+    }
+
+    candidates.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(candidates)
+}
@@ -9,6 +9,10 @@ use crate::model::{AssetError, AssetKind, SUPPORTED_IMAGE_EXTENSIONS};
 
 pub fn sanitize_rel_path(rel: &Path) -> Result<PathBuf, AssetError> {
     use std::path::Component::*;
+    let raw = rel.to_string_lossy();
+    if has_url_scheme(&raw) || has_drive_prefix(&raw) {
+        return Err(AssetError::UnsafePath(raw.into_owned()));
+    }
     let mut out = PathBuf::new();
     for component in rel.components() {
         match component {
@@ -20,6 +24,30 @@ pub fn sanitize_rel_path(rel: &Path) -> Result<PathBuf, AssetError> {
     Ok(out)
 }
 
+/// Returns `true` for asset references that untrusted scripts must not load:
+/// absolute paths, `..` segments, drive prefixes and http(s) URLs.
+pub fn is_unsafe_asset_path(value: &str) -> bool {
+    let path = value.trim();
+    if path.is_empty() {
+        return false;
+    }
+    path.split(['/', '\\']).any(|segment| segment == "..")
+        || path.starts_with('/')
+        || path.starts_with('\\')
+        || has_url_scheme(path)
+        || has_drive_prefix(path)
+}
+
+// Covers http(s) as well as any other `scheme://` reference.
+fn has_url_scheme(path: &str) -> bool {
+    path.contains("://")
+}
+
+fn has_drive_prefix(path: &str) -> bool {
+    let bytes = path.trim_start().as_bytes();
+    bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic()
+}
+
 pub(crate) fn normalize_asset_key(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
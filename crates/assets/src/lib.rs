@@ -8,13 +8,13 @@ mod platform;
 mod store;
 
 pub use catalog::AssetFingerprintCatalog;
-pub use helpers::sanitize_rel_path;
+pub use helpers::{is_unsafe_asset_path, sanitize_rel_path};
 pub use model::{
     AssetEntry, AssetError, AssetFingerprintEntry, AssetKind, AssetLimits, AssetManifest,
-    BudgetReport, LoadedImage, PlatformBudget, PlatformTarget, ScenePreloadPlan, SecurityMode,
-    TranscodePreset, TranscodeRecommendation,
+    BudgetReport, CacheStats, LoadedImage, PlatformBudget, PlatformTarget, ScenePreloadPlan,
+    SecurityMode, TranscodePreset, TranscodeRecommendation,
 };
-pub use store::AssetStore;
+pub use store::{AssetStore, EvictCallback};
 
 #[cfg(test)]
 use helpers::sha256_hex;
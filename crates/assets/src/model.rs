@@ -15,6 +15,8 @@ pub struct AssetLimits {
     pub max_bytes: u64,
     pub max_width: u32,
     pub max_height: u32,
+    /// Upper bound on `width * height`, checked against the image header before decoding.
+    pub max_pixels: u64,
 }
 
 impl Default for AssetLimits {
@@ -23,6 +25,7 @@ impl Default for AssetLimits {
             max_bytes: 15 * 1024 * 1024,
             max_width: 4096,
             max_height: 4096,
+            max_pixels: 4096 * 4096,
         }
     }
 }
@@ -45,6 +48,8 @@ pub enum AssetError {
     Io(#[from] std::io::Error),
     #[error("asset path traversal blocked")]
     Traversal,
+    #[error("unsafe asset path '{0}' (absolute, parent, drive or URL reference)")]
+    UnsafePath(String),
     #[error("unsupported image extension for '{0}' (supported: png, jpg, jpeg)")]
     UnsupportedExtension(String),
     #[error("asset too large: {size} bytes (max {max})")]
@@ -56,6 +61,15 @@ pub enum AssetError {
         max_width: u32,
         max_height: u32,
     },
+    #[error(
+        "asset dimensions {width}x{height} ({pixels} pixels) exceed pixel budget {max_pixels}"
+    )]
+    PixelBudgetExceeded {
+        width: u32,
+        height: u32,
+        pixels: u64,
+        max_pixels: u64,
+    },
     #[error("manifest required for untrusted assets")]
     ManifestMissing,
     #[error("unsupported manifest version {0}")]
@@ -101,6 +115,16 @@ pub struct BudgetReport {
     pub within_budget: bool,
 }
 
+/// Snapshot of one [`crate::AssetStore`] cache, for tuning its budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub current_bytes: usize,
+    pub max_bytes: usize,
+    pub entries: usize,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum PlatformTarget {
@@ -145,6 +169,7 @@ pub struct ScenePreloadPlan {
     pub cache_hit_rate: f32,
 }
 
+#[derive(Clone, Debug)]
 pub struct LoadedImage {
     pub name: String,
     pub size: [usize; 2],
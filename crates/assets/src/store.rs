@@ -1,17 +1,31 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
-use crate::cache::ByteCache;
+use crate::cache::{ByteCache, ImageCache};
 use crate::helpers::{
-    candidate_image_paths, canonicalize_within_root, normalize_asset_key, normalize_asset_request,
-    sanitize_rel_path, sha256_hex,
+    candidate_image_paths, canonicalize_within_root, is_unsafe_asset_path, normalize_asset_key,
+    normalize_asset_request, sanitize_rel_path, sha256_hex,
 };
 use crate::model::{
-    AssetError, AssetLimits, AssetManifest, LoadedImage, SecurityMode, SUPPORTED_IMAGE_EXTENSIONS,
+    AssetError, AssetLimits, AssetManifest, CacheStats, LoadedImage, SecurityMode,
+    SUPPORTED_IMAGE_EXTENSIONS,
 };
 
+/// Called with the key and size of each byte-cache entry evicted for budget
+/// or dropped by [`AssetStore::invalidate`].
+pub type EvictCallback = Box<dyn Fn(&str, usize) + Send + Sync>;
+
+struct EvictHook(EvictCallback);
+
+impl std::fmt::Debug for EvictHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EvictHook")
+    }
+}
+
 #[derive(Debug)]
 pub struct AssetStore {
     root: PathBuf,
@@ -20,9 +34,19 @@ pub struct AssetStore {
     limits: AssetLimits,
     manifest: Option<AssetManifest>,
     require_manifest: bool,
+    // Lock order: the two caches are never held at the same time. `load_image`
+    // releases `image_cache` before `load_bytes` takes `byte_cache`.
     byte_cache: Mutex<ByteCache>,
+    image_cache: Mutex<ImageCache>,
+    on_evict: Option<EvictHook>,
 }
 
+// `AssetStore` is shared across loader threads behind an `Arc`.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<AssetStore>();
+};
+
 impl AssetStore {
     pub fn new(
         root: PathBuf,
@@ -62,9 +86,16 @@ impl AssetStore {
             manifest,
             require_manifest,
             byte_cache: Mutex::new(ByteCache::new(64 * 1024 * 1024)),
+            image_cache: Mutex::new(ImageCache::new(128 * 1024 * 1024)),
+            on_evict: None,
         })
     }
 
+    /// Wraps the store for sharing between threads; every loader takes `&self`.
+    pub fn into_shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
     pub fn with_limits(mut self, limits: AssetLimits) -> Self {
         self.limits = limits;
         self
@@ -75,17 +106,62 @@ impl AssetStore {
         self
     }
 
+    /// Sets the budget for decoded RGBA pixels, independent of the byte cache.
+    pub fn with_image_cache_budget(mut self, budget_bytes: usize) -> Self {
+        self.image_cache = Mutex::new(ImageCache::new(budget_bytes));
+        self
+    }
+
+    /// Registers a callback run once per byte-cache entry evicted under
+    /// budget pressure or dropped by [`AssetStore::invalidate`] (not when a
+    /// key is overwritten). It runs after the cache lock is released, so it
+    /// may call back into the store.
+    pub fn on_evict(mut self, callback: EvictCallback) -> Self {
+        self.on_evict = Some(EvictHook(callback));
+        self
+    }
+
+    /// Hit/miss counters and occupancy of the raw byte cache. Counters are
+    /// updated under the cache mutex, so they match what `load_bytes` saw.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.lock_byte_cache().stats()
+    }
+
+    /// Same as [`AssetStore::cache_stats`] for the decoded-image cache.
+    pub fn image_cache_stats(&self) -> CacheStats {
+        self.lock_image_cache().stats()
+    }
+
+    /// Zeroes hit/miss counters of both caches without evicting anything.
+    pub fn reset_cache_stats(&self) {
+        self.lock_byte_cache().reset_stats();
+        self.lock_image_cache().reset_stats();
+    }
+
+    /// Forgets cached bytes and decoded pixels for `asset_path` so the next
+    /// load reads the file again. A dropped byte entry is reported to the
+    /// [`AssetStore::on_evict`] callback. Returns whether anything was cached.
+    pub fn invalidate(&self, asset_path: &str) -> bool {
+        let normalized = normalize_asset_request(asset_path);
+        let Ok(rel) = sanitize_rel_path(Path::new(&normalized)) else {
+            return false;
+        };
+        let cache_key = normalize_asset_key(&rel);
+        let dropped_bytes = self.lock_byte_cache().remove(&cache_key);
+        let dropped_image = self.lock_image_cache().remove(&cache_key).is_some();
+        if let (Some(bytes), Some(EvictHook(callback))) = (dropped_bytes, &self.on_evict) {
+            callback(&cache_key, bytes);
+        }
+        dropped_bytes.is_some() || dropped_image
+    }
+
     pub fn load_bytes(&self, asset_path: &str) -> Result<Vec<u8>, AssetError> {
+        self.reject_unsafe_path(asset_path)?;
         let normalized = normalize_asset_request(asset_path);
         let rel = sanitize_rel_path(Path::new(&normalized))?;
         let cache_key = normalize_asset_key(&rel);
 
-        if let Some(bytes) = self
-            .byte_cache
-            .lock()
-            .map_err(|_| std::io::Error::other("asset cache lock poisoned"))?
-            .get(&cache_key)
-        {
+        if let Some(bytes) = self.lock_byte_cache().get(&cache_key) {
             return Ok(bytes);
         }
 
@@ -100,16 +176,27 @@ impl AssetStore {
             });
         }
         self.verify_manifest(&cache_key, size, &bytes)?;
-        self.byte_cache
-            .lock()
-            .map_err(|_| std::io::Error::other("asset cache lock poisoned"))?
-            .insert(cache_key, bytes.clone());
+        let evicted = self.lock_byte_cache().insert(cache_key, bytes.clone());
+        if let Some(EvictHook(callback)) = &self.on_evict {
+            for (key, evicted_bytes) in &evicted {
+                callback(key, *evicted_bytes);
+            }
+        }
         Ok(bytes)
     }
 
-    pub fn load_image(&self, asset_path: &str) -> Result<LoadedImage, AssetError> {
+    /// Decodes `asset_path` to RGBA, or returns the shared copy already in
+    /// the decoded-image cache.
+    pub fn load_image(&self, asset_path: &str) -> Result<Arc<LoadedImage>, AssetError> {
+        self.reject_unsafe_path(asset_path)?;
         let resolved_path = self.resolve_image_path(asset_path)?;
+        let cache_key = normalize_asset_key(&sanitize_rel_path(Path::new(&resolved_path))?);
+        if let Some(image) = self.lock_image_cache().get(&cache_key) {
+            return Ok(image);
+        }
+
         let bytes = self.load_bytes(&resolved_path)?;
+        self.check_declared_dimensions(&resolved_path, &bytes)?;
 
         let image = image::load_from_memory(&bytes).map_err(|err| AssetError::Decode {
             path: resolved_path.clone(),
@@ -125,11 +212,14 @@ impl AssetStore {
                 max_height: self.limits.max_height,
             });
         }
-        Ok(LoadedImage {
+        let loaded = Arc::new(LoadedImage {
             name: resolved_path,
             size: [width as usize, height as usize],
             pixels: rgba.into_raw(),
-        })
+        });
+        self.lock_image_cache()
+            .insert(cache_key, Arc::clone(&loaded));
+        Ok(loaded)
     }
 
     pub fn resolve_image_path(&self, asset_path: &str) -> Result<String, AssetError> {
@@ -167,6 +257,59 @@ impl AssetStore {
         })
     }
 
+    // Reads only the image header so oversized images are rejected before any
+    // pixel buffer is allocated.
+    fn check_declared_dimensions(&self, path: &str, bytes: &[u8]) -> Result<(), AssetError> {
+        let decode_error = |reason: String| AssetError::Decode {
+            path: path.to_string(),
+            reason,
+        };
+        let (width, height) = image::ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|err| decode_error(err.to_string()))?
+            .into_dimensions()
+            .map_err(|err| decode_error(err.to_string()))?;
+        if width > self.limits.max_width || height > self.limits.max_height {
+            return Err(AssetError::InvalidDimensions {
+                width,
+                height,
+                max_width: self.limits.max_width,
+                max_height: self.limits.max_height,
+            });
+        }
+        let pixels = u64::from(width) * u64::from(height);
+        if pixels > self.limits.max_pixels {
+            return Err(AssetError::PixelBudgetExceeded {
+                width,
+                height,
+                pixels,
+                max_pixels: self.limits.max_pixels,
+            });
+        }
+        Ok(())
+    }
+
+    // The caches only hold copies of files on disk, so a lock poisoned by a
+    // panicking thread is recovered instead of failing every later load.
+    fn lock_byte_cache(&self) -> MutexGuard<'_, ByteCache> {
+        self.byte_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_image_cache(&self) -> MutexGuard<'_, ImageCache> {
+        self.image_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn reject_unsafe_path(&self, asset_path: &str) -> Result<(), AssetError> {
+        if self.mode == SecurityMode::Untrusted && is_unsafe_asset_path(asset_path) {
+            return Err(AssetError::UnsafePath(asset_path.to_string()));
+        }
+        Ok(())
+    }
+
     fn verify_manifest(&self, asset_key: &str, size: u64, bytes: &[u8]) -> Result<(), AssetError> {
         if self.mode == SecurityMode::Untrusted && self.require_manifest && self.manifest.is_none()
         {
@@ -107,6 +107,49 @@ fn load_bytes_uses_cache_for_repeated_reads() {
     let _ = std::fs::remove_dir_all(root);
 }
 
+#[test]
+fn load_image_is_consistent_across_threads_and_skips_redecode() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock must be after unix epoch")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_assets_threads_{unique}"));
+    std::fs::create_dir_all(root.join("bg")).expect("asset dir");
+    write_png(&root.join("bg").join("room.png"));
+
+    // Byte cache disabled so repeat hits can only come from the decoded-image cache.
+    let store = AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+        .expect("asset store should initialize")
+        .with_cache_budget(0)
+        .into_shared();
+    let expected = store.load_image("bg/room.png").expect("first load");
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let store = std::sync::Arc::clone(&store);
+            std::thread::spawn(move || {
+                (0..25)
+                    .map(|_| store.load_image("bg/room.png").expect("threaded load"))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for handle in handles {
+        for image in handle.join().expect("loader thread should not panic") {
+            assert!(std::sync::Arc::ptr_eq(&image, &expected));
+        }
+    }
+
+    let other = image::RgbaImage::from_pixel(1, 1, image::Rgba([200, 0, 0, 255]));
+    other
+        .save(root.join("bg").join("room.png"))
+        .expect("overwrite png");
+    let cached = store.load_image("bg/room.png").expect("cached load");
+    assert!(std::sync::Arc::ptr_eq(&cached, &expected));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
 #[test]
 fn load_bytes_manifest_lookup_normalizes_separators() {
     let unique = SystemTime::now()
@@ -179,6 +222,130 @@ fn load_bytes_blocks_symlink_escape() {
     let _ = std::fs::remove_file(outside);
 }
 
+#[test]
+fn untrusted_store_rejects_unsafe_asset_references() {
+    let store = AssetStore::new(PathBuf::from("."), SecurityMode::Untrusted, None, false)
+        .expect("asset store should initialize");
+
+    for path in [
+        "/etc/passwd",
+        "\\\\server\\share\\bg.png",
+        "../secrets.png",
+        "bg/../../secrets.png",
+        "C:\\Windows\\bg.png",
+        "c:bg.png",
+        "http://example.com/bg.png",
+        "https://example.com/bg.png",
+    ] {
+        let err = store
+            .load_bytes(path)
+            .expect_err("unsafe path must be rejected");
+        assert!(
+            matches!(err, AssetError::UnsafePath(ref rejected) if rejected == path),
+            "{path}: {err}"
+        );
+        assert!(matches!(
+            store.load_image(path),
+            Err(AssetError::UnsafePath(_))
+        ));
+    }
+}
+
+#[test]
+fn unsafe_asset_path_only_rejects_parent_dir_segments() {
+    assert!(is_unsafe_asset_path(".."));
+    assert!(is_unsafe_asset_path("bg\\..\\secrets.png"));
+    assert!(!is_unsafe_asset_path("bg/room..v2.png"));
+    assert!(!is_unsafe_asset_path("bg/..hidden/room.png"));
+}
+
+#[test]
+fn sanitize_rel_path_rejects_urls_and_drive_prefixes() {
+    for path in [
+        "https://example.com/bg.png",
+        "ftp://host/bg.png",
+        "D:bg.png",
+    ] {
+        let err = sanitize_rel_path(Path::new(path)).expect_err("must be rejected");
+        assert!(matches!(err, AssetError::UnsafePath(_)), "{path}: {err}");
+    }
+    assert!(matches!(
+        sanitize_rel_path(Path::new("../bg.png")),
+        Err(AssetError::Traversal)
+    ));
+    assert!(sanitize_rel_path(Path::new("bg/room.png")).is_ok());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn png_chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut body = kind.to_vec();
+    body.extend_from_slice(data);
+    let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(&body);
+    chunk.extend_from_slice(&crc32(&body).to_be_bytes());
+    chunk
+}
+
+#[test]
+fn load_image_rejects_declared_huge_dimensions_before_decode() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock must be after unix epoch")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_assets_pixel_bomb_{unique}"));
+    std::fs::create_dir_all(&root).expect("asset dir");
+
+    // Header declares 100k x 100k RGBA; the IDAT payload is not a valid image
+    // stream, so only a header-level check can report the dimensions.
+    let mut ihdr = Vec::new();
+    ihdr.extend_from_slice(&100_000u32.to_be_bytes());
+    ihdr.extend_from_slice(&100_000u32.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+    let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    bytes.extend(png_chunk(b"IHDR", &ihdr));
+    bytes.extend(png_chunk(b"IDAT", &[0x78, 0x9c, 0x03, 0x00]));
+    bytes.extend(png_chunk(b"IEND", &[]));
+    std::fs::write(root.join("bomb.png"), bytes).expect("write png");
+
+    let store = AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+        .expect("asset store should initialize")
+        .with_limits(AssetLimits {
+            max_bytes: 1024,
+            max_width: u32::MAX,
+            max_height: u32::MAX,
+            max_pixels: 4096 * 4096,
+        });
+    let err = match store.load_image("bomb.png") {
+        Ok(_) => panic!("pixel bomb must be rejected"),
+        Err(err) => err,
+    };
+    assert!(
+        matches!(
+            err,
+            AssetError::PixelBudgetExceeded {
+                width: 100_000,
+                height: 100_000,
+                pixels: 10_000_000_000,
+                max_pixels: 16_777_216,
+            }
+        ),
+        "{err}"
+    );
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
 #[test]
 fn fingerprint_catalog_detects_duplicate_blobs_and_budget() {
     let unique = SystemTime::now()
@@ -335,3 +502,114 @@ fn transcode_recommendations_follow_platform_presets() {
 
     let _ = std::fs::remove_dir_all(root);
 }
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_fingerprint_build_matches_serial() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock must be after unix epoch")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_assets_parallel_{unique}"));
+    for dir in ["audio", "bg/night", "bg/day", "ui"] {
+        std::fs::create_dir_all(root.join(dir)).expect("create dir");
+    }
+    for index in 0..24u8 {
+        let dir = ["audio", "bg/night", "bg/day", "ui"][usize::from(index % 4)];
+        let ext = if index % 2 == 0 { "png" } else { "ogg" };
+        // Every third file repeats a payload so dedup groups get several members.
+        let payload = vec![index % 3; usize::from(index) + 1];
+        let payload = if index % 3 == 0 {
+            vec![7u8; 5]
+        } else {
+            payload
+        };
+        std::fs::write(root.join(dir).join(format!("f{index}.{ext}")), payload)
+            .expect("write asset");
+    }
+    std::fs::write(root.join("ui/notes.txt"), b"ignored").expect("write txt");
+
+    let serial = AssetFingerprintCatalog::build(&root, &["ogg", "png"]).expect("serial");
+    for _ in 0..4 {
+        let parallel =
+            AssetFingerprintCatalog::build_parallel(&root, &["ogg", "png"]).expect("parallel");
+        assert_eq!(parallel, serial);
+    }
+    assert_eq!(serial.entries.len(), 24);
+    assert!(serial.duplicate_blob_count() > 0);
+    for members in serial.dedup_groups.values() {
+        assert!(members.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn cache_stats_count_hits_and_misses() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock must be after unix epoch")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_assets_stats_{unique}"));
+    std::fs::create_dir_all(root.join("audio")).expect("audio dir");
+    std::fs::write(root.join("audio/theme.ogg"), [1u8, 2, 3, 4]).expect("write asset");
+
+    let store = AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+        .expect("asset store")
+        .with_cache_budget(1024);
+    assert_eq!(
+        store.cache_stats(),
+        CacheStats {
+            max_bytes: 1024,
+            ..CacheStats::default()
+        }
+    );
+
+    store.load_bytes("audio/theme.ogg").expect("first read");
+    store.load_bytes("audio/theme.ogg").expect("second read");
+    let stats = store.cache_stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.current_bytes, 4);
+
+    store.reset_cache_stats();
+    let stats = store.cache_stats();
+    assert_eq!((stats.hits, stats.misses), (0, 0));
+    assert_eq!(stats.entries, 1);
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn on_evict_reports_each_budget_eviction_once() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock must be after unix epoch")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_assets_evict_{unique}"));
+    std::fs::create_dir_all(root.join("audio")).expect("audio dir");
+    std::fs::write(root.join("audio/a.ogg"), [1u8; 6]).expect("write a");
+    std::fs::write(root.join("audio/b.ogg"), [2u8; 6]).expect("write b");
+
+    let evictions = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = std::sync::Arc::clone(&evictions);
+    let store = AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+        .expect("asset store")
+        .with_cache_budget(8)
+        .on_evict(Box::new(move |key, bytes| {
+            sink.lock().expect("sink").push((key.to_string(), bytes));
+        }));
+
+    store.load_bytes("audio/a.ogg").expect("load a");
+    assert!(evictions.lock().expect("evictions").is_empty());
+    store.load_bytes("audio/b.ogg").expect("load b");
+    assert_eq!(
+        *evictions.lock().expect("evictions"),
+        vec![("audio/a.ogg".to_string(), 6)]
+    );
+    // A cache hit evicts nothing.
+    store.load_bytes("audio/b.ogg").expect("reload b");
+    assert_eq!(evictions.lock().expect("evictions").len(), 1);
+
+    let _ = std::fs::remove_dir_all(root);
+}
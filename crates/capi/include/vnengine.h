@@ -0,0 +1,50 @@
+/*
+ * C interface to the visual novel engine (crates/capi).
+ *
+ * Ownership:
+ *   - vn_engine_new returns a handle owned by the caller; release it with
+ *     vn_engine_free.
+ *   - Every char* returned by this library is owned by the caller and must be
+ *     released with vn_free (never the host's free).
+ *   - Input strings are only borrowed for the duration of the call.
+ *
+ * Errors: failing calls return NULL; vn_last_error returns a copy of the
+ * message recorded on the calling thread (free it with vn_free).
+ */
+#ifndef VNENGINE_H
+#define VNENGINE_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct VnEngine VnEngine;
+
+/* Compiles a UTF-8 JSON script. Returns NULL on failure. */
+VnEngine *vn_engine_new(const char *script_json);
+
+/* Destroys an engine. NULL is ignored. */
+void vn_engine_free(VnEngine *engine);
+
+/* Advances past the current event and returns it as JSON. */
+char *vn_engine_step(VnEngine *engine);
+
+/* Picks an option on the current choice and returns the choice event as JSON. */
+char *vn_engine_choose(VnEngine *engine, uint32_t option_index);
+
+/* Returns the event the engine is waiting on as JSON. */
+char *vn_engine_current_event_json(const VnEngine *engine);
+
+/* Returns a copy of the last error on this thread, or NULL if none. */
+char *vn_last_error(void);
+
+/* Releases a string returned by any vn_* function. NULL is ignored. */
+void vn_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VNENGINE_H */
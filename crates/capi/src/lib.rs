@@ -0,0 +1,182 @@
+//! C ABI for embedding the engine in non-Rust hosts (C/C++, game engines).
+//!
+//! The matching header lives in `include/vnengine.h`.
+//!
+//! Ownership rules:
+//! - `vn_engine_new` returns a handle owned by the caller; release it with
+//!   `vn_engine_free`.
+//! - Every `char *` returned by this library is owned by the caller and must
+//!   be released with `vn_free`, never with the host's `free`.
+//! - Input strings are borrowed for the duration of the call only.
+//!
+//! Failing calls return `NULL` and record a message readable with
+//! `vn_last_error` on the same thread. Successful calls do not clear it.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use visual_novel_engine::{
+    Engine, EventCompiled, ResourceLimiter, ScriptRaw, SecurityPolicy, VnResult,
+};
+
+/// Opaque engine handle handed out to C callers.
+pub struct VnEngine {
+    inner: Engine,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', "\\0");
+    let message = CString::new(message).expect("interior NULs were escaped");
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn into_c_string(value: String) -> *mut c_char {
+    match CString::new(value) {
+        Ok(value) => value.into_raw(),
+        Err(_) => {
+            set_last_error("output contains an interior NUL byte");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Runs `body`, turning errors and panics into `NULL` plus a last-error message.
+fn guard<T>(body: impl FnOnce() -> Result<T, String>, fallback: T) -> T {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(message)) => {
+            set_last_error(message);
+            fallback
+        }
+        Err(_) => {
+            set_last_error("panic inside vnengine");
+            fallback
+        }
+    }
+}
+
+fn event_json(result: VnResult<EventCompiled>) -> Result<*mut c_char, String> {
+    result
+        .map(|event| into_c_string(event.to_json_string()))
+        .map_err(|err| err.to_string())
+}
+
+/// Compiles a JSON script and returns a new engine, or `NULL` on failure.
+///
+/// # Safety
+/// `script_json` must be `NULL` or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vn_engine_new(script_json: *const c_char) -> *mut VnEngine {
+    guard(
+        || {
+            if script_json.is_null() {
+                return Err("script_json is NULL".to_string());
+            }
+            let json = CStr::from_ptr(script_json)
+                .to_str()
+                .map_err(|_| "script_json is not valid UTF-8".to_string())?;
+            let script = ScriptRaw::from_json(json).map_err(|err| err.to_string())?;
+            let inner = Engine::new(
+                script,
+                SecurityPolicy::default(),
+                ResourceLimiter::default(),
+            )
+            .map_err(|err| err.to_string())?;
+            Ok(Box::into_raw(Box::new(VnEngine { inner })))
+        },
+        ptr::null_mut(),
+    )
+}
+
+/// Destroys an engine created by `vn_engine_new`. `NULL` is ignored.
+///
+/// # Safety
+/// `engine` must be `NULL` or a handle from `vn_engine_new` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn vn_engine_free(engine: *mut VnEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Advances past the current event and returns it as JSON.
+///
+/// # Safety
+/// `engine` must be `NULL` or a live handle from `vn_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn vn_engine_step(engine: *mut VnEngine) -> *mut c_char {
+    guard(
+        || {
+            let engine = engine
+                .as_mut()
+                .ok_or_else(|| "engine is NULL".to_string())?;
+            event_json(engine.inner.step_event())
+        },
+        ptr::null_mut(),
+    )
+}
+
+/// Picks `option_index` on the current choice and returns the choice event as JSON.
+///
+/// # Safety
+/// `engine` must be `NULL` or a live handle from `vn_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn vn_engine_choose(engine: *mut VnEngine, option_index: u32) -> *mut c_char {
+    guard(
+        || {
+            let engine = engine
+                .as_mut()
+                .ok_or_else(|| "engine is NULL".to_string())?;
+            event_json(engine.inner.choose(option_index as usize))
+        },
+        ptr::null_mut(),
+    )
+}
+
+/// Returns the event the engine is waiting on as JSON.
+///
+/// # Safety
+/// `engine` must be `NULL` or a live handle from `vn_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn vn_engine_current_event_json(engine: *const VnEngine) -> *mut c_char {
+    guard(
+        || {
+            let engine = engine
+                .as_ref()
+                .ok_or_else(|| "engine is NULL".to_string())?;
+            engine
+                .inner
+                .current_event_json()
+                .map(into_c_string)
+                .map_err(|err| err.to_string())
+        },
+        ptr::null_mut(),
+    )
+}
+
+/// Returns a copy of the last error recorded on this thread, or `NULL` if none.
+#[no_mangle]
+pub extern "C" fn vn_last_error() -> *mut c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null_mut(), |message| message.clone().into_raw())
+    })
+}
+
+/// Releases a string returned by this library. `NULL` is ignored.
+///
+/// # Safety
+/// `value` must be `NULL` or a string returned by a `vn_*` function, freed once.
+#[no_mangle]
+pub unsafe extern "C" fn vn_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
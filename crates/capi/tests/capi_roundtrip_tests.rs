@@ -0,0 +1,103 @@
+use std::ffi::{c_char, CStr, CString};
+
+use vnengine_capi::{
+    vn_engine_choose, vn_engine_current_event_json, vn_engine_free, vn_engine_new, vn_engine_step,
+    vn_free, vn_last_error,
+};
+
+const SCRIPT: &str = r#"{
+  "script_schema_version": "1.0",
+  "events": [
+    { "type": "dialogue", "speaker": "Ava", "text": "Hola" },
+    { "type": "choice", "prompt": "Ruta", "options": [
+      { "text": "Fin", "target": "end" }
+    ] },
+    { "type": "dialogue", "speaker": "Ava", "text": "Adios" }
+  ],
+  "labels": { "start": 0, "end": 2 }
+}"#;
+
+/// Copies and releases a library-owned string, mirroring what a C host does.
+fn take(value: *mut c_char) -> String {
+    assert!(
+        !value.is_null(),
+        "expected a string, last error: {:?}",
+        last_error()
+    );
+    let text = unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .expect("utf-8")
+        .to_string();
+    unsafe { vn_free(value) };
+    text
+}
+
+fn last_error() -> Option<String> {
+    let value = vn_last_error();
+    (!value.is_null()).then(|| take(value))
+}
+
+#[test]
+fn c_api_plays_script_round_trip() {
+    let script = CString::new(SCRIPT).expect("script");
+    let engine = unsafe { vn_engine_new(script.as_ptr()) };
+    assert!(!engine.is_null(), "engine: {:?}", last_error());
+
+    let current = take(unsafe { vn_engine_current_event_json(engine) });
+    assert!(current.contains("\"Hola\""), "{current}");
+
+    let stepped = take(unsafe { vn_engine_step(engine) });
+    assert_eq!(stepped, current);
+
+    let choice = take(unsafe { vn_engine_current_event_json(engine) });
+    assert!(choice.contains("\"Ruta\""), "{choice}");
+    take(unsafe { vn_engine_choose(engine, 0) });
+
+    let last = take(unsafe { vn_engine_current_event_json(engine) });
+    assert!(last.contains("\"Adios\""), "{last}");
+    take(unsafe { vn_engine_step(engine) });
+
+    assert!(unsafe { vn_engine_step(engine) }.is_null());
+    assert!(last_error().is_some_and(|message| !message.is_empty()));
+
+    unsafe { vn_engine_free(engine) };
+}
+
+#[test]
+fn c_api_reports_errors_through_last_error() {
+    let broken = CString::new("{ not json").expect("script");
+    assert!(unsafe { vn_engine_new(broken.as_ptr()) }.is_null());
+    assert!(last_error().is_some());
+
+    assert!(unsafe { vn_engine_new(std::ptr::null()) }.is_null());
+    assert_eq!(last_error().as_deref(), Some("script_json is NULL"));
+
+    assert!(unsafe { vn_engine_step(std::ptr::null_mut()) }.is_null());
+    assert_eq!(last_error().as_deref(), Some("engine is NULL"));
+
+    let script = CString::new(SCRIPT).expect("script");
+    let engine = unsafe { vn_engine_new(script.as_ptr()) };
+    assert!(unsafe { vn_engine_choose(engine, 0) }.is_null());
+    assert!(last_error().is_some());
+    unsafe {
+        vn_engine_free(engine);
+        vn_engine_free(std::ptr::null_mut());
+        vn_free(std::ptr::null_mut());
+    }
+}
+
+#[test]
+fn header_declares_every_exported_function() {
+    let header = include_str!("../include/vnengine.h");
+    for name in [
+        "vn_engine_new(",
+        "vn_engine_free(",
+        "vn_engine_step(",
+        "vn_engine_choose(",
+        "vn_engine_current_event_json(",
+        "vn_last_error(",
+        "vn_free(",
+    ] {
+        assert!(header.contains(name), "header is missing {name}");
+    }
+}
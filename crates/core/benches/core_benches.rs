@@ -20,17 +20,25 @@ fn sample_raw_script() -> ScriptRaw {
                 ChoiceOptionRaw {
                     text: "Go".to_string(),
                     target: "next".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
                 ChoiceOptionRaw {
                     text: "Stay".to_string(),
                     target: "next".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
             ],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Scene(SceneUpdateRaw {
             background: Some("bg_room".to_string()),
             music: Some("song".to_string()),
             characters: vec![],
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Dialogue(DialogueRaw {
             speaker: "B".to_string(),
@@ -40,7 +48,7 @@ fn sample_raw_script() -> ScriptRaw {
     let mut labels = BTreeMap::new();
     labels.insert("start".to_string(), 0);
     labels.insert("next".to_string(), 2);
-    ScriptRaw { events, labels }
+    ScriptRaw::new(events, labels)
 }
 
 fn loop_script(event_count: usize) -> ScriptRaw {
@@ -54,7 +62,7 @@ fn loop_script(event_count: usize) -> ScriptRaw {
         .collect();
     let mut labels = BTreeMap::new();
     labels.insert("start".to_string(), 0);
-    ScriptRaw { events, labels }
+    ScriptRaw::new(events, labels)
 }
 
 fn choice_script() -> ScriptRaw {
@@ -65,12 +73,18 @@ fn choice_script() -> ScriptRaw {
                 ChoiceOptionRaw {
                     text: "Yes".to_string(),
                     target: "next".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
                 ChoiceOptionRaw {
                     text: "No".to_string(),
                     target: "next".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
             ],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Dialogue(DialogueRaw {
             speaker: "Narrator".to_string(),
@@ -80,7 +94,7 @@ fn choice_script() -> ScriptRaw {
     let mut labels = BTreeMap::new();
     labels.insert("start".to_string(), 0);
     labels.insert("next".to_string(), 1);
-    ScriptRaw { events, labels }
+    ScriptRaw::new(events, labels)
 }
 
 fn bench_parse_json(c: &mut Criterion) {
@@ -148,12 +162,16 @@ fn build_scene_update(count: usize) -> SceneUpdateCompiled {
             x: None,
             y: None,
             scale: None,
+            z: None,
+            flip_x: false,
         });
     }
     SceneUpdateCompiled {
         background: Some(Arc::from("bg_scene")),
         music: Some(Arc::from("theme")),
         characters,
+        chapter: None,
+        tags: Vec::new(),
     }
 }
 
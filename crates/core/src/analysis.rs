@@ -0,0 +1,494 @@
+//! Headless script analysis: compile checks, dry runs and preview/runtime parity.
+//!
+//! This is the engine side of the editor compile pipeline. It has no UI
+//! dependency so bindings and CI can run the same checks the editor runs.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::engine::Engine;
+use crate::graph::StoryGraph;
+use crate::resource::ResourceLimiter;
+use crate::script::ScriptRaw;
+use crate::security::SecurityPolicy;
+use crate::state::EngineState;
+
+pub const DRY_RUN_MAX_STEPS: usize = 2048;
+pub const DRY_RUN_EXHAUSTIVE_ROUTE_LIMIT: usize = 32;
+pub const DRY_RUN_EXHAUSTIVE_CHOICE_DEPTH: usize = 12;
+pub const REPRO_DEFAULT_RADIUS: usize = 12;
+
+/// Limits for dry runs and minimal repro extraction.
+///
+/// The defaults are [`DRY_RUN_MAX_STEPS`] and [`REPRO_DEFAULT_RADIUS`]; large
+/// scripts can raise `max_steps`, tests can lower it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DryRunConfig {
+    /// Steps a route may execute before stopping with
+    /// [`DryRunStopReason::StepLimit`].
+    pub max_steps: usize,
+    /// Events kept on each side of the failing event in a minimal repro.
+    pub repro_radius: usize,
+}
+
+impl Default for DryRunConfig {
+    fn default() -> Self {
+        Self {
+            max_steps: DRY_RUN_MAX_STEPS,
+            repro_radius: REPRO_DEFAULT_RADIUS,
+        }
+    }
+}
+
+mod coverage;
+mod dead_branch;
+mod dry_run;
+mod parity;
+mod route_sim;
+mod signatures;
+
+pub use coverage::{coverage_for_routes, CoverageReport, CoverageTracker};
+pub use dead_branch::{find_dead_branches, DeadBranch, DeadBranchSide};
+pub use dry_run::{
+    run_dry_run, run_dry_run_with_breakpoints, run_dry_run_with_config, DryRunOutcome,
+};
+pub use parity::check_preview_runtime_parity;
+pub use route_sim::{enumerate_choice_routes, simulate_raw_sequence, RawStepTrace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChoiceStrategy {
+    First,
+    Last,
+    Alternating,
+}
+
+impl ChoiceStrategy {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChoiceStrategy::First => "first",
+            ChoiceStrategy::Last => "last",
+            ChoiceStrategy::Alternating => "alternating",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChoicePolicy {
+    Strategy(ChoiceStrategy),
+    Scripted(Vec<usize>),
+}
+
+impl ChoicePolicy {
+    pub fn label(&self) -> String {
+        match self {
+            ChoicePolicy::Strategy(strategy) => strategy.label().to_string(),
+            ChoicePolicy::Scripted(path) => {
+                let route = path
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(".");
+                format!("scripted({route})")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilationPhase {
+    GraphSync,
+    GraphValidation,
+    ScriptCompile,
+    RuntimeInit,
+    DryRun,
+}
+
+impl CompilationPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            CompilationPhase::GraphSync => "GRAPH_SYNC",
+            CompilationPhase::GraphValidation => "GRAPH_VALIDATION",
+            CompilationPhase::ScriptCompile => "SCRIPT_COMPILE",
+            CompilationPhase::RuntimeInit => "RUNTIME_INIT",
+            CompilationPhase::DryRun => "DRY_RUN",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseTrace {
+    pub phase: CompilationPhase,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DryRunStopReason {
+    Finished,
+    StepLimit,
+    RuntimeError,
+    /// Halted on reaching a breakpoint ip, before executing that event.
+    Breakpoint,
+}
+
+impl DryRunStopReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            DryRunStopReason::Finished => "finished",
+            DryRunStopReason::StepLimit => "step_limit",
+            DryRunStopReason::RuntimeError => "runtime_error",
+            DryRunStopReason::Breakpoint => "breakpoint",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunStepTrace {
+    pub step: usize,
+    pub event_ip: u32,
+    pub event_kind: String,
+    pub event_signature: String,
+    pub visual_background: Option<String>,
+    pub visual_music: Option<String>,
+    pub character_count: usize,
+    /// Digest of flag and variable values by name before the event runs.
+    pub symbols_digest: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunReport {
+    pub max_steps: usize,
+    pub executed_steps: usize,
+    pub stop_reason: DryRunStopReason,
+    pub stop_message: String,
+    pub failing_event_ip: Option<u32>,
+    pub steps: Vec<DryRunStepTrace>,
+    /// Engine state at the breakpoint when `stop_reason` is [`DryRunStopReason::Breakpoint`].
+    pub breakpoint_state: Option<EngineState>,
+}
+
+impl DryRunReport {
+    pub fn first_event_ip(&self) -> Option<u32> {
+        self.steps.first().map(|step| step.event_ip)
+    }
+
+    pub fn minimal_repro_script(&self, script: &ScriptRaw, radius: usize) -> Option<ScriptRaw> {
+        let candidate_ip = self.failing_event_ip.or_else(|| self.first_event_ip())?;
+        script.minimal_repro(candidate_ip, radius)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// What an [`AnalysisIssue`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisIssueKind {
+    CompileError,
+    RuntimeInitError,
+    UnreachableCompiled,
+    StepLimit,
+    RuntimeError,
+    /// Runtime error hit while replaying an alternate choice route.
+    RouteRuntimeError,
+    ParityMismatch,
+    Finished,
+    /// `JumpIf` branch ruled out by the flags and vars set before it.
+    DeadBranch,
+}
+
+impl AnalysisIssueKind {
+    pub fn phase(self) -> CompilationPhase {
+        match self {
+            AnalysisIssueKind::CompileError => CompilationPhase::ScriptCompile,
+            AnalysisIssueKind::RuntimeInitError => CompilationPhase::RuntimeInit,
+            AnalysisIssueKind::UnreachableCompiled
+            | AnalysisIssueKind::StepLimit
+            | AnalysisIssueKind::RuntimeError
+            | AnalysisIssueKind::RouteRuntimeError
+            | AnalysisIssueKind::ParityMismatch
+            | AnalysisIssueKind::Finished
+            | AnalysisIssueKind::DeadBranch => CompilationPhase::DryRun,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisIssue {
+    pub severity: AnalysisSeverity,
+    pub kind: AnalysisIssueKind,
+    pub event_ip: Option<u32>,
+    pub blocked_by: Option<String>,
+    pub message: String,
+}
+
+impl AnalysisIssue {
+    pub fn new(
+        severity: AnalysisSeverity,
+        kind: AnalysisIssueKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            kind,
+            event_ip: None,
+            blocked_by: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(kind: AnalysisIssueKind, message: impl Into<String>) -> Self {
+        Self::new(AnalysisSeverity::Error, kind, message)
+    }
+
+    pub fn warning(kind: AnalysisIssueKind, message: impl Into<String>) -> Self {
+        Self::new(AnalysisSeverity::Warning, kind, message)
+    }
+
+    pub fn info(kind: AnalysisIssueKind, message: impl Into<String>) -> Self {
+        Self::new(AnalysisSeverity::Info, kind, message)
+    }
+
+    pub fn with_event_ip(mut self, event_ip: Option<u32>) -> Self {
+        self.event_ip = event_ip;
+        self
+    }
+
+    pub fn with_blocked_by(mut self, blocked_by: impl Into<String>) -> Self {
+        self.blocked_by = Some(blocked_by.into());
+        self
+    }
+}
+
+/// Result of [`analyze_script`].
+pub struct ScriptAnalysis {
+    pub engine_result: Result<Engine, String>,
+    pub issues: Vec<AnalysisIssue>,
+    pub phase_trace: Vec<PhaseTrace>,
+    pub dry_run_report: Option<DryRunReport>,
+}
+
+impl ScriptAnalysis {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == AnalysisSeverity::Error)
+    }
+}
+
+/// Compiles `script`, initializes a runtime and dry-runs every enumerated
+/// choice route, checking each against the raw preview simulation.
+pub fn analyze_script(script: &ScriptRaw) -> ScriptAnalysis {
+    analyze_script_with_config(script, &DryRunConfig::default())
+}
+
+/// [`analyze_script`] with custom dry-run limits.
+pub fn analyze_script_with_config(script: &ScriptRaw, config: &DryRunConfig) -> ScriptAnalysis {
+    let mut phase_trace = Vec::new();
+    let mut issues = Vec::new();
+    let mut dry_run_report = None;
+
+    let engine_result = match script.compile() {
+        Ok(compiled) => {
+            phase_trace.push(PhaseTrace {
+                phase: CompilationPhase::ScriptCompile,
+                ok: true,
+                detail: "ScriptRaw compiled successfully".to_string(),
+            });
+
+            let story_graph = StoryGraph::from_script(&compiled);
+            for event_ip in story_graph.unreachable_nodes() {
+                let incoming = story_graph
+                    .incoming_edges(event_ip)
+                    .into_iter()
+                    .map(|edge| edge.from.to_string())
+                    .collect::<Vec<_>>();
+                let blocked_by = if incoming.is_empty() {
+                    "no incoming compiled edges".to_string()
+                } else {
+                    format!("incoming_event_ips={}", incoming.join(","))
+                };
+                issues.push(
+                    AnalysisIssue::warning(
+                        AnalysisIssueKind::UnreachableCompiled,
+                        format!("Dry Run detected unreachable compiled event at ip={event_ip}"),
+                    )
+                    .with_event_ip(Some(event_ip))
+                    .with_blocked_by(blocked_by),
+                );
+            }
+            for dead in find_dead_branches(script) {
+                let side = match dead.side {
+                    DeadBranchSide::Taken => "jump is never taken",
+                    DeadBranchSide::FallThrough => "fall-through is never reached",
+                };
+                issues.push(
+                    AnalysisIssue::warning(
+                        AnalysisIssueKind::DeadBranch,
+                        format!("Dead branch at ip={}: {side}", dead.event_ip),
+                    )
+                    .with_event_ip(Some(dead.event_ip)),
+                );
+            }
+
+            match Engine::from_compiled(
+                compiled.clone(),
+                SecurityPolicy::default(),
+                ResourceLimiter::default(),
+            ) {
+                Ok(engine) => {
+                    phase_trace.push(PhaseTrace {
+                        phase: CompilationPhase::RuntimeInit,
+                        ok: true,
+                        detail: "Engine initialized".to_string(),
+                    });
+
+                    let primary_policy = ChoicePolicy::Strategy(ChoiceStrategy::First);
+                    let outcome = run_dry_run_with_config(
+                        engine.clone(),
+                        &primary_policy,
+                        &BTreeSet::new(),
+                        config,
+                    );
+                    dry_run_report = Some(outcome.report.clone());
+                    issues.extend(outcome.issues);
+
+                    let parity_issues =
+                        check_preview_runtime_parity(script, &outcome.report, &primary_policy);
+                    if let Some(report) = dry_run_report.as_mut() {
+                        report.failing_event_ip = report
+                            .failing_event_ip
+                            .or_else(|| parity_issues.iter().find_map(|issue| issue.event_ip));
+                    }
+                    issues.extend(parity_issues);
+
+                    let mut route_policies = vec![
+                        ChoicePolicy::Strategy(ChoiceStrategy::Last),
+                        ChoicePolicy::Strategy(ChoiceStrategy::Alternating),
+                    ];
+                    for path in enumerate_choice_routes(
+                        script,
+                        config.max_steps,
+                        DRY_RUN_EXHAUSTIVE_ROUTE_LIMIT,
+                        DRY_RUN_EXHAUSTIVE_CHOICE_DEPTH,
+                    ) {
+                        route_policies.push(ChoicePolicy::Scripted(path));
+                    }
+
+                    let mut seen_policies = HashSet::new();
+                    seen_policies.insert(primary_policy.clone());
+                    for policy in route_policies {
+                        if !seen_policies.insert(policy.clone()) {
+                            continue;
+                        }
+
+                        match Engine::from_compiled(
+                            compiled.clone(),
+                            SecurityPolicy::default(),
+                            ResourceLimiter::default(),
+                        ) {
+                            Ok(route_engine) => {
+                                let route_outcome = run_dry_run_with_config(
+                                    route_engine,
+                                    &policy,
+                                    &BTreeSet::new(),
+                                    config,
+                                );
+                                let mut route_issues = check_preview_runtime_parity(
+                                    script,
+                                    &route_outcome.report,
+                                    &policy,
+                                );
+                                if route_outcome.report.stop_reason
+                                    == DryRunStopReason::RuntimeError
+                                {
+                                    route_issues.push(
+                                        AnalysisIssue::error(
+                                            AnalysisIssueKind::RouteRuntimeError,
+                                            format!(
+                                                "Dry Run route '{}' runtime error: {}",
+                                                policy.label(),
+                                                route_outcome.report.stop_message
+                                            ),
+                                        )
+                                        .with_event_ip(route_outcome.report.failing_event_ip),
+                                    );
+                                }
+
+                                if let Some(report) = dry_run_report.as_mut() {
+                                    report.failing_event_ip =
+                                        report.failing_event_ip.or_else(|| {
+                                            route_issues.iter().find_map(|issue| issue.event_ip)
+                                        });
+                                }
+                                issues.extend(route_issues);
+                            }
+                            Err(err) => {
+                                issues.push(AnalysisIssue::error(
+                                    AnalysisIssueKind::RuntimeInitError,
+                                    format!(
+                                        "Runtime initialization failed for route '{}': {}",
+                                        policy.label(),
+                                        err
+                                    ),
+                                ));
+                            }
+                        }
+                    }
+
+                    let dry_run_errors = issues
+                        .iter()
+                        .filter(|issue| {
+                            issue.kind.phase() == CompilationPhase::DryRun
+                                && issue.severity == AnalysisSeverity::Error
+                        })
+                        .count();
+                    phase_trace.push(PhaseTrace {
+                        phase: CompilationPhase::DryRun,
+                        ok: dry_run_errors == 0,
+                        detail: format!("Dry run complete ({} dry-run error(s))", dry_run_errors),
+                    });
+
+                    Ok(engine)
+                }
+                Err(e) => {
+                    issues.push(AnalysisIssue::error(
+                        AnalysisIssueKind::RuntimeInitError,
+                        format!("Runtime initialization failed: {}", e),
+                    ));
+                    phase_trace.push(PhaseTrace {
+                        phase: CompilationPhase::RuntimeInit,
+                        ok: false,
+                        detail: e.to_string(),
+                    });
+                    Err(format!("Runtime Init Error: {}", e))
+                }
+            }
+        }
+        Err(e) => {
+            issues.push(AnalysisIssue::error(
+                AnalysisIssueKind::CompileError,
+                format!("Compilation Error: {}", e),
+            ));
+            phase_trace.push(PhaseTrace {
+                phase: CompilationPhase::ScriptCompile,
+                ok: false,
+                detail: e.to_string(),
+            });
+            Err(format!("Compilation Failed: {}", e))
+        }
+    };
+
+    ScriptAnalysis {
+        engine_result,
+        issues,
+        phase_trace,
+        dry_run_report,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+
+use crate::engine::Engine;
+use crate::error::VnResult;
+use crate::event::EventCompiled;
+use crate::graph::StoryGraph;
+use crate::resource::ResourceLimiter;
+use crate::script::{ScriptCompiled, ScriptRaw};
+use crate::security::SecurityPolicy;
+
+/// Visited share of a script's reachable events and choice options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    pub visited_ips: usize,
+    pub reachable_ips: usize,
+    pub visited_options: usize,
+    pub reachable_options: usize,
+    /// Reachable ips no recorded run executed, ascending.
+    pub unvisited_ips: Vec<u32>,
+    /// `(choice ip, option index)` pairs no recorded run picked.
+    pub unvisited_options: Vec<(u32, usize)>,
+}
+
+impl CoverageReport {
+    /// Visited reachable ips in percent; 100 for a script with none.
+    pub fn ip_percent(&self) -> f32 {
+        percent(self.visited_ips, self.reachable_ips)
+    }
+
+    /// Picked reachable choice options in percent; 100 for a script with none.
+    pub fn option_percent(&self) -> f32 {
+        percent(self.visited_options, self.reachable_options)
+    }
+}
+
+fn percent(visited: usize, total: usize) -> f32 {
+    if total == 0 {
+        100.0
+    } else {
+        visited as f32 * 100.0 / total as f32
+    }
+}
+
+/// Accumulates the event ips and choice options visited by many runs of one
+/// script, measured against what is reachable from `start`.
+#[derive(Debug, Clone)]
+pub struct CoverageTracker {
+    reachable_ips: BTreeSet<u32>,
+    reachable_options: BTreeSet<(u32, usize)>,
+    visited_ips: BTreeSet<u32>,
+    visited_options: BTreeSet<(u32, usize)>,
+}
+
+impl CoverageTracker {
+    pub fn new(script: &ScriptCompiled) -> Self {
+        let graph = StoryGraph::from_script(script);
+        let reachable_ips: BTreeSet<u32> = graph
+            .nodes
+            .iter()
+            .filter(|node| node.reachable)
+            .map(|node| node.id)
+            .collect();
+        let reachable_options = reachable_ips
+            .iter()
+            .filter_map(|ip| match script.events.get(*ip as usize) {
+                Some(EventCompiled::Choice(choice)) => Some((*ip, choice.options.len())),
+                _ => None,
+            })
+            .flat_map(|(ip, count)| (0..count).map(move |option| (ip, option)))
+            .collect();
+        Self {
+            reachable_ips,
+            reachable_options,
+            visited_ips: BTreeSet::new(),
+            visited_options: BTreeSet::new(),
+        }
+    }
+
+    /// Replays `log` on a copy of `engine` (usually fresh at `start`) and
+    /// marks every event and option it passes through.
+    pub fn record_choice_log(&mut self, engine: &Engine, log: &[usize]) -> VnResult<()> {
+        let mut engine = engine.clone();
+        engine.replay_choice_log_traced(log, |state, _, chosen| {
+            self.visited_ips.insert(state.position);
+            if let Some(option) = chosen {
+                self.visited_options.insert((state.position, option));
+            }
+        })
+    }
+
+    pub fn report(&self) -> CoverageReport {
+        let unvisited_ips: Vec<u32> = self
+            .reachable_ips
+            .difference(&self.visited_ips)
+            .copied()
+            .collect();
+        let unvisited_options: Vec<(u32, usize)> = self
+            .reachable_options
+            .difference(&self.visited_options)
+            .copied()
+            .collect();
+        CoverageReport {
+            visited_ips: self.reachable_ips.len() - unvisited_ips.len(),
+            reachable_ips: self.reachable_ips.len(),
+            visited_options: self.reachable_options.len() - unvisited_options.len(),
+            reachable_options: self.reachable_options.len(),
+            unvisited_ips,
+            unvisited_options,
+        }
+    }
+}
+
+/// Plays each route (a choice log from `start`) and reports the combined
+/// coverage.
+pub fn coverage_for_routes(script: &ScriptRaw, routes: &[Vec<usize>]) -> VnResult<CoverageReport> {
+    let engine = Engine::new(
+        script.clone(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )?;
+    let mut tracker = CoverageTracker::new(engine.script());
+    for route in routes {
+        tracker.record_choice_log(&engine, route)?;
+    }
+    Ok(tracker.report())
+}
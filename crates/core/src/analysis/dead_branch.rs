@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::event::{CmpOp, CondRaw, EventRaw};
+use crate::script::ScriptRaw;
+
+/// Which side of a `JumpIf` can never run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadBranchSide {
+    /// The condition is always false, so the jump is never taken.
+    Taken,
+    /// The condition is always true, so execution never falls through.
+    FallThrough,
+}
+
+/// A `JumpIf` whose condition has the same value on every path reaching it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadBranch {
+    pub event_ip: u32,
+    pub side: DeadBranchSide,
+}
+
+/// Flag and var values known to hold on every path to an ip. Symbols not
+/// listed may have any value, including the ones the script starts with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct KnownValues {
+    flags: BTreeMap<String, bool>,
+    vars: BTreeMap<String, i32>,
+}
+
+impl KnownValues {
+    /// Keeps only the facts both states agree on. Returns true if `self` lost any.
+    fn meet(&mut self, other: &KnownValues) -> bool {
+        let before = (self.flags.len(), self.vars.len());
+        self.flags
+            .retain(|key, value| other.flags.get(key) == Some(value));
+        self.vars
+            .retain(|key, value| other.vars.get(key) == Some(value));
+        before != (self.flags.len(), self.vars.len())
+    }
+
+    fn eval(&self, cond: &CondRaw) -> Option<bool> {
+        match cond {
+            CondRaw::Flag { key, is_set } => self.flags.get(key).map(|value| value == is_set),
+            CondRaw::VarCmp { key, op, value } => {
+                let current = *self.vars.get(key)?;
+                Some(match op {
+                    CmpOp::Eq => current == *value,
+                    CmpOp::Ne => current != *value,
+                    CmpOp::Lt => current < *value,
+                    CmpOp::Le => current <= *value,
+                    CmpOp::Gt => current > *value,
+                    CmpOp::Ge => current >= *value,
+                })
+            }
+            CondRaw::StrEq { .. } => None,
+        }
+    }
+}
+
+/// Finds `JumpIf` branches that are provably never taken, by propagating the
+/// flag and var values set on every path from `start`.
+///
+/// Nothing is assumed about values the script does not set itself, and an
+/// `ExtCall` forgets everything known, so only branches ruled out by the
+/// script's own `SetFlag`/`SetVar` events are reported. Scripts with
+/// unresolved includes or macros report nothing.
+pub fn find_dead_branches(script: &ScriptRaw) -> Vec<DeadBranch> {
+    let Ok(start) = script.start_index() else {
+        return Vec::new();
+    };
+    if script.has_includes() || script.has_expansions() || start >= script.events.len() {
+        return Vec::new();
+    }
+
+    let mut states: Vec<Option<KnownValues>> = vec![None; script.events.len()];
+    let mut queue = VecDeque::from([start]);
+    states[start] = Some(KnownValues::default());
+
+    while let Some(ip) = queue.pop_front() {
+        let Some(state) = states[ip].clone() else {
+            continue;
+        };
+        for (next, next_state) in successors(script, ip, state) {
+            let Some(next) = next.filter(|next| *next < script.events.len()) else {
+                continue;
+            };
+            let changed = match states[next].as_mut() {
+                Some(existing) => existing.meet(&next_state),
+                None => {
+                    states[next] = Some(next_state);
+                    true
+                }
+            };
+            if changed && !queue.contains(&next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut dead = Vec::new();
+    for (ip, event) in script.events.iter().enumerate() {
+        let (EventRaw::JumpIf { cond, target }, Some(state)) = (event, &states[ip]) else {
+            continue;
+        };
+        if script.labels.get(target) == Some(&(ip + 1)) {
+            continue;
+        }
+        let side = match state.eval(cond) {
+            Some(true) => DeadBranchSide::FallThrough,
+            Some(false) => DeadBranchSide::Taken,
+            None => continue,
+        };
+        dead.push(DeadBranch {
+            event_ip: ip as u32,
+            side,
+        });
+    }
+    dead
+}
+
+/// Next ips reachable from `ip` (`None` for an unknown label) with the state
+/// after executing it.
+fn successors(
+    script: &ScriptRaw,
+    ip: usize,
+    mut state: KnownValues,
+) -> Vec<(Option<usize>, KnownValues)> {
+    let label = |target: &str| script.labels.get(target).copied();
+    match &script.events[ip] {
+        EventRaw::Jump { target } => vec![(label(target), state)],
+        EventRaw::JumpIf { cond, target } => match state.eval(cond) {
+            Some(true) => vec![(label(target), state)],
+            Some(false) => vec![(Some(ip + 1), state)],
+            None => vec![(label(target), state.clone()), (Some(ip + 1), state)],
+        },
+        EventRaw::Choice(choice) => choice
+            .options
+            .iter()
+            .map(|option| {
+                let mut option_state = state.clone();
+                for effect in &option.effects {
+                    match effect {
+                        EventRaw::SetFlag { key, value } => {
+                            option_state.flags.insert(key.clone(), *value);
+                        }
+                        EventRaw::SetVar { key, value } => {
+                            option_state.vars.insert(key.clone(), *value);
+                        }
+                        _ => {}
+                    }
+                }
+                (label(&option.target), option_state)
+            })
+            .chain(
+                choice
+                    .fallthrough
+                    .as_deref()
+                    .map(|target| (label(target), state.clone())),
+            )
+            .collect(),
+        EventRaw::SetFlag { key, value } => {
+            state.flags.insert(key.clone(), *value);
+            vec![(Some(ip + 1), state)]
+        }
+        EventRaw::SetVar { key, value } => {
+            state.vars.insert(key.clone(), *value);
+            vec![(Some(ip + 1), state)]
+        }
+        EventRaw::ExtCall { .. } => vec![(Some(ip + 1), KnownValues::default())],
+        EventRaw::Dialogue(_)
+        | EventRaw::Scene(_)
+        | EventRaw::Patch(_)
+        | EventRaw::AudioAction(_)
+        | EventRaw::Transition(_)
+        | EventRaw::SetCharacterPosition(_)
+        | EventRaw::SetStrVar { .. }
+        | EventRaw::InputText { .. }
+        | EventRaw::UnlockGallery { .. }
+        | EventRaw::Achieve { .. }
+        | EventRaw::Include { .. }
+        | EventRaw::Expand { .. } => vec![(Some(ip + 1), state)],
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeSet;
+
+use crate::engine::Engine;
+use crate::event::EventCompiled;
+
+use super::route_sim::select_choice_index;
+use super::signatures::{compiled_event_signature, event_kind_compiled, symbols_digest};
+use super::{
+    AnalysisIssue, AnalysisIssueKind, ChoicePolicy, DryRunConfig, DryRunReport, DryRunStepTrace,
+    DryRunStopReason,
+};
+
+#[derive(Debug, Clone)]
+pub struct DryRunOutcome {
+    pub issues: Vec<AnalysisIssue>,
+    pub report: DryRunReport,
+}
+
+/// Steps `engine` until the script ends, errors, or hits
+/// [`DRY_RUN_MAX_STEPS`](super::DRY_RUN_MAX_STEPS),
+/// resolving choices with `policy`.
+pub fn run_dry_run(engine: Engine, policy: &ChoicePolicy) -> DryRunOutcome {
+    run_dry_run_with_breakpoints(engine, policy, &BTreeSet::new())
+}
+
+/// Like [`run_dry_run`], but halts with [`DryRunStopReason::Breakpoint`] as soon
+/// as the engine reaches one of `breakpoints` (event ips). The breakpoint event
+/// is traced but not executed, and the engine state is kept in the report.
+pub fn run_dry_run_with_breakpoints(
+    engine: Engine,
+    policy: &ChoicePolicy,
+    breakpoints: &BTreeSet<u32>,
+) -> DryRunOutcome {
+    run_dry_run_with_config(engine, policy, breakpoints, &DryRunConfig::default())
+}
+
+/// [`run_dry_run_with_breakpoints`] stopping after `config.max_steps` steps
+/// instead of [`DRY_RUN_MAX_STEPS`](super::DRY_RUN_MAX_STEPS).
+pub fn run_dry_run_with_config(
+    mut engine: Engine,
+    policy: &ChoicePolicy,
+    breakpoints: &BTreeSet<u32>,
+    config: &DryRunConfig,
+) -> DryRunOutcome {
+    let max_steps = config.max_steps;
+    let mut issues = Vec::new();
+    let mut traces = Vec::new();
+    let mut steps = 0usize;
+    let mut choice_cursor = 0usize;
+    let mut failing_event_ip = None;
+    let mut breakpoint_state = None;
+
+    let (stop_reason, stop_message) = loop {
+        if steps >= max_steps {
+            let stop_message = format!(
+                "Dry Run reached {} steps; possible loop or blocking flow",
+                max_steps
+            );
+            issues.push(
+                AnalysisIssue::warning(AnalysisIssueKind::StepLimit, stop_message.clone())
+                    .with_event_ip(Some(engine.state().position)),
+            );
+            break (DryRunStopReason::StepLimit, stop_message);
+        }
+
+        let ip = engine.state().position;
+        let event = match engine.current_event() {
+            Ok(event) => event,
+            Err(_) => {
+                let msg = format!("Dry Run finished in {} step(s)", steps);
+                issues.push(AnalysisIssue::info(
+                    AnalysisIssueKind::Finished,
+                    msg.clone(),
+                ));
+                break (DryRunStopReason::Finished, msg);
+            }
+        };
+
+        traces.push(DryRunStepTrace {
+            step: steps,
+            event_ip: ip,
+            event_kind: event_kind_compiled(&event).to_string(),
+            event_signature: compiled_event_signature(&event),
+            visual_background: engine
+                .state()
+                .visual
+                .background
+                .as_ref()
+                .map(|value| value.as_ref().to_string()),
+            visual_music: engine
+                .state()
+                .visual
+                .music
+                .as_ref()
+                .map(|value| value.as_ref().to_string()),
+            character_count: engine.state().visual.characters.len(),
+            symbols_digest: runtime_symbols_digest(&engine),
+        });
+
+        if breakpoints.contains(&ip) {
+            breakpoint_state = Some(engine.state().clone());
+            break (
+                DryRunStopReason::Breakpoint,
+                format!(
+                    "Dry Run hit breakpoint at ip {} after {} step(s)",
+                    ip, steps
+                ),
+            );
+        }
+
+        let run_result = match event {
+            EventCompiled::Choice(choice) if !choice.options.is_empty() => {
+                let idx = select_choice_index(policy, steps, choice.options.len(), choice_cursor);
+                choice_cursor = choice_cursor.saturating_add(1);
+                engine.choose(idx).map(|_| ())
+            }
+            EventCompiled::ExtCall { .. } => engine.resume(),
+            EventCompiled::InputText { .. } => engine.submit_text(""),
+            _ => engine.step().map(|_| ()),
+        };
+
+        if let Err(err) = run_result {
+            let stop_message = format!("Dry Run runtime error at ip {}: {}", ip, err);
+            failing_event_ip = Some(ip);
+            issues.push(
+                AnalysisIssue::error(AnalysisIssueKind::RuntimeError, stop_message.clone())
+                    .with_event_ip(Some(ip)),
+            );
+            break (DryRunStopReason::RuntimeError, stop_message);
+        }
+
+        steps += 1;
+    };
+
+    DryRunOutcome {
+        issues,
+        report: DryRunReport {
+            max_steps,
+            executed_steps: steps,
+            stop_reason,
+            stop_message,
+            failing_event_ip,
+            steps: traces,
+            breakpoint_state,
+        },
+    }
+}
+
+fn runtime_symbols_digest(engine: &Engine) -> u32 {
+    let script = engine.script();
+    let state = engine.state();
+    symbols_digest(
+        script
+            .flag_names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (&**name, state.get_flag(id as u32))),
+        script
+            .var_names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (&**name, state.get_var(id as u32))),
+    )
+}
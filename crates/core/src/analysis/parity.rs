@@ -1,15 +1,16 @@
-use std::collections::{BTreeMap, HashMap};
+use crate::script::ScriptRaw;
 
-use super::super::{ChoicePolicy, DryRunReport};
 use super::route_sim::simulate_raw_sequence;
-use crate::editor::validator::{LintCode, LintIssue, ValidationPhase};
-use visual_novel_engine::{EventRaw, ScriptRaw};
+use super::{AnalysisIssue, AnalysisIssueKind, ChoicePolicy, DryRunReport};
 
-pub(in crate::editor::compiler) fn check_preview_runtime_parity(
+/// Compares a runtime dry-run report against the raw preview simulation of
+/// the same route (events, visuals, then flag/var values) and reports the
+/// first divergence.
+pub fn check_preview_runtime_parity(
     script: &ScriptRaw,
     report: &DryRunReport,
     policy: &ChoicePolicy,
-) -> Vec<LintIssue> {
+) -> Vec<AnalysisIssue> {
     let mut issues = Vec::new();
     let runtime_steps = &report.steps;
     let raw_steps = simulate_raw_sequence(script, report.max_steps, policy);
@@ -22,10 +23,8 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
 
         if runtime.event_kind != raw.event_kind {
             issues.push(
-                LintIssue::error(
-                    None,
-                    ValidationPhase::DryRun,
-                    LintCode::DryRunParityMismatch,
+                AnalysisIssue::error(
+                    AnalysisIssueKind::ParityMismatch,
                     format!(
                         "Parity mismatch [route={}] at step {}: preview {}@{} vs runtime {}@{}",
                         route_label.as_str(),
@@ -43,10 +42,8 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
 
         if runtime.event_signature != raw.event_signature {
             issues.push(
-                LintIssue::error(
-                    None,
-                    ValidationPhase::DryRun,
-                    LintCode::DryRunParityMismatch,
+                AnalysisIssue::error(
+                    AnalysisIssueKind::ParityMismatch,
                     format!(
                         "Parity payload mismatch [route={}] at step {}: preview '{}' vs runtime '{}'",
                         route_label.as_str(),
@@ -65,10 +62,8 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
             || runtime.character_count != raw.character_count
         {
             issues.push(
-                LintIssue::error(
-                    None,
-                    ValidationPhase::DryRun,
-                    LintCode::DryRunParityMismatch,
+                AnalysisIssue::error(
+                    AnalysisIssueKind::ParityMismatch,
                     format!(
                         "Parity visual mismatch [route={}] at step {}: preview bg={:?}, music={:?}, chars={} vs runtime bg={:?}, music={:?}, chars={}",
                         route_label.as_str(),
@@ -85,6 +80,23 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
             );
             break;
         }
+
+        if runtime.symbols_digest != raw.symbols_digest {
+            issues.push(
+                AnalysisIssue::error(
+                    AnalysisIssueKind::ParityMismatch,
+                    format!(
+                        "Parity state mismatch [route={}] at step {}: preview flags/vars digest {:08x} vs runtime {:08x}",
+                        route_label.as_str(),
+                        idx,
+                        raw.symbols_digest,
+                        runtime.symbols_digest
+                    ),
+                )
+                .with_event_ip(Some(runtime.event_ip)),
+            );
+            break;
+        }
     }
 
     if runtime_steps.len() != raw_steps.len() {
@@ -94,10 +106,8 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
             .map(|entry| entry.event_ip)
             .or_else(|| raw_steps.get(mismatch_step).map(|entry| entry.event_ip));
         issues.push(
-            LintIssue::error(
-                None,
-                ValidationPhase::DryRun,
-                LintCode::DryRunParityMismatch,
+            AnalysisIssue::error(
+                AnalysisIssueKind::ParityMismatch,
                 format!(
                     "Parity length mismatch [route={}]: preview={} runtime={}",
                     route_label.as_str(),
@@ -111,69 +121,3 @@ pub(in crate::editor::compiler) fn check_preview_runtime_parity(
 
     issues
 }
-
-pub(in crate::editor::compiler) fn build_minimal_repro_script(
-    script: &ScriptRaw,
-    failure_ip: u32,
-    radius: usize,
-) -> Option<ScriptRaw> {
-    if script.events.is_empty() {
-        return Some(ScriptRaw::new(Vec::new(), BTreeMap::new()));
-    }
-
-    let failure_idx = (failure_ip as usize).min(script.events.len().saturating_sub(1));
-    let start_idx = failure_idx.saturating_sub(radius);
-    let end_idx = (failure_idx + radius + 1).min(script.events.len());
-    let mut events = script.events[start_idx..end_idx].to_vec();
-
-    let mut old_to_new_label: HashMap<String, String> = HashMap::new();
-    let mut labels = BTreeMap::new();
-
-    for offset in 0..events.len() {
-        let local_name = format!("repro_{}", offset);
-        labels.insert(local_name.clone(), offset);
-    }
-
-    for (label, old_idx) in &script.labels {
-        if *old_idx >= start_idx && *old_idx < end_idx {
-            old_to_new_label.insert(label.clone(), format!("repro_{}", old_idx - start_idx));
-        }
-    }
-
-    labels.insert("start".to_string(), 0);
-
-    for event in &mut events {
-        if !rewrite_event_targets(event, &old_to_new_label) {
-            return None;
-        }
-    }
-
-    Some(ScriptRaw::new(events, labels))
-}
-
-fn rewrite_event_targets(event: &mut EventRaw, old_to_new_label: &HashMap<String, String>) -> bool {
-    match event {
-        EventRaw::Jump { target } => {
-            let Some(mapped) = old_to_new_label.get(target).cloned() else {
-                return false;
-            };
-            *target = mapped;
-        }
-        EventRaw::JumpIf { target, .. } => {
-            let Some(mapped) = old_to_new_label.get(target).cloned() else {
-                return false;
-            };
-            *target = mapped;
-        }
-        EventRaw::Choice(choice) => {
-            for option in &mut choice.options {
-                let Some(mapped) = old_to_new_label.get(&option.target).cloned() else {
-                    return false;
-                };
-                option.target = mapped;
-            }
-        }
-        _ => {}
-    }
-    true
-}
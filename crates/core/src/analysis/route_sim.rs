@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use super::super::{ChoicePolicy, ChoiceStrategy};
-use super::signatures::{event_kind_raw, raw_event_signature};
-use visual_novel_engine::{CmpOp, CondRaw, EventRaw, ScriptRaw};
+use crate::event::{CmpOp, CondRaw, EventRaw};
+use crate::script::ScriptRaw;
+
+use super::signatures::{event_kind_raw, raw_event_signature, symbols_digest};
+use super::{ChoicePolicy, ChoiceStrategy};
 
 pub(super) fn select_choice_index(
     policy: &ChoicePolicy,
@@ -36,17 +38,20 @@ struct RawVisualState {
 struct RawSimulationState {
     flags: HashMap<String, bool>,
     vars: HashMap<String, i32>,
+    str_vars: HashMap<String, String>,
     visual: RawVisualState,
 }
 
 #[derive(Debug, Clone)]
-pub(in crate::editor::compiler) struct RawStepTrace {
-    pub(in crate::editor::compiler) event_ip: u32,
-    pub(in crate::editor::compiler) event_kind: String,
-    pub(in crate::editor::compiler) event_signature: String,
-    pub(in crate::editor::compiler) visual_background: Option<String>,
-    pub(in crate::editor::compiler) visual_music: Option<String>,
-    pub(in crate::editor::compiler) character_count: usize,
+pub struct RawStepTrace {
+    pub event_ip: u32,
+    pub event_kind: String,
+    pub event_signature: String,
+    pub visual_background: Option<String>,
+    pub visual_music: Option<String>,
+    pub character_count: usize,
+    /// Digest of flag and variable values by name before the event runs.
+    pub symbols_digest: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -58,7 +63,8 @@ struct RawRouteFrame {
     state: RawSimulationState,
 }
 
-pub(in crate::editor::compiler) fn enumerate_choice_routes(
+/// Enumerates distinct choice paths (option indices) through the raw script.
+pub fn enumerate_choice_routes(
     script: &ScriptRaw,
     max_steps: usize,
     max_routes: usize,
@@ -92,6 +98,19 @@ pub(in crate::editor::compiler) fn enumerate_choice_routes(
 
         let event = &script.events[frame.ip];
         if let EventRaw::Choice(choice) = event {
+            if choice.options.is_empty() {
+                if let Some(target_ip) = choice
+                    .fallthrough
+                    .as_ref()
+                    .and_then(|label| script.labels.get(label).copied())
+                {
+                    let mut next = frame;
+                    next.steps = next.steps.saturating_add(1);
+                    next.ip = target_ip;
+                    stack.push(next);
+                    continue;
+                }
+            }
             if choice.options.is_empty() || frame.choice_depth >= max_choice_depth {
                 routes.push(frame.choices);
                 continue;
@@ -111,6 +130,9 @@ pub(in crate::editor::compiler) fn enumerate_choice_routes(
                 next.choice_depth = next.choice_depth.saturating_add(1);
                 next.ip = target_ip;
                 next.choices.push(option_idx);
+                for effect in &choice.options[option_idx].effects {
+                    apply_state_mutations(effect, &mut next.state);
+                }
                 stack.push(next);
                 pushed = true;
             }
@@ -148,8 +170,14 @@ pub(in crate::editor::compiler) fn enumerate_choice_routes(
             | EventRaw::Scene(_)
             | EventRaw::Patch(_)
             | EventRaw::SetCharacterPosition(_)
+            | EventRaw::InputText { .. }
+            | EventRaw::UnlockGallery { .. }
+            | EventRaw::Achieve { .. }
+            | EventRaw::Include { .. }
+            | EventRaw::Expand { .. }
             | EventRaw::SetFlag { .. }
             | EventRaw::SetVar { .. }
+            | EventRaw::SetStrVar { .. }
             | EventRaw::Choice(_) => {}
         }
 
@@ -169,7 +197,8 @@ pub(in crate::editor::compiler) fn enumerate_choice_routes(
     routes
 }
 
-pub(in crate::editor::compiler) fn simulate_raw_sequence(
+/// Simulates the raw script the way the editor preview does, without a runtime.
+pub fn simulate_raw_sequence(
     script: &ScriptRaw,
     max_steps: usize,
     policy: &ChoicePolicy,
@@ -193,6 +222,16 @@ pub(in crate::editor::compiler) fn simulate_raw_sequence(
             visual_background: state.visual.background.clone(),
             visual_music: state.visual.music.clone(),
             character_count: state.visual.characters.len(),
+            symbols_digest: symbols_digest(
+                state
+                    .flags
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), *value)),
+                state
+                    .vars
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), *value)),
+            ),
         });
 
         let mut next_ip = ip + 1;
@@ -204,20 +243,29 @@ pub(in crate::editor::compiler) fn simulate_raw_sequence(
                 };
                 next_ip = target_ip;
             }
+            EventRaw::Choice(choice) if choice.options.is_empty() => {
+                let Some(target_ip) = choice
+                    .fallthrough
+                    .as_ref()
+                    .and_then(|label| script.labels.get(label).copied())
+                else {
+                    break;
+                };
+                next_ip = target_ip;
+            }
             EventRaw::Choice(choice) => {
                 let choice_idx =
                     select_choice_index(policy, steps, choice.options.len(), choice_cursor);
                 choice_cursor = choice_cursor.saturating_add(1);
-                let Some(target_label) = choice
-                    .options
-                    .get(choice_idx)
-                    .map(|opt| opt.target.as_str())
-                else {
+                let Some(option) = choice.options.get(choice_idx) else {
                     break;
                 };
-                let Some(target_ip) = script.labels.get(target_label).copied() else {
+                let Some(target_ip) = script.labels.get(&option.target).copied() else {
                     break;
                 };
+                for effect in &option.effects {
+                    apply_state_mutations(effect, &mut state);
+                }
                 next_ip = target_ip;
             }
             EventRaw::JumpIf { cond, target } => {
@@ -235,8 +283,14 @@ pub(in crate::editor::compiler) fn simulate_raw_sequence(
             | EventRaw::Scene(_)
             | EventRaw::Patch(_)
             | EventRaw::SetCharacterPosition(_)
+            | EventRaw::InputText { .. }
+            | EventRaw::UnlockGallery { .. }
+            | EventRaw::Achieve { .. }
+            | EventRaw::Include { .. }
+            | EventRaw::Expand { .. }
             | EventRaw::SetFlag { .. }
-            | EventRaw::SetVar { .. } => {}
+            | EventRaw::SetVar { .. }
+            | EventRaw::SetStrVar { .. } => {}
         }
 
         ip = next_ip;
@@ -294,13 +348,21 @@ fn apply_state_mutations(event: &EventRaw, state: &mut RawSimulationState) {
         EventRaw::SetVar { key, value } => {
             state.vars.insert(key.clone(), *value);
         }
+        EventRaw::SetStrVar { key, value } => {
+            state.str_vars.insert(key.clone(), value.clone());
+        }
         EventRaw::Dialogue(_)
         | EventRaw::Choice(_)
         | EventRaw::Jump { .. }
         | EventRaw::JumpIf { .. }
         | EventRaw::ExtCall { .. }
         | EventRaw::AudioAction(_)
-        | EventRaw::Transition(_) => {}
+        | EventRaw::Transition(_)
+        | EventRaw::InputText { .. }
+        | EventRaw::UnlockGallery { .. }
+        | EventRaw::Achieve { .. }
+        | EventRaw::Include { .. }
+        | EventRaw::Expand { .. } => {}
     }
 }
 
@@ -318,5 +380,8 @@ fn eval_cond_raw(cond: &CondRaw, state: &RawSimulationState) -> bool {
                 CmpOp::Ge => current >= *value,
             }
         }
+        CondRaw::StrEq { key, value } => {
+            state.str_vars.get(key).map_or("", String::as_str) == value
+        }
     }
 }
@@ -1,20 +1,39 @@
-use visual_novel_engine::{CondCompiled, CondRaw, EventCompiled, EventRaw};
+use std::collections::{BTreeMap, BTreeSet};
 
-pub(super) fn event_kind_compiled(event: &EventCompiled) -> &'static str {
-    match event {
-        EventCompiled::Dialogue(_) => "dialogue",
-        EventCompiled::Choice(_) => "choice",
-        EventCompiled::Scene(_) => "scene",
-        EventCompiled::Jump { .. } => "jump",
-        EventCompiled::SetFlag { .. } => "set_flag",
-        EventCompiled::SetVar { .. } => "set_var",
-        EventCompiled::JumpIf { .. } => "jump_if",
-        EventCompiled::Patch(_) => "patch",
-        EventCompiled::ExtCall { .. } => "ext_call",
-        EventCompiled::AudioAction(_) => "audio_action",
-        EventCompiled::Transition(_) => "transition",
-        EventCompiled::SetCharacterPosition(_) => "set_character_position",
+use crate::event::{CondCompiled, CondRaw, EventCompiled, EventRaw};
+
+/// Name-keyed CRC32 of flag and variable values, comparable between the raw
+/// simulation and the runtime whatever ids the compiler assigned.
+///
+/// Hashes `b'f'`, the name and a NUL for each set flag, then `b'v'`, the name,
+/// a NUL and the little-endian value for each non-zero var, in name order.
+pub(super) fn symbols_digest<'a>(
+    flags: impl IntoIterator<Item = (&'a str, bool)>,
+    vars: impl IntoIterator<Item = (&'a str, i32)>,
+) -> u32 {
+    let flags: BTreeSet<&str> = flags
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| name)
+        .collect();
+    let vars: BTreeMap<&str, i32> = vars.into_iter().filter(|(_, value)| *value != 0).collect();
+    let mut hasher = crc32fast::Hasher::new();
+    for name in flags {
+        hasher.update(b"f");
+        hasher.update(name.as_bytes());
+        hasher.update(&[0]);
+    }
+    for (name, value) in vars {
+        hasher.update(b"v");
+        hasher.update(name.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&value.to_le_bytes());
     }
+    hasher.finalize()
+}
+
+pub(super) fn event_kind_compiled(event: &EventCompiled) -> &'static str {
+    event.kind()
 }
 
 pub(super) fn event_kind_raw(event: &EventRaw) -> &'static str {
@@ -25,12 +44,18 @@ pub(super) fn event_kind_raw(event: &EventRaw) -> &'static str {
         EventRaw::Jump { .. } => "jump",
         EventRaw::SetFlag { .. } => "set_flag",
         EventRaw::SetVar { .. } => "set_var",
+        EventRaw::SetStrVar { .. } => "set_str_var",
         EventRaw::JumpIf { .. } => "jump_if",
         EventRaw::Patch(_) => "patch",
         EventRaw::ExtCall { .. } => "ext_call",
         EventRaw::AudioAction(_) => "audio_action",
         EventRaw::Transition(_) => "transition",
         EventRaw::SetCharacterPosition(_) => "set_character_position",
+        EventRaw::InputText { .. } => "input_text",
+        EventRaw::UnlockGallery { .. } => "unlock_gallery",
+        EventRaw::Achieve { .. } => "achieve",
+        EventRaw::Include { .. } => "include",
+        EventRaw::Expand { .. } => "expand",
     }
 }
 
@@ -51,6 +76,7 @@ pub(super) fn compiled_event_signature(event: &EventCompiled) -> String {
         EventCompiled::Jump { .. } => "jump".to_string(),
         EventCompiled::SetFlag { value, .. } => format!("set_flag|{}", value),
         EventCompiled::SetVar { value, .. } => format!("set_var|{}", value),
+        EventCompiled::SetStrVar { value, .. } => format!("set_str_var|{}", value),
         EventCompiled::JumpIf { cond, .. } => format!("jump_if|{}", compiled_cond_signature(cond)),
         EventCompiled::Patch(p) => format!(
             "patch|bg={:?}|music={:?}|add={}|upd={}|rm={}",
@@ -85,6 +111,11 @@ pub(super) fn compiled_event_signature(event: &EventCompiled) -> String {
             p.y,
             fmt_opt_f32(p.scale)
         ),
+        EventCompiled::InputText {
+            prompt, max_len, ..
+        } => format!("input_text|{prompt}|{max_len}"),
+        EventCompiled::UnlockGallery { id } => format!("unlock_gallery|{id}"),
+        EventCompiled::Achieve { id } => format!("achieve|{id}"),
     }
 }
 
@@ -101,6 +132,7 @@ pub(super) fn raw_event_signature(event: &EventRaw) -> String {
         EventRaw::Jump { .. } => "jump".to_string(),
         EventRaw::SetFlag { value, .. } => format!("set_flag|{}", value),
         EventRaw::SetVar { value, .. } => format!("set_var|{}", value),
+        EventRaw::SetStrVar { value, .. } => format!("set_str_var|{}", value),
         EventRaw::JumpIf { cond, .. } => format!("jump_if|{}", raw_cond_signature(cond)),
         EventRaw::Patch(p) => format!(
             "patch|bg={:?}|music={:?}|add={}|upd={}|rm={}",
@@ -135,6 +167,13 @@ pub(super) fn raw_event_signature(event: &EventRaw) -> String {
             p.y,
             fmt_opt_f32(p.scale)
         ),
+        EventRaw::InputText {
+            prompt, max_len, ..
+        } => format!("input_text|{prompt}|{max_len}"),
+        EventRaw::UnlockGallery { id } => format!("unlock_gallery|{id}"),
+        EventRaw::Achieve { id } => format!("achieve|{id}"),
+        EventRaw::Include { path } => format!("include|{path}"),
+        EventRaw::Expand { name, args } => format!("expand|{name}|{}", args.join(",")),
     }
 }
 
@@ -142,6 +181,7 @@ fn compiled_cond_signature(cond: &CondCompiled) -> String {
     match cond {
         CondCompiled::Flag { is_set, .. } => format!("flag|{}", is_set),
         CondCompiled::VarCmp { op, value, .. } => format!("var|{:?}|{}", op, value),
+        CondCompiled::StrEq { value, .. } => format!("str|{}", value),
     }
 }
 
@@ -149,6 +189,7 @@ fn raw_cond_signature(cond: &CondRaw) -> String {
     match cond {
         CondRaw::Flag { is_set, .. } => format!("flag|{}", is_set),
         CondRaw::VarCmp { op, value, .. } => format!("var|{:?}|{}", op, value),
+        CondRaw::StrEq { value, .. } => format!("str|{}", value),
     }
 }
 
@@ -0,0 +1,474 @@
+use std::collections::BTreeMap;
+
+use crate::ScriptRaw;
+use crate::{ChoiceOptionRaw, ChoiceRaw, DialogueRaw, EventRaw};
+
+use super::*;
+
+fn dialogue(speaker: &str, text: &str) -> EventRaw {
+    EventRaw::Dialogue(DialogueRaw {
+        speaker: speaker.to_string(),
+        text: text.to_string(),
+    })
+}
+
+// Mirrors what the editor emits for Start -> intro -> choice(A|B) -> End.
+fn graph_branching_script() -> ScriptRaw {
+    ScriptRaw::new(
+        vec![
+            dialogue("Narrador", "Inicio"),
+            EventRaw::Choice(ChoiceRaw {
+                prompt: "Ruta".to_string(),
+                options: vec![
+                    ChoiceOptionRaw {
+                        text: "A".to_string(),
+                        target: "node_4".to_string(),
+                        weight: None,
+                        effects: Vec::new(),
+                    },
+                    ChoiceOptionRaw {
+                        text: "B".to_string(),
+                        target: "node_5".to_string(),
+                        weight: None,
+                        effects: Vec::new(),
+                    },
+                ],
+                auto: false,
+                fallthrough: None,
+            }),
+            dialogue("A", "Ruta A"),
+            dialogue("B", "Ruta B"),
+        ],
+        BTreeMap::from([
+            ("node_2".to_string(), 0usize),
+            ("node_3".to_string(), 1usize),
+            ("node_4".to_string(), 2usize),
+            ("node_5".to_string(), 3usize),
+            ("start".to_string(), 0usize),
+        ]),
+    )
+}
+
+#[test]
+fn analyze_graph_script_produces_dry_run_report() {
+    let analysis = analyze_script(&graph_branching_script());
+
+    assert!(analysis.engine_result.is_ok());
+    let phases: Vec<CompilationPhase> = analysis.phase_trace.iter().map(|p| p.phase).collect();
+    assert_eq!(
+        phases,
+        vec![
+            CompilationPhase::ScriptCompile,
+            CompilationPhase::RuntimeInit,
+            CompilationPhase::DryRun,
+        ]
+    );
+
+    let report = analysis.dry_run_report.expect("dry run report");
+    assert_eq!(report.stop_reason, DryRunStopReason::Finished);
+    assert_eq!(report.first_event_ip(), Some(0));
+    assert!(!analysis
+        .issues
+        .iter()
+        .any(|issue| issue.kind == AnalysisIssueKind::ParityMismatch));
+    assert!(analysis
+        .issues
+        .iter()
+        .any(|issue| issue.kind == AnalysisIssueKind::Finished));
+}
+
+#[test]
+fn analyze_reports_compile_error_for_missing_label() {
+    let script = ScriptRaw::new(
+        vec![EventRaw::Jump {
+            target: "missing".to_string(),
+        }],
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    let analysis = analyze_script(&script);
+
+    assert!(analysis.engine_result.is_err());
+    assert!(analysis.dry_run_report.is_none());
+    assert!(analysis.has_errors());
+    assert_eq!(
+        analysis.issues[0].kind.phase(),
+        CompilationPhase::ScriptCompile
+    );
+}
+
+#[test]
+fn raw_simulation_follows_each_choice_route() {
+    let script = graph_branching_script();
+    let first = simulate_raw_sequence(&script, 32, &ChoicePolicy::Strategy(ChoiceStrategy::First));
+    let last = simulate_raw_sequence(&script, 32, &ChoicePolicy::Strategy(ChoiceStrategy::Last));
+
+    assert_eq!(first.len(), 4);
+    assert_eq!(last.len(), 3);
+    assert_eq!(last[2].event_signature, "dialogue|B|Ruta B");
+
+    let routes = enumerate_choice_routes(&script, 64, 16, 8);
+    assert!(routes.iter().any(|route| route.as_slice() == [0]));
+    assert!(routes.iter().any(|route| route.as_slice() == [1]));
+}
+
+#[test]
+fn minimal_repro_compiles_and_reproduces_dry_run_failure() {
+    let script = ScriptRaw::new(
+        vec![
+            dialogue("A", "0"),
+            dialogue("A", "1"),
+            dialogue("A", "2"),
+            EventRaw::Jump {
+                target: "mid".to_string(),
+            },
+            dialogue("A", "skipped"),
+            dialogue("B", "mid"),
+            EventRaw::Choice(ChoiceRaw {
+                prompt: "Broken".to_string(),
+                options: Vec::new(),
+                auto: false,
+                fallthrough: None,
+            }),
+            dialogue("C", "after"),
+        ],
+        BTreeMap::from([("start".to_string(), 0usize), ("mid".to_string(), 5usize)]),
+    );
+    let original = analyze_script(&script)
+        .dry_run_report
+        .expect("dry run report");
+    assert_eq!(original.stop_reason, DryRunStopReason::RuntimeError);
+    assert_eq!(original.failing_event_ip, Some(6));
+
+    let repro = original
+        .minimal_repro_script(&script, 1)
+        .expect("repro script");
+    assert_eq!(repro.events.len(), 3);
+    assert_eq!(repro.start_index().expect("start label"), 0);
+    assert!(repro.compile().is_ok());
+
+    let replay = analyze_script(&repro)
+        .dry_run_report
+        .expect("repro dry run report");
+    assert_eq!(replay.stop_reason, original.stop_reason);
+    let failing_ip = replay.failing_event_ip.expect("repro failing ip");
+    let failing_step = replay
+        .steps
+        .iter()
+        .find(|step| step.event_ip == failing_ip)
+        .expect("repro failing step");
+    assert_eq!(
+        failing_step.event_signature,
+        original.steps.last().expect("failing step").event_signature
+    );
+}
+
+#[test]
+fn minimal_repro_rejects_targets_outside_window() {
+    let script = ScriptRaw::new(
+        vec![
+            dialogue("A", "0"),
+            dialogue("A", "1"),
+            dialogue("A", "2"),
+            EventRaw::Jump {
+                target: "start".to_string(),
+            },
+        ],
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    assert!(script.minimal_repro(3, 1).is_none());
+    assert!(script.minimal_repro(3, 3).is_some());
+}
+
+#[test]
+fn minimal_repro_rewrites_choice_fallthrough() {
+    let script = ScriptRaw::new(
+        vec![
+            dialogue("A", "0"),
+            EventRaw::Choice(ChoiceRaw {
+                prompt: "Ask?".to_string(),
+                options: Vec::new(),
+                auto: false,
+                fallthrough: Some("after".to_string()),
+            }),
+            dialogue("A", "2"),
+            dialogue("A", "3"),
+        ],
+        BTreeMap::from([("start".to_string(), 0usize), ("after".to_string(), 3usize)]),
+    );
+    assert!(script.minimal_repro(1, 1).is_none());
+
+    let repro = script
+        .minimal_repro(1, 2)
+        .expect("fallthrough inside window");
+    let EventRaw::Choice(choice) = &repro.events[1] else {
+        panic!("expected choice, got {:?}", repro.events[1]);
+    };
+    assert_eq!(choice.fallthrough.as_deref(), Some("repro_3"));
+    repro.compile().expect("repro compiles");
+}
+
+#[test]
+fn minimal_repro_keeps_macros_and_aliases() {
+    let mut script = ScriptRaw::new(
+        vec![
+            dialogue("A", "0"),
+            dialogue("A", "1"),
+            EventRaw::Expand {
+                name: "greet".to_string(),
+                args: vec!["ava".to_string()],
+            },
+            dialogue("A", "3"),
+        ],
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    script
+        .macros
+        .insert("greet".to_string(), vec![dialogue("$1", "Hi!")]);
+    script
+        .character_aliases
+        .insert("ava".to_string(), "???".to_string());
+
+    let repro = script.minimal_repro(2, 0).expect("no targets to rewrite");
+    assert_eq!(repro.events.len(), 1);
+    assert_eq!(repro.macros, script.macros);
+    assert_eq!(repro.character_aliases, script.character_aliases);
+    let compiled = repro.compile().expect("repro expands its macro");
+    assert_eq!(
+        compiled.character_aliases.get("ava").map(String::as_str),
+        Some("???")
+    );
+}
+
+#[test]
+fn dry_run_halts_at_breakpoint_before_executing_it() {
+    let script = ScriptRaw::new(
+        vec![
+            dialogue("A", "0"),
+            dialogue("A", "1"),
+            dialogue("B", "mid"),
+            dialogue("C", "after"),
+        ],
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    let engine = crate::Engine::new(
+        script,
+        crate::SecurityPolicy::default(),
+        crate::ResourceLimiter::default(),
+    )
+    .expect("engine");
+
+    let outcome = run_dry_run_with_breakpoints(
+        engine,
+        &ChoicePolicy::Strategy(ChoiceStrategy::First),
+        &std::collections::BTreeSet::from([2u32]),
+    );
+
+    let report = outcome.report;
+    assert_eq!(report.stop_reason, DryRunStopReason::Breakpoint);
+    assert_eq!(report.executed_steps, 2);
+    assert_eq!(report.steps.last().map(|step| step.event_ip), Some(2));
+    let state = report.breakpoint_state.expect("breakpoint state");
+    assert_eq!(state.position, 2);
+    assert_eq!(state.history.len(), 2);
+    assert!(outcome.issues.is_empty());
+}
+
+#[test]
+fn dead_branch_detected_after_flag_cleared_on_every_path() {
+    let events = vec![
+        EventRaw::SetFlag {
+            key: "flag".to_string(),
+            value: false,
+        },
+        EventRaw::JumpIf {
+            cond: crate::CondRaw::Flag {
+                key: "flag".to_string(),
+                is_set: true,
+            },
+            target: "secret".to_string(),
+        },
+        dialogue("A", "normal"),
+        dialogue("A", "secret"),
+    ];
+    let labels = BTreeMap::from([
+        ("start".to_string(), 0usize),
+        ("secret".to_string(), 3usize),
+    ]);
+    let script = ScriptRaw::new(events.clone(), labels.clone());
+
+    assert_eq!(
+        find_dead_branches(&script),
+        vec![DeadBranch {
+            event_ip: 1,
+            side: DeadBranchSide::Taken,
+        }]
+    );
+    let analysis = analyze_script(&script);
+    assert!(analysis.issues.iter().any(|issue| {
+        issue.kind == AnalysisIssueKind::DeadBranch
+            && issue.severity == AnalysisSeverity::Warning
+            && issue.event_ip == Some(1)
+    }));
+
+    // Without the SetFlag the flag's value is unknown, so nothing is provable.
+    let unknown = ScriptRaw::new(
+        events[1..].to_vec(),
+        BTreeMap::from([
+            ("start".to_string(), 0usize),
+            ("secret".to_string(), 2usize),
+        ]),
+    );
+    assert!(find_dead_branches(&unknown).is_empty());
+}
+
+#[test]
+fn dead_branch_applies_each_choice_options_effects() {
+    let set_flag = |value: bool| EventRaw::SetFlag {
+        key: "flag".to_string(),
+        value,
+    };
+    let option = |text: &str, effects: Vec<EventRaw>| ChoiceOptionRaw {
+        text: text.to_string(),
+        target: "check".to_string(),
+        weight: None,
+        effects,
+    };
+    let script_with = |effects_b: Vec<EventRaw>| {
+        ScriptRaw::new(
+            vec![
+                set_flag(false),
+                EventRaw::Choice(ChoiceRaw {
+                    prompt: "Tell?".to_string(),
+                    options: vec![option("A", vec![set_flag(true)]), option("B", effects_b)],
+                    auto: false,
+                    fallthrough: None,
+                }),
+                EventRaw::JumpIf {
+                    cond: crate::CondRaw::Flag {
+                        key: "flag".to_string(),
+                        is_set: true,
+                    },
+                    target: "secret".to_string(),
+                },
+                dialogue("A", "normal"),
+                dialogue("A", "secret"),
+            ],
+            BTreeMap::from([
+                ("start".to_string(), 0usize),
+                ("check".to_string(), 2usize),
+                ("secret".to_string(), 4usize),
+            ]),
+        )
+    };
+
+    // Only option A sets the flag, so both sides of the JumpIf are live.
+    assert!(find_dead_branches(&script_with(Vec::new())).is_empty());
+    // Both options set it, so falling through is impossible.
+    assert_eq!(
+        find_dead_branches(&script_with(vec![set_flag(true)])),
+        vec![DeadBranch {
+            event_ip: 2,
+            side: DeadBranchSide::FallThrough,
+        }]
+    );
+}
+
+#[test]
+fn coverage_of_both_routes_reaches_every_reachable_ip() {
+    let script = graph_branching_script();
+
+    let only_b = coverage_for_routes(&script, &[vec![1]]).expect("route B replays");
+    assert_eq!(only_b.unvisited_ips, vec![2]);
+    assert_eq!(only_b.unvisited_options, vec![(1, 0)]);
+    assert!((only_b.ip_percent() - 75.0).abs() < f32::EPSILON);
+
+    let both = coverage_for_routes(&script, &[vec![0], vec![1]]).expect("routes replay");
+    assert_eq!((both.visited_ips, both.reachable_ips), (4, 4));
+    assert!(both.unvisited_ips.is_empty());
+    assert!(both.unvisited_options.is_empty());
+    assert!((both.ip_percent() - 100.0).abs() < f32::EPSILON);
+    assert!((both.option_percent() - 100.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn dry_run_config_step_limit_stops_a_finishing_script() {
+    let script = ScriptRaw::new(
+        (0..4).map(|i| dialogue("A", &i.to_string())).collect(),
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    let finished = analyze_script(&script)
+        .dry_run_report
+        .expect("default report");
+    assert_eq!(finished.stop_reason, DryRunStopReason::Finished);
+
+    let config = DryRunConfig {
+        max_steps: 2,
+        ..DryRunConfig::default()
+    };
+    let analysis = analyze_script_with_config(&script, &config);
+    let report = analysis.dry_run_report.expect("limited report");
+    assert_eq!(report.stop_reason, DryRunStopReason::StepLimit);
+    assert_eq!(report.max_steps, 2);
+    assert_eq!(report.executed_steps, 2);
+    assert!(analysis
+        .issues
+        .iter()
+        .any(|issue| issue.kind == AnalysisIssueKind::StepLimit));
+    assert!(!analysis
+        .issues
+        .iter()
+        .any(|issue| issue.kind == AnalysisIssueKind::ParityMismatch));
+}
+
+#[test]
+fn parity_catches_set_var_compiled_to_the_wrong_variable() {
+    let script = ScriptRaw::new(
+        vec![
+            EventRaw::SetVar {
+                key: "trust".to_string(),
+                value: 1,
+            },
+            EventRaw::SetVar {
+                key: "gold".to_string(),
+                value: 2,
+            },
+            dialogue("A", "done"),
+        ],
+        BTreeMap::from([("start".to_string(), 0usize)]),
+    );
+    let policy = ChoicePolicy::Strategy(ChoiceStrategy::First);
+    let mut compiled = script.compile().expect("compile");
+    let engine = crate::Engine::from_compiled(
+        compiled.clone(),
+        crate::SecurityPolicy::default(),
+        crate::ResourceLimiter::default(),
+    )
+    .expect("engine");
+    let report = run_dry_run(engine, &policy).report;
+    assert!(check_preview_runtime_parity(&script, &report, &policy).is_empty());
+
+    // Simulate a compiler bug: the first SetVar writes `gold` instead of
+    // `trust`. Event kinds and signatures still match the raw script.
+    let gold = compiled
+        .var_names
+        .iter()
+        .position(|name| &**name == "gold")
+        .expect("gold id") as u32;
+    match &mut compiled.events[0] {
+        crate::EventCompiled::SetVar { var_id, .. } => *var_id = gold,
+        other => panic!("unexpected event {other:?}"),
+    }
+    let engine = crate::Engine::from_compiled(
+        compiled,
+        crate::SecurityPolicy::default(),
+        crate::ResourceLimiter::default(),
+    )
+    .expect("tampered engine");
+    let report = run_dry_run(engine, &policy).report;
+    let issues = check_preview_runtime_parity(&script, &report, &policy);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, AnalysisIssueKind::ParityMismatch);
+    assert!(issues[0].message.contains("state mismatch"));
+    assert_eq!(issues[0].event_ip, Some(1));
+}
@@ -1,10 +1,25 @@
 //! Runtime engine that executes compiled scripts.
 
 mod audio;
+mod chapters;
+mod event_stream;
+mod hooks;
+mod metrics;
+mod navigation;
+mod peek;
 mod prefetch;
+mod replay;
+mod rng;
 mod runtime;
+mod snapshot;
+mod symbols;
 
+pub use event_stream::{EngineEvent, ENGINE_EVENT_QUEUE_LIMIT};
+pub use hooks::{EventHook, EventHookId};
+pub use metrics::EngineMetrics;
+pub use peek::NextStep;
 pub use runtime::{ChoiceHistoryEntry, Engine, StateChange};
+pub use snapshot::{EngineSnapshot, VisualSummary};
 
 #[cfg(test)]
 #[path = "tests/engine_tests.rs"]
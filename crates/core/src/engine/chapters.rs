@@ -0,0 +1,38 @@
+use crate::event::{EventCompiled, SceneUpdateCompiled};
+use crate::script::ScriptSource;
+
+use super::runtime::Engine;
+
+impl<S: ScriptSource> Engine<S> {
+    /// Lists each scene `chapter` with its entry IP, in script order.
+    ///
+    /// A chapter named by several scenes is entered at the first of them.
+    /// Pass the IP to [`Engine::goto_ip`] to start playing from it.
+    pub fn chapters(&self) -> Vec<(String, u32)> {
+        let mut chapters: Vec<(String, u32)> = Vec::new();
+        for (ip, scene) in self.scenes() {
+            if let Some(chapter) = &scene.chapter {
+                if !chapters.iter().any(|(name, _)| name == &**chapter) {
+                    chapters.push((chapter.to_string(), ip));
+                }
+            }
+        }
+        chapters
+    }
+
+    /// IPs of every scene tagged `tag`, in script order.
+    pub fn scenes_with_tag(&self, tag: &str) -> Vec<u32> {
+        self.scenes()
+            .filter(|(_, scene)| scene.tags.iter().any(|candidate| &**candidate == tag))
+            .map(|(ip, _)| ip)
+            .collect()
+    }
+
+    fn scenes(&self) -> impl Iterator<Item = (u32, SceneUpdateCompiled)> + '_ {
+        let count = u32::try_from(self.source().event_count()).unwrap_or(u32::MAX);
+        (0..count).filter_map(move |ip| match self.source().event(ip)?.as_ref() {
+            EventCompiled::Scene(scene) => Some((ip, scene.clone())),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,57 @@
+//! Optional stream of gameplay events for external analytics.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::event::SharedStr;
+
+/// Events kept before the oldest is dropped; drain at least this often.
+pub const ENGINE_EVENT_QUEUE_LIMIT: usize = 256;
+
+/// Something that happened while the engine ran.
+///
+/// Events carry ids, ips and shared script strings only, so recording them
+/// never allocates; resolve names with `Engine::flag_name` or the script when
+/// needed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EngineEvent {
+    /// A dialogue became the current event.
+    DialogueShown { ip: u32 },
+    /// A choice became the current event.
+    ChoicePresented { ip: u32, option_count: usize },
+    /// `choose` picked option `index` of the choice at `ip`.
+    ChoiceMade {
+        ip: u32,
+        index: usize,
+        target_ip: u32,
+    },
+    /// The choice at `ip` had nothing to pick and jumped to its fallthrough.
+    ChoiceFellThrough { ip: u32, target_ip: u32 },
+    /// A `SetFlag` event changed the flag's value.
+    FlagChanged { flag_id: u32, value: bool },
+    /// A `Scene` event was applied.
+    SceneChanged { ip: u32 },
+    /// An `Achieve` event recorded achievement `id` for the first time.
+    AchievementUnlocked { ip: u32, id: SharedStr },
+}
+
+/// Bounded queue behind `Engine::drain_events`.
+#[derive(Clone, Debug, Default)]
+pub(super) struct EngineEventQueue {
+    events: VecDeque<EngineEvent>,
+}
+
+impl EngineEventQueue {
+    pub(super) fn push(&mut self, event: EngineEvent) {
+        if self.events.len() >= ENGINE_EVENT_QUEUE_LIMIT {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    pub(super) fn drain(&mut self) -> Vec<EngineEvent> {
+        self.events.drain(..).collect()
+    }
+}
@@ -0,0 +1,62 @@
+//! Observer callbacks fired when the engine lands on a new event.
+
+use std::fmt;
+
+use crate::event::EventCompiled;
+
+/// Callback observing each new current event; it only receives a shared borrow.
+///
+/// `Sync` keeps [`crate::Engine`] shareable across threads, which embedders
+/// such as the Python bindings rely on.
+pub type EventHook = Box<dyn FnMut(&EventCompiled) + Send + Sync>;
+
+/// Handle returned by `Engine::on_event`, used to remove the callback later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EventHookId(u64);
+
+#[derive(Default)]
+pub(super) struct EventHooks {
+    next_id: u64,
+    hooks: Vec<(EventHookId, EventHook)>,
+}
+
+impl EventHooks {
+    pub(super) fn register(&mut self, hook: EventHook) -> EventHookId {
+        self.next_id += 1;
+        let id = EventHookId(self.next_id);
+        self.hooks.push((id, hook));
+        id
+    }
+
+    pub(super) fn remove(&mut self, id: EventHookId) -> bool {
+        let before = self.hooks.len();
+        self.hooks.retain(|(hook_id, _)| *hook_id != id);
+        self.hooks.len() != before
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.hooks.clear();
+    }
+
+    pub(super) fn notify(&mut self, event: &EventCompiled) {
+        for (_, hook) in &mut self.hooks {
+            hook(event);
+        }
+    }
+}
+
+// Callbacks are tied to the embedder that registered them, so cloned engines
+// start without hooks.
+impl Clone for EventHooks {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for EventHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventHooks")
+            .field("count", &self.hooks.len())
+            .finish()
+    }
+}
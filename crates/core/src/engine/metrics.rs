@@ -0,0 +1,92 @@
+//! Optional playthrough counters for profiling and analytics.
+
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// `std::time::Instant::now` panics on wasm32-unknown-unknown.
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::event::EventCompiled;
+
+/// Counters collected while the engine runs.
+///
+/// Only recorded when `ResourceLimiter::collect_metrics` is set; otherwise the
+/// engine keeps no recorder and `Engine::metrics` returns `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineMetrics {
+    /// Events the engine moved past, via `step` or `choose`.
+    pub events_visited: u64,
+    /// Choices resolved through `choose`.
+    pub choices_made: u64,
+    /// Visits keyed by event kind (`"dialogue"`, `"choice"`, ...).
+    pub events_by_kind: BTreeMap<String, u64>,
+    /// Wall-clock microseconds spent on each event kind before leaving it.
+    pub micros_by_kind: BTreeMap<String, u64>,
+}
+
+impl EngineMetrics {
+    /// Returns how many events of the given kind were visited.
+    pub fn visits(&self, kind: &str) -> u64 {
+        self.events_by_kind.get(kind).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct MetricsRecorder {
+    metrics: EngineMetrics,
+    entered_at: Instant,
+}
+
+impl MetricsRecorder {
+    pub(super) fn new(metrics: EngineMetrics) -> Self {
+        Self {
+            metrics,
+            entered_at: Instant::now(),
+        }
+    }
+
+    pub(super) fn metrics(&self) -> &EngineMetrics {
+        &self.metrics
+    }
+
+    /// Records a `step`; choices with options wait for `choose` so they are
+    /// not counted twice.
+    pub(super) fn record_step(&mut self, event: &EventCompiled) {
+        if !matches!(event, EventCompiled::Choice(choice) if !choice.options.is_empty()) {
+            self.leave(event.kind());
+        }
+    }
+
+    pub(super) fn record_choice(&mut self) {
+        self.metrics.choices_made += 1;
+        self.leave("choice");
+    }
+
+    /// Records a choice left through its fallthrough; no option was chosen.
+    pub(super) fn record_fallthrough(&mut self) {
+        self.leave("choice");
+    }
+
+    fn leave(&mut self, kind: &str) {
+        let now = Instant::now();
+        let micros =
+            u64::try_from(now.duration_since(self.entered_at).as_micros()).unwrap_or(u64::MAX);
+        self.entered_at = now;
+
+        self.metrics.events_visited += 1;
+        *self
+            .metrics
+            .events_by_kind
+            .entry(kind.to_string())
+            .or_default() += 1;
+        let spent = self
+            .metrics
+            .micros_by_kind
+            .entry(kind.to_string())
+            .or_default();
+        *spent = spent.saturating_add(micros);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::event::EventCompiled;
+use crate::script::ScriptSource;
+use crate::visual::VisualState;
+
+/// Rebuilds the visual state a player would see on arriving at `ip`.
+///
+/// Starts from the nearest `Scene` at or before `ip` and replays every
+/// scene-affecting event up to it. A `Scene` at `ip` itself is applied, the
+/// same way the engine applies the start scene on boot.
+///
+/// The replay follows script order, not control flow: when `ip` is reached
+/// by a jump, scenes from sibling branches in between are applied too.
+pub(super) fn visual_state_at<S: ScriptSource>(script: &S, ip: u32) -> VisualState {
+    let ip = ip.min(script.event_count() as u32);
+    let anchor = (0..ip)
+        .rev()
+        .find(|&index| {
+            matches!(
+                script.event(index).as_deref(),
+                Some(EventCompiled::Scene(_))
+            )
+        })
+        .unwrap_or(0);
+    let mut visual = VisualState::default();
+    for index in anchor..ip {
+        if let Some(event) = script.event(index) {
+            apply_visual_event(&mut visual, &event);
+        }
+    }
+    if let Some(EventCompiled::Scene(scene)) = script.event(ip).as_deref() {
+        visual.apply_scene(scene);
+    }
+    visual
+}
+
+fn apply_visual_event(visual: &mut VisualState, event: &EventCompiled) {
+    match event {
+        EventCompiled::Scene(scene) => visual.apply_scene(scene),
+        EventCompiled::Patch(patch) => visual.apply_patch(patch),
+        EventCompiled::SetCharacterPosition(pos) => visual.set_character_position(pos),
+        _ => {}
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::{VnError, VnResult};
+use crate::event::{CmpOp, CondCompiled, EventCompiled};
+use crate::script::ScriptSource;
+
+use super::runtime::Engine;
+
+/// What [`Engine::step`] would do with the current event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NextStep {
+    /// Position of the event about to run.
+    pub ip: u32,
+    /// Event type tag, as returned by [`EventCompiled::kind`].
+    pub kind: &'static str,
+    /// Position after the step; `None` for choices and ext calls, which wait
+    /// for [`Engine::choose`] or [`Engine::resume`].
+    pub next_ip: Option<u32>,
+    /// For `JumpIf`, whether the condition holds against the current state.
+    pub branch_taken: Option<bool>,
+    pub is_choice: bool,
+}
+
+impl<S: ScriptSource> Engine<S> {
+    /// Resolves the next step against the current state without running it.
+    ///
+    /// Nothing is mutated: position, flags, history and hooks are untouched.
+    /// Errors match what [`Engine::step`] would report.
+    pub fn peek_next(&self) -> VnResult<NextStep> {
+        let ip = self.state().position;
+        let event = self.current_event_ref()?;
+        if let EventCompiled::ExtCall { command, .. } = event {
+            self.policy().check_ext_call(command)?;
+        }
+        let event_count = self.source().event_count() as u32;
+        let fall_through = ip.saturating_add(1).min(event_count);
+        let checked_target = |target_ip: u32| {
+            if target_ip >= event_count {
+                Err(VnError::corrupt_script(format!(
+                    "jump target '{target_ip}' outside script"
+                )))
+            } else {
+                Ok(target_ip)
+            }
+        };
+        let (next_ip, branch_taken) = match event {
+            EventCompiled::Jump { target_ip } => (Some(checked_target(*target_ip)?), None),
+            EventCompiled::JumpIf { cond, target_ip } => {
+                if self.evaluate_cond(cond)? {
+                    (Some(checked_target(*target_ip)?), Some(true))
+                } else {
+                    (Some(fall_through), Some(false))
+                }
+            }
+            EventCompiled::Choice(choice) if choice.options.is_empty() => {
+                match choice.fallthrough_ip {
+                    Some(target_ip) => (Some(checked_target(target_ip)?), None),
+                    None => return Err(VnError::NoChoiceOptions { ip }),
+                }
+            }
+            EventCompiled::Choice(_)
+            | EventCompiled::ExtCall { .. }
+            | EventCompiled::InputText { .. } => (None, None),
+            _ => (Some(fall_through), None),
+        };
+        Ok(NextStep {
+            ip,
+            kind: event.kind(),
+            next_ip,
+            branch_taken,
+            is_choice: matches!(event, EventCompiled::Choice(_)),
+        })
+    }
+
+    /// Describes the current event for the inspector, with symbol names,
+    /// conditions evaluated against the current state and resolved targets,
+    /// e.g. `JumpIf (var affection >= 3 → true) → will jump to IP 42 (label 'good_end')`.
+    pub fn explain_current(&self) -> String {
+        let event = match self.current_event_ref() {
+            Ok(event) => event,
+            Err(VnError::EndOfScript) => return "End of script".to_string(),
+            Err(err) => return format!("Unavailable: {err}"),
+        };
+        let next = match self.peek_next() {
+            Ok(next) => next,
+            Err(err) => return format!("{} at IP {}: {err}", event.kind(), self.state().position),
+        };
+        let next_ip = next.next_ip.unwrap_or(next.ip);
+        match event {
+            EventCompiled::Dialogue(dialogue) => format!(
+                "Dialogue {}: \"{}\" → continues at {}",
+                &*dialogue.speaker,
+                &*dialogue.text,
+                self.describe_ip(next_ip)
+            ),
+            EventCompiled::Choice(choice) => {
+                let options = choice
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(index, option)| {
+                        format!(
+                            "[{index}] '{}' → {}",
+                            &*option.text,
+                            self.describe_ip(option.target_ip)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!(
+                    "Choice '{}' waiting for input: {}",
+                    &*choice.prompt,
+                    options.join("; ")
+                )
+            }
+            EventCompiled::Jump { .. } => format!("Jump → {}", self.describe_ip(next_ip)),
+            EventCompiled::JumpIf { cond, .. } => {
+                let taken = next.branch_taken.unwrap_or(false);
+                let outcome = if taken {
+                    "will jump to"
+                } else {
+                    "falls through to"
+                };
+                format!(
+                    "JumpIf ({} → {taken}) → {outcome} {}",
+                    self.describe_cond(cond),
+                    self.describe_ip(next_ip)
+                )
+            }
+            EventCompiled::SetFlag { flag_id, value } => format!(
+                "SetFlag {} = {value} (currently {})",
+                self.flag_label(*flag_id),
+                self.state().get_flag(*flag_id)
+            ),
+            EventCompiled::SetVar { var_id, value } => format!(
+                "SetVar {} = {value} (currently {})",
+                self.var_label(*var_id),
+                self.state().get_var(*var_id)
+            ),
+            EventCompiled::SetStrVar { str_var_id, value } => format!(
+                "SetStrVar {} = {value:?} (currently {:?})",
+                self.str_var_label(*str_var_id),
+                self.state().get_str_var(*str_var_id)
+            ),
+            EventCompiled::UnlockGallery { id } => format!(
+                "UnlockGallery '{id}' (already unlocked: {})",
+                self.state().unlocked.contains(&**id)
+            ),
+            EventCompiled::Achieve { id } => format!(
+                "Achieve '{id}' (already achieved: {})",
+                self.state().achievements.contains(&**id)
+            ),
+            EventCompiled::ExtCall { command, args } => format!(
+                "ExtCall '{command}' with {} arg(s), waiting for resume",
+                args.len()
+            ),
+            EventCompiled::InputText {
+                str_var_id, prompt, ..
+            } => format!(
+                "InputText '{prompt}' waiting for submit_text into '{}'",
+                self.str_var_label(*str_var_id)
+            ),
+            other => format!(
+                "{} → continues at {}",
+                other.kind(),
+                self.describe_ip(next_ip)
+            ),
+        }
+    }
+
+    fn describe_cond(&self, cond: &CondCompiled) -> String {
+        match cond {
+            CondCompiled::Flag { flag_id, is_set } => format!(
+                "flag {} is {}",
+                self.flag_label(*flag_id),
+                if *is_set { "set" } else { "unset" }
+            ),
+            CondCompiled::VarCmp { var_id, op, value } => {
+                let op = match op {
+                    CmpOp::Eq => "==",
+                    CmpOp::Ne => "!=",
+                    CmpOp::Lt => "<",
+                    CmpOp::Le => "<=",
+                    CmpOp::Gt => ">",
+                    CmpOp::Ge => ">=",
+                };
+                format!("var {} {op} {value}", self.var_label(*var_id))
+            }
+            CondCompiled::StrEq { str_var_id, value } => {
+                format!("str var {} == {value:?}", self.str_var_label(*str_var_id))
+            }
+        }
+    }
+
+    /// `IP n`, plus the first label pointing there, or `end of script`.
+    fn describe_ip(&self, ip: u32) -> String {
+        if ip as usize >= self.source().event_count() {
+            return format!("IP {ip} (end of script)");
+        }
+        match self.labels().iter().find(|(_, target)| **target == ip) {
+            Some((label, _)) => format!("IP {ip} (label '{label}')"),
+            None => format!("IP {ip}"),
+        }
+    }
+
+    fn flag_label(&self, id: u32) -> String {
+        self.flag_name(id)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("#{id}"))
+    }
+
+    fn var_label(&self, id: u32) -> String {
+        self.var_name(id)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("#{id}"))
+    }
+
+    fn str_var_label(&self, id: u32) -> String {
+        self.str_var_name(id)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("#{id}"))
+    }
+}
@@ -1,21 +1,21 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
 
 use crate::assets::AssetId;
 use crate::event::EventCompiled;
+use crate::script::ScriptSource;
 
 use super::runtime::Engine;
 
-impl Engine {
+impl<S: ScriptSource> Engine<S> {
     /// Returns unique upcoming asset paths that can be prefetched safely.
     ///
     /// This intentionally excludes non-path semantic fields to avoid prefetching invalid resources.
     pub fn peek_next_asset_paths(&self, depth: usize) -> Vec<String> {
         let mut seen = HashSet::new();
         let mut paths = Vec::new();
-        let start = self.state().position as usize;
-        let end = (start + depth).min(self.script().events.len());
-        for event in &self.script().events[start..end] {
-            collect_prefetch_paths_from_event(event, &mut seen, &mut paths);
+        for event in self.upcoming_events(depth) {
+            collect_prefetch_paths_from_event(&event, &mut seen, &mut paths);
         }
         paths
     }
@@ -24,10 +24,8 @@ impl Engine {
     pub fn peek_next_assets(&self, depth: usize) -> Vec<AssetId> {
         let mut seen = HashSet::new();
         let mut assets = Vec::new();
-        let start = self.state().position as usize;
-        let end = (start + depth).min(self.script().events.len());
-        for event in &self.script().events[start..end] {
-            match event {
+        for event in self.upcoming_events(depth) {
+            match &*event {
                 EventCompiled::Scene(scene) => {
                     if let Some(background) = &scene.background {
                         let id = AssetId::from_path(background.as_ref());
@@ -97,6 +95,13 @@ impl Engine {
         }
         assets
     }
+
+    /// The next `depth` events from the current position.
+    fn upcoming_events(&self, depth: usize) -> impl Iterator<Item = Cow<'_, EventCompiled>> {
+        let start = self.state().position as usize;
+        let end = start.saturating_add(depth).min(self.source().event_count());
+        (start..end).filter_map(|ip| self.source().event(ip as u32))
+    }
 }
 
 fn collect_prefetch_paths_from_event(
@@ -0,0 +1,120 @@
+use crate::error::{VnError, VnResult};
+use crate::event::{ChoiceCompiled, EventCompiled};
+use crate::script::ScriptSource;
+use crate::state::EngineState;
+use crate::trace::{StateDigest, UiTrace, UiView};
+
+use super::runtime::Engine;
+
+/// Events a replay may execute before it is treated as a loop.
+const REPLAY_MAX_STEPS: usize = 100_000;
+
+impl<S: ScriptSource> Engine<S> {
+    /// Records a [`UiTrace`] of playing `choices` from the current event on a
+    /// copy of the engine, leaving `self` untouched.
+    ///
+    /// Dialogue text and choice prompts/options are replaced by
+    /// [`UiView::digested`] hashes so golden files stay small; the trace is
+    /// byte-for-byte reproducible for the same script and choices.
+    pub fn record_trace(&self, choices: &[usize]) -> VnResult<UiTrace>
+    where
+        S: Clone,
+    {
+        let mut engine = self.clone();
+        let flag_count = engine.flag_count() as usize;
+        let mut trace = UiTrace::new();
+        engine.replay_choice_log_traced(choices, |state, event, _| {
+            let step = trace.steps.len() as u32;
+            trace.push(
+                step,
+                UiView::from_event(event).digested(),
+                StateDigest::from_state(state, flag_count),
+            );
+        })?;
+        Ok(trace)
+    }
+
+    /// Plays `choices` from the current event on a copy of the engine and
+    /// returns one compiled-event JSON object per line (NDJSON), ready to pipe
+    /// into `jq`.
+    ///
+    /// Every visited event gets a line, in order. If the replay fails, the
+    /// output ends with the event that failed.
+    pub fn replay_ndjson(&self, choices: &[usize]) -> String
+    where
+        S: Clone,
+    {
+        let mut engine = self.clone();
+        let mut output = String::new();
+        let _ = engine.replay_choice_log_traced(choices, |_, event, _| {
+            output.push_str(&event.to_json_string());
+            output.push('\n');
+        });
+        output
+    }
+
+    /// Plays forward from the current event, answering each choice with the
+    /// next index from `log`, and returns every event executed.
+    ///
+    /// Steps like the dry run: ext calls are resumed immediately and text
+    /// inputs get an empty answer. Choices without options, and auto choices
+    /// whose options are all weighted out, take their fallthrough and use no
+    /// entry from `log`. Stops at the end of the script
+    /// or at the first choice once `log` is used up, so a
+    /// [`Engine::choice_log`] recorded from `start` re-walks the same route.
+    pub fn replay_choice_log(&mut self, log: &[usize]) -> VnResult<Vec<EventCompiled>> {
+        let mut events = Vec::new();
+        self.replay_choice_log_traced(log, |_, event, _| events.push(event.clone()))?;
+        Ok(events)
+    }
+
+    /// [`Engine::replay_choice_log`] that shows `visit` the state before each
+    /// event runs, the event, and for choices the option about to be taken.
+    pub(crate) fn replay_choice_log_traced(
+        &mut self,
+        log: &[usize],
+        mut visit: impl FnMut(&EngineState, &EventCompiled, Option<usize>),
+    ) -> VnResult<()> {
+        let mut choices = log.iter();
+        for _ in 0..REPLAY_MAX_STEPS {
+            let event = match self.current_event() {
+                Ok(event) => event,
+                Err(VnError::EndOfScript) => return Ok(()),
+                Err(err) => return Err(err),
+            };
+            let chosen = match &event {
+                EventCompiled::Choice(choice) if awaits_selection(choice) => match choices.next() {
+                    Some(option_index) => Some(*option_index),
+                    None => return Ok(()),
+                },
+                _ => None,
+            };
+            visit(self.state(), &event, chosen);
+            match (&event, chosen) {
+                (EventCompiled::Choice(_), Some(option_index)) => {
+                    self.choose(option_index)?;
+                }
+                (EventCompiled::Choice(choice), None) if !choice.options.is_empty() => {
+                    self.auto_choose_weighted()?;
+                }
+                (EventCompiled::ExtCall { .. }, _) => self.resume()?,
+                (EventCompiled::InputText { .. }, _) => self.submit_text("")?,
+                _ => {
+                    self.step()?;
+                }
+            }
+        }
+        Err(VnError::ResourceLimit(format!(
+            "replay exceeded {REPLAY_MAX_STEPS} steps"
+        )))
+    }
+}
+
+/// Whether `choice` waits for an option from the log rather than falling
+/// through on its own.
+fn awaits_selection(choice: &ChoiceCompiled) -> bool {
+    let weighted_out = choice.auto
+        && choice.fallthrough_ip.is_some()
+        && choice.options.iter().all(|option| option.weight == Some(0));
+    !choice.options.is_empty() && !weighted_out
+}
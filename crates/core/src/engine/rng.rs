@@ -0,0 +1,46 @@
+//! Seeded random source for engine-side picks such as auto choices.
+
+/// Seed used until [`crate::Engine::seed_rng`] is called.
+pub(super) const DEFAULT_RNG_SEED: u64 = 0;
+
+/// SplitMix64: small and platform independent, so a seed always replays
+/// the same sequence.
+#[derive(Clone, Debug)]
+pub(super) struct EngineRng {
+    state: u64,
+}
+
+impl EngineRng {
+    pub(super) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(super) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Index chosen by `roll` with odds proportional to `weights`, or uniformly
+/// when every weight is zero; `None` only for an empty slice.
+pub(super) fn pick_weighted(weights: &[u32], roll: u64) -> Option<usize> {
+    if weights.is_empty() {
+        return None;
+    }
+    let total: u64 = weights.iter().map(|weight| u64::from(*weight)).sum();
+    if total == 0 {
+        return Some((roll % weights.len() as u64) as usize);
+    }
+    let mut remaining = roll % total;
+    for (index, weight) in weights.iter().enumerate() {
+        let weight = u64::from(*weight);
+        if remaining < weight {
+            return Some(index);
+        }
+        remaining -= weight;
+    }
+    Some(weights.len() - 1)
+}
@@ -1,15 +1,22 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 
 use crate::audio::AudioCommand;
 use crate::error::{VnError, VnResult};
 use crate::event::{CmpOp, CondCompiled, EventCompiled};
 use crate::render::{RenderBackend, RenderOutput};
 use crate::resource::ResourceLimiter;
-use crate::script::{ScriptCompiled, ScriptRaw};
-use crate::security::SecurityPolicy;
+use crate::script::{LazyScript, ScriptCompiled, ScriptRaw, ScriptSource};
+use crate::security::{check_character_alias, SecurityPolicy};
 use crate::state::EngineState;
+use crate::ui::{UiState, UiView};
+use crate::visual::{append_visual_delta, VisualCommand};
 
 use super::audio::{append_music_delta, audio_command_from_action, initial_audio_commands};
+use super::event_stream::{EngineEvent, EngineEventQueue};
+use super::hooks::{EventHook, EventHookId, EventHooks};
+use super::metrics::{EngineMetrics, MetricsRecorder};
+use super::navigation::visual_state_at;
+use super::rng::{pick_weighted, EngineRng, DEFAULT_RNG_SEED};
 
 const CHOICE_HISTORY_LIMIT: usize = 512;
 
@@ -23,16 +30,32 @@ pub struct ChoiceHistoryEntry {
 }
 
 /// Execution engine for compiled scripts.
+///
+/// `S` is the script storage: an eager [`ScriptCompiled`] (the default) or a
+/// [`LazyScript`] that decodes events as the instruction pointer advances.
 #[derive(Clone, Debug)]
-pub struct Engine {
-    script: ScriptCompiled,
+pub struct Engine<S = ScriptCompiled> {
+    script: S,
     state: EngineState,
     policy: SecurityPolicy,
     queued_audio: Vec<AudioCommand>,
+    queued_visual: Vec<VisualCommand>,
     read_dialogue_ips: BTreeSet<u32>,
     choice_history: VecDeque<ChoiceHistoryEntry>,
+    metrics: Option<MetricsRecorder>,
+    event_stream: Option<EngineEventQueue>,
+    event_hooks: EventHooks,
+    paused: bool,
+    rng: EngineRng,
+    limits: ResourceLimiter,
 }
 
+// Embedders (e.g. the Python bindings) share engines across threads.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Engine>();
+};
+
 impl Engine {
     /// Builds an engine by validating and compiling a raw script.
     pub fn new(
@@ -52,35 +75,83 @@ impl Engine {
         limits: ResourceLimiter,
     ) -> VnResult<Self> {
         policy.validate_compiled(&script, limits)?;
-        Ok(Self::from_validated_compiled(script, policy))
+        let mut engine = Self::from_validated_compiled(script, policy, limits);
+        if limits.collect_metrics {
+            engine.metrics = Some(MetricsRecorder::new(EngineMetrics::default()));
+        }
+        Ok(engine)
+    }
+
+    pub(super) fn from_validated_compiled(
+        script: ScriptCompiled,
+        policy: SecurityPolicy,
+        limits: ResourceLimiter,
+    ) -> Self {
+        Self::from_validated_source(script, policy, limits)
+    }
+
+    /// Returns a reference to the compiled script.
+    pub fn script(&self) -> &ScriptCompiled {
+        &self.script
+    }
+}
+
+impl Engine<LazyScript> {
+    /// Builds an engine over a lazily decoded script.
+    ///
+    /// Validation streams through every event once; afterwards only the
+    /// window around the instruction pointer stays decoded.
+    pub fn from_lazy(
+        script: LazyScript,
+        policy: SecurityPolicy,
+        limits: ResourceLimiter,
+    ) -> VnResult<Self> {
+        policy.validate_compiled(&script, limits)?;
+        let mut engine = Self::from_validated_source(script, policy, limits);
+        if limits.collect_metrics {
+            engine.metrics = Some(MetricsRecorder::new(EngineMetrics::default()));
+        }
+        Ok(engine)
     }
+}
 
-    fn from_validated_compiled(script: ScriptCompiled, policy: SecurityPolicy) -> Self {
-        let state = initialize_state(&script);
+impl<S: ScriptSource> Engine<S> {
+    fn from_validated_source(
+        mut script: S,
+        policy: SecurityPolicy,
+        limits: ResourceLimiter,
+    ) -> Self {
+        let state = initialize_state(&mut script);
         let queued_audio = initial_audio_commands(&state);
         Self {
             script,
             state,
             policy,
             queued_audio,
+            queued_visual: Vec::new(),
             read_dialogue_ips: BTreeSet::new(),
             choice_history: VecDeque::with_capacity(64),
+            metrics: None,
+            event_stream: None,
+            event_hooks: EventHooks::default(),
+            paused: false,
+            rng: EngineRng::new(DEFAULT_RNG_SEED),
+            limits,
         }
     }
 
-    /// Returns a reference to the compiled script.
-    pub fn script(&self) -> &ScriptCompiled {
+    /// Returns the script storage backing this engine.
+    pub fn source(&self) -> &S {
         &self.script
     }
 
     /// Returns a reference to the current compiled event.
     pub fn current_event_ref(&self) -> VnResult<&EventCompiled> {
-        if self.state.position as usize >= self.script.events.len() {
+        if self.state.position as usize >= self.script.event_count() {
             return Err(VnError::EndOfScript);
         }
         self.script
-            .events
-            .get(self.state.position as usize)
+            .resident_event(self.state.position)
             .ok_or(VnError::EndOfScript)
     }
 
@@ -92,8 +163,28 @@ impl Engine {
     /// Advances the engine by applying the current event.
     pub fn step(&mut self) -> VnResult<(Vec<AudioCommand>, StateChange)> {
         let event = self.current_event()?;
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("engine.step", ip = self.state.position, kind = event.kind())
+                .entered();
+        if let EventCompiled::ExtCall { command, .. } = &event {
+            self.policy.check_ext_call(command)?;
+        }
         let mut audio_commands = self.take_audio_commands();
         self.advance_from(&event, &mut audio_commands)?;
+        if let Some(recorder) = &mut self.metrics {
+            recorder.record_step(&event);
+        }
+        let waits_for_choice =
+            matches!(&event, EventCompiled::Choice(choice) if !choice.options.is_empty());
+        if !waits_for_choice
+            && !matches!(
+                event,
+                EventCompiled::ExtCall { .. } | EventCompiled::InputText { .. }
+            )
+        {
+            self.notify_event_hooks();
+        }
         let change = StateChange {
             event,
             visual: self.state.visual.clone(),
@@ -110,25 +201,191 @@ impl Engine {
     /// Applies a choice selection on the current choice event.
     pub fn choose(&mut self, option_index: usize) -> VnResult<EventCompiled> {
         let event = self.current_event()?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "engine.choose",
+            ip = self.state.position,
+            kind = event.kind(),
+            option_index
+        )
+        .entered();
         match &event {
             EventCompiled::Choice(choice) => {
                 let option = choice
                     .options
                     .get(option_index)
                     .ok_or(VnError::InvalidChoice)?;
+                self.check_choice_effects(&option.effects)?;
+                self.state.choice_log.push(option_index);
+                self.record_engine_event(EngineEvent::ChoiceMade {
+                    ip: self.state.position,
+                    index: option_index,
+                    target_ip: option.target_ip,
+                });
                 self.record_choice_decision(
                     self.state.position,
                     option_index,
                     option.text.as_ref(),
                     option.target_ip,
                 );
+                self.apply_choice_effects(&option.effects);
                 self.jump_to_ip(option.target_ip)?;
+                if let Some(recorder) = &mut self.metrics {
+                    recorder.record_choice();
+                }
+                self.notify_event_hooks();
             }
             _ => return Err(VnError::InvalidChoice),
         }
         Ok(event)
     }
 
+    /// Answers the pending `input_text` event and advances past it.
+    ///
+    /// Control characters are dropped, surrounding whitespace trimmed and the
+    /// result cut to the event's `max_len` characters before it is stored in
+    /// the event's string variable. Fails with [`VnError::NoPendingInput`]
+    /// when the current event is not `input_text`; `step` never advances past
+    /// one.
+    pub fn submit_text(&mut self, value: &str) -> VnResult<()> {
+        let (str_var_id, max_len) = match self.current_event_ref()? {
+            EventCompiled::InputText {
+                str_var_id,
+                max_len,
+                ..
+            } => (*str_var_id, *max_len as usize),
+            _ => return Err(VnError::NoPendingInput),
+        };
+        self.check_str_var_id(str_var_id)?;
+        let cleaned: String = value.chars().filter(|ch| !ch.is_control()).collect();
+        let stored: String = cleaned.trim().chars().take(max_len).collect();
+        self.state.set_str_var(str_var_id, stored.into());
+        self.advance_position()?;
+        self.notify_event_hooks();
+        Ok(())
+    }
+
+    /// Reseeds the generator behind [`Engine::auto_choose_weighted`]. Its
+    /// state is not saved, so reseed after loading to replay the same picks.
+    pub fn seed_rng(&mut self, seed: u64) {
+        self.rng = EngineRng::new(seed);
+    }
+
+    /// Resolves the current `auto` choice by a seeded random pick weighted by
+    /// each option's `weight` (`None` counts as 1), then follows it like
+    /// [`Engine::choose`].
+    ///
+    /// Zero-weight options are switched off; when every option is, the choice
+    /// jumps to its fallthrough, or picks uniformly if it has none.
+    pub fn auto_choose_weighted(&mut self) -> VnResult<EventCompiled> {
+        let (weights, fallthrough_ip): (Vec<u32>, _) = match self.current_event_ref()? {
+            EventCompiled::Choice(choice) if choice.auto => (
+                choice
+                    .options
+                    .iter()
+                    .map(|option| option.weight.unwrap_or(1))
+                    .collect(),
+                choice.fallthrough_ip,
+            ),
+            _ => return Err(VnError::InvalidChoice),
+        };
+        let target_ip = fallthrough_ip.filter(|_| weights.iter().all(|weight| *weight == 0));
+        if let Some(target_ip) = target_ip {
+            let event = self.current_event()?;
+            self.take_fallthrough(target_ip)?;
+            if let Some(recorder) = &mut self.metrics {
+                recorder.record_fallthrough();
+            }
+            self.notify_event_hooks();
+            return Ok(event);
+        }
+        let option_index =
+            pick_weighted(&weights, self.rng.next_u64()).ok_or(VnError::NoChoiceOptions {
+                ip: self.state.position,
+            })?;
+        self.choose(option_index)
+    }
+
+    /// Leaves the current choice for `target_ip` without a selection, so
+    /// nothing is added to the choice log.
+    fn take_fallthrough(&mut self, target_ip: u32) -> VnResult<()> {
+        self.record_engine_event(EngineEvent::ChoiceFellThrough {
+            ip: self.state.position,
+            target_ip,
+        });
+        self.jump_to_ip(target_ip)
+    }
+
+    /// Registers a callback invoked with the new current event after each
+    /// `step`, `choose`, `resume` or `goto_ip` that moves the engine.
+    pub fn on_event(&mut self, callback: EventHook) -> EventHookId {
+        self.event_hooks.register(callback)
+    }
+
+    /// Removes a callback registered with [`Engine::on_event`]; returns `false` if unknown.
+    pub fn remove_event_hook(&mut self, id: EventHookId) -> bool {
+        self.event_hooks.remove(id)
+    }
+
+    /// Removes every registered event callback.
+    pub fn clear_event_hooks(&mut self) {
+        self.event_hooks.clear();
+    }
+
+    fn notify_event_hooks(&mut self) {
+        self.record_arrival();
+        if let Some(event) = self.script.resident_event(self.state.position) {
+            self.event_hooks.notify(event);
+        }
+    }
+
+    /// Starts recording [`EngineEvent`]s for [`Engine::drain_events`],
+    /// beginning with the current event. Disabled by default, so engines that
+    /// never call this pay nothing.
+    pub fn enable_event_stream(&mut self) {
+        if self.event_stream.is_none() {
+            self.event_stream = Some(EngineEventQueue::default());
+            self.record_arrival();
+        }
+    }
+
+    /// Stops recording and discards undrained events.
+    pub fn disable_event_stream(&mut self) {
+        self.event_stream = None;
+    }
+
+    /// Returns the events recorded since the last drain, oldest first. Only
+    /// the last [`ENGINE_EVENT_QUEUE_LIMIT`](super::ENGINE_EVENT_QUEUE_LIMIT)
+    /// are kept.
+    pub fn drain_events(&mut self) -> Vec<EngineEvent> {
+        self.event_stream
+            .as_mut()
+            .map(EngineEventQueue::drain)
+            .unwrap_or_default()
+    }
+
+    fn record_engine_event(&mut self, event: EngineEvent) {
+        if let Some(stream) = &mut self.event_stream {
+            stream.push(event);
+        }
+    }
+
+    fn record_arrival(&mut self) {
+        if self.event_stream.is_none() {
+            return;
+        }
+        let ip = self.state.position;
+        let arrival = match self.script.resident_event(ip) {
+            Some(EventCompiled::Dialogue(_)) => EngineEvent::DialogueShown { ip },
+            Some(EventCompiled::Choice(choice)) => EngineEvent::ChoicePresented {
+                ip,
+                option_count: choice.options.len(),
+            },
+            _ => return,
+        };
+        self.record_engine_event(arrival);
+    }
+
     fn advance_from(
         &mut self,
         event: &EventCompiled,
@@ -138,15 +395,30 @@ impl Engine {
         match event {
             EventCompiled::Jump { target_ip } => self.jump_to_ip(*target_ip),
             EventCompiled::SetFlag { flag_id, value } => {
+                self.check_flag_id(*flag_id)?;
+                if self.state.get_flag(*flag_id) != *value {
+                    self.record_engine_event(EngineEvent::FlagChanged {
+                        flag_id: *flag_id,
+                        value: *value,
+                    });
+                }
                 self.state.set_flag(*flag_id, *value);
                 self.advance_position()
             }
             EventCompiled::Scene(scene) => {
-                let before_music = self.state.visual.music.clone();
+                self.record_engine_event(EngineEvent::SceneChanged { ip: current_ip });
+                let before = self.state.visual.clone();
                 self.state.visual.apply_scene(scene);
-                append_music_delta(before_music, &self.state.visual.music, audio_commands);
+                append_visual_delta(&before, &self.state.visual, &mut self.queued_visual);
+                append_music_delta(before.music, &self.state.visual.music, audio_commands);
                 self.advance_position()
             }
+            EventCompiled::Choice(choice) if choice.options.is_empty() => {
+                match choice.fallthrough_ip {
+                    Some(target_ip) => self.take_fallthrough(target_ip),
+                    None => Err(VnError::NoChoiceOptions { ip: current_ip }),
+                }
+            }
             EventCompiled::Choice(_) => Ok(()),
             EventCompiled::Dialogue(dialogue) => {
                 self.state.record_dialogue(dialogue);
@@ -154,23 +426,43 @@ impl Engine {
                 self.advance_position()
             }
             EventCompiled::SetVar { var_id, value } => {
+                self.check_var_id(*var_id)?;
                 self.state.set_var(*var_id, *value);
                 self.advance_position()
             }
+            EventCompiled::SetStrVar { str_var_id, value } => {
+                self.check_str_var_id(*str_var_id)?;
+                self.state.set_str_var(*str_var_id, value.clone());
+                self.advance_position()
+            }
+            EventCompiled::UnlockGallery { id } => {
+                self.state.unlocked.insert(id.to_string());
+                self.advance_position()
+            }
+            EventCompiled::Achieve { id } => {
+                if self.state.achievements.insert(id.to_string()) {
+                    self.record_engine_event(EngineEvent::AchievementUnlocked {
+                        ip: current_ip,
+                        id: id.clone(),
+                    });
+                }
+                self.advance_position()
+            }
             EventCompiled::JumpIf { cond, target_ip } => {
-                if self.evaluate_cond(cond) {
+                if self.evaluate_cond(cond)? {
                     self.jump_to_ip(*target_ip)
                 } else {
                     self.advance_position()
                 }
             }
             EventCompiled::Patch(patch) => {
-                let before_music = self.state.visual.music.clone();
+                let before = self.state.visual.clone();
                 self.state.visual.apply_patch(patch);
-                append_music_delta(before_music, &self.state.visual.music, audio_commands);
+                append_visual_delta(&before, &self.state.visual, &mut self.queued_visual);
+                append_music_delta(before.music, &self.state.visual.music, audio_commands);
                 self.advance_position()
             }
-            EventCompiled::ExtCall { .. } => Ok(()),
+            EventCompiled::ExtCall { .. } | EventCompiled::InputText { .. } => Ok(()),
             EventCompiled::AudioAction(action) => {
                 if let Some(command) = audio_command_from_action(action) {
                     audio_commands.push(command);
@@ -178,17 +470,30 @@ impl Engine {
                 self.advance_position()
             }
             EventCompiled::SetCharacterPosition(pos) => {
+                let before = self.state.visual.clone();
                 self.state.visual.set_character_position(pos);
+                append_visual_delta(&before, &self.state.visual, &mut self.queued_visual);
+                self.advance_position()
+            }
+            EventCompiled::Transition(transition) => {
+                self.queued_visual.push(VisualCommand::Transition {
+                    kind: transition.kind,
+                    duration_ms: transition.duration_ms,
+                    color: transition.color.clone(),
+                });
                 self.advance_position()
             }
-            EventCompiled::Transition(_) => self.advance_position(),
         }
     }
 
-    fn evaluate_cond(&self, cond: &CondCompiled) -> bool {
-        match cond {
-            CondCompiled::Flag { flag_id, is_set } => self.state.get_flag(*flag_id) == *is_set,
+    pub(super) fn evaluate_cond(&self, cond: &CondCompiled) -> VnResult<bool> {
+        let result = match cond {
+            CondCompiled::Flag { flag_id, is_set } => {
+                self.check_flag_id(*flag_id)?;
+                self.state.get_flag(*flag_id) == *is_set
+            }
             CondCompiled::VarCmp { var_id, op, value } => {
+                self.check_var_id(*var_id)?;
                 let var_val = self.state.get_var(*var_id);
                 match op {
                     CmpOp::Eq => var_val == *value,
@@ -199,47 +504,193 @@ impl Engine {
                     CmpOp::Ge => var_val >= *value,
                 }
             }
+            CondCompiled::StrEq { str_var_id, value } => {
+                self.check_str_var_id(*str_var_id)?;
+                self.state.get_str_var(*str_var_id) == &**value
+            }
+        };
+        Ok(result)
+    }
+
+    fn check_choice_effects(&self, effects: &[EventCompiled]) -> VnResult<()> {
+        for effect in effects {
+            match effect {
+                EventCompiled::SetFlag { flag_id, .. } => self.check_flag_id(*flag_id)?,
+                EventCompiled::SetVar { var_id, .. } => self.check_var_id(*var_id)?,
+                other => {
+                    return Err(VnError::corrupt_script(format!(
+                        "choice effect '{}' is not set_flag or set_var",
+                        other.kind()
+                    )))
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Applies effects already vetted by [`Engine::check_choice_effects`].
+    fn apply_choice_effects(&mut self, effects: &[EventCompiled]) {
+        for effect in effects {
+            match effect {
+                EventCompiled::SetFlag { flag_id, value } => {
+                    if self.state.get_flag(*flag_id) != *value {
+                        self.record_engine_event(EngineEvent::FlagChanged {
+                            flag_id: *flag_id,
+                            value: *value,
+                        });
+                    }
+                    self.state.set_flag(*flag_id, *value);
+                }
+                EventCompiled::SetVar { var_id, value } => self.state.set_var(*var_id, *value),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_flag_id(&self, flag_id: u32) -> VnResult<()> {
+        if flag_id >= self.script.flag_count() {
+            return Err(VnError::corrupt_script(format!(
+                "flag id {flag_id} outside compiled range"
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_var_id(&self, var_id: u32) -> VnResult<()> {
+        if var_id as usize >= self.script.var_names().len() {
+            return Err(VnError::corrupt_script(format!(
+                "var id {var_id} outside compiled range"
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_str_var_id(&self, str_var_id: u32) -> VnResult<()> {
+        if str_var_id as usize >= self.script.str_var_names().len() {
+            return Err(VnError::corrupt_script(format!(
+                "string var id {str_var_id} outside compiled range"
+            )));
+        }
+        Ok(())
     }
 
     fn advance_position(&mut self) -> VnResult<()> {
         let next = self.state.position.saturating_add(1);
-        if next as usize >= self.script.events.len() {
-            self.state.position = self.script.events.len() as u32;
+        if next as usize >= self.script.event_count() {
+            self.state.position = self.script.event_count() as u32;
             return Ok(());
         }
-        self.state.position = next;
+        self.set_position(next);
         Ok(())
     }
 
     fn jump_to_ip(&mut self, target_ip: u32) -> VnResult<()> {
-        if target_ip as usize >= self.script.events.len() {
-            return Err(VnError::InvalidScript(format!(
+        if target_ip as usize >= self.script.event_count() {
+            return Err(VnError::corrupt_script(format!(
                 "jump target '{target_ip}' outside script"
             )));
         }
-        self.state.position = target_ip;
+        self.set_position(target_ip);
         Ok(())
     }
 
+    fn set_position(&mut self, ip: u32) {
+        self.script.seek(ip);
+        self.state.position = ip;
+    }
+
     /// Returns the full engine state.
     pub fn state(&self) -> &EngineState {
         &self.state
     }
 
+    /// Returns collected metrics, or `None` when collection is disabled.
+    pub fn metrics(&self) -> Option<&EngineMetrics> {
+        self.metrics.as_ref().map(MetricsRecorder::metrics)
+    }
+
+    /// Continues counting from previously saved metrics; ignored when collection is disabled.
+    pub fn restore_metrics(&mut self, metrics: EngineMetrics) {
+        if let Some(recorder) = &mut self.metrics {
+            *recorder = MetricsRecorder::new(metrics);
+        }
+    }
+
+    /// Suspends timed behaviors (auto-advance, timed choices) driven by the host loop.
+    ///
+    /// Manual `step`/`choose` keep working, and ext-call `resume` is unaffected.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Lifts a previous [`Engine::pause`].
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns `true` while timed behaviors are suspended.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     /// Returns the security policy in use.
     pub fn policy(&self) -> &SecurityPolicy {
         &self.policy
     }
 
+    /// Replaces the ext-call allowlist; `None` allows every command.
+    pub fn set_ext_call_allowlist(&mut self, allowlist: Option<HashSet<String>>) {
+        self.policy.ext_call_allowlist = allowlist;
+    }
+
     /// Returns the current visual state.
     pub fn visual_state(&self) -> &crate::visual::VisualState {
         &self.state.visual
     }
 
+    /// Shows `display` instead of the speaker id `id` in [`Engine::ui_state`].
+    /// History keeps the id; the alias is saved with the state.
+    ///
+    /// Fails with [`VnError::ResourceLimit`] when `id` or `display` exceeds
+    /// the limits applied to authored aliases.
+    pub fn set_character_alias(&mut self, id: &str, display: &str) -> VnResult<()> {
+        check_character_alias(id, display, self.limits)?;
+        self.state
+            .character_aliases
+            .insert(id.to_string(), display.to_string());
+        Ok(())
+    }
+
+    /// Drops the alias for `id`, returning whether one was set.
+    pub fn clear_character_alias(&mut self, id: &str) -> bool {
+        self.state.character_aliases.remove(id).is_some()
+    }
+
+    /// Returns the active speaker-id → display-name aliases.
+    pub fn character_aliases(&self) -> &BTreeMap<String, String> {
+        &self.state.character_aliases
+    }
+
+    /// Builds the UI view of the current event with speaker aliases applied.
+    ///
+    /// Dialogue text has `{strvar:key}` placeholders filled in (see
+    /// [`Engine::interpolate`]).
+    pub fn ui_state(&self) -> VnResult<UiState> {
+        let event = self.current_event_ref()?;
+        let mut ui = UiState::from_event_with_aliases(
+            event,
+            &self.state.visual,
+            &self.state.character_aliases,
+        );
+        if let UiView::Dialogue { text, .. } = &mut ui.view {
+            *text = self.interpolate(text);
+        }
+        Ok(ui)
+    }
+
     /// Returns the configured flag count.
     pub fn flag_count(&self) -> u32 {
-        self.script.flag_count
+        self.script.flag_count()
     }
 
     pub fn take_audio_commands(&mut self) -> Vec<AudioCommand> {
@@ -250,17 +701,29 @@ impl Engine {
         self.queued_audio.push(command);
     }
 
+    /// Drains the [`VisualCommand`]s emitted by `step` since the last call.
+    /// Jumps such as `goto_ip` or `set_state` replace the visual state
+    /// wholesale and emit none.
+    pub fn take_visual_commands(&mut self) -> Vec<VisualCommand> {
+        std::mem::take(&mut self.queued_visual)
+    }
+
     pub fn resume(&mut self) -> VnResult<()> {
         let event = self.current_event()?;
         match event {
-            EventCompiled::ExtCall { .. } => self.advance_position(),
+            EventCompiled::ExtCall { command, .. } => {
+                self.policy.check_ext_call(&command)?;
+                self.advance_position()?;
+                self.notify_event_hooks();
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
 
     /// Returns compiled script labels.
     pub fn labels(&self) -> &std::collections::BTreeMap<String, u32> {
-        &self.script.labels
+        self.script.labels()
     }
 
     /// Sets a flag value by id.
@@ -268,31 +731,154 @@ impl Engine {
         self.state.set_flag(id, value);
     }
 
+    /// Sets several flags at once. Every id is checked first, so one id
+    /// outside the compiled range rejects the whole batch unchanged with
+    /// [`VnError::UnknownSymbolId`].
+    pub fn set_flags(&mut self, flags: &[(u32, bool)]) -> VnResult<()> {
+        for (id, _) in flags {
+            if *id >= self.script.flag_count() {
+                return Err(VnError::UnknownSymbolId {
+                    kind: "flag",
+                    id: *id,
+                });
+            }
+        }
+        for (id, value) in flags {
+            self.state.set_flag(*id, *value);
+        }
+        Ok(())
+    }
+
+    /// Sets several variables at once, rejecting the whole batch with
+    /// [`VnError::UnknownSymbolId`] if any id names no variable in the
+    /// compiled script.
+    pub fn set_vars(&mut self, vars: &[(u32, i32)]) -> VnResult<()> {
+        for (id, _) in vars {
+            if *id as usize >= self.script.var_names().len() {
+                return Err(VnError::UnknownSymbolId {
+                    kind: "variable",
+                    id: *id,
+                });
+            }
+        }
+        for (id, value) in vars {
+            self.state.set_var(*id, *value);
+        }
+        Ok(())
+    }
+
     /// Jumps to a label by name.
     pub fn jump_to_label(&mut self, label: &str) -> VnResult<()> {
         let target_ip = self
             .script
-            .labels
+            .labels()
             .get(label)
             .copied()
             .ok_or_else(|| VnError::InvalidScript(format!("label '{label}' not found")))?;
         self.jump_to_ip(target_ip)
     }
 
+    /// Resolves `label` and moves there with [`Engine::goto_ip`].
+    pub fn goto_label(&mut self, label: &str) -> VnResult<()> {
+        let target_ip = self
+            .script
+            .labels()
+            .get(label)
+            .copied()
+            .ok_or_else(|| VnError::InvalidScript(format!("label '{label}' not found")))?;
+        self.goto_ip(target_ip)
+    }
+
+    /// Moves execution to event `ip` and rebuilds the visual state for it.
+    ///
+    /// Unlike [`Engine::jump_to_label`], the background, music and characters
+    /// are reconstructed as if the script had been played up to `ip`, and a
+    /// music change is queued as audio. Fails when `ip` is outside the script
+    /// or a choice is still waiting for [`Engine::choose`].
+    pub fn goto_ip(&mut self, ip: u32) -> VnResult<()> {
+        if ip as usize >= self.script.event_count() {
+            return Err(VnError::InvalidScript(format!(
+                "goto target '{ip}' outside script"
+            )));
+        }
+        if let Ok(EventCompiled::Choice(_)) = self.current_event_ref() {
+            return Err(VnError::InvalidScript(format!(
+                "choice at event {} is awaiting a selection",
+                self.state.position
+            )));
+        }
+        self.move_to(ip);
+        Ok(())
+    }
+
+    /// Starts playback at event `ip` with the visual state rebuilt for it,
+    /// like [`Engine::goto_ip`], but also leaves a pending choice. Meant for
+    /// tools that begin a run at an arbitrary event.
+    pub fn start_at(&mut self, ip: u32) -> VnResult<()> {
+        if ip as usize >= self.script.event_count() {
+            return Err(VnError::InvalidScript(format!(
+                "start target '{ip}' outside script"
+            )));
+        }
+        self.move_to(ip);
+        Ok(())
+    }
+
+    /// Seeks to `ip` with a rebuilt visual state and queued music delta,
+    /// without any of [`Engine::goto_ip`]'s checks.
+    pub(super) fn move_to(&mut self, ip: u32) {
+        let before_music = self.state.visual.music.clone();
+        self.rebuild_visual_from(ip);
+        append_music_delta(
+            before_music,
+            &self.state.visual.music,
+            &mut self.queued_audio,
+        );
+        self.set_position(ip);
+        self.notify_event_hooks();
+    }
+
+    pub(super) fn state_mut(&mut self) -> &mut EngineState {
+        &mut self.state
+    }
+
     /// Restores the engine state from a saved snapshot.
-    pub fn set_state(&mut self, state: EngineState) -> VnResult<()> {
-        if state.position as usize > self.script.events.len() {
+    ///
+    /// A saved visual state is kept, since it reflects the branch the player
+    /// actually took; an empty one (a snapshot captured without visuals) is
+    /// rebuilt with [`Engine::rebuild_visual_from`]. Gallery unlocks already
+    /// earned are kept alongside the snapshot's own.
+    pub fn set_state(&mut self, mut state: EngineState) -> VnResult<()> {
+        if state.position as usize > self.script.event_count() {
             return Err(VnError::InvalidScript(format!(
                 "state position '{}' outside script",
                 state.position
             )));
         }
+        for (id, display) in &state.character_aliases {
+            check_character_alias(id, display, self.limits)?;
+        }
+        self.script.seek(state.position);
+        state.unlocked.append(&mut self.state.unlocked);
         self.state = state;
+        if self.state.visual.is_empty() {
+            self.rebuild_visual_from(self.state.position);
+        }
         self.read_dialogue_ips.clear();
         self.choice_history.clear();
         Ok(())
     }
 
+    /// Replaces the visual state with the one reached by replaying
+    /// Scene/Patch/SetCharacterPosition events from the last Scene before `ip`.
+    ///
+    /// Events are replayed in script order, so branches that jump over each
+    /// other's scenes can yield visuals the player never saw; prefer a saved
+    /// [`VisualState`](crate::visual::VisualState) when one is available.
+    pub fn rebuild_visual_from(&mut self, ip: u32) {
+        self.state.visual = visual_state_at(&self.script, ip);
+    }
+
     /// Returns `true` if a dialogue at the given instruction pointer was already displayed.
     pub fn is_dialogue_read(&self, ip: u32) -> bool {
         self.read_dialogue_ips.contains(&ip)
@@ -309,10 +895,31 @@ impl Engine {
         &self.choice_history
     }
 
-    /// Clears runtime-only session history (read dialogue marks and choice history).
+    /// Gallery (CG) ids unlocked by `unlock_gallery` events, in this run or
+    /// any save restored with [`Engine::set_state`].
+    pub fn unlocked_gallery(&self) -> &BTreeSet<String> {
+        &self.state.unlocked
+    }
+
+    /// Achievement ids recorded by `achieve` events. Each is recorded once;
+    /// hosts see it arrive as [`EngineEvent::AchievementUnlocked`].
+    pub fn achievements(&self) -> &BTreeSet<String> {
+        &self.state.achievements
+    }
+
+    /// Returns every option index chosen so far. Unlike
+    /// [`Engine::choice_history`] it is unbounded and saved with the state,
+    /// so [`Engine::replay_choice_log`] can re-walk the route after a load.
+    pub fn choice_log(&self) -> &[usize] {
+        &self.state.choice_log
+    }
+
+    /// Clears session history: read dialogue marks, choice history and the
+    /// choice log.
     pub fn clear_session_history(&mut self) {
         self.read_dialogue_ips.clear();
         self.choice_history.clear();
+        self.state.choice_log.clear();
     }
 
     /// Renders the current event using the provided renderer.
@@ -352,10 +959,12 @@ pub struct StateChange {
     pub visual: crate::visual::VisualState,
 }
 
-fn initialize_state(script: &ScriptCompiled) -> EngineState {
-    let position = script.start_ip;
-    let mut state = EngineState::new(position, script.flag_count);
-    if let Some(EventCompiled::Scene(scene)) = script.events.get(position as usize) {
+fn initialize_state<S: ScriptSource>(script: &mut S) -> EngineState {
+    let position = script.start_ip();
+    script.seek(position);
+    let mut state = EngineState::new(position, script.flag_count());
+    state.character_aliases = script.character_aliases().clone();
+    if let Some(EventCompiled::Scene(scene)) = script.resident_event(position) {
         state.visual.apply_scene(scene);
     }
     state
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{VnError, VnResult};
+use crate::script::ScriptSource;
+
+use super::runtime::Engine;
+
+/// Name-keyed view of the engine state for embedders (debug consoles,
+/// external tools) that should not depend on flag/var ids or the save layout.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    pub position: u32,
+    #[serde(default)]
+    pub flags: BTreeMap<String, bool>,
+    #[serde(default)]
+    pub vars: BTreeMap<String, i32>,
+    /// Informational only: [`Engine::apply_snapshot`] rebuilds visuals from
+    /// `position`.
+    #[serde(default)]
+    pub visual: VisualSummary,
+}
+
+/// What is on screen, reduced to asset and character names.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VisualSummary {
+    pub background: Option<String>,
+    pub music: Option<String>,
+    pub characters: Vec<String>,
+}
+
+impl<S: ScriptSource> Engine<S> {
+    /// Captures the position, every named flag and variable, and a summary of
+    /// the visual state.
+    pub fn snapshot(&self) -> EngineSnapshot {
+        let state = self.state();
+        let flags = self
+            .source()
+            .flag_names()
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.to_string(), state.get_flag(id as u32)))
+            .collect();
+        let vars = self
+            .source()
+            .var_names()
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.to_string(), state.get_var(id as u32)))
+            .collect();
+        let visual = &state.visual;
+        EngineSnapshot {
+            position: state.position,
+            flags,
+            vars,
+            visual: VisualSummary {
+                background: visual.background.as_ref().map(|bg| bg.to_string()),
+                music: visual.music.as_ref().map(|music| music.to_string()),
+                characters: visual
+                    .characters
+                    .iter()
+                    .map(|character| character.name.to_string())
+                    .collect(),
+            },
+        }
+    }
+
+    /// Moves to `snapshot.position` and sets the listed flags and variables.
+    ///
+    /// The whole snapshot is validated first, so a bad position or unknown
+    /// symbol name leaves the engine untouched. Symbols missing from the
+    /// snapshot keep their value; read marks and choice history are kept too,
+    /// unlike [`Engine::set_state`].
+    pub fn apply_snapshot(&mut self, snapshot: EngineSnapshot) -> VnResult<()> {
+        if snapshot.position as usize > self.source().event_count() {
+            return Err(VnError::InvalidScript(format!(
+                "snapshot position '{}' outside script",
+                snapshot.position
+            )));
+        }
+        let flags = snapshot
+            .flags
+            .iter()
+            .map(|(name, value)| {
+                self.flag_id(name)
+                    .map(|id| (id, *value))
+                    .ok_or_else(|| VnError::InvalidScript(format!("unknown flag '{name}'")))
+            })
+            .collect::<VnResult<Vec<_>>>()?;
+        let vars = snapshot
+            .vars
+            .iter()
+            .map(|(name, value)| {
+                self.var_id(name)
+                    .map(|id| (id, *value))
+                    .ok_or_else(|| VnError::InvalidScript(format!("unknown variable '{name}'")))
+            })
+            .collect::<VnResult<Vec<_>>>()?;
+
+        let state = self.state_mut();
+        for (id, value) in flags {
+            state.set_flag(id, value);
+        }
+        for (id, value) in vars {
+            state.set_var(id, value);
+        }
+        self.move_to(snapshot.position);
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use crate::event::SharedStr;
+use crate::script::ScriptSource;
+
+use super::runtime::Engine;
+
+/// Opening of a string variable placeholder in dialogue text.
+const STR_VAR_PLACEHOLDER: &str = "{strvar:";
+
+impl<S: ScriptSource> Engine<S> {
+    /// Returns the source name of a flag id, if the script recorded one.
+    pub fn flag_name(&self, id: u32) -> Option<&str> {
+        self.source()
+            .flag_names()
+            .get(id as usize)
+            .map(|name| &**name)
+    }
+
+    /// Returns the source name of a variable id, if the script recorded one.
+    pub fn var_name(&self, id: u32) -> Option<&str> {
+        self.source()
+            .var_names()
+            .get(id as usize)
+            .map(|name| &**name)
+    }
+
+    /// Returns the source name of a string variable id, if the script recorded one.
+    pub fn str_var_name(&self, id: u32) -> Option<&str> {
+        self.source()
+            .str_var_names()
+            .get(id as usize)
+            .map(|name| &**name)
+    }
+
+    /// Resolves a flag name to its id.
+    pub fn flag_id(&self, name: &str) -> Option<u32> {
+        symbol_id(self.source().flag_names(), name)
+    }
+
+    /// Resolves a variable name to its id.
+    pub fn var_id(&self, name: &str) -> Option<u32> {
+        symbol_id(self.source().var_names(), name)
+    }
+
+    /// Resolves a string variable name to its id.
+    pub fn str_var_id(&self, name: &str) -> Option<u32> {
+        symbol_id(self.source().str_var_names(), name)
+    }
+
+    /// Reads a string variable by name; `None` if the script never names it.
+    pub fn str_var(&self, name: &str) -> Option<&str> {
+        self.str_var_id(name).map(|id| self.state().get_str_var(id))
+    }
+
+    /// Replaces every `{strvar:key}` in `text` with the value of string
+    /// variable `key`. Unknown keys and unmatched braces are left as written.
+    pub fn interpolate(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find(STR_VAR_PLACEHOLDER) {
+            out.push_str(&rest[..open]);
+            let after = &rest[open + STR_VAR_PLACEHOLDER.len()..];
+            match after
+                .find('}')
+                .and_then(|close| Some((close, self.str_var(&after[..close])?)))
+            {
+                Some((close, value)) => {
+                    out.push_str(value);
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    out.push_str(STR_VAR_PLACEHOLDER);
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Current value of every named numeric variable, keyed by name.
+    /// Variables the script names but has not set yet read as 0.
+    pub fn numeric_vars_snapshot(&self) -> BTreeMap<String, i32> {
+        self.source()
+            .var_names()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let id = u32::try_from(index).ok()?;
+                Some((name.to_string(), self.state().get_var(id)))
+            })
+            .collect()
+    }
+
+    /// [`Engine::numeric_vars_snapshot`] rendered one line per variable, in
+    /// name order, by `format(name, value)`; e.g. a stats screen can map
+    /// `affection_ava` to `Ava ♥ 3`.
+    pub fn format_numeric_vars<F>(&self, format: F) -> Vec<String>
+    where
+        F: Fn(&str, i32) -> String,
+    {
+        self.numeric_vars_snapshot()
+            .iter()
+            .map(|(name, value)| format(name, *value))
+            .collect()
+    }
+}
+
+fn symbol_id(names: &[SharedStr], name: &str) -> Option<u32> {
+    names
+        .iter()
+        .position(|candidate| &**candidate == name)
+        .and_then(|index| u32::try_from(index).ok())
+}
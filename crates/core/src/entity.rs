@@ -141,6 +141,9 @@ pub struct CharacterData {
     pub name: SharedStr,
     /// Current expression/pose.
     pub expression: Option<SharedStr>,
+    /// Draw the sprite mirrored horizontally.
+    #[serde(default)]
+    pub flip_x: bool,
 }
 
 /// Data for a video entity.
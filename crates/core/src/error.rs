@@ -15,6 +15,12 @@ pub enum VnError {
     #[error("choice index out of range")]
     #[diagnostic(code("vn.invalid_choice"))]
     InvalidChoice,
+    #[error("choice at event {ip} has no options and no fallthrough")]
+    #[diagnostic(
+        code("vn.no_choice_options"),
+        help("give the choice an option or a fallthrough label")
+    )]
+    NoChoiceOptions { ip: u32 },
     #[error("resource limit exceeded: {0}")]
     #[diagnostic(code("vn.resource_limit"))]
     ResourceLimit(String),
@@ -31,6 +37,49 @@ pub enum VnError {
         #[label("here")]
         span: SourceSpan,
     },
+    #[error("event {event_index} targets unknown label '{target}'")]
+    #[diagnostic(
+        code("vn.unknown_label"),
+        help("define the label or fix the target name")
+    )]
+    UnknownLabel {
+        event_index: usize,
+        target: String,
+        #[source_code]
+        src: String,
+        #[label("unknown label")]
+        span: SourceSpan,
+    },
+    #[error("ext_call '{command}' denied by security policy")]
+    #[diagnostic(
+        code("vn.ext_call_denied"),
+        help("add the command to SecurityPolicy::ext_call_allowlist")
+    )]
+    ExtCallDenied { command: String },
+    #[error("corrupt compiled script: {detail}")]
+    #[diagnostic(
+        code("vn.corrupt_script"),
+        help("recompile the script from its source JSON")
+    )]
+    CorruptScript { detail: String },
+    #[error("include cycle: {chain}")]
+    #[diagnostic(
+        code("vn.include_cycle"),
+        help("remove one of the includes so no script includes itself")
+    )]
+    IncludeCycle { chain: String },
+    #[error("no {kind} has id {id}")]
+    #[diagnostic(
+        code("vn.unknown_symbol_id"),
+        help("look ids up with Engine::flag_id or Engine::var_id")
+    )]
+    UnknownSymbolId { kind: &'static str, id: u32 },
+    #[error("no text input is pending")]
+    #[diagnostic(
+        code("vn.no_pending_input"),
+        help("call submit_text only while the current event is input_text")
+    )]
+    NoPendingInput,
     #[error("binary format error: {0}")]
     #[diagnostic(code("vn.binary_format"))]
     BinaryFormat(String),
@@ -52,6 +101,13 @@ impl VnError {
         VnError::SecurityPolicy(message.into())
     }
 
+    #[cold]
+    pub fn corrupt_script(detail: impl Into<String>) -> Self {
+        VnError::CorruptScript {
+            detail: detail.into(),
+        }
+    }
+
     #[cold]
     pub fn binary_format(message: impl Into<String>) -> Self {
         VnError::BinaryFormat(message.into())
@@ -3,13 +3,27 @@ use serde::{Deserialize, Serialize};
 
 use crate::resource::StringBudget;
 
+use super::SharedStr;
+
 /// Condition for conditional jumps (raw form).
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum CondRaw {
-    Flag { key: String, is_set: bool },
-    VarCmp { key: String, op: CmpOp, value: i32 },
+    Flag {
+        key: String,
+        is_set: bool,
+    },
+    VarCmp {
+        key: String,
+        op: CmpOp,
+        value: i32,
+    },
+    /// True when the string var `key` equals `value` (unset reads as `""`).
+    StrEq {
+        key: String,
+        value: String,
+    },
 }
 
 impl StringBudget for CondRaw {
@@ -17,6 +31,7 @@ impl StringBudget for CondRaw {
         match self {
             CondRaw::Flag { key, .. } => key.string_bytes(),
             CondRaw::VarCmp { key, .. } => key.string_bytes(),
+            CondRaw::StrEq { key, value } => key.string_bytes() + value.string_bytes(),
         }
     }
 }
@@ -26,6 +41,7 @@ impl StringBudget for CondRaw {
 pub enum CondCompiled {
     Flag { flag_id: u32, is_set: bool },
     VarCmp { var_id: u32, op: CmpOp, value: i32 },
+    StrEq { str_var_id: u32, value: SharedStr },
 }
 
 /// Comparison operators for variable conditions.
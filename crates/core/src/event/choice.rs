@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::resource::StringBudget;
 
-use super::SharedStr;
+use super::{EventCompiled, EventRaw, SharedStr};
 
 /// Choice prompt and options in raw form.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
@@ -11,11 +11,22 @@ use super::SharedStr;
 pub struct ChoiceRaw {
     pub prompt: String,
     pub options: Vec<ChoiceOptionRaw>,
+    /// Resolved by the engine through [`crate::Engine::auto_choose_weighted`]
+    /// instead of waiting for the player.
+    #[serde(default)]
+    pub auto: bool,
+    /// Label jumped to when the choice has no options to offer, instead of
+    /// failing with [`crate::VnError::NoChoiceOptions`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub fallthrough: Option<String>,
 }
 
 impl StringBudget for ChoiceRaw {
     fn string_bytes(&self) -> usize {
-        self.prompt.string_bytes() + self.options.string_bytes()
+        self.prompt.string_bytes()
+            + self.options.string_bytes()
+            + self.fallthrough.as_ref().map_or(0, String::len)
     }
 }
 
@@ -24,6 +35,9 @@ impl StringBudget for ChoiceRaw {
 pub struct ChoiceCompiled {
     pub prompt: SharedStr,
     pub options: Vec<ChoiceOptionCompiled>,
+    pub auto: bool,
+    /// Resolved [`ChoiceRaw::fallthrough`].
+    pub fallthrough_ip: Option<u32>,
 }
 
 /// Choice option with label target in raw form.
@@ -32,11 +46,19 @@ pub struct ChoiceCompiled {
 pub struct ChoiceOptionRaw {
     pub text: String,
     pub target: String,
+    /// Relative odds for auto choices; `None` counts as 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    /// `set_flag`/`set_var` events applied when this option is picked, before
+    /// jumping to `target`. Other event types are rejected at compile time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub effects: Vec<EventRaw>,
 }
 
 impl StringBudget for ChoiceOptionRaw {
     fn string_bytes(&self) -> usize {
-        self.text.string_bytes() + self.target.string_bytes()
+        self.text.string_bytes() + self.target.string_bytes() + self.effects.string_bytes()
     }
 }
 
@@ -45,4 +67,7 @@ impl StringBudget for ChoiceOptionRaw {
 pub struct ChoiceOptionCompiled {
     pub text: SharedStr,
     pub target_ip: u32,
+    pub weight: Option<u32>,
+    /// Only `SetFlag` and `SetVar`.
+    pub effects: Vec<EventCompiled>,
 }
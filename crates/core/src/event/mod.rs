@@ -40,16 +40,65 @@ pub enum EventRaw {
     Dialogue(DialogueRaw),
     Choice(ChoiceRaw),
     Scene(SceneUpdateRaw),
-    Jump { target: String },
-    SetFlag { key: String, value: bool },
-    SetVar { key: String, value: i32 },
-    JumpIf { cond: CondRaw, target: String },
+    Jump {
+        target: String,
+    },
+    SetFlag {
+        key: String,
+        value: bool,
+    },
+    SetVar {
+        key: String,
+        value: i32,
+    },
+    /// Sets a string var; string vars are numbered apart from `set_var` ones.
+    SetStrVar {
+        key: String,
+        value: String,
+    },
+    JumpIf {
+        cond: CondRaw,
+        target: String,
+    },
     Patch(ScenePatchRaw),
-    ExtCall { command: String, args: Vec<String> },
+    ExtCall {
+        command: String,
+        args: Vec<String>,
+    },
     AudioAction(AudioActionRaw),
 
     Transition(SceneTransitionRaw),
     SetCharacterPosition(SetCharacterPositionRaw),
+    /// Waits for the player to type a value (e.g. their name), stored in the
+    /// string var `var_key` by [`crate::Engine::submit_text`].
+    InputText {
+        var_key: String,
+        prompt: String,
+        max_len: u32,
+    },
+    /// Marks CG `id` as unlocked in [`crate::EngineState::unlocked`], which
+    /// saves carry so the gallery keeps it across playthroughs.
+    UnlockGallery {
+        id: String,
+    },
+    /// Records achievement `id` once, emitting
+    /// [`crate::EngineEvent::AchievementUnlocked`] the first time.
+    Achieve {
+        id: String,
+    },
+    /// Another script inlined by [`crate::ScriptRaw::resolve_includes`];
+    /// never compiled directly.
+    Include {
+        path: String,
+    },
+    /// Script macro spliced in at compile time, with `$1`, `$2`, ... in its
+    /// events replaced by `args`.
+    Expand {
+        #[serde(rename = "macro")]
+        name: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
 }
 
 impl StringBudget for EventRaw {
@@ -61,12 +110,20 @@ impl StringBudget for EventRaw {
             EventRaw::Jump { target } => target.len(),
             EventRaw::SetFlag { key, .. } => key.len(),
             EventRaw::SetVar { key, .. } => key.len(),
+            EventRaw::SetStrVar { key, value } => key.len() + value.len(),
             EventRaw::JumpIf { cond, target } => cond.string_bytes() + target.len(),
             EventRaw::Patch(inner) => inner.string_bytes(),
             EventRaw::ExtCall { command, args } => command.len() + args.string_bytes(),
             EventRaw::AudioAction(inner) => inner.string_bytes(),
             EventRaw::Transition(inner) => inner.string_bytes(),
             EventRaw::SetCharacterPosition(inner) => inner.string_bytes(),
+            EventRaw::InputText {
+                var_key, prompt, ..
+            } => var_key.len() + prompt.len(),
+            EventRaw::UnlockGallery { id } => id.len(),
+            EventRaw::Achieve { id } => id.len(),
+            EventRaw::Include { path } => path.len(),
+            EventRaw::Expand { name, args } => name.len() + args.string_bytes(),
         }
     }
 }
@@ -78,15 +135,44 @@ pub enum EventCompiled {
     Dialogue(DialogueCompiled),
     Choice(ChoiceCompiled),
     Scene(SceneUpdateCompiled),
-    Jump { target_ip: u32 },
-    SetFlag { flag_id: u32, value: bool },
-    SetVar { var_id: u32, value: i32 },
-    JumpIf { cond: CondCompiled, target_ip: u32 },
+    Jump {
+        target_ip: u32,
+    },
+    SetFlag {
+        flag_id: u32,
+        value: bool,
+    },
+    SetVar {
+        var_id: u32,
+        value: i32,
+    },
+    SetStrVar {
+        str_var_id: u32,
+        value: SharedStr,
+    },
+    JumpIf {
+        cond: CondCompiled,
+        target_ip: u32,
+    },
     Patch(ScenePatchCompiled),
-    ExtCall { command: String, args: Vec<String> },
+    ExtCall {
+        command: String,
+        args: Vec<String>,
+    },
     AudioAction(AudioActionCompiled),
     Transition(SceneTransitionCompiled),
     SetCharacterPosition(SetCharacterPositionCompiled),
+    InputText {
+        str_var_id: u32,
+        prompt: String,
+        max_len: u32,
+    },
+    UnlockGallery {
+        id: SharedStr,
+    },
+    Achieve {
+        id: SharedStr,
+    },
 }
 
 impl EventRaw {
@@ -102,6 +188,28 @@ impl EventRaw {
 }
 
 impl EventCompiled {
+    /// Returns the snake_case event type tag, matching the serialized `type`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EventCompiled::Dialogue(_) => "dialogue",
+            EventCompiled::Choice(_) => "choice",
+            EventCompiled::Scene(_) => "scene",
+            EventCompiled::Jump { .. } => "jump",
+            EventCompiled::SetFlag { .. } => "set_flag",
+            EventCompiled::SetVar { .. } => "set_var",
+            EventCompiled::SetStrVar { .. } => "set_str_var",
+            EventCompiled::JumpIf { .. } => "jump_if",
+            EventCompiled::Patch(_) => "patch",
+            EventCompiled::ExtCall { .. } => "ext_call",
+            EventCompiled::AudioAction(_) => "audio_action",
+            EventCompiled::Transition(_) => "transition",
+            EventCompiled::SetCharacterPosition(_) => "set_character_position",
+            EventCompiled::InputText { .. } => "input_text",
+            EventCompiled::UnlockGallery { .. } => "unlock_gallery",
+            EventCompiled::Achieve { .. } => "achieve",
+        }
+    }
+
     /// Serializes the compiled event to JSON.
     pub fn to_json_value(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
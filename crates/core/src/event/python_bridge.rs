@@ -59,12 +59,18 @@ impl PyEvent {
                 EventRaw::Jump { .. } => "jump",
                 EventRaw::SetFlag { .. } => "set_flag",
                 EventRaw::SetVar { .. } => "set_var",
+                EventRaw::SetStrVar { .. } => "set_str_var",
                 EventRaw::JumpIf { .. } => "jump_if",
                 EventRaw::Patch(_) => "patch",
                 EventRaw::ExtCall { .. } => "ext_call",
                 EventRaw::AudioAction(_) => "audio_action",
                 EventRaw::Transition(_) => "transition",
                 EventRaw::SetCharacterPosition(_) => "set_character_position",
+                EventRaw::InputText { .. } => "input_text",
+                EventRaw::UnlockGallery { .. } => "unlock_gallery",
+                EventRaw::Achieve { .. } => "achieve",
+                EventRaw::Include { .. } => "include",
+                EventRaw::Expand { .. } => "expand",
             },
             PyEventData::Compiled(event) => match event {
                 EventCompiled::Dialogue(_) => "dialogue",
@@ -73,12 +79,16 @@ impl PyEvent {
                 EventCompiled::Jump { .. } => "jump",
                 EventCompiled::SetFlag { .. } => "set_flag",
                 EventCompiled::SetVar { .. } => "set_var",
+                EventCompiled::SetStrVar { .. } => "set_str_var",
                 EventCompiled::JumpIf { .. } => "jump_if",
                 EventCompiled::Patch(_) => "patch",
                 EventCompiled::ExtCall { .. } => "ext_call",
                 EventCompiled::AudioAction(_) => "audio_action",
                 EventCompiled::Transition(_) => "transition",
                 EventCompiled::SetCharacterPosition(_) => "set_character_position",
+                EventCompiled::InputText { .. } => "input_text",
+                EventCompiled::UnlockGallery { .. } => "unlock_gallery",
+                EventCompiled::Achieve { .. } => "achieve",
             },
         }
     }
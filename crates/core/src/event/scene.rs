@@ -13,11 +13,21 @@ pub struct SceneUpdateRaw {
     pub music: Option<String>,
     #[serde(default)]
     pub characters: Vec<CharacterPlacementRaw>,
+    /// Chapter this scene opens, listed by [`crate::Engine::chapters`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chapter: Option<String>,
+    /// Free-form labels, queried by [`crate::Engine::scenes_with_tag`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl StringBudget for SceneUpdateRaw {
     fn string_bytes(&self) -> usize {
-        self.background.string_bytes() + self.music.string_bytes() + self.characters.string_bytes()
+        self.background.string_bytes()
+            + self.music.string_bytes()
+            + self.characters.string_bytes()
+            + self.chapter.string_bytes()
+            + self.tags.string_bytes()
     }
 }
 
@@ -27,6 +37,8 @@ pub struct SceneUpdateCompiled {
     pub background: Option<SharedStr>,
     pub music: Option<SharedStr>,
     pub characters: Vec<CharacterPlacementCompiled>,
+    pub chapter: Option<SharedStr>,
+    pub tags: Vec<SharedStr>,
 }
 
 /// Character placement in raw form.
@@ -42,6 +54,14 @@ pub struct CharacterPlacementRaw {
     pub y: Option<i32>,
     #[serde(default)]
     pub scale: Option<f32>,
+    /// Draw layer; higher values are drawn on top. `None` counts as 0, and
+    /// equal layers keep their insertion order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z: Option<i32>,
+    /// Mirrors the sprite horizontally, so one facing-right sprite can face
+    /// left too.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flip_x: bool,
 }
 
 impl StringBudget for CharacterPlacementRaw {
@@ -59,6 +79,8 @@ pub struct CharacterPlacementCompiled {
     pub x: Option<i32>,
     pub y: Option<i32>,
     pub scale: Option<f32>,
+    pub z: Option<i32>,
+    pub flip_x: bool,
 }
 
 /// Character patch for partial updates.
@@ -68,6 +90,9 @@ pub struct CharacterPatchRaw {
     pub name: String,
     pub expression: Option<String>,
     pub position: Option<String>,
+    /// Moves the character to this draw layer; see [`CharacterPlacementRaw::z`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z: Option<i32>,
 }
 
 impl StringBudget for CharacterPatchRaw {
@@ -82,6 +107,7 @@ pub struct CharacterPatchCompiled {
     pub name: SharedStr,
     pub expression: Option<SharedStr>,
     pub position: Option<SharedStr>,
+    pub z: Option<i32>,
 }
 
 /// Scene patch in raw form (handling partial updates).
@@ -126,6 +152,9 @@ pub struct SetCharacterPositionRaw {
     pub x: i32,
     pub y: i32,
     pub scale: Option<f32>,
+    /// See [`CharacterPlacementRaw::flip_x`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flip_x: bool,
 }
 
 impl StringBudget for SetCharacterPositionRaw {
@@ -141,4 +170,5 @@ pub struct SetCharacterPositionCompiled {
     pub x: i32,
     pub y: i32,
     pub scale: Option<f32>,
+    pub flip_x: bool,
 }
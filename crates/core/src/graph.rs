@@ -62,6 +62,8 @@ pub enum NodeType {
         y: i32,
         scale: Option<f32>,
     },
+    /// A text prompt the player answers (e.g. their name).
+    InputText { str_var_id: u32, prompt: String },
 }
 
 /// A node in the story graph.
@@ -228,7 +230,7 @@ impl StoryGraph {
                     prompt: choice.prompt.to_string(),
                     option_count: choice.options.len(),
                 };
-                let edges = choice
+                let mut edges: Vec<GraphEdge> = choice
                     .options
                     .iter()
                     .enumerate()
@@ -239,6 +241,14 @@ impl StoryGraph {
                         label: Some(opt.text.to_string()),
                     })
                     .collect();
+                if let Some(target_ip) = choice.fallthrough_ip {
+                    edges.push(GraphEdge {
+                        from: ip,
+                        to: target_ip,
+                        edge_type: EdgeType::Jump,
+                        label: Some("fallthrough".to_string()),
+                    });
+                }
                 (node_type, edges)
             }
 
@@ -321,6 +331,54 @@ impl StoryGraph {
                 (node_type, edges)
             }
 
+            EventCompiled::SetStrVar { str_var_id, value } => {
+                let desc = format!("strvar[{}] = {:?}", str_var_id, value);
+                let node_type = NodeType::StateChange { description: desc };
+                let edges = if has_next {
+                    vec![GraphEdge {
+                        from: ip,
+                        to: next_ip,
+                        edge_type: EdgeType::Sequential,
+                        label: None,
+                    }]
+                } else {
+                    vec![]
+                };
+                (node_type, edges)
+            }
+
+            EventCompiled::UnlockGallery { id } => {
+                let desc = format!("gallery[{}] unlocked", id);
+                let node_type = NodeType::StateChange { description: desc };
+                let edges = if has_next {
+                    vec![GraphEdge {
+                        from: ip,
+                        to: next_ip,
+                        edge_type: EdgeType::Sequential,
+                        label: None,
+                    }]
+                } else {
+                    vec![]
+                };
+                (node_type, edges)
+            }
+
+            EventCompiled::Achieve { id } => {
+                let desc = format!("achievement[{}]", id);
+                let node_type = NodeType::StateChange { description: desc };
+                let edges = if has_next {
+                    vec![GraphEdge {
+                        from: ip,
+                        to: next_ip,
+                        edge_type: EdgeType::Sequential,
+                        label: None,
+                    }]
+                } else {
+                    vec![]
+                };
+                (node_type, edges)
+            }
+
             EventCompiled::Patch(_) => {
                 let edges = if has_next {
                     vec![GraphEdge {
@@ -412,6 +470,25 @@ impl StoryGraph {
                 };
                 (node_type, edges)
             }
+            EventCompiled::InputText {
+                str_var_id, prompt, ..
+            } => {
+                let node_type = NodeType::InputText {
+                    str_var_id: *str_var_id,
+                    prompt: prompt.clone(),
+                };
+                let edges = if has_next {
+                    vec![GraphEdge {
+                        from: ip,
+                        to: next_ip,
+                        edge_type: EdgeType::Sequential,
+                        label: None,
+                    }]
+                } else {
+                    vec![]
+                };
+                (node_type, edges)
+            }
         }
     }
 
@@ -516,7 +593,11 @@ fn preview_text(text: &str, max_chars: usize) -> String {
 // Tests
 // =============================================================================
 
+mod branches;
 mod export;
+
+pub use branches::{BranchLength, BranchLengthReport, BRANCH_IMBALANCE_RATIO};
+
 #[cfg(test)]
 #[path = "tests/graph_tests.rs"]
 mod tests;
@@ -0,0 +1,144 @@
+use super::*;
+
+/// Ratio between the longest and shortest route above which
+/// [`BranchLengthReport::imbalanced`] is set.
+pub const BRANCH_IMBALANCE_RATIO: f32 = 3.0;
+
+/// Event counts from the start node to one reachable end, both inclusive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchLength {
+    /// The last event played on this route.
+    pub end_id: NodeId,
+    /// Fewest events needed to reach the end.
+    pub shortest: usize,
+    /// Most events on a loop-free path to the end.
+    pub longest: usize,
+}
+
+/// Route lengths per reachable end, from [`StoryGraph::branch_lengths`].
+///
+/// `min`, `max` and `mean` summarize the `shortest` length of each end, so
+/// loops never inflate them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BranchLengthReport {
+    /// One entry per end, ordered by node id.
+    pub ends: Vec<BranchLength>,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f32,
+    /// True when the longest route is at least [`BRANCH_IMBALANCE_RATIO`]
+    /// times the shortest one.
+    pub imbalanced: bool,
+}
+
+impl StoryGraph {
+    /// Measures the route from start to every reachable end.
+    ///
+    /// An end is a reachable node with no outgoing edge, or whose edge leaves
+    /// the script (e.g. a jump to a trailing `__end` label).
+    pub fn branch_lengths(&self) -> BranchLengthReport {
+        let count = self.nodes.len();
+        if self.start_id as usize >= count {
+            return BranchLengthReport::default();
+        }
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut leaves_script = vec![false; count];
+        for edge in &self.edges {
+            let from = edge.from as usize;
+            if from >= count {
+                continue;
+            }
+            if (edge.to as usize) < count {
+                successors[from].push(edge.to as usize);
+            } else {
+                leaves_script[from] = true;
+            }
+        }
+
+        let shortest = shortest_lengths(self.start_id as usize, &successors);
+        let longest = longest_lengths(self.start_id as usize, &successors);
+        let ends: Vec<BranchLength> = (0..count)
+            .filter(|&id| shortest[id].is_some())
+            .filter(|&id| successors[id].is_empty() || leaves_script[id])
+            .map(|id| BranchLength {
+                end_id: id as NodeId,
+                shortest: shortest[id].unwrap_or(0),
+                longest: longest[id],
+            })
+            .collect();
+
+        let Some(min) = ends.iter().map(|end| end.shortest).min() else {
+            return BranchLengthReport::default();
+        };
+        let max = ends.iter().map(|end| end.shortest).max().unwrap_or(min);
+        let mean = ends.iter().map(|end| end.shortest).sum::<usize>() as f32 / ends.len() as f32;
+        BranchLengthReport {
+            imbalanced: max as f32 >= min as f32 * BRANCH_IMBALANCE_RATIO,
+            ends,
+            min,
+            max,
+            mean,
+        }
+    }
+}
+
+/// Breadth-first event counts from `start`; `None` for unreachable nodes.
+fn shortest_lengths(start: usize, successors: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let mut lengths = vec![None; successors.len()];
+    let mut queue = VecDeque::from([start]);
+    lengths[start] = Some(1);
+    while let Some(id) = queue.pop_front() {
+        let next_length = lengths[id].map(|length| length + 1);
+        for &succ in &successors[id] {
+            if lengths[succ].is_none() {
+                lengths[succ] = next_length;
+                queue.push_back(succ);
+            }
+        }
+    }
+    lengths
+}
+
+/// Longest event counts from `start`, ignoring edges that close a loop.
+fn longest_lengths(start: usize, successors: &[Vec<usize>]) -> Vec<usize> {
+    const UNVISITED: u8 = 0;
+    const ON_STACK: u8 = 1;
+    const DONE: u8 = 2;
+    let mut mark = vec![UNVISITED; successors.len()];
+    let mut back_edges = HashSet::new();
+    let mut post_order = Vec::new();
+    // Iterative DFS so long linear scripts cannot overflow the stack.
+    let mut stack = vec![(start, 0usize)];
+    mark[start] = ON_STACK;
+    while let Some((id, next)) = stack.last_mut() {
+        let id = *id;
+        if let Some(&succ) = successors[id].get(*next) {
+            *next += 1;
+            match mark[succ] {
+                UNVISITED => {
+                    mark[succ] = ON_STACK;
+                    stack.push((succ, 0));
+                }
+                ON_STACK => {
+                    back_edges.insert((id, succ));
+                }
+                _ => {}
+            }
+            continue;
+        }
+        mark[id] = DONE;
+        post_order.push(id);
+        stack.pop();
+    }
+
+    let mut lengths = vec![0usize; successors.len()];
+    lengths[start] = 1;
+    for &id in post_order.iter().rev() {
+        for &succ in &successors[id] {
+            if !back_edges.contains(&(id, succ)) {
+                lengths[succ] = lengths[succ].max(lengths[id] + 1);
+            }
+        }
+    }
+    lengths
+}
@@ -13,6 +13,9 @@ impl StoryGraph {
             CondCompiled::VarCmp { var_id, op, value } => {
                 format!("var[{}] {:?} {}", var_id, op, value)
             }
+            CondCompiled::StrEq { str_var_id, value } => {
+                format!("strvar[{}] == {:?}", str_var_id, value)
+            }
         }
     }
 
@@ -80,6 +83,9 @@ impl StoryGraph {
                         node.id, name, x, y, scale
                     )
                 }
+                NodeType::InputText { str_var_id, prompt } => {
+                    format!("[{}] Input: {} (strvar[{}])", node.id, prompt, str_var_id)
+                }
             };
 
             let shape = match &node.node_type {
@@ -1,5 +1,7 @@
+pub mod analysis;
 mod assets;
 mod audio;
+#[cfg(feature = "native")]
 mod bundle;
 mod engine;
 mod entity;
@@ -10,6 +12,7 @@ mod localization;
 pub mod manifest;
 mod migration;
 mod render;
+#[cfg(feature = "native")]
 mod renpy_import;
 mod repro;
 mod resource;
@@ -22,14 +25,20 @@ mod trace;
 mod ui;
 mod version;
 mod visual;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub mod wasm;
 
 pub use assets::{AssetId, AssetId128, AssetManifest};
 pub use audio::AudioCommand;
+#[cfg(feature = "native")]
 pub use bundle::{
     export_bundle, BundleAssetEntry, BundleIntegrity, ExportBundleReport, ExportBundleSpec,
     ExportTargetPlatform,
 };
-pub use engine::{ChoiceHistoryEntry, Engine, StateChange};
+pub use engine::{
+    ChoiceHistoryEntry, Engine, EngineEvent, EngineMetrics, EngineSnapshot, EventHook, EventHookId,
+    NextStep, StateChange, VisualSummary, ENGINE_EVENT_QUEUE_LIMIT,
+};
 pub use error::{VnError, VnResult};
 pub use event::{
     AudioActionCompiled, AudioActionRaw, CharacterPatchCompiled, CharacterPatchRaw,
@@ -49,6 +58,7 @@ pub use migration::{
     MigrationTraceEntry,
 };
 pub use render::{RenderBackend, RenderOutput, TextRenderer};
+#[cfg(feature = "native")]
 pub use renpy_import::{
     import_renpy_project, ImportArea, ImportFallbackPolicy, ImportIssue, ImportPhase,
     ImportProfile, ImportRenpyOptions, ImportReport,
@@ -57,18 +67,22 @@ pub use repro::{
     run_repro_case, run_repro_case_with_limits, ReproCase, ReproMonitor, ReproMonitorResult,
     ReproOracle, ReproRunReport, ReproStepTrace, ReproStopReason, REPRO_CASE_SCHEMA,
 };
-pub use resource::{LruCache, ResourceLimiter};
-pub use script::{ScriptCompiled, ScriptRaw};
+pub use resource::{BudgetEntry, BudgetReport, LruCache, ResourceLimiter};
+pub use script::{
+    ContentStats, LazyScript, MarkdownWarning, ScriptCompiled, ScriptRaw, ScriptSource,
+    DEFAULT_LAZY_WINDOW, DEFAULT_READING_WPM, INCLUDE_LABEL_SEPARATOR, MAX_EXPANDED_EVENTS,
+};
 pub use security::SecurityPolicy;
-pub use state::EngineState;
-pub use storage::{
-    compute_script_id, SaveData, SaveError, SaveSlotEntry, SaveSlotMetadata, SaveSlotStore,
-    SaveStoreError, ScriptId, AUTH_SAVE_KEY,
+pub use state::{EngineState, TranscriptFormat};
+pub use storage::{compute_script_id, SaveData, SaveError, ScriptId, AUTH_SAVE_KEY};
+#[cfg(feature = "native")]
+pub use storage::{SaveSlotEntry, SaveSlotMetadata, SaveSlotStore, SaveStoreError};
+pub use trace::{
+    StateDigest, TraceDivergence, UiTrace, UiTraceStep, UiView as TraceUiView, VisualDigest,
 };
-pub use trace::{StateDigest, UiTrace, UiTraceStep, UiView as TraceUiView, VisualDigest};
 pub use ui::{UiState, UiView};
 pub use version::{COMPILED_FORMAT_VERSION, SAVE_FORMAT_VERSION, SCRIPT_SCHEMA_VERSION};
-pub use visual::VisualState;
+pub use visual::{VisualCommand, VisualState};
 
 // Phase 1: Entity System exports
 pub use entity::{
@@ -78,12 +92,15 @@ pub use entity::{
 
 // Phase 2: Timeline System exports
 pub use timeline::{
-    Easing, Fixed, Keyframe, PropertyType, PropertyValue, Timeline, TimelineError, Track,
-    MAX_KEYFRAMES_PER_TRACK, MAX_TRACKS,
+    CompiledTimeline, CompiledTrack, Easing, Fixed, Keyframe, PropertyType, PropertyValue,
+    Timeline, TimelineError, Track, MAX_KEYFRAMES_PER_TRACK, MAX_TRACKS,
 };
 
 // Phase 3: Story Graph exports
-pub use graph::{EdgeType, GraphEdge, GraphNode, GraphStats, NodeType, StoryGraph};
+pub use graph::{
+    BranchLength, BranchLengthReport, EdgeType, GraphEdge, GraphNode, GraphStats, NodeType,
+    StoryGraph, BRANCH_IMBALANCE_RATIO,
+};
 
 pub type Event = EventCompiled;
 pub type Script = ScriptRaw;
@@ -194,7 +194,11 @@ mod tests {
                     options: vec![ChoiceOptionRaw {
                         text: "loc:choice.a".to_string(),
                         target: "start".to_string(),
+                        weight: None,
+                        effects: Vec::new(),
                     }],
+                    auto: false,
+                    fallthrough: None,
                 }),
             ],
             BTreeMap::from([("start".to_string(), 0usize)]),
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "native")]
+use std::path::Path;
+use std::path::PathBuf;
 
 use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
@@ -395,6 +397,7 @@ impl ProjectManifest {
     }
 
     /// load a manifest from a file path.
+    #[cfg(feature = "native")]
     pub fn load(path: &Path) -> Result<Self, ManifestError> {
         let content = std::fs::read_to_string(path)?;
         let (manifest, _) = Self::from_toml_with_migration(&content)?;
@@ -402,6 +405,7 @@ impl ProjectManifest {
     }
 
     /// save the manifest to a file path.
+    #[cfg(feature = "native")]
     pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
         let mut normalized = self.clone();
         normalized.manifest_schema_version = MANIFEST_SCHEMA_VERSION.to_string();
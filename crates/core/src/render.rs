@@ -79,6 +79,9 @@ impl RenderBackend for TextRenderer {
             EventCompiled::SetVar { var_id, value } => {
                 format!("Var {var_id} = {value}")
             }
+            EventCompiled::SetStrVar { str_var_id, value } => {
+                format!("StrVar {str_var_id} = {value:?}")
+            }
             EventCompiled::JumpIf { target_ip, .. } => {
                 format!("JumpIf to {target_ip}")
             }
@@ -90,6 +93,9 @@ impl RenderBackend for TextRenderer {
             EventCompiled::SetCharacterPosition(pos) => {
                 format!("SetCharacterPosition {} ({}, {})", pos.name, pos.x, pos.y)
             }
+            EventCompiled::InputText { prompt, .. } => format!("Input: {prompt}"),
+            EventCompiled::UnlockGallery { id } => format!("Gallery unlocked: {id}"),
+            EventCompiled::Achieve { id } => format!("Achievement: {id}"),
         };
         RenderOutput { text }
     }
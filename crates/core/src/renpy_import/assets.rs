@@ -396,6 +396,8 @@ mod tests {
             background: Some("bg/escape".to_string()),
             music: None,
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         })];
         let output_root = dir.path().join("out");
         let mut trace_seq = 0usize;
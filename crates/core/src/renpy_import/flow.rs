@@ -111,6 +111,8 @@ impl ImportState {
             options.push(ChoiceOptionRaw {
                 text: block.text,
                 target: target.clone(),
+                weight: None,
+                effects: Vec::new(),
             });
             block_plan.push((target, block.body_start, block.body_end, block.line));
         }
@@ -126,8 +128,12 @@ impl ImportState {
             return;
         }
 
-        self.events
-            .push(EventRaw::Choice(ChoiceRaw { prompt, options }));
+        self.events.push(EventRaw::Choice(ChoiceRaw {
+            prompt,
+            options,
+            auto: false,
+            fallthrough: None,
+        }));
 
         for (target, start, end, opt_line) in block_plan {
             self.labels.insert(target, self.events.len());
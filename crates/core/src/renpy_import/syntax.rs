@@ -237,6 +237,8 @@ pub(super) fn parse_scene_decl(
             background,
             music: None,
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         },
         transition: with_clause.and_then(parse_with_kind),
     })
@@ -289,6 +291,8 @@ pub(super) fn parse_show_decl(
                     x: None,
                     y: None,
                     scale: None,
+                    z: None,
+                    flip_x: false,
                 }],
                 update: Vec::new(),
                 remove: Vec::new(),
@@ -324,6 +328,8 @@ pub(super) fn parse_show_decl(
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
             update: Vec::new(),
             remove: Vec::new(),
@@ -48,7 +48,7 @@ pub fn run_repro_case_with_limits(
                     traces.push(build_step_trace(steps, event_ip, &event, &engine));
 
                     let step_result = match &event {
-                        EventCompiled::Choice(choice) => {
+                        EventCompiled::Choice(choice) if !choice.options.is_empty() => {
                             let selected = case
                                 .choice_route
                                 .get(choice_cursor)
@@ -58,6 +58,7 @@ pub fn run_repro_case_with_limits(
                             choice_cursor = choice_cursor.saturating_add(1);
                             engine.choose(selected).map(|_| ())
                         }
+                        EventCompiled::InputText { .. } => engine.submit_text(""),
                         _ => engine.step().map(|_| ()),
                     };
                     if let Err(err) = step_result {
@@ -179,20 +179,7 @@ pub(super) fn evaluate_monitors(
 }
 
 pub(super) fn event_kind_compiled(event: &EventCompiled) -> &'static str {
-    match event {
-        EventCompiled::Dialogue(_) => "dialogue",
-        EventCompiled::Choice(_) => "choice",
-        EventCompiled::Scene(_) => "scene",
-        EventCompiled::Jump { .. } => "jump",
-        EventCompiled::SetFlag { .. } => "set_flag",
-        EventCompiled::SetVar { .. } => "set_var",
-        EventCompiled::JumpIf { .. } => "jump_if",
-        EventCompiled::Patch(_) => "patch",
-        EventCompiled::ExtCall { .. } => "ext_call",
-        EventCompiled::AudioAction(_) => "audio_action",
-        EventCompiled::Transition(_) => "transition",
-        EventCompiled::SetCharacterPosition(_) => "set_character_position",
-    }
+    event.kind()
 }
 
 pub(super) fn compiled_event_signature(event: &EventCompiled) -> String {
@@ -216,6 +203,7 @@ pub(super) fn compiled_event_signature(event: &EventCompiled) -> String {
         EventCompiled::Jump { .. } => "jump".to_string(),
         EventCompiled::SetFlag { value, .. } => format!("set_flag|{}", value),
         EventCompiled::SetVar { value, .. } => format!("set_var|{}", value),
+        EventCompiled::SetStrVar { value, .. } => format!("set_str_var|{}", value),
         EventCompiled::JumpIf { cond, .. } => format!("jump_if|{}", cond_signature(cond)),
         EventCompiled::Patch(patch) => format!(
             "patch|bg={:?}|music={:?}|add={}|upd={}|rm={}",
@@ -250,6 +238,11 @@ pub(super) fn compiled_event_signature(event: &EventCompiled) -> String {
             pos.y,
             fmt_opt_f32(pos.scale)
         ),
+        EventCompiled::InputText {
+            prompt, max_len, ..
+        } => format!("input_text|{prompt}|{max_len}"),
+        EventCompiled::UnlockGallery { id } => format!("unlock_gallery|{id}"),
+        EventCompiled::Achieve { id } => format!("achieve|{id}"),
     }
 }
 
@@ -257,6 +250,7 @@ fn cond_signature(cond: &CondCompiled) -> String {
     match cond {
         CondCompiled::Flag { is_set, .. } => format!("flag|{}", is_set),
         CondCompiled::VarCmp { op, value, .. } => format!("var|{:?}|{}", op, value),
+        CondCompiled::StrEq { value, .. } => format!("str|{}", value),
     }
 }
 
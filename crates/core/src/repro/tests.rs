@@ -54,12 +54,18 @@ fn run_repro_case_honors_choice_route() {
                     ChoiceOptionRaw {
                         text: "A".to_string(),
                         target: "left".to_string(),
+                        weight: None,
+                        effects: Vec::new(),
                     },
                     ChoiceOptionRaw {
                         text: "B".to_string(),
                         target: "right".to_string(),
+                        weight: None,
+                        effects: Vec::new(),
                     },
                 ],
+                auto: false,
+                fallthrough: None,
             }),
             EventRaw::Dialogue(DialogueRaw {
                 speaker: "L".to_string(),
@@ -9,6 +9,8 @@ pub struct ResourceLimiter {
     pub max_asset_length: usize,
     pub max_characters: usize,
     pub max_script_bytes: usize,
+    /// Enables `EngineMetrics` collection; disabled engines keep no recorder.
+    pub collect_metrics: bool,
 }
 
 impl Default for ResourceLimiter {
@@ -20,10 +22,65 @@ impl Default for ResourceLimiter {
             max_asset_length: 128,
             max_characters: 32,
             max_script_bytes: 512 * 1024,
+            collect_metrics: false,
         }
     }
 }
 
+/// Usage of a single resource budget against its limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetEntry {
+    pub used: usize,
+    pub limit: usize,
+}
+
+impl BudgetEntry {
+    pub fn new(used: usize, limit: usize) -> Self {
+        Self { used, limit }
+    }
+
+    /// Percentage of the limit in use; a zero limit reports 100% once anything is used.
+    pub fn utilization_percent(&self) -> f32 {
+        if self.limit == 0 {
+            return if self.used == 0 { 0.0 } else { 100.0 };
+        }
+        self.used as f32 / self.limit as f32 * 100.0
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.used > self.limit
+    }
+}
+
+/// Informational snapshot of how close a script is to its [`ResourceLimiter`] budgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetReport {
+    pub events: BudgetEntry,
+    pub string_bytes: BudgetEntry,
+    pub longest_text: BudgetEntry,
+    pub longest_label: BudgetEntry,
+    pub label_count: usize,
+}
+
+impl BudgetReport {
+    /// Named entries in display order.
+    pub fn entries(&self) -> [(&'static str, BudgetEntry); 4] {
+        [
+            ("events", self.events),
+            ("string_bytes", self.string_bytes),
+            ("longest_text", self.longest_text),
+            ("longest_label", self.longest_label),
+        ]
+    }
+
+    pub fn max_utilization_percent(&self) -> f32 {
+        self.entries()
+            .iter()
+            .map(|(_, entry)| entry.utilization_percent())
+            .fold(0.0, f32::max)
+    }
+}
+
 /// Trait for calculating the string budget (size in bytes) of a resource.
 pub trait StringBudget {
     fn string_bytes(&self) -> usize;
@@ -109,6 +166,16 @@ where
         self.evict_overflow();
     }
 
+    /// Drops `key`, returning its bytes if it was cached.
+    pub fn remove(&mut self, key: &K) -> Option<Vec<u8>> {
+        let value = self.map.remove(key)?;
+        if let Some(pos) = self.order.iter().position(|entry| entry == key) {
+            self.order.remove(pos);
+        }
+        self.current_bytes = self.current_bytes.saturating_sub(value.len());
+        Some(value)
+    }
+
     fn touch(&mut self, key: &K) {
         if let Some(pos) = self.order.iter().position(|entry| entry == key) {
             self.order.remove(pos);
@@ -173,4 +240,21 @@ mod tests {
         assert!(cache.get(&3).is_none()); // Evicted (LRU)
         assert!(cache.get(&4).is_some());
     }
+
+    #[test]
+    fn test_lru_remove_releases_budget() {
+        let mut cache = LruCache::<u32>::new(10);
+        cache.insert(1, vec![1, 2, 3, 4]);
+        cache.insert(2, vec![5, 6]);
+
+        assert_eq!(cache.remove(&1), Some(vec![1, 2, 3, 4]));
+        assert_eq!(cache.remove(&1), None);
+        assert_eq!(cache.current_bytes(), 2);
+        assert_eq!(cache.len(), 1);
+
+        // The removed key no longer takes part in eviction order.
+        cache.insert(3, vec![0; 8]);
+        assert!(cache.get(&2).is_some());
+        assert!(cache.get(&3).is_some());
+    }
 }
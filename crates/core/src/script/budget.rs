@@ -0,0 +1,41 @@
+use crate::event::EventRaw;
+use crate::resource::{BudgetEntry, BudgetReport, ResourceLimiter};
+
+use super::raw::ScriptRaw;
+
+impl ScriptRaw {
+    /// Measures the script against `limits` without compiling or failing.
+    ///
+    /// String bytes are counted by the same walk as
+    /// [`ScriptRaw::ensure_string_budget`], macros and aliases included.
+    pub fn budget_report(&self, limits: &ResourceLimiter) -> BudgetReport {
+        let string_bytes = self
+            .header_string_bytes()
+            .chain(self.body_string_bytes())
+            .fold(0usize, usize::saturating_add);
+        let longest_label = self.labels.keys().map(String::len).max().unwrap_or(0);
+        let longest_text = self.events.iter().map(longest_text_len).max().unwrap_or(0);
+
+        BudgetReport {
+            events: BudgetEntry::new(self.events.len(), limits.max_events),
+            string_bytes: BudgetEntry::new(string_bytes, limits.max_script_bytes),
+            longest_text: BudgetEntry::new(longest_text, limits.max_text_length),
+            longest_label: BudgetEntry::new(longest_label, limits.max_label_length),
+            label_count: self.labels.len(),
+        }
+    }
+}
+
+fn longest_text_len(event: &EventRaw) -> usize {
+    match event {
+        EventRaw::Dialogue(dialogue) => dialogue.text.len(),
+        EventRaw::Choice(choice) => choice
+            .options
+            .iter()
+            .map(|option| option.text.len())
+            .chain(std::iter::once(choice.prompt.len()))
+            .max()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
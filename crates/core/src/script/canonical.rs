@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{VnError, VnResult};
+use crate::event::EventRaw;
+use crate::version::SCRIPT_SCHEMA_VERSION;
+
+use super::raw::ScriptRaw;
+
+#[derive(Serialize)]
+struct CanonicalScript<'a> {
+    script_schema_version: &'a str,
+    events: &'a [EventRaw],
+    labels: &'a BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    macros: &'a BTreeMap<String, Vec<EventRaw>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    character_aliases: &'a BTreeMap<String, String>,
+}
+
+impl ScriptRaw {
+    /// Serializes the script to JSON that is byte-identical for identical scripts.
+    ///
+    /// Fields follow declaration order, labels are sorted, and floats are
+    /// written at `f32` precision with negative zero folded to zero.
+    pub fn to_canonical_json(&self) -> VnResult<String> {
+        let mut events = self.events.clone();
+        for event in &mut events {
+            normalize_event_floats(event);
+        }
+        let mut macros = self.macros.clone();
+        for event in macros.values_mut().flatten() {
+            normalize_event_floats(event);
+        }
+        let canonical = CanonicalScript {
+            script_schema_version: SCRIPT_SCHEMA_VERSION,
+            events: &events,
+            labels: &self.labels,
+            macros: &macros,
+            character_aliases: &self.character_aliases,
+        };
+        serde_json::to_string_pretty(&canonical).map_err(|e| VnError::Serialization {
+            message: e.to_string(),
+            src: "".to_string(),
+            span: (0, 0).into(),
+        })
+    }
+
+    /// Returns the hex-encoded SHA-256 of [`ScriptRaw::to_canonical_json`].
+    pub fn script_checksum(&self) -> VnResult<String> {
+        let canonical = self.to_canonical_json()?;
+        let digest = Sha256::digest(canonical.as_bytes());
+        let mut out = String::with_capacity(digest.len() * 2);
+        for byte in digest.iter() {
+            let _ = write!(&mut out, "{byte:02x}");
+        }
+        Ok(out)
+    }
+}
+
+fn normalize_event_floats(event: &mut EventRaw) {
+    match event {
+        EventRaw::Scene(scene) => {
+            for character in &mut scene.characters {
+                normalize_f32(&mut character.scale);
+            }
+        }
+        EventRaw::Patch(patch) => {
+            for character in &mut patch.add {
+                normalize_f32(&mut character.scale);
+            }
+        }
+        EventRaw::AudioAction(action) => normalize_f32(&mut action.volume),
+        EventRaw::SetCharacterPosition(position) => normalize_f32(&mut position.scale),
+        EventRaw::Dialogue(_)
+        | EventRaw::Choice(_)
+        | EventRaw::Jump { .. }
+        | EventRaw::SetFlag { .. }
+        | EventRaw::SetVar { .. }
+        | EventRaw::SetStrVar { .. }
+        | EventRaw::JumpIf { .. }
+        | EventRaw::ExtCall { .. }
+        | EventRaw::Transition(_)
+        | EventRaw::InputText { .. }
+        | EventRaw::UnlockGallery { .. }
+        | EventRaw::Achieve { .. }
+        | EventRaw::Include { .. }
+        | EventRaw::Expand { .. } => {}
+    }
+}
+
+fn normalize_f32(value: &mut Option<f32>) {
+    if let Some(v) = value {
+        if *v == 0.0 {
+            *v = 0.0;
+        }
+    }
+}
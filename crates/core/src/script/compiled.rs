@@ -1,8 +1,8 @@
 use std::collections::BTreeMap;
 
 use crate::error::{VnError, VnResult};
-use crate::event::EventCompiled;
-use crate::version::{COMPILED_FORMAT_VERSION, SCRIPT_BINARY_MAGIC};
+use crate::event::{EventCompiled, SharedStr};
+use crate::version::{COMPILED_FORMAT_VERSION, SCRIPT_BINARY_MAGIC, SCRIPT_COMPACT_TAG};
 
 /// Runtime-ready script that resolves labels and interns strings.
 ///
@@ -13,12 +13,28 @@ use crate::version::{COMPILED_FORMAT_VERSION, SCRIPT_BINARY_MAGIC};
 /// - Checksum (4): CRC32 of payload
 /// - Length (4): LE u32
 /// - Payload: Postcard-serialized data
+///
+/// With the `compact-binary` feature, [`ScriptCompiled::to_postcard`] drops the
+/// checksum and length for a 3-byte header (tag byte + LE u16 version);
+/// `from_binary` accepts either layout.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ScriptCompiled {
     pub events: Vec<EventCompiled>,
     pub labels: BTreeMap<String, u32>,
     pub start_ip: u32,
     pub flag_count: u32,
+    /// Source names of flags, indexed by flag id.
+    #[serde(default)]
+    pub flag_names: Vec<SharedStr>,
+    /// Source names of variables, indexed by variable id.
+    #[serde(default)]
+    pub var_names: Vec<SharedStr>,
+    /// Display names keyed by speaker id.
+    #[serde(default)]
+    pub character_aliases: BTreeMap<String, String>,
+    /// Source names of string variables, indexed by string variable id.
+    #[serde(default)]
+    pub str_var_names: Vec<SharedStr>,
 }
 
 impl ScriptCompiled {
@@ -55,44 +71,106 @@ impl ScriptCompiled {
     /// - Returns a fully reconstructed `ScriptCompiled`.
     ///
     /// # Errors
-    /// - `VnError::BinaryFormat` if magic bytes, version, or checksum are invalid.
+    /// - `VnError::BinaryFormat` if magic bytes or version are invalid.
+    /// - `VnError::CorruptScript` if the payload length or CRC32 does not match
+    ///   the header, i.e. the file was truncated or altered after export.
     pub fn from_binary(input: &[u8]) -> VnResult<Self> {
-        if input.len() < 14 {
-            return Err(binary_format_error("binary payload too small"));
+        if input.first() == Some(&SCRIPT_COMPACT_TAG) {
+            #[cfg(feature = "compact-binary")]
+            return Self::from_postcard(input);
+            #[cfg(not(feature = "compact-binary"))]
+            return Err(binary_format_error(
+                "compact script encoding requires the `compact-binary` feature",
+            ));
+        }
+        postcard::from_bytes(verified_payload(input)?).map_err(binary_serialize_error)
+    }
+
+    /// Serializes to the compact encoding: [`SCRIPT_COMPACT_TAG`], the LE u16
+    /// [`COMPILED_FORMAT_VERSION`], then the bare postcard payload. Smaller
+    /// than [`ScriptCompiled::to_binary`] but without corruption detection.
+    #[cfg(feature = "compact-binary")]
+    pub fn to_postcard(&self) -> VnResult<Vec<u8>> {
+        let mut output = vec![SCRIPT_COMPACT_TAG];
+        output.extend_from_slice(&COMPILED_FORMAT_VERSION.to_le_bytes());
+        postcard::to_extend(self, output).map_err(binary_serialize_error)
+    }
+
+    /// Deserializes the compact encoding produced by `to_postcard`.
+    ///
+    /// # Errors
+    /// - `VnError::BinaryFormat` if the tag or version is wrong, or the
+    ///   payload does not decode completely.
+    #[cfg(feature = "compact-binary")]
+    pub fn from_postcard(input: &[u8]) -> VnResult<Self> {
+        if input.len() < 3 {
+            return Err(binary_format_error("compact payload too small"));
         }
-        if input[0..4] != SCRIPT_BINARY_MAGIC {
-            return Err(binary_format_error("missing script magic bytes"));
+        if input[0] != SCRIPT_COMPACT_TAG {
+            return Err(binary_format_error("missing compact script tag"));
         }
-        let version = u16::from_le_bytes([input[4], input[5]]);
+        let version = u16::from_le_bytes([input[1], input[2]]);
         if version != COMPILED_FORMAT_VERSION {
             return Err(binary_format_error(format!(
                 "unsupported script version {version} (expected {COMPILED_FORMAT_VERSION})"
             )));
         }
-        let checksum = u32::from_le_bytes([input[6], input[7], input[8], input[9]]);
-        let payload_len = u32::from_le_bytes([input[10], input[11], input[12], input[13]]) as usize;
-        let payload = input
-            .get(14..)
-            .ok_or_else(|| binary_format_error("missing payload"))?;
-        if payload.len() != payload_len {
-            return Err(binary_format_error("payload length mismatch"));
+        let (script, rest) =
+            postcard::take_from_bytes(&input[3..]).map_err(binary_serialize_error)?;
+        if !rest.is_empty() {
+            return Err(binary_format_error("trailing bytes after compact payload"));
         }
-        let payload_checksum = crc32fast::hash(payload);
-        if payload_checksum != checksum {
-            return Err(binary_format_error("payload checksum mismatch"));
-        }
-        postcard::from_bytes(payload).map_err(binary_serialize_error)
+        Ok(script)
     }
 }
 
+/// Checks the `to_binary` header (magic, version, length, CRC32) and returns
+/// the postcard payload it guards.
+pub(super) fn verified_payload(input: &[u8]) -> VnResult<&[u8]> {
+    if input.len() < 14 {
+        return Err(binary_format_error("binary payload too small"));
+    }
+    if input[0..4] != SCRIPT_BINARY_MAGIC {
+        return Err(binary_format_error("missing script magic bytes"));
+    }
+    let version = u16::from_le_bytes([input[4], input[5]]);
+    if version != COMPILED_FORMAT_VERSION {
+        return Err(binary_format_error(format!(
+            "unsupported script version {version} (expected {COMPILED_FORMAT_VERSION})"
+        )));
+    }
+    let checksum = u32::from_le_bytes([input[6], input[7], input[8], input[9]]);
+    let payload_len = u32::from_le_bytes([input[10], input[11], input[12], input[13]]) as usize;
+    let payload = input
+        .get(14..)
+        .ok_or_else(|| binary_format_error("missing payload"))?;
+    if payload.len() != payload_len {
+        return Err(VnError::corrupt_script(format!(
+            "payload length mismatch (header {payload_len}, found {})",
+            payload.len()
+        )));
+    }
+    let payload_checksum = crc32fast::hash(payload);
+    if payload_checksum != checksum {
+        return Err(VnError::corrupt_script(format!(
+            "payload checksum mismatch (expected {checksum:08x}, found {payload_checksum:08x})"
+        )));
+    }
+    Ok(payload)
+}
+
 #[cold]
 #[inline(never)]
-fn binary_format_error(message: impl Into<String>) -> VnError {
+pub(super) fn binary_format_error(message: impl Into<String>) -> VnError {
     VnError::BinaryFormat(message.into())
 }
 
 #[cold]
 #[inline(never)]
-fn binary_serialize_error(error: impl std::fmt::Display) -> VnError {
+pub(super) fn binary_serialize_error(error: impl std::fmt::Display) -> VnError {
     VnError::BinaryFormat(format!("binary serialization error: {error}"))
 }
+
+#[cfg(test)]
+#[path = "tests/compiled_tests.rs"]
+mod tests;
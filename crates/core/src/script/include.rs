@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, HashSet};
+#[cfg(feature = "native")]
+use std::path::{Component, Path};
+
+use crate::error::{VnError, VnResult};
+use crate::event::EventRaw;
+
+use super::macros::MAX_EXPANDED_EVENTS;
+use super::raw::ScriptRaw;
+
+/// Separator between an include namespace and a sub-script label.
+pub const INCLUDE_LABEL_SEPARATOR: &str = "::";
+
+impl ScriptRaw {
+    /// Inlines every `Include` event, recursively, using `load` to fetch the
+    /// script behind each include path.
+    ///
+    /// Included labels become `<path>::<label>` (`<path>#2::<label>` for a
+    /// second include of the same path) and jumps inside the sub-script and
+    /// its macro bodies are retargeted to match; targets it does not define
+    /// are left pointing at the including script. Macro bodies always point
+    /// at the first include of their file. Execution enters at the
+    /// sub-script's `start` label and falls through to the event after the
+    /// include. Macros are merged; a name defined differently by two scripts
+    /// is an error.
+    ///
+    /// Fails once the output would exceed [`MAX_EXPANDED_EVENTS`]; use
+    /// [`ScriptRaw::resolve_includes_with_limit`] for a tighter cap.
+    pub fn resolve_includes<F>(&self, load: F) -> VnResult<ScriptRaw>
+    where
+        F: FnMut(&str) -> VnResult<ScriptRaw>,
+    {
+        self.resolve_includes_with_limit(load, MAX_EXPANDED_EVENTS)
+    }
+
+    /// Like [`ScriptRaw::resolve_includes`], but fails with
+    /// [`VnError::ResourceLimit`] as soon as the linked script would hold
+    /// more than `max_events` events or resolve more than `max_events`
+    /// includes, before diamond-shaped includes can balloon it.
+    pub fn resolve_includes_with_limit<F>(&self, load: F, max_events: usize) -> VnResult<ScriptRaw>
+    where
+        F: FnMut(&str) -> VnResult<ScriptRaw>,
+    {
+        let mut linker = Linker {
+            load,
+            stack: Vec::new(),
+            max_events,
+            includes_resolved: 0,
+        };
+        linker.link(self).map(|(linked, _)| linked)
+    }
+
+    /// [`ScriptRaw::resolve_includes`] reading JSON scripts relative to
+    /// `base_dir`. Absolute paths and `..` components are rejected.
+    #[cfg(feature = "native")]
+    pub fn resolve_includes_from_dir(&self, base_dir: &Path) -> VnResult<ScriptRaw> {
+        self.resolve_includes(|path| {
+            let rel = Path::new(path);
+            if rel
+                .components()
+                .any(|part| !matches!(part, Component::Normal(_) | Component::CurDir))
+            {
+                return Err(VnError::InvalidScript(format!(
+                    "include path '{path}' must stay inside the script directory"
+                )));
+            }
+            let json = std::fs::read_to_string(base_dir.join(rel)).map_err(|err| {
+                VnError::InvalidScript(format!("cannot read include '{path}': {err}"))
+            })?;
+            ScriptRaw::from_json(&json)
+        })
+    }
+
+    /// Returns true when any event is an unresolved `Include`.
+    pub fn has_includes(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, EventRaw::Include { .. }))
+    }
+}
+
+/// Macro bodies as written in their defining script, before any include
+/// namespacing. Conflicts are judged on these, so a file reached through two
+/// include paths does not clash with itself.
+type AuthoredMacros = BTreeMap<String, Vec<EventRaw>>;
+
+struct Linker<F> {
+    load: F,
+    stack: Vec<String>,
+    max_events: usize,
+    includes_resolved: usize,
+}
+
+impl<F> Linker<F>
+where
+    F: FnMut(&str) -> VnResult<ScriptRaw>,
+{
+    fn link(&mut self, script: &ScriptRaw) -> VnResult<(ScriptRaw, AuthoredMacros)> {
+        let mut events = Vec::with_capacity(script.events.len().min(self.max_events));
+        // Output index where each input event (and the end) landed.
+        let mut new_index = Vec::with_capacity(script.events.len() + 1);
+        let mut labels = BTreeMap::new();
+        let mut namespaces = HashSet::new();
+        let mut macros = script.macros.clone();
+        let mut authored = script.macros.clone();
+        let mut character_aliases = script.character_aliases.clone();
+
+        for event in &script.events {
+            new_index.push(events.len());
+            let EventRaw::Include { path } = event else {
+                self.push(&mut events, event.clone())?;
+                continue;
+            };
+            if self.stack.iter().any(|open| open == path) {
+                let mut chain = self.stack.clone();
+                chain.push(path.clone());
+                return Err(VnError::IncludeCycle {
+                    chain: chain.join(" -> "),
+                });
+            }
+            if self.includes_resolved >= self.max_events {
+                return Err(VnError::ResourceLimit("resolved include count".to_string()));
+            }
+            self.includes_resolved += 1;
+
+            self.stack.push(path.clone());
+            let loaded = (self.load)(path)?;
+            let (sub, sub_authored) = self.link(&loaded)?;
+            self.stack.pop();
+
+            let sub_labels: HashSet<&str> = sub.labels.keys().map(String::as_str).collect();
+            let qualifier = |namespace: &str| {
+                let namespace = namespace.to_string();
+                let sub_labels = &sub_labels;
+                move |target: &mut String| {
+                    if sub_labels.contains(target.as_str()) {
+                        *target = format!("{namespace}{INCLUDE_LABEL_SEPARATOR}{target}");
+                    }
+                }
+            };
+            merge_macros(
+                &mut macros,
+                &mut authored,
+                &sub.macros,
+                &sub_authored,
+                qualifier(path),
+                path,
+            )?;
+            for (id, display) in &sub.character_aliases {
+                character_aliases
+                    .entry(id.clone())
+                    .or_insert_with(|| display.clone());
+            }
+
+            let namespace = unique_namespace(path, &mut namespaces);
+            let mut qualify = qualifier(&namespace);
+            if sub.labels.get("start").is_some_and(|entry| *entry != 0) {
+                self.push(
+                    &mut events,
+                    EventRaw::Jump {
+                        target: format!("{namespace}{INCLUDE_LABEL_SEPARATOR}start"),
+                    },
+                )?;
+            }
+            let base = events.len();
+            for mut sub_event in sub.events {
+                retarget(&mut sub_event, &mut qualify);
+                self.push(&mut events, sub_event)?;
+            }
+            for (label, index) in &sub.labels {
+                let qualified = format!("{namespace}{INCLUDE_LABEL_SEPARATOR}{label}");
+                insert_label(&mut labels, qualified, base + index)?;
+            }
+        }
+        new_index.push(events.len());
+
+        for (label, index) in &script.labels {
+            let mapped = new_index.get(*index).copied().ok_or_else(|| {
+                VnError::InvalidScript(format!("label '{label}' points outside events"))
+            })?;
+            insert_label(&mut labels, label.clone(), mapped)?;
+        }
+
+        let mut linked = ScriptRaw::new(events, labels);
+        linked.macros = macros;
+        linked.character_aliases = character_aliases;
+        Ok((linked, authored))
+    }
+
+    fn push(&self, events: &mut Vec<EventRaw>, event: EventRaw) -> VnResult<()> {
+        if events.len() >= self.max_events {
+            return Err(VnError::ResourceLimit("linked event count".to_string()));
+        }
+        events.push(event);
+        Ok(())
+    }
+}
+
+fn merge_macros(
+    macros: &mut BTreeMap<String, Vec<EventRaw>>,
+    authored: &mut AuthoredMacros,
+    incoming: &BTreeMap<String, Vec<EventRaw>>,
+    incoming_authored: &AuthoredMacros,
+    mut qualify: impl FnMut(&mut String),
+    path: &str,
+) -> VnResult<()> {
+    for (name, body) in incoming {
+        let source = incoming_authored.get(name).unwrap_or(body);
+        match authored.get(name) {
+            Some(existing) if existing != source => {
+                return Err(VnError::InvalidScript(format!(
+                    "macro '{name}' from include '{path}' conflicts with an existing definition"
+                )));
+            }
+            Some(_) => {}
+            None => {
+                let mut body = body.clone();
+                for event in &mut body {
+                    retarget(event, &mut qualify);
+                }
+                authored.insert(name.clone(), source.clone());
+                macros.insert(name.clone(), body);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn unique_namespace(path: &str, used: &mut HashSet<String>) -> String {
+    let mut candidate = path.to_string();
+    let mut occurrence = 1;
+    while !used.insert(candidate.clone()) {
+        occurrence += 1;
+        candidate = format!("{path}#{occurrence}");
+    }
+    candidate
+}
+
+fn insert_label(labels: &mut BTreeMap<String, usize>, label: String, index: usize) -> VnResult<()> {
+    if labels.contains_key(&label) {
+        return Err(VnError::InvalidScript(format!(
+            "label '{label}' defined twice after resolving includes"
+        )));
+    }
+    labels.insert(label, index);
+    Ok(())
+}
+
+fn retarget(event: &mut EventRaw, mut qualify: impl FnMut(&mut String)) {
+    match event {
+        EventRaw::Jump { target } | EventRaw::JumpIf { target, .. } => qualify(target),
+        EventRaw::Choice(choice) => {
+            for option in &mut choice.options {
+                qualify(&mut option.target);
+            }
+            if let Some(fallthrough) = &mut choice.fallthrough {
+                qualify(fallthrough);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/include_tests.rs"]
+mod tests;
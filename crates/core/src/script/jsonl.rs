@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::error::{VnError, VnResult};
+use crate::event::EventRaw;
+use crate::resource::ResourceLimiter;
+
+use super::raw::ScriptRaw;
+
+const LABEL_DIRECTIVE: &str = "#label";
+
+impl ScriptRaw {
+    /// Parses the line-oriented authoring format: one event JSON object per
+    /// line, `#label name` to label the next event, and `#` comments.
+    ///
+    /// Blank lines and comments are skipped. A `start` label is added at the
+    /// first event unless the text defines one. Errors name the 1-based line.
+    ///
+    /// ```text
+    /// #label start
+    /// {"type": "dialogue", "speaker": "Ava", "text": "Hola"}
+    /// # a comment
+    /// {"type": "jump", "target": "start"}
+    /// ```
+    pub fn from_jsonl(text: &str) -> VnResult<Self> {
+        let limits = ResourceLimiter::default();
+        if text.len() > limits.max_script_bytes {
+            return Err(VnError::ResourceLimit(
+                "script jsonl input budget".to_string(),
+            ));
+        }
+        let mut events = Vec::new();
+        let mut labels = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_no = index + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix(LABEL_DIRECTIVE) {
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    let name = parse_label_name(rest, line_no)?;
+                    if labels.insert(name.to_string(), events.len()).is_some() {
+                        return Err(VnError::InvalidScript(format!(
+                            "line {line_no}: duplicate label '{name}'"
+                        )));
+                    }
+                    continue;
+                }
+            }
+            if trimmed.starts_with('#') {
+                continue;
+            }
+            let event: EventRaw =
+                serde_json::from_str(trimmed).map_err(|err| line_error(line, line_no, &err))?;
+            events.push(event);
+        }
+        labels.entry("start".to_string()).or_insert(0);
+        let script = Self::new(events, labels);
+        script.ensure_string_budget(limits.max_script_bytes)?;
+        Ok(script)
+    }
+}
+
+fn parse_label_name(rest: &str, line_no: usize) -> VnResult<&str> {
+    let mut words = rest.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some(name), None) => Ok(name),
+        (None, _) => Err(VnError::InvalidScript(format!(
+            "line {line_no}: {LABEL_DIRECTIVE} needs a name"
+        ))),
+        (Some(_), Some(_)) => Err(VnError::InvalidScript(format!(
+            "line {line_no}: label names cannot contain spaces"
+        ))),
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn line_error(line: &str, line_no: usize, err: &serde_json::Error) -> VnError {
+    let indent = line.len() - line.trim_start().len();
+    let offset = (indent + err.column().saturating_sub(1)).min(line.len());
+    let length = usize::from(offset < line.len());
+    VnError::Serialization {
+        message: format!("line {line_no}: {err}"),
+        src: line.to_string(),
+        span: (offset, length).into(),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/jsonl_tests.rs"]
+mod tests;
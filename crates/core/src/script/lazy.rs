@@ -0,0 +1,200 @@
+//! Compiled script that decodes events on demand.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::error::{VnError, VnResult};
+use crate::event::{EventCompiled, SharedStr};
+
+use super::compiled::{binary_format_error, binary_serialize_error, verified_payload};
+use super::source::ScriptSource;
+
+/// Events kept decoded around the current position by default.
+pub const DEFAULT_LAZY_WINDOW: usize = 64;
+
+/// Script tables stored after the event list. Postcard writes struct fields
+/// in order without names, so these must mirror the fields of
+/// [`super::ScriptCompiled`] after `events`, in the same order; the
+/// `lazy_script_round_trips_every_compiled_table` test destructures both.
+#[derive(serde::Deserialize)]
+struct ScriptTail {
+    labels: BTreeMap<String, u32>,
+    start_ip: u32,
+    flag_count: u32,
+    flag_names: Vec<SharedStr>,
+    var_names: Vec<SharedStr>,
+    character_aliases: BTreeMap<String, String>,
+    str_var_names: Vec<SharedStr>,
+}
+
+/// A `to_binary` script that keeps only byte offsets for its events and
+/// decodes a small window of them as execution advances.
+///
+/// Opening walks the payload once to index event boundaries (which also
+/// proves every event decodes), then drops the decoded values.
+#[derive(Clone)]
+pub struct LazyScript {
+    payload: Arc<[u8]>,
+    /// Start offset of each event in `payload`, plus the end of the last one.
+    offsets: Vec<usize>,
+    labels: BTreeMap<String, u32>,
+    start_ip: u32,
+    flag_count: u32,
+    flag_names: Vec<SharedStr>,
+    var_names: Vec<SharedStr>,
+    character_aliases: BTreeMap<String, String>,
+    str_var_names: Vec<SharedStr>,
+    window_start: u32,
+    window: Vec<EventCompiled>,
+    window_size: usize,
+}
+
+impl LazyScript {
+    /// Indexes a binary produced by [`super::ScriptCompiled::to_binary`].
+    ///
+    /// # Errors
+    /// Same as `ScriptCompiled::from_binary`: `BinaryFormat` for a bad
+    /// header or undecodable payload, `CorruptScript` for integrity failures.
+    pub fn from_binary(input: &[u8]) -> VnResult<Self> {
+        Self::with_window(input, DEFAULT_LAZY_WINDOW)
+    }
+
+    /// Like [`LazyScript::from_binary`] with a custom decoded window size
+    /// (at least one event).
+    pub fn with_window(input: &[u8], window_size: usize) -> VnResult<Self> {
+        let payload: Arc<[u8]> = Arc::from(verified_payload(input)?);
+        let (event_count, mut rest) =
+            postcard::take_from_bytes::<u64>(&payload).map_err(binary_serialize_error)?;
+        let event_count = usize::try_from(event_count)
+            .map_err(|_| binary_format_error("event count does not fit in memory"))?;
+
+        let mut offsets = Vec::with_capacity(event_count.saturating_add(1).min(rest.len() + 1));
+        offsets.push(payload.len() - rest.len());
+        for _ in 0..event_count {
+            let (_, next) =
+                postcard::take_from_bytes::<EventCompiled>(rest).map_err(binary_serialize_error)?;
+            rest = next;
+            offsets.push(payload.len() - rest.len());
+        }
+
+        let (tail, rest) =
+            postcard::take_from_bytes::<ScriptTail>(rest).map_err(binary_serialize_error)?;
+        if !rest.is_empty() {
+            return Err(binary_format_error("trailing bytes after script payload"));
+        }
+        let ScriptTail {
+            labels,
+            start_ip,
+            flag_count,
+            flag_names,
+            var_names,
+            character_aliases,
+            str_var_names,
+        } = tail;
+
+        let mut script = Self {
+            payload,
+            offsets,
+            labels,
+            start_ip,
+            flag_count,
+            flag_names,
+            var_names,
+            character_aliases,
+            str_var_names,
+            window_start: 0,
+            window: Vec::new(),
+            window_size: window_size.max(1),
+        };
+        script.seek(start_ip);
+        Ok(script)
+    }
+
+    /// Number of events currently held decoded.
+    pub fn resident_len(&self) -> usize {
+        self.window.len()
+    }
+
+    fn decode(&self, ip: u32) -> VnResult<EventCompiled> {
+        let index = ip as usize;
+        let (Some(&start), Some(&end)) = (self.offsets.get(index), self.offsets.get(index + 1))
+        else {
+            return Err(VnError::corrupt_script(format!(
+                "event {ip} outside lazy script"
+            )));
+        };
+        postcard::from_bytes(&self.payload[start..end]).map_err(binary_serialize_error)
+    }
+}
+
+impl ScriptSource for LazyScript {
+    fn event_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn event(&self, ip: u32) -> Option<Cow<'_, EventCompiled>> {
+        match self.resident_event(ip) {
+            Some(event) => Some(Cow::Borrowed(event)),
+            None => self.decode(ip).ok().map(Cow::Owned),
+        }
+    }
+
+    fn resident_event(&self, ip: u32) -> Option<&EventCompiled> {
+        let offset = ip.checked_sub(self.window_start)?;
+        self.window.get(offset as usize)
+    }
+
+    fn seek(&mut self, ip: u32) {
+        if self.resident_event(ip).is_some() {
+            return;
+        }
+        let end = (ip as usize)
+            .saturating_add(self.window_size)
+            .min(self.event_count());
+        // Indexing already decoded every event once, so this cannot fail.
+        self.window = (ip as usize..end)
+            .filter_map(|index| self.decode(index as u32).ok())
+            .collect();
+        self.window_start = ip;
+    }
+
+    fn labels(&self) -> &BTreeMap<String, u32> {
+        &self.labels
+    }
+
+    fn start_ip(&self) -> u32 {
+        self.start_ip
+    }
+
+    fn flag_count(&self) -> u32 {
+        self.flag_count
+    }
+
+    fn flag_names(&self) -> &[SharedStr] {
+        &self.flag_names
+    }
+
+    fn var_names(&self) -> &[SharedStr] {
+        &self.var_names
+    }
+
+    fn character_aliases(&self) -> &BTreeMap<String, String> {
+        &self.character_aliases
+    }
+
+    fn str_var_names(&self) -> &[SharedStr] {
+        &self.str_var_names
+    }
+}
+
+impl std::fmt::Debug for LazyScript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyScript")
+            .field("events", &self.event_count())
+            .field("payload_bytes", &self.payload.len())
+            .field("window_start", &self.window_start)
+            .field("resident", &self.window.len())
+            .finish()
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use crate::error::{VnError, VnResult};
+use crate::event::EventRaw;
+
+use super::raw::ScriptRaw;
+
+/// Upper bound on the events [`ScriptRaw::expand_macros`] may produce.
+pub const MAX_EXPANDED_EVENTS: usize = 1 << 20;
+
+impl ScriptRaw {
+    /// Returns a copy with every `Expand` event replaced by its macro body.
+    ///
+    /// `$1`, `$2`, ... in any string of the body are substituted with the
+    /// positional args; a macro's arity is its highest placeholder. Bodies
+    /// may expand other macros but not themselves. Labels are shifted so they
+    /// keep pointing at the same events.
+    ///
+    /// Fails once the output would exceed [`MAX_EXPANDED_EVENTS`]; use
+    /// [`ScriptRaw::expand_macros_with_limit`] for a tighter cap.
+    pub fn expand_macros(&self) -> VnResult<ScriptRaw> {
+        self.expand_macros_with_limit(MAX_EXPANDED_EVENTS)
+    }
+
+    /// Like [`ScriptRaw::expand_macros`], but fails with
+    /// [`VnError::ResourceLimit`] as soon as the expanded script would hold
+    /// more than `max_events` events, before nested macros can balloon it.
+    pub fn expand_macros_with_limit(&self, max_events: usize) -> VnResult<ScriptRaw> {
+        let mut events = Vec::with_capacity(self.events.len().min(max_events));
+        // Output index where each input event (and the end) landed.
+        let mut new_index = Vec::with_capacity(self.events.len() + 1);
+        let mut stack = Vec::new();
+        for event in &self.events {
+            new_index.push(events.len());
+            expand_into(event, &self.macros, &mut stack, &mut events, max_events)?;
+        }
+        new_index.push(events.len());
+
+        let mut labels = BTreeMap::new();
+        for (label, index) in &self.labels {
+            let mapped = new_index.get(*index).copied().ok_or_else(|| {
+                VnError::InvalidScript(format!("label '{label}' points outside events"))
+            })?;
+            labels.insert(label.clone(), mapped);
+        }
+        let mut expanded = ScriptRaw::new(events, labels);
+        expanded.character_aliases = self.character_aliases.clone();
+        Ok(expanded)
+    }
+
+    /// Returns true when any event is an `Expand` still to be substituted.
+    pub fn has_expansions(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, EventRaw::Expand { .. }))
+    }
+}
+
+fn expand_into(
+    event: &EventRaw,
+    macros: &BTreeMap<String, Vec<EventRaw>>,
+    stack: &mut Vec<String>,
+    out: &mut Vec<EventRaw>,
+    max_events: usize,
+) -> VnResult<()> {
+    let EventRaw::Expand { name, args } = event else {
+        if out.len() >= max_events {
+            return Err(VnError::ResourceLimit("expanded event count".to_string()));
+        }
+        out.push(event.clone());
+        return Ok(());
+    };
+    let body = macros
+        .get(name)
+        .ok_or_else(|| VnError::InvalidScript(format!("macro '{name}' is not defined")))?;
+    if stack.iter().any(|open| open == name) {
+        return Err(VnError::InvalidScript(format!(
+            "macro '{name}' expands itself ({} -> {name})",
+            stack.join(" -> ")
+        )));
+    }
+    let arity = body.iter().map(max_placeholder).max().unwrap_or(0);
+    if args.len() != arity {
+        return Err(VnError::InvalidScript(format!(
+            "macro '{name}' takes {arity} argument(s), got {}",
+            args.len()
+        )));
+    }
+
+    stack.push(name.clone());
+    for template in body {
+        let substituted = substitute(template, args)?;
+        expand_into(&substituted, macros, stack, out, max_events)?;
+    }
+    stack.pop();
+    Ok(())
+}
+
+fn substitute(template: &EventRaw, args: &[String]) -> VnResult<EventRaw> {
+    if args.is_empty() {
+        return Ok(template.clone());
+    }
+    let mut value = serde_json::to_value(template)
+        .map_err(|err| VnError::InvalidScript(format!("macro event not serializable: {err}")))?;
+    visit_strings(&mut value, &mut |text| {
+        if text.contains('$') {
+            *text = replace_placeholders(text, args);
+        }
+    });
+    serde_json::from_value(value)
+        .map_err(|err| VnError::InvalidScript(format!("macro expansion is invalid: {err}")))
+}
+
+fn max_placeholder(event: &EventRaw) -> usize {
+    let mut max = 0;
+    let Ok(mut value) = serde_json::to_value(event) else {
+        return 0;
+    };
+    visit_strings(&mut value, &mut |text| {
+        for (index, _) in placeholders(text) {
+            max = max.max(index);
+        }
+    });
+    max
+}
+
+fn visit_strings(value: &mut serde_json::Value, visit: &mut impl FnMut(&mut String)) {
+    match value {
+        serde_json::Value::String(text) => visit(text),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                visit_strings(item, visit);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                visit_strings(field, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Yields `(n, byte_range)` for each `$n` placeholder with `n >= 1`.
+fn placeholders(text: &str) -> impl Iterator<Item = (usize, std::ops::Range<usize>)> + '_ {
+    let bytes = text.as_bytes();
+    let mut cursor = 0;
+    std::iter::from_fn(move || {
+        while cursor < bytes.len() {
+            let start = cursor;
+            cursor += 1;
+            if bytes[start] != b'$' {
+                continue;
+            }
+            let digits_end = bytes[cursor..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .map_or(bytes.len(), |offset| cursor + offset);
+            if digits_end == cursor {
+                continue;
+            }
+            let index = text[cursor..digits_end].parse::<usize>().unwrap_or(0);
+            cursor = digits_end;
+            if index > 0 {
+                return Some((index, start..digits_end));
+            }
+        }
+        None
+    })
+}
+
+fn replace_placeholders(text: &str, args: &[String]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (index, range) in placeholders(text) {
+        out.push_str(&text[last..range.start]);
+        match args.get(index - 1) {
+            Some(arg) => out.push_str(arg),
+            None => out.push_str(&text[range.clone()]),
+        }
+        last = range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+#[cfg(test)]
+#[path = "tests/macros_tests.rs"]
+mod tests;
@@ -0,0 +1,378 @@
+//! Plain-text authoring format parsed by [`ScriptRaw::from_markdown`] and
+//! emitted by [`ScriptRaw::to_markdown`].
+//!
+//! Grammar, one construct per line (surrounding whitespace is ignored):
+//!
+//! ```text
+//! line      := blank | label | option | directive | fence | dialogue
+//! label     := "#" WS (NAME | QUOTED)      -- labels the next event
+//! option    := ">" TEXT "->" NAME          -- consecutive options form one choice
+//! directive := "@scene" (WS KEY "=" VALUE)* -- KEY is bg | background | music
+//! fence     := "```json" JSON "```"        -- any single event, over several lines
+//!            | "```json script" JSON "```" -- `macros` and `character_aliases`
+//! dialogue  := SPEAKER ":" TEXT            -- SPEAKER and TEXT non-empty
+//! ```
+//!
+//! `NAME` and `VALUE` contain no whitespace; `QUOTED` is a JSON string for
+//! label names that are not a `NAME`; `TEXT` is trimmed. Blank lines are
+//! skipped and do not split a choice. Any other line produces a
+//! [`MarkdownWarning`] and is dropped.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{VnError, VnResult};
+use crate::event::{ChoiceOptionRaw, ChoiceRaw, DialogueRaw, EventRaw, SceneUpdateRaw};
+use crate::resource::ResourceLimiter;
+
+use super::raw::ScriptRaw;
+
+const FENCE_OPEN: &str = "```json";
+const SCRIPT_FENCE_OPEN: &str = "```json script";
+const FENCE_CLOSE: &str = "```";
+
+/// A line [`ScriptRaw::from_markdown`] skipped, with its 1-based number.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkdownWarning {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ScriptRaw {
+    /// Parses the plain-text format described in this module's grammar.
+    ///
+    /// A `start` label is added at the first event unless the text defines
+    /// one. Duplicate labels are an error; unrecognized lines are returned as
+    /// warnings alongside the script.
+    pub fn from_markdown(text: &str) -> VnResult<(Self, Vec<MarkdownWarning>)> {
+        let limits = ResourceLimiter::default();
+        if text.len() > limits.max_script_bytes {
+            return Err(VnError::ResourceLimit(
+                "script markdown input budget".to_string(),
+            ));
+        }
+        let mut parser = MarkdownParser::default();
+        for (index, line) in text.lines().enumerate() {
+            parser.line(index + 1, line.trim())?;
+        }
+        if let Some(fence) = parser.fence {
+            return Err(VnError::InvalidScript(format!(
+                "line {}: unterminated {FENCE_OPEN} block",
+                fence.line
+            )));
+        }
+        parser.close_choice();
+        let MarkdownParser {
+            events,
+            mut labels,
+            warnings,
+            extras,
+            ..
+        } = parser;
+        labels.entry("start".to_string()).or_insert(0);
+        let mut script = Self::new(events, labels);
+        if let Some(extras) = extras {
+            script.macros = extras.macros;
+            script.character_aliases = extras.character_aliases;
+        }
+        script.ensure_string_budget(limits.max_script_bytes)?;
+        Ok((script, warnings))
+    }
+
+    /// Writes the script in the format read by [`ScriptRaw::from_markdown`].
+    ///
+    /// Dialogue, plain choices and character-less scenes use the line syntax;
+    /// anything else (ext calls, conditions, weighted or prompted choices,
+    /// text the line syntax cannot hold) becomes a fenced JSON block. Macros
+    /// and character aliases lead the output in a `json script` fence, so
+    /// parsing the output yields the same script.
+    pub fn to_markdown(&self) -> String {
+        let mut labels_at: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+        for (name, index) in &self.labels {
+            labels_at.entry(*index).or_default().push(name);
+        }
+        let mut output = String::new();
+        if !self.macros.is_empty() || !self.character_aliases.is_empty() {
+            let extras = ScriptExtras {
+                macros: self.macros.clone(),
+                character_aliases: self.character_aliases.clone(),
+            };
+            let json = serde_json::to_string_pretty(&extras).unwrap_or_else(|_| "{}".to_string());
+            output.push_str(&format!("{SCRIPT_FENCE_OPEN}\n{json}\n{FENCE_CLOSE}\n"));
+        }
+        let mut after_choice = false;
+        for index in 0..=self.events.len() {
+            if let Some(names) = labels_at.get(&index) {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                for name in names {
+                    output.push_str(&format!("# {}\n", label_text(name)));
+                }
+                after_choice = false;
+            }
+            let Some(event) = self.events.get(index) else {
+                break;
+            };
+            // Adjacent option lines would merge two choices into one.
+            let line = match event_line(event) {
+                Some(line) if !(after_choice && matches!(event, EventRaw::Choice(_))) => line,
+                _ => fenced_event(event),
+            };
+            after_choice = matches!(event, EventRaw::Choice(_)) && line.starts_with('>');
+            output.push_str(&line);
+        }
+        output
+    }
+}
+
+fn event_line(event: &EventRaw) -> Option<String> {
+    match event {
+        EventRaw::Dialogue(dialogue) => {
+            let speaker = dialogue.speaker.as_str();
+            let plain_speaker = is_line_text(speaker)
+                && !speaker.contains(':')
+                && !speaker.starts_with(['#', '>', '@', '`']);
+            (plain_speaker && is_line_text(&dialogue.text))
+                .then(|| format!("{speaker}: {}\n", dialogue.text))
+        }
+        EventRaw::Choice(choice) => {
+            let plain = choice.prompt.is_empty()
+                && !choice.auto
+                && choice.fallthrough.is_none()
+                && !choice.options.is_empty()
+                && choice.options.iter().all(|option| {
+                    option.weight.is_none()
+                        && option.effects.is_empty()
+                        && is_line_text(&option.text)
+                        && is_name(&option.target)
+                });
+            plain.then(|| {
+                choice
+                    .options
+                    .iter()
+                    .map(|option| format!("> {} -> {}\n", option.text, option.target))
+                    .collect()
+            })
+        }
+        EventRaw::Scene(scene) => {
+            let plain = scene.characters.is_empty()
+                && scene.chapter.is_none()
+                && scene.tags.is_empty()
+                && [&scene.background, &scene.music]
+                    .into_iter()
+                    .flatten()
+                    .all(|value| is_name(value));
+            plain.then(|| {
+                let mut line = "@scene".to_string();
+                if let Some(background) = &scene.background {
+                    line.push_str(&format!(" bg={background}"));
+                }
+                if let Some(music) = &scene.music {
+                    line.push_str(&format!(" music={music}"));
+                }
+                line.push('\n');
+                line
+            })
+        }
+        _ => None,
+    }
+}
+
+/// `name` as written after `# `: bare when it is a `NAME`, otherwise quoted.
+fn label_text(name: &str) -> String {
+    if is_name(name) && !name.starts_with('"') {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).unwrap_or_default()
+    }
+}
+
+fn fenced_event(event: &EventRaw) -> String {
+    let json = serde_json::to_string_pretty(event).unwrap_or_else(|_| "null".to_string());
+    format!("{FENCE_OPEN}\n{json}\n{FENCE_CLOSE}\n")
+}
+
+/// Non-empty, single-line and already trimmed, so it survives a re-parse.
+fn is_line_text(value: &str) -> bool {
+    !value.is_empty() && value.trim() == value && !value.contains(['\n', '\r'])
+}
+
+/// Script-wide data carried by the `json script` fence.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptExtras {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    macros: BTreeMap<String, Vec<EventRaw>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    character_aliases: BTreeMap<String, String>,
+}
+
+/// An unclosed JSON fence: its opening line number, kind and body so far.
+struct Fence {
+    line: usize,
+    script: bool,
+    body: String,
+}
+
+#[derive(Default)]
+struct MarkdownParser {
+    events: Vec<EventRaw>,
+    labels: BTreeMap<String, usize>,
+    warnings: Vec<MarkdownWarning>,
+    options: Vec<ChoiceOptionRaw>,
+    fence: Option<Fence>,
+    extras: Option<ScriptExtras>,
+}
+
+impl MarkdownParser {
+    fn line(&mut self, line_no: usize, line: &str) -> VnResult<()> {
+        if let Some(fence) = &mut self.fence {
+            if line != FENCE_CLOSE {
+                fence.body.push_str(line);
+                fence.body.push('\n');
+                return Ok(());
+            }
+            let Some(Fence { line, script, body }) = self.fence.take() else {
+                return Ok(());
+            };
+            let parse_error = |err: serde_json::Error| VnError::Serialization {
+                message: format!("line {line}: {err}"),
+                src: body.clone(),
+                span: (0, 0).into(),
+            };
+            if !script {
+                self.events
+                    .push(serde_json::from_str(&body).map_err(parse_error)?);
+            } else if self.extras.is_some() {
+                return Err(VnError::InvalidScript(format!(
+                    "line {line}: duplicate {SCRIPT_FENCE_OPEN} block"
+                )));
+            } else {
+                self.extras = Some(serde_json::from_str(&body).map_err(parse_error)?);
+            }
+            return Ok(());
+        }
+        if line.is_empty() {
+            return Ok(());
+        }
+        if let Some(option) = line.strip_prefix('>') {
+            match option.rsplit_once("->").map(|(t, n)| (t.trim(), n.trim())) {
+                Some((text, target)) if !text.is_empty() && is_name(target) => {
+                    self.options.push(ChoiceOptionRaw {
+                        text: text.to_string(),
+                        target: target.to_string(),
+                        weight: None,
+                        effects: Vec::new(),
+                    });
+                }
+                _ => self.warn(line_no, "choice option must be '> text -> label'"),
+            }
+            return Ok(());
+        }
+        self.close_choice();
+
+        if line == FENCE_OPEN || line == SCRIPT_FENCE_OPEN {
+            self.fence = Some(Fence {
+                line: line_no,
+                script: line == SCRIPT_FENCE_OPEN,
+                body: String::new(),
+            });
+        } else if let Some(rest) = line.strip_prefix('#') {
+            let Some(name) = label_name(rest) else {
+                self.warn(
+                    line_no,
+                    "label must be '# name' without spaces, or '# \"quoted name\"'",
+                );
+                return Ok(());
+            };
+            if self
+                .labels
+                .insert(name.clone(), self.events.len())
+                .is_some()
+            {
+                return Err(VnError::InvalidScript(format!(
+                    "line {line_no}: duplicate label '{name}'"
+                )));
+            }
+        } else if let Some(rest) = line.strip_prefix('@') {
+            self.directive(line_no, rest);
+        } else {
+            match line.split_once(':').map(|(s, t)| (s.trim(), t.trim())) {
+                Some((speaker, text)) if !speaker.is_empty() && !text.is_empty() => {
+                    self.events.push(EventRaw::Dialogue(DialogueRaw {
+                        speaker: speaker.to_string(),
+                        text: text.to_string(),
+                    }));
+                }
+                _ => self.warn(line_no, "unrecognized line"),
+            }
+        }
+        Ok(())
+    }
+
+    fn directive(&mut self, line_no: usize, rest: &str) {
+        let mut words = rest.split_whitespace();
+        if words.next() != Some("scene") {
+            self.warn(line_no, "unknown directive; only '@scene' is supported");
+            return;
+        }
+        let mut scene = SceneUpdateRaw::default();
+        for word in words {
+            match word.split_once('=') {
+                Some(("bg" | "background", value)) if !value.is_empty() => {
+                    scene.background = Some(value.to_string());
+                }
+                Some(("music", value)) if !value.is_empty() => {
+                    scene.music = Some(value.to_string());
+                }
+                _ => self.warn(line_no, &format!("ignored scene argument '{word}'")),
+            }
+        }
+        self.events.push(EventRaw::Scene(scene));
+    }
+
+    fn close_choice(&mut self) {
+        if self.options.is_empty() {
+            return;
+        }
+        self.events.push(EventRaw::Choice(ChoiceRaw {
+            prompt: String::new(),
+            options: std::mem::take(&mut self.options),
+            auto: false,
+            fallthrough: None,
+        }));
+    }
+
+    fn warn(&mut self, line: usize, message: &str) {
+        self.warnings.push(MarkdownWarning {
+            line,
+            message: message.to_string(),
+        });
+    }
+}
+
+/// The label named after `#`, bare or as a JSON string; `None` if malformed.
+fn label_name(rest: &str) -> Option<String> {
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let text = rest.trim();
+    let name = if text.starts_with('"') {
+        serde_json::from_str::<String>(text).ok()?
+    } else {
+        text.to_string()
+    };
+    (text.starts_with('"') || is_name(&name))
+        .then_some(name)
+        .filter(|name| !name.is_empty())
+}
+
+fn is_name(value: &str) -> bool {
+    !value.is_empty() && !value.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+#[path = "tests/markdown_tests.rs"]
+mod tests;
@@ -1,8 +1,26 @@
+mod budget;
+mod canonical;
 mod compiled;
+mod include;
+mod jsonl;
+mod lazy;
+mod macros;
+mod markdown;
 mod raw;
+mod repro;
+mod schema;
+mod source;
+mod stats;
+mod strict;
 
 pub use compiled::ScriptCompiled;
+pub use include::INCLUDE_LABEL_SEPARATOR;
+pub use lazy::{LazyScript, DEFAULT_LAZY_WINDOW};
+pub use macros::MAX_EXPANDED_EVENTS;
+pub use markdown::MarkdownWarning;
 pub use raw::ScriptRaw;
+pub use source::ScriptSource;
+pub use stats::{ContentStats, DEFAULT_READING_WPM};
 
 #[cfg(test)]
 mod tests {
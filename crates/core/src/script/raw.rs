@@ -16,11 +16,15 @@ use crate::version::SCRIPT_SCHEMA_VERSION;
 use super::compiled::ScriptCompiled;
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
-struct ScriptEnvelope {
+pub(super) struct ScriptEnvelope {
     #[serde(default)]
     script_schema_version: Option<String>,
     events: Vec<EventRaw>,
     labels: BTreeMap<String, usize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    macros: BTreeMap<String, Vec<EventRaw>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    character_aliases: BTreeMap<String, String>,
 }
 
 /// JSON-facing script format with label names and raw string data.
@@ -29,12 +33,24 @@ struct ScriptEnvelope {
 pub struct ScriptRaw {
     pub events: Vec<EventRaw>,
     pub labels: BTreeMap<String, usize>,
+    /// Event sequences spliced in by `Expand` events at compile time.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub macros: BTreeMap<String, Vec<EventRaw>>,
+    /// Display names keyed by speaker id (e.g. `"alice"` → `"???"`); the id
+    /// stays the key for logic and history.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub character_aliases: BTreeMap<String, String>,
 }
 
 impl ScriptRaw {
     /// Creates a raw script from events and labels.
     pub fn new(events: Vec<EventRaw>, labels: BTreeMap<String, usize>) -> Self {
-        Self { events, labels }
+        Self {
+            events,
+            labels,
+            macros: BTreeMap::new(),
+            character_aliases: BTreeMap::new(),
+        }
     }
 
     /// Parses a JSON script into a raw script structure.
@@ -48,6 +64,8 @@ impl ScriptRaw {
             script_schema_version: Some(SCRIPT_SCHEMA_VERSION.to_string()),
             events: self.events.clone(),
             labels: self.labels.clone(),
+            macros: self.macros.clone(),
+            character_aliases: self.character_aliases.clone(),
         };
         serde_json::to_string_pretty(&envelope).map_err(|e| VnError::Serialization {
             message: e.to_string(),
@@ -58,6 +76,8 @@ impl ScriptRaw {
 
     /// Parses a JSON script into a raw script structure with resource limits.
     pub fn from_json_with_limits(input: &str, limits: ResourceLimiter) -> VnResult<Self> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("script.from_json", bytes = input.len()).entered();
         if input.len() > limits.max_script_bytes {
             return Err(VnError::ResourceLimit(
                 "script json input budget".to_string(),
@@ -81,6 +101,8 @@ impl ScriptRaw {
                 let script = Self {
                     events: envelope.events,
                     labels: envelope.labels,
+                    macros: envelope.macros,
+                    character_aliases: envelope.character_aliases,
                 };
                 script.ensure_string_budget(limits.max_script_bytes)?;
                 Ok(script)
@@ -93,6 +115,8 @@ impl ScriptRaw {
                 let script = Self {
                     events: envelope.events,
                     labels: envelope.labels,
+                    macros: envelope.macros,
+                    character_aliases: envelope.character_aliases,
                 };
                 script.ensure_string_budget(limits.max_script_bytes)?;
                 Ok(script)
@@ -101,19 +125,16 @@ impl ScriptRaw {
     }
 
     pub fn ensure_string_budget(&self, max_bytes: usize) -> VnResult<()> {
-        let mut total = 0usize;
-        for label in self.labels.keys() {
-            total = total.saturating_add(label.len());
-        }
+        let mut total = self
+            .header_string_bytes()
+            .fold(0usize, usize::saturating_add);
         if total > max_bytes {
             return Err(VnError::ResourceLimit(
                 "script string budget (labels)".to_string(),
             ));
         }
-
-        use crate::resource::StringBudget;
-        for event in &self.events {
-            total = total.saturating_add(event.string_bytes());
+        for bytes in self.body_string_bytes() {
+            total = total.saturating_add(bytes);
             if total > max_bytes {
                 return Err(VnError::ResourceLimit("script string budget".to_string()));
             }
@@ -121,6 +142,26 @@ impl ScriptRaw {
         Ok(())
     }
 
+    /// String bytes charged for labels and character aliases.
+    pub(super) fn header_string_bytes(&self) -> impl Iterator<Item = usize> + '_ {
+        let labels = self.labels.keys().map(String::len);
+        let aliases = self
+            .character_aliases
+            .iter()
+            .map(|(id, display)| id.len().saturating_add(display.len()));
+        labels.chain(aliases)
+    }
+
+    /// String bytes charged for each macro (name and body), then each event.
+    pub(super) fn body_string_bytes(&self) -> impl Iterator<Item = usize> + '_ {
+        use crate::resource::StringBudget;
+        let macros = self
+            .macros
+            .iter()
+            .map(|(name, body)| name.len().saturating_add(body.string_bytes()));
+        macros.chain(self.events.iter().map(EventRaw::string_bytes))
+    }
+
     /// Returns the index of the `start` label.
     pub fn start_index(&self) -> VnResult<usize> {
         self.labels
@@ -131,8 +172,13 @@ impl ScriptRaw {
 
     /// Compiles a raw script into its runtime representation.
     ///
-    /// Resolves label targets, assigns flag ids, and interns repeated strings.
+    /// Expands macros, resolves label targets, assigns flag, variable and
+    /// string variable ids (each numbered on its own), and interns repeated
+    /// strings.
     pub fn compile(&self) -> VnResult<ScriptCompiled> {
+        if self.has_expansions() {
+            return self.expand_macros()?.compile();
+        }
         let _event_len = u32::try_from(self.events.len())
             .map_err(|_| VnError::InvalidScript("event count exceeds u32::MAX".to_string()))?;
         let mut pool = StringPool::default();
@@ -140,6 +186,7 @@ impl ScriptRaw {
         let mut compiled_labels = BTreeMap::new();
         let mut flag_map: HashMap<String, u32> = HashMap::new();
         let mut var_map: HashMap<String, u32> = HashMap::new();
+        let mut str_var_map: HashMap<String, u32> = HashMap::new();
 
         for (label, index) in &self.labels {
             if *index > self.events.len() {
@@ -181,9 +228,34 @@ impl ScriptRaw {
                             Ok(ChoiceOptionCompiled {
                                 text: pool.intern(&option.text),
                                 target_ip,
+                                weight: option.weight,
+                                effects: option
+                                    .effects
+                                    .iter()
+                                    .map(|effect| {
+                                        compile_choice_effect(
+                                            &option.text,
+                                            effect,
+                                            &mut flag_map,
+                                            &mut var_map,
+                                        )
+                                    })
+                                    .collect::<VnResult<Vec<_>>>()?,
                             })
                         })
                         .collect::<VnResult<Vec<_>>>()?,
+                    auto: choice.auto,
+                    fallthrough_ip: choice
+                        .fallthrough
+                        .as_ref()
+                        .map(|label| {
+                            compiled_labels.get(label).copied().ok_or_else(|| {
+                                VnError::InvalidScript(format!(
+                                    "choice fallthrough '{label}' not found"
+                                ))
+                            })
+                        })
+                        .transpose()?,
                 }),
                 EventRaw::Scene(scene) => EventCompiled::Scene(SceneUpdateCompiled {
                     background: scene.background.as_deref().map(|value| pool.intern(value)),
@@ -204,8 +276,12 @@ impl ScriptRaw {
                             x: character.x,
                             y: character.y,
                             scale: character.scale,
+                            z: character.z,
+                            flip_x: character.flip_x,
                         })
                         .collect(),
+                    chapter: scene.chapter.as_deref().map(|value| pool.intern(value)),
+                    tags: scene.tags.iter().map(|tag| pool.intern(tag)).collect(),
                 }),
                 EventRaw::Jump { target } => {
                     let target_ip = compiled_labels.get(target).copied().ok_or_else(|| {
@@ -227,11 +303,27 @@ impl ScriptRaw {
                         value: *value,
                     }
                 }
+                EventRaw::SetStrVar { key, value } => EventCompiled::SetStrVar {
+                    str_var_id: get_or_insert_id(&mut str_var_map, key)?,
+                    value: pool.intern(value),
+                },
+                EventRaw::UnlockGallery { id } => EventCompiled::UnlockGallery {
+                    id: pool.intern(id),
+                },
+                EventRaw::Achieve { id } => EventCompiled::Achieve {
+                    id: pool.intern(id),
+                },
                 EventRaw::JumpIf { cond, target } => {
                     let target_ip = compiled_labels.get(target).copied().ok_or_else(|| {
                         VnError::InvalidScript(format!("jump_if target '{target}' not found"))
                     })?;
-                    let cond = compile_cond(cond, &mut flag_map, &mut var_map)?;
+                    let cond = compile_cond(
+                        cond,
+                        &mut flag_map,
+                        &mut var_map,
+                        &mut str_var_map,
+                        &mut pool,
+                    )?;
                     EventCompiled::JumpIf { cond, target_ip }
                 }
                 EventRaw::Patch(patch) => EventCompiled::Patch(ScenePatchCompiled {
@@ -253,6 +345,8 @@ impl ScriptRaw {
                             x: character.x,
                             y: character.y,
                             scale: character.scale,
+                            z: character.z,
+                            flip_x: character.flip_x,
                         })
                         .collect(),
                     update: patch
@@ -268,6 +362,7 @@ impl ScriptRaw {
                                 .position
                                 .as_deref()
                                 .map(|value| pool.intern(value)),
+                            z: character.z,
                         })
                         .collect(),
                     remove: patch.remove.iter().map(|name| pool.intern(name)).collect(),
@@ -299,8 +394,35 @@ impl ScriptRaw {
                         x: pos.x,
                         y: pos.y,
                         scale: pos.scale,
+                        flip_x: pos.flip_x,
                     },
                 ),
+                EventRaw::InputText {
+                    var_key,
+                    prompt,
+                    max_len,
+                } => {
+                    if var_key.is_empty() || *max_len == 0 {
+                        return Err(VnError::InvalidScript(format!(
+                            "input_text '{prompt}' needs a var_key and a max_len above 0"
+                        )));
+                    }
+                    EventCompiled::InputText {
+                        str_var_id: get_or_insert_id(&mut str_var_map, var_key)?,
+                        prompt: prompt.clone(),
+                        max_len: *max_len,
+                    }
+                }
+                EventRaw::Include { path } => {
+                    return Err(VnError::InvalidScript(format!(
+                        "unresolved include '{path}'; call resolve_includes before compiling"
+                    )));
+                }
+                EventRaw::Expand { name, .. } => {
+                    return Err(VnError::InvalidScript(format!(
+                        "macro '{name}' left unexpanded"
+                    )));
+                }
             };
             compiled_events.push(compiled);
         }
@@ -310,6 +432,10 @@ impl ScriptRaw {
             labels: compiled_labels,
             start_ip,
             flag_count: flag_map.len() as u32,
+            flag_names: symbol_names(&flag_map, &mut pool),
+            var_names: symbol_names(&var_map, &mut pool),
+            character_aliases: self.character_aliases.clone(),
+            str_var_names: symbol_names(&str_var_map, &mut pool),
         })
     }
 }
@@ -376,7 +502,7 @@ fn json_error_span(input: &str, error: &serde_json::Error) -> (usize, usize) {
 
 #[cold]
 #[inline(never)]
-fn json_error_window(input: &str, offset: usize, length: usize) -> (String, usize) {
+pub(super) fn json_error_window(input: &str, offset: usize, length: usize) -> (String, usize) {
     const CONTEXT: usize = 160;
     let mut start = offset.saturating_sub(CONTEXT);
     let mut end = (offset + length + CONTEXT).min(input.len());
@@ -406,6 +532,15 @@ impl StringPool {
     }
 }
 
+/// Inverts a name-to-id table into a vector indexed by id.
+fn symbol_names(map: &HashMap<String, u32>, pool: &mut StringPool) -> Vec<SharedStr> {
+    let mut names = vec![SharedStr::from(""); map.len()];
+    for (name, id) in map {
+        names[*id as usize] = pool.intern(name);
+    }
+    names
+}
+
 fn get_or_insert_id(map: &mut HashMap<String, u32>, key: &str) -> VnResult<u32> {
     if let Some(id) = map.get(key) {
         return Ok(*id);
@@ -420,6 +555,8 @@ fn compile_cond(
     cond: &CondRaw,
     flag_map: &mut HashMap<String, u32>,
     var_map: &mut HashMap<String, u32>,
+    str_var_map: &mut HashMap<String, u32>,
+    pool: &mut StringPool,
 ) -> VnResult<CondCompiled> {
     match cond {
         CondRaw::Flag { key, is_set } => {
@@ -437,6 +574,31 @@ fn compile_cond(
                 value: *value,
             })
         }
+        CondRaw::StrEq { key, value } => Ok(CondCompiled::StrEq {
+            str_var_id: get_or_insert_id(str_var_map, key)?,
+            value: pool.intern(value),
+        }),
+    }
+}
+
+fn compile_choice_effect(
+    option_text: &str,
+    effect: &EventRaw,
+    flag_map: &mut HashMap<String, u32>,
+    var_map: &mut HashMap<String, u32>,
+) -> VnResult<EventCompiled> {
+    match effect {
+        EventRaw::SetFlag { key, value } => Ok(EventCompiled::SetFlag {
+            flag_id: get_or_insert_id(flag_map, key)?,
+            value: *value,
+        }),
+        EventRaw::SetVar { key, value } => Ok(EventCompiled::SetVar {
+            var_id: get_or_insert_id(var_map, key)?,
+            value: *value,
+        }),
+        _ => Err(VnError::InvalidScript(format!(
+            "choice option '{option_text}' effects may only be set_flag or set_var"
+        ))),
     }
 }
 
@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::event::EventRaw;
+
+use super::raw::ScriptRaw;
+
+impl ScriptRaw {
+    /// Extracts the events within `radius` of `failure_ip` into a standalone script.
+    ///
+    /// Labels are rewritten to `repro_N` and `start` is relocated to the first
+    /// kept event; macros and character aliases are kept whole. Returns `None`
+    /// when a kept event targets a label outside the window, since the trimmed
+    /// script could not compile.
+    pub fn minimal_repro(&self, failure_ip: u32, radius: usize) -> Option<ScriptRaw> {
+        if self.events.is_empty() {
+            return Some(self.with_events(Vec::new(), BTreeMap::new()));
+        }
+
+        let failure_idx = (failure_ip as usize).min(self.events.len().saturating_sub(1));
+        let start_idx = failure_idx.saturating_sub(radius);
+        let end_idx = (failure_idx + radius + 1).min(self.events.len());
+        let mut events = self.events[start_idx..end_idx].to_vec();
+
+        let mut old_to_new_label: HashMap<String, String> = HashMap::new();
+        let mut labels = BTreeMap::new();
+
+        for offset in 0..events.len() {
+            let local_name = format!("repro_{}", offset);
+            labels.insert(local_name.clone(), offset);
+        }
+
+        for (label, old_idx) in &self.labels {
+            if *old_idx >= start_idx && *old_idx < end_idx {
+                old_to_new_label.insert(label.clone(), format!("repro_{}", old_idx - start_idx));
+            }
+        }
+
+        labels.insert("start".to_string(), 0);
+
+        for event in &mut events {
+            if !rewrite_event_targets(event, &old_to_new_label) {
+                return None;
+            }
+        }
+
+        Some(self.with_events(events, labels))
+    }
+
+    /// A script with this one's macros and aliases around new events and labels.
+    fn with_events(&self, events: Vec<EventRaw>, labels: BTreeMap<String, usize>) -> ScriptRaw {
+        ScriptRaw {
+            macros: self.macros.clone(),
+            character_aliases: self.character_aliases.clone(),
+            ..ScriptRaw::new(events, labels)
+        }
+    }
+}
+
+fn rewrite_event_targets(event: &mut EventRaw, old_to_new_label: &HashMap<String, String>) -> bool {
+    match event {
+        EventRaw::Jump { target } => {
+            let Some(mapped) = old_to_new_label.get(target).cloned() else {
+                return false;
+            };
+            *target = mapped;
+        }
+        EventRaw::JumpIf { target, .. } => {
+            let Some(mapped) = old_to_new_label.get(target).cloned() else {
+                return false;
+            };
+            *target = mapped;
+        }
+        EventRaw::Choice(choice) => {
+            for option in &mut choice.options {
+                let Some(mapped) = old_to_new_label.get(&option.target).cloned() else {
+                    return false;
+                };
+                option.target = mapped;
+            }
+            if let Some(fallthrough) = &mut choice.fallthrough {
+                let Some(mapped) = old_to_new_label.get(fallthrough).cloned() else {
+                    return false;
+                };
+                *fallthrough = mapped;
+            }
+        }
+        _ => {}
+    }
+    true
+}
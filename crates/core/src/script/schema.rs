@@ -0,0 +1,44 @@
+use schemars::schema_for;
+
+use crate::error::{VnError, VnResult};
+use crate::version::SCRIPT_SCHEMA_VERSION;
+
+use super::raw::{ScriptEnvelope, ScriptRaw};
+
+impl ScriptRaw {
+    /// Returns the JSON Schema for script files, including the version envelope.
+    ///
+    /// The schema carries `$id` and `x-script-schema-version` tied to
+    /// [`SCRIPT_SCHEMA_VERSION`] so external editors can detect stale copies.
+    pub fn json_schema() -> VnResult<serde_json::Value> {
+        let schema = schema_for!(ScriptEnvelope);
+        let mut value = serde_json::to_value(schema).map_err(|err| VnError::Serialization {
+            message: err.to_string(),
+            src: "".to_string(),
+            span: (0, 0).into(),
+        })?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "$id".to_string(),
+                serde_json::Value::String(format!(
+                    "vnengine://script/{SCRIPT_SCHEMA_VERSION}/script.schema.json"
+                )),
+            );
+            object.insert(
+                "title".to_string(),
+                serde_json::Value::String("ScriptRaw".to_string()),
+            );
+            object.insert(
+                "description".to_string(),
+                serde_json::Value::String(
+                    "JSON-facing visual novel script with events and labels.".to_string(),
+                ),
+            );
+            object.insert(
+                "x-script-schema-version".to_string(),
+                serde_json::Value::String(SCRIPT_SCHEMA_VERSION.to_string()),
+            );
+        }
+        Ok(value)
+    }
+}
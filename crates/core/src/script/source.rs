@@ -0,0 +1,79 @@
+//! Storage abstraction the engine reads compiled events through.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::event::{EventCompiled, SharedStr};
+
+use super::compiled::ScriptCompiled;
+
+/// Compiled script storage: either fully decoded ([`ScriptCompiled`]) or
+/// decoded on demand ([`super::LazyScript`]).
+///
+/// # Contract
+/// - `event(ip)` is `Some` for every `ip < event_count()`.
+/// - After `seek(ip)`, `resident_event(ip)` is `Some` while `ip` is in range.
+pub trait ScriptSource {
+    /// Number of events in the script.
+    fn event_count(&self) -> usize;
+
+    /// Returns the event at `ip`, decoding it if it is not resident.
+    fn event(&self, ip: u32) -> Option<Cow<'_, EventCompiled>>;
+
+    /// Returns the event at `ip` only if it is already decoded.
+    fn resident_event(&self, ip: u32) -> Option<&EventCompiled>;
+
+    /// Called whenever execution moves to `ip`, so the source can keep the
+    /// events around it decoded.
+    fn seek(&mut self, _ip: u32) {}
+
+    fn labels(&self) -> &BTreeMap<String, u32>;
+    fn start_ip(&self) -> u32;
+    fn flag_count(&self) -> u32;
+    fn flag_names(&self) -> &[SharedStr];
+    fn var_names(&self) -> &[SharedStr];
+    fn character_aliases(&self) -> &BTreeMap<String, String>;
+    fn str_var_names(&self) -> &[SharedStr];
+}
+
+impl ScriptSource for ScriptCompiled {
+    fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    fn event(&self, ip: u32) -> Option<Cow<'_, EventCompiled>> {
+        self.events.get(ip as usize).map(Cow::Borrowed)
+    }
+
+    fn resident_event(&self, ip: u32) -> Option<&EventCompiled> {
+        self.events.get(ip as usize)
+    }
+
+    fn labels(&self) -> &BTreeMap<String, u32> {
+        &self.labels
+    }
+
+    fn start_ip(&self) -> u32 {
+        self.start_ip
+    }
+
+    fn flag_count(&self) -> u32 {
+        self.flag_count
+    }
+
+    fn flag_names(&self) -> &[SharedStr] {
+        &self.flag_names
+    }
+
+    fn var_names(&self) -> &[SharedStr] {
+        &self.var_names
+    }
+
+    fn character_aliases(&self) -> &BTreeMap<String, String> {
+        &self.character_aliases
+    }
+
+    fn str_var_names(&self) -> &[SharedStr] {
+        &self.str_var_names
+    }
+}
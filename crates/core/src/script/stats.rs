@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::event::EventRaw;
+
+use super::raw::ScriptRaw;
+
+/// Reading speed assumed by [`ContentStats::estimated_reading_time`].
+pub const DEFAULT_READING_WPM: u32 = 200;
+
+/// Writing metrics for a script, from [`ScriptRaw::content_stats`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentStats {
+    /// Words across all dialogue text, split on Unicode word boundaries.
+    pub dialogue_words: usize,
+    pub dialogue_lines: usize,
+    pub unique_speakers: usize,
+    pub choice_count: usize,
+    /// Events on the longest loop-free path from `start`.
+    pub longest_branch: usize,
+}
+
+impl ContentStats {
+    /// Time to read every dialogue word at `words_per_minute`.
+    pub fn reading_time(&self, words_per_minute: u32) -> Duration {
+        if words_per_minute == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.dialogue_words as f64 * 60.0 / f64::from(words_per_minute))
+    }
+
+    /// [`ContentStats::reading_time`] at [`DEFAULT_READING_WPM`].
+    pub fn estimated_reading_time(&self) -> Duration {
+        self.reading_time(DEFAULT_READING_WPM)
+    }
+}
+
+impl ScriptRaw {
+    /// Counts dialogue words, speakers, choices and the longest branch.
+    pub fn content_stats(&self) -> ContentStats {
+        let mut stats = ContentStats::default();
+        let mut speakers = BTreeSet::new();
+        for event in &self.events {
+            match event {
+                EventRaw::Dialogue(dialogue) => {
+                    stats.dialogue_lines += 1;
+                    stats.dialogue_words += dialogue.text.unicode_words().count();
+                    speakers.insert(dialogue.speaker.as_str());
+                }
+                EventRaw::Choice(_) => stats.choice_count += 1,
+                _ => {}
+            }
+        }
+        stats.unique_speakers = speakers.len();
+        stats.longest_branch = self.longest_branch();
+        stats
+    }
+
+    /// Longest path from `start` in events, ignoring edges that close a loop.
+    fn longest_branch(&self) -> usize {
+        let Ok(start) = self.start_index() else {
+            return 0;
+        };
+        if start >= self.events.len() {
+            return 0;
+        }
+
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+        let successors: Vec<Vec<usize>> = (0..self.events.len())
+            .map(|ip| self.event_successors(ip))
+            .collect();
+        let mut mark = vec![UNVISITED; self.events.len()];
+        let mut longest = vec![0usize; self.events.len()];
+        // Iterative DFS so long linear scripts cannot overflow the stack.
+        let mut stack = vec![(start, 0usize)];
+        mark[start] = ON_STACK;
+        while let Some((ip, next)) = stack.last_mut() {
+            let ip = *ip;
+            if let Some(&succ) = successors[ip].get(*next) {
+                *next += 1;
+                if mark[succ] == UNVISITED {
+                    mark[succ] = ON_STACK;
+                    stack.push((succ, 0));
+                }
+                continue;
+            }
+            longest[ip] = 1 + successors[ip]
+                .iter()
+                .filter(|succ| mark[**succ] == DONE)
+                .map(|succ| longest[*succ])
+                .max()
+                .unwrap_or(0);
+            mark[ip] = DONE;
+            stack.pop();
+        }
+        longest[start]
+    }
+
+    fn event_successors(&self, ip: usize) -> Vec<usize> {
+        let label = |target: &String| self.labels.get(target).copied();
+        let next = ip + 1;
+        let mut out: Vec<usize> = match &self.events[ip] {
+            EventRaw::Jump { target } => label(target).into_iter().collect(),
+            EventRaw::JumpIf { target, .. } => label(target).into_iter().chain([next]).collect(),
+            EventRaw::Choice(choice) => choice
+                .options
+                .iter()
+                .filter_map(|option| label(&option.target))
+                .collect(),
+            _ => vec![next],
+        };
+        out.retain(|succ| *succ < self.events.len());
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/stats_tests.rs"]
+mod tests;
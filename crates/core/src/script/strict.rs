@@ -0,0 +1,180 @@
+use crate::error::{VnError, VnResult};
+use crate::event::EventRaw;
+use crate::resource::ResourceLimiter;
+
+use super::raw::{json_error_window, ScriptRaw};
+
+impl ScriptRaw {
+    /// Parses a JSON script and rejects structural problems up front.
+    ///
+    /// On top of [`ScriptRaw::from_json`], checks that `start` exists, every
+    /// label points inside the script and every jump/choice target names a
+    /// known label. Dangling targets are reported as [`VnError::UnknownLabel`]
+    /// with a span into `input`.
+    pub fn from_json_strict(input: &str) -> VnResult<Self> {
+        let script = Self::from_json_with_limits(input, ResourceLimiter::default())?;
+        script.start_index()?;
+        let event_count = script.events.len();
+        for (label, index) in &script.labels {
+            if *index > event_count {
+                return Err(VnError::InvalidScript(format!(
+                    "label '{label}' points to event {index} but script has {event_count} events"
+                )));
+            }
+        }
+        for (event_index, event) in script.events.iter().enumerate() {
+            for target in event_targets(event) {
+                if !script.labels.contains_key(target) {
+                    return Err(unknown_label_error(input, event_index, target));
+                }
+            }
+        }
+        Ok(script)
+    }
+}
+
+fn event_targets(event: &EventRaw) -> Vec<&str> {
+    match event {
+        EventRaw::Jump { target } | EventRaw::JumpIf { target, .. } => vec![target.as_str()],
+        EventRaw::Choice(choice) => choice
+            .options
+            .iter()
+            .map(|option| option.target.as_str())
+            .chain(choice.fallthrough.as_deref())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn unknown_label_error(input: &str, event_index: usize, target: &str) -> VnError {
+    let (src, span) = match target_span(input, event_index, target) {
+        Some((offset, len)) => {
+            let (window, local_offset) = json_error_window(input, offset, len);
+            (window, (local_offset, len).into())
+        }
+        None => (String::new(), (0, 0).into()),
+    };
+    VnError::UnknownLabel {
+        event_index,
+        target: target.to_string(),
+        src,
+        span,
+    }
+}
+
+/// Byte offset and length of the label reference inside event `event_index`.
+///
+/// Only `target`/`fallthrough` values of that event are considered, so a label
+/// name that also shows up in dialogue or in another event is never blamed.
+/// Falls back to the whole event when the value is written with escapes.
+fn target_span(input: &str, event_index: usize, target: &str) -> Option<(usize, usize)> {
+    let (start, end) = event_range(input.as_bytes(), event_index)?;
+    let event = &input[start..end];
+    let quoted = format!("\"{target}\"");
+    let found = event.match_indices(&quoted).find(|(offset, _)| {
+        let before = event[..*offset].trim_end();
+        before.strip_suffix(':').is_some_and(|key| {
+            let key = key.trim_end();
+            key.ends_with("\"target\"") || key.ends_with("\"fallthrough\"")
+        })
+    });
+    Some(match found {
+        Some((offset, _)) => (start + offset, quoted.len()),
+        None => (start, end - start),
+    })
+}
+
+/// Byte range of element `index` of the top-level `events` array.
+fn event_range(bytes: &[u8], index: usize) -> Option<(usize, usize)> {
+    let mut pos = skip_whitespace(bytes, 0);
+    if bytes.get(pos) != Some(&b'{') {
+        return None;
+    }
+    pos += 1;
+    loop {
+        pos = skip_whitespace(bytes, pos);
+        if bytes.get(pos) != Some(&b'"') {
+            return None;
+        }
+        let key_end = skip_value(bytes, pos)?;
+        let is_events = &bytes[pos..key_end] == b"\"events\"";
+        pos = skip_whitespace(bytes, key_end);
+        if bytes.get(pos) != Some(&b':') {
+            return None;
+        }
+        pos = skip_whitespace(bytes, pos + 1);
+        if is_events {
+            if bytes.get(pos) != Some(&b'[') {
+                return None;
+            }
+            pos += 1;
+            for current in 0.. {
+                pos = skip_whitespace(bytes, pos);
+                if bytes.get(pos) == Some(&b']') {
+                    return None;
+                }
+                let end = skip_value(bytes, pos)?;
+                if current == index {
+                    return Some((pos, end));
+                }
+                pos = skip_whitespace(bytes, end);
+                if bytes.get(pos) == Some(&b',') {
+                    pos += 1;
+                }
+            }
+        }
+        pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+        match bytes.get(pos) {
+            Some(b',') => pos += 1,
+            _ => return None,
+        }
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// End offset of the JSON value starting at `pos`; the input is already known
+/// to parse, so this only has to track strings and nesting.
+fn skip_value(bytes: &[u8], pos: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (offset, byte) in bytes.iter().enumerate().skip(pos) {
+        if in_string {
+            match (escaped, byte) {
+                (true, _) => escaped = false,
+                (false, b'\\') => escaped = true,
+                (false, b'"') => {
+                    in_string = false;
+                    if depth == 0 {
+                        return Some(offset + 1);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return Some(offset),
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(offset + 1);
+                }
+            }
+            b',' if depth == 0 => return Some(offset),
+            byte if depth == 0 && byte.is_ascii_whitespace() => return Some(offset),
+            _ => {}
+        }
+    }
+    (depth == 0 && !in_string).then_some(bytes.len())
+}
@@ -0,0 +1,88 @@
+use super::*;
+use crate::script::ScriptRaw;
+
+fn sample_script() -> ScriptCompiled {
+    let json = r#"{
+        "script_schema_version": "1.0",
+        "events": [
+            {"type": "scene", "background": "bg/hall.png", "music": "bgm/calm.ogg", "characters": [
+                {"name": "Ava", "expression": "smile", "position": "left"}
+            ]},
+            {"type": "dialogue", "speaker": "Ava", "text": "Ready to go?"},
+            {"type": "set_flag", "key": "met_ava", "value": true},
+            {"type": "choice", "prompt": "Answer", "options": [
+                {"text": "Yes", "target": "yes"},
+                {"text": "No", "target": "no"}
+            ]},
+            {"type": "dialogue", "speaker": "Ava", "text": "Great."},
+            {"type": "jump", "target": "end"},
+            {"type": "dialogue", "speaker": "Ava", "text": "Maybe later."},
+            {"type": "dialogue", "speaker": "Narrator", "text": "The end."}
+        ],
+        "labels": {"start": 0, "yes": 4, "no": 6, "end": 7}
+    }"#;
+    ScriptRaw::from_json(json).unwrap().compile().unwrap()
+}
+
+#[test]
+fn binary_round_trip_keeps_nontrivial_script() {
+    let script = sample_script();
+    let bytes = script.to_binary().unwrap();
+    let loaded = ScriptCompiled::from_binary(&bytes).unwrap();
+    assert_eq!(loaded.to_binary().unwrap(), bytes);
+    assert_eq!(loaded.labels, script.labels);
+    assert_eq!(loaded.flag_names, script.flag_names);
+}
+
+#[cfg(not(feature = "compact-binary"))]
+#[test]
+fn compact_tag_without_feature_is_reported() {
+    let err = ScriptCompiled::from_binary(&[SCRIPT_COMPACT_TAG, 3, 0, 0]).unwrap_err();
+    assert!(err.to_string().contains("compact-binary"));
+}
+
+#[cfg(feature = "compact-binary")]
+#[test]
+fn postcard_round_trip_and_size() {
+    let script = sample_script();
+    let standard = script.to_binary().unwrap();
+    let compact = script.to_postcard().unwrap();
+
+    assert_eq!(compact[0], SCRIPT_COMPACT_TAG);
+    assert_eq!(&compact[1..3], &COMPILED_FORMAT_VERSION.to_le_bytes());
+    // Same payload, 3-byte header instead of 14.
+    assert_eq!(standard.len() - compact.len(), 11);
+
+    let via_compact = ScriptCompiled::from_postcard(&compact).unwrap();
+    let via_loader = ScriptCompiled::from_binary(&compact).unwrap();
+    assert_eq!(via_compact.to_binary().unwrap(), standard);
+    assert_eq!(via_loader.to_binary().unwrap(), standard);
+}
+
+#[cfg(feature = "compact-binary")]
+#[test]
+fn postcard_rejects_other_versions_and_trailing_bytes() {
+    let mut compact = sample_script().to_postcard().unwrap();
+    let mut stale = compact.clone();
+    stale[1..3].copy_from_slice(&(COMPILED_FORMAT_VERSION - 1).to_le_bytes());
+    let err = ScriptCompiled::from_postcard(&stale).unwrap_err();
+    assert!(err.to_string().contains("unsupported script version"));
+
+    compact.push(0);
+    assert!(ScriptCompiled::from_postcard(&compact).is_err());
+}
+
+#[test]
+fn flipped_payload_byte_fails_integrity_check() {
+    let mut bytes = sample_script().to_binary().unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0x01;
+    let err = ScriptCompiled::from_binary(&bytes).unwrap_err();
+    assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+    assert!(err.to_string().contains("checksum mismatch"));
+
+    let mut truncated = sample_script().to_binary().unwrap();
+    truncated.pop();
+    let err = ScriptCompiled::from_binary(&truncated).unwrap_err();
+    assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+}
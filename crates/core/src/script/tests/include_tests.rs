@@ -0,0 +1,205 @@
+use super::*;
+use crate::event::{DialogueRaw, EventCompiled};
+
+fn dialogue(text: &str) -> EventRaw {
+    EventRaw::Dialogue(DialogueRaw {
+        speaker: "N".to_string(),
+        text: text.to_string(),
+    })
+}
+
+fn script(events: Vec<EventRaw>, labels: &[(&str, usize)]) -> ScriptRaw {
+    ScriptRaw::new(
+        events,
+        labels
+            .iter()
+            .map(|(name, index)| (name.to_string(), *index))
+            .collect(),
+    )
+}
+
+fn include(path: &str) -> EventRaw {
+    EventRaw::Include {
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn include_inlines_sub_script_with_namespaced_labels() {
+    let main = script(
+        vec![dialogue("before"), include("sub.json"), dialogue("after")],
+        &[("start", 0), ("after", 2)],
+    );
+    let sub = script(
+        vec![
+            dialogue("inside"),
+            EventRaw::Jump {
+                target: "end".to_string(),
+            },
+        ],
+        &[("start", 0), ("end", 2)],
+    );
+
+    let linked = main
+        .resolve_includes(|path| {
+            assert_eq!(path, "sub.json");
+            Ok(sub.clone())
+        })
+        .expect("include resolves");
+
+    assert!(!linked.has_includes());
+    assert_eq!(linked.events.len(), 4);
+    assert_eq!(linked.labels["start"], 0);
+    assert_eq!(linked.labels["sub.json::start"], 1);
+    assert_eq!(linked.labels["sub.json::end"], 3);
+    assert_eq!(linked.labels["after"], 3);
+    assert_eq!(
+        linked.events[2],
+        EventRaw::Jump {
+            target: "sub.json::end".to_string()
+        }
+    );
+
+    let compiled = linked.compile().expect("linked script compiles");
+    assert!(matches!(
+        compiled.events[2],
+        EventCompiled::Jump { target_ip: 3 }
+    ));
+}
+
+#[test]
+fn include_qualifies_choice_fallthrough_labels() {
+    let main = script(vec![include("sub.json")], &[("start", 0)]);
+    let sub = script(
+        vec![
+            EventRaw::Choice(crate::event::ChoiceRaw {
+                prompt: "Ask?".to_string(),
+                options: Vec::new(),
+                auto: false,
+                fallthrough: Some("after".to_string()),
+            }),
+            dialogue("after"),
+        ],
+        &[("start", 0), ("after", 1)],
+    );
+
+    let linked = main
+        .resolve_includes(|_| Ok(sub.clone()))
+        .expect("include resolves");
+
+    let EventRaw::Choice(choice) = &linked.events[0] else {
+        panic!("expected choice, got {:?}", linked.events[0]);
+    };
+    assert_eq!(choice.fallthrough.as_deref(), Some("sub.json::after"));
+    linked.compile().expect("linked script compiles");
+}
+
+#[test]
+fn include_cycle_is_rejected() {
+    let main = script(vec![include("a.json")], &[("start", 0)]);
+    let err = main
+        .resolve_includes(|path| {
+            let next = if path == "a.json" { "b.json" } else { "a.json" };
+            Ok(script(vec![include(next)], &[("start", 0)]))
+        })
+        .expect_err("cycle must fail");
+    match err {
+        VnError::IncludeCycle { chain } => assert_eq!(chain, "a.json -> b.json -> a.json"),
+        other => panic!("expected IncludeCycle, got {other:?}"),
+    }
+}
+
+#[test]
+fn compile_rejects_unresolved_include() {
+    let main = script(vec![include("sub.json")], &[("start", 0)]);
+    let err = main.compile().expect_err("include must be resolved first");
+    assert!(err.to_string().contains("unresolved include 'sub.json'"));
+}
+
+#[test]
+fn include_namespaces_macro_bodies_from_sub_scripts() {
+    let main = script(
+        vec![
+            include("sub.json"),
+            EventRaw::Expand {
+                name: "go_end".to_string(),
+                args: Vec::new(),
+            },
+            dialogue("main end"),
+        ],
+        &[("start", 0), ("end", 2)],
+    );
+    let mut sub = script(vec![dialogue("inside")], &[("start", 0), ("end", 0)]);
+    sub.macros.insert(
+        "go_end".to_string(),
+        vec![EventRaw::Jump {
+            target: "end".to_string(),
+        }],
+    );
+
+    let linked = main
+        .resolve_includes(|_| Ok(sub.clone()))
+        .expect("include resolves");
+    assert_eq!(
+        linked.macros["go_end"],
+        vec![EventRaw::Jump {
+            target: "sub.json::end".to_string()
+        }]
+    );
+
+    let compiled = linked
+        .expand_macros()
+        .expect("macros expand")
+        .compile()
+        .expect("linked script compiles");
+    assert!(matches!(
+        compiled.events[1],
+        EventCompiled::Jump { target_ip: 0 }
+    ));
+}
+
+#[test]
+fn diamond_includes_share_macros_and_respect_the_event_limit() {
+    // Every level includes the next one twice, doubling the inlined events.
+    let level = |depth: usize| {
+        let mut events = vec![dialogue("leaf")];
+        if depth < 12 {
+            let next = format!("level{}.json", depth + 1);
+            events = vec![include(&next), include(&next)];
+        }
+        let mut level = script(events, &[("start", 0), ("here", 0)]);
+        level.macros.insert(
+            "again".to_string(),
+            vec![EventRaw::Jump {
+                target: "here".to_string(),
+            }],
+        );
+        level
+    };
+    let load = |path: &str| {
+        let depth = path
+            .trim_start_matches("level")
+            .trim_end_matches(".json")
+            .parse()
+            .expect("level path");
+        Ok(level(depth))
+    };
+
+    let small = script(vec![include("level10.json")], &[("start", 0)])
+        .resolve_includes_with_limit(load, 16)
+        .expect("four leaves fit the limit");
+    assert_eq!(small.events.len(), 4);
+    // Every level defines `again` the same way, so the copies reached through
+    // both include branches merge instead of conflicting.
+    assert_eq!(
+        small.macros["again"],
+        vec![EventRaw::Jump {
+            target: "level10.json::here".to_string()
+        }]
+    );
+
+    let err = script(vec![include("level0.json")], &[("start", 0)])
+        .resolve_includes_with_limit(load, 1000)
+        .expect_err("4096 leaves exceed the limit");
+    assert!(matches!(err, VnError::ResourceLimit(_)), "{err:?}");
+}
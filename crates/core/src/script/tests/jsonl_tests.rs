@@ -0,0 +1,47 @@
+use super::*;
+use crate::event::DialogueRaw;
+
+fn dialogue(speaker: &str, text: &str) -> EventRaw {
+    EventRaw::Dialogue(DialogueRaw {
+        speaker: speaker.to_string(),
+        text: text.to_string(),
+    })
+}
+
+#[test]
+fn from_jsonl_builds_events_and_labels() {
+    let text = r#"
+# Chapter one
+{"type": "dialogue", "speaker": "Ava", "text": "Hola"}
+
+#label intro
+  {"type": "dialogue", "speaker": "Ben", "text": "Buenas"}
+"#;
+    let script = ScriptRaw::from_jsonl(text).unwrap();
+
+    assert_eq!(
+        script.events,
+        vec![dialogue("Ava", "Hola"), dialogue("Ben", "Buenas")]
+    );
+    assert_eq!(
+        script.labels,
+        BTreeMap::from([("intro".to_string(), 1), ("start".to_string(), 0)])
+    );
+    script.compile().unwrap();
+}
+
+#[test]
+fn from_jsonl_reports_the_malformed_line() {
+    let text = "{\"type\": \"dialogue\", \"speaker\": \"Ava\", \"text\": \"Hola\"}\n\n{\"type\": \"dialogue\",";
+    let err = ScriptRaw::from_jsonl(text).unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{err}");
+
+    let err = ScriptRaw::from_jsonl("#label\n").unwrap_err();
+    assert!(err.to_string().contains("line 1"), "{err}");
+
+    let err = ScriptRaw::from_jsonl("#label a\n#label a\n").unwrap_err();
+    assert!(
+        err.to_string().contains("line 2: duplicate label 'a'"),
+        "{err}"
+    );
+}
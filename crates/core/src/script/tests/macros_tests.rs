@@ -0,0 +1,177 @@
+use super::*;
+use crate::event::{DialogueRaw, EventCompiled, SceneTransitionRaw};
+
+fn labels(entries: &[(&str, usize)]) -> BTreeMap<String, usize> {
+    entries
+        .iter()
+        .map(|(name, index)| (name.to_string(), *index))
+        .collect()
+}
+
+fn expand(name: &str, args: &[&str]) -> EventRaw {
+    EventRaw::Expand {
+        name: name.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+    }
+}
+
+fn script_with_enter_macro(events: Vec<EventRaw>, entries: &[(&str, usize)]) -> ScriptRaw {
+    let mut script = ScriptRaw::new(events, labels(entries));
+    script.macros.insert(
+        "enter".to_string(),
+        vec![
+            EventRaw::Transition(SceneTransitionRaw {
+                kind: "fade".to_string(),
+                duration_ms: 300,
+                color: None,
+            }),
+            EventRaw::Dialogue(DialogueRaw {
+                speaker: "$1".to_string(),
+                text: "Hello, I am $1.".to_string(),
+            }),
+        ],
+    );
+    script
+}
+
+#[test]
+fn expand_substitutes_positional_arg_and_shifts_labels() {
+    let script = script_with_enter_macro(
+        vec![
+            expand("enter", &["Ava"]),
+            EventRaw::Jump {
+                target: "start".to_string(),
+            },
+        ],
+        &[("start", 0), ("loop", 1)],
+    );
+
+    let expanded = script.expand_macros().expect("macro expands");
+    assert!(!expanded.has_expansions());
+    assert_eq!(expanded.events.len(), 3);
+    assert_eq!(
+        expanded.events[1],
+        EventRaw::Dialogue(DialogueRaw {
+            speaker: "Ava".to_string(),
+            text: "Hello, I am Ava.".to_string(),
+        })
+    );
+    assert_eq!(expanded.labels["start"], 0);
+    assert_eq!(expanded.labels["loop"], 2);
+
+    let compiled = script.compile().expect("compile expands macros");
+    assert_eq!(compiled.events.len(), 3);
+    assert!(matches!(compiled.events[0], EventCompiled::Transition(_)));
+    match &compiled.events[1] {
+        EventCompiled::Dialogue(dialogue) => assert_eq!(&*dialogue.speaker, "Ava"),
+        other => panic!("expected dialogue, got {other:?}"),
+    }
+}
+
+#[test]
+fn expand_rejects_undefined_macro_and_arity_mismatch() {
+    let undefined = ScriptRaw::new(vec![expand("missing", &[])], labels(&[("start", 0)]));
+    let err = undefined.compile().expect_err("undefined macro");
+    assert!(err.to_string().contains("macro 'missing' is not defined"));
+
+    let wrong_arity =
+        script_with_enter_macro(vec![expand("enter", &["Ava", "Ben"])], &[("start", 0)]);
+    let err = wrong_arity.compile().expect_err("arity mismatch");
+    assert!(err.to_string().contains("takes 1 argument(s), got 2"));
+}
+
+#[test]
+fn expand_rejects_recursive_macro() {
+    let mut script = ScriptRaw::new(vec![expand("loop", &[])], labels(&[("start", 0)]));
+    script
+        .macros
+        .insert("loop".to_string(), vec![expand("loop", &[])]);
+    let err = script.expand_macros().expect_err("recursion");
+    assert!(err.to_string().contains("expands itself"));
+}
+
+#[test]
+fn expand_stops_at_event_limit_before_nested_macros_balloon() {
+    let mut script = ScriptRaw::new(vec![expand("m0", &[])], labels(&[("start", 0)]));
+    // Each level expands the next ten times: 10^8 events if left unchecked.
+    for level in 0..8 {
+        let body = if level == 7 {
+            vec![EventRaw::Dialogue(DialogueRaw {
+                speaker: "N".to_string(),
+                text: "x".to_string(),
+            })]
+        } else {
+            vec![expand(&format!("m{}", level + 1), &[]); 10]
+        };
+        script.macros.insert(format!("m{level}"), body);
+    }
+
+    let err = script.expand_macros_with_limit(1_000).expect_err("capped");
+    assert!(matches!(err, VnError::ResourceLimit(_)));
+}
+
+#[test]
+fn validate_raw_checks_macro_bodies_and_expanded_events() {
+    let policy = crate::SecurityPolicy::default();
+    let limits = crate::ResourceLimiter::default();
+    let with_body = |body: Vec<EventRaw>, args: &[&str]| {
+        let mut script = ScriptRaw::new(vec![expand("m", args)], labels(&[("start", 0)]));
+        script.macros.insert("m".to_string(), body);
+        script
+    };
+
+    let long_path = with_body(
+        vec![EventRaw::AudioAction(crate::event::AudioActionRaw {
+            channel: "bgm".to_string(),
+            action: "play".to_string(),
+            asset: Some("a".repeat(limits.max_asset_length + 1)),
+            volume: None,
+            fade_duration_ms: None,
+            loop_playback: None,
+        })],
+        &[],
+    );
+    assert!(policy.validate_raw(&long_path, limits).is_err());
+
+    let placeholder_target = with_body(
+        vec![EventRaw::Jump {
+            target: "$1".to_string(),
+        }],
+        &["start"],
+    );
+    policy
+        .validate_raw(&placeholder_target, limits)
+        .expect("placeholder target resolves after expansion");
+
+    let long_arg = "a".repeat(limits.max_text_length / 2 + 1);
+    let doubled = with_body(
+        vec![EventRaw::Dialogue(DialogueRaw {
+            speaker: "N".to_string(),
+            text: "$1$1".to_string(),
+        })],
+        &[&long_arg],
+    );
+    assert!(matches!(
+        policy.validate_raw(&doubled, limits),
+        Err(VnError::ResourceLimit(_))
+    ));
+
+    let many = with_body(
+        vec![
+            EventRaw::Dialogue(DialogueRaw {
+                speaker: "N".to_string(),
+                text: "x".to_string(),
+            });
+            3
+        ],
+        &[],
+    );
+    let tight = crate::ResourceLimiter {
+        max_events: 2,
+        ..limits
+    };
+    assert!(matches!(
+        policy.validate_raw(&many, tight),
+        Err(VnError::ResourceLimit(_))
+    ));
+}
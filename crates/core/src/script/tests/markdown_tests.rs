@@ -0,0 +1,164 @@
+use super::*;
+
+fn option(text: &str, target: &str) -> ChoiceOptionRaw {
+    ChoiceOptionRaw {
+        text: text.to_string(),
+        target: target.to_string(),
+        weight: None,
+        effects: Vec::new(),
+    }
+}
+
+#[test]
+fn from_markdown_parses_labels_dialogue_scenes_and_choices() {
+    let text = "
+# start
+@scene bg=bg/room.png music=bgm/calm.ogg
+Ava: Hola, ¿qué hacemos?
+> Ir al parque -> park
+> Quedarse -> home
+
+# park
+Ava: Vamos.
+# home
+Ava: Me quedo.
+";
+    let (script, warnings) = ScriptRaw::from_markdown(text).unwrap();
+
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(
+        script.labels,
+        BTreeMap::from([
+            ("home".to_string(), 4),
+            ("park".to_string(), 3),
+            ("start".to_string(), 0),
+        ])
+    );
+    assert_eq!(
+        script.events[0],
+        EventRaw::Scene(SceneUpdateRaw {
+            background: Some("bg/room.png".to_string()),
+            music: Some("bgm/calm.ogg".to_string()),
+            characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
+        })
+    );
+    assert_eq!(
+        script.events[1],
+        EventRaw::Dialogue(DialogueRaw {
+            speaker: "Ava".to_string(),
+            text: "Hola, ¿qué hacemos?".to_string(),
+        })
+    );
+    let EventRaw::Choice(choice) = &script.events[2] else {
+        panic!("expected a choice, got {:?}", script.events[2]);
+    };
+    assert_eq!(
+        choice.options,
+        vec![option("Ir al parque", "park"), option("Quedarse", "home")]
+    );
+    assert_eq!(script.events.len(), 5);
+    script.compile().unwrap();
+}
+
+#[test]
+fn from_markdown_warns_about_unrecognized_lines() {
+    let text = "Ava: Hola\njust some prose\n@music theme.ogg\n> missing target\n@scene fog=on";
+    let (script, warnings) = ScriptRaw::from_markdown(text).unwrap();
+
+    let lines: Vec<usize> = warnings.iter().map(|warning| warning.line).collect();
+    assert_eq!(lines, vec![2, 3, 4, 5]);
+    assert_eq!(script.events.len(), 2);
+
+    let err = ScriptRaw::from_markdown("# a\nAva: uno\n# a\n").unwrap_err();
+    assert!(
+        err.to_string().contains("line 3: duplicate label 'a'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn to_markdown_round_trips_markdown_authored_scripts() {
+    let text = "
+# start
+@scene bg=bg/room.png
+Ava: Hola.
+> Izquierda -> left
+> Derecha -> right
+# left
+> Otra vez -> start
+# right
+Ben: Fin.
+";
+    let (script, _) = ScriptRaw::from_markdown(text).unwrap();
+    let emitted = script.to_markdown();
+    let (reparsed, warnings) = ScriptRaw::from_markdown(&emitted).unwrap();
+
+    assert!(warnings.is_empty(), "{warnings:?}\n{emitted}");
+    assert_eq!(reparsed.events, script.events, "{emitted}");
+    assert_eq!(reparsed.labels, script.labels);
+    assert!(!emitted.contains(FENCE_OPEN), "{emitted}");
+}
+
+#[test]
+fn to_markdown_fences_events_without_line_syntax() {
+    let mut script = ScriptRaw::from_markdown("Ava: Hola\n> Seguir -> start\n")
+        .unwrap()
+        .0;
+    script.events.push(EventRaw::ExtCall {
+        command: "shake".to_string(),
+        args: vec!["3".to_string(), "fast".to_string()],
+    });
+    // Back-to-back choices need the fence so their options do not merge.
+    script.events.insert(
+        2,
+        EventRaw::Choice(ChoiceRaw {
+            prompt: String::new(),
+            options: vec![option("Otra", "start")],
+            auto: false,
+            fallthrough: None,
+        }),
+    );
+
+    let emitted = script.to_markdown();
+    assert_eq!(emitted.matches(FENCE_OPEN).count(), 2, "{emitted}");
+    let (reparsed, warnings) = ScriptRaw::from_markdown(&emitted).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(reparsed.events, script.events, "{emitted}");
+
+    let err = ScriptRaw::from_markdown("```json\n{}\n").unwrap_err();
+    assert!(err.to_string().contains("line 1: unterminated"), "{err}");
+}
+
+#[test]
+fn to_markdown_round_trips_macros_aliases_and_quoted_labels() {
+    let json = r#"{
+        "script_schema_version": "1.0",
+        "events": [
+            { "type": "expand", "macro": "greet", "args": ["Ava"] },
+            { "type": "dialogue", "speaker": "ava", "text": "Bye." }
+        ],
+        "labels": { "start": 0, "the end": 1, "\"quoted\"": 1 },
+        "macros": {
+            "greet": [{ "type": "dialogue", "speaker": "$1", "text": "Hi!" }]
+        },
+        "character_aliases": { "ava": "???" }
+    }"#;
+    let script = ScriptRaw::from_json(json).unwrap();
+    let emitted = script.to_markdown();
+    assert!(emitted.starts_with(SCRIPT_FENCE_OPEN), "{emitted}");
+    assert!(emitted.contains("# \"the end\""), "{emitted}");
+
+    let (reparsed, warnings) = ScriptRaw::from_markdown(&emitted).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}\n{emitted}");
+    assert_eq!(reparsed.events, script.events);
+    assert_eq!(reparsed.labels, script.labels);
+    assert_eq!(reparsed.macros, script.macros);
+    assert_eq!(reparsed.character_aliases, script.character_aliases);
+    reparsed.compile().unwrap();
+
+    let twice = format!("{emitted}{SCRIPT_FENCE_OPEN}\n{{}}\n{FENCE_CLOSE}\n");
+    let err = ScriptRaw::from_markdown(&twice).unwrap_err();
+    assert!(err.to_string().contains("duplicate"), "{err}");
+}
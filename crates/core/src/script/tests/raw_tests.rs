@@ -44,3 +44,26 @@ fn compile_rejects_invalid_transition_kind() {
         .expect_err("invalid transition kind must fail");
     assert!(err.to_string().contains("invalid transition kind"));
 }
+
+#[test]
+fn to_json_omits_unset_choice_option_weight() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Go?", "options": [
+                    { "text": "Yes", "target": "start" },
+                    { "text": "Maybe", "target": "start", "weight": 2 }
+                ] }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let script = ScriptRaw::from_json(json).unwrap();
+    for output in [
+        script.to_json().unwrap(),
+        script.to_canonical_json().unwrap(),
+    ] {
+        assert!(!output.contains("null"), "{output}");
+        assert_eq!(output.matches("\"weight\"").count(), 1, "{output}");
+        assert_eq!(ScriptRaw::from_json(&output).unwrap().events, script.events);
+    }
+}
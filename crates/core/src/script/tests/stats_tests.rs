@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::*;
+use crate::event::{ChoiceOptionRaw, ChoiceRaw, DialogueRaw};
+
+fn dialogue(speaker: &str, text: &str) -> EventRaw {
+    EventRaw::Dialogue(DialogueRaw {
+        speaker: speaker.to_string(),
+        text: text.to_string(),
+    })
+}
+
+fn option(target: &str) -> ChoiceOptionRaw {
+    ChoiceOptionRaw {
+        text: target.to_string(),
+        target: target.to_string(),
+        weight: None,
+        effects: Vec::new(),
+    }
+}
+
+#[test]
+fn content_stats_counts_unicode_words_speakers_and_branches() {
+    let script = ScriptRaw::new(
+        vec![
+            // "can't" is one word; punctuation and the em dash are not words.
+            dialogue("Ava", "Hello, world — can't stop!"),
+            dialogue("Ben", "¿Qué tal, señor?"),
+            EventRaw::Choice(ChoiceRaw {
+                prompt: "Where?".to_string(),
+                options: vec![option("short"), option("long")],
+                auto: false,
+                fallthrough: None,
+            }),
+            dialogue("Ava", "short"),
+            EventRaw::Jump {
+                target: "start".to_string(),
+            },
+            dialogue("Ava", "long one"),
+            dialogue("Ben", "longer still"),
+        ],
+        BTreeMap::from([
+            ("start".to_string(), 0),
+            ("short".to_string(), 3),
+            ("long".to_string(), 5),
+        ]),
+    );
+
+    let stats = script.content_stats();
+    assert_eq!(stats.dialogue_words, 4 + 3 + 1 + 2 + 2);
+    assert_eq!(stats.dialogue_lines, 5);
+    assert_eq!(stats.unique_speakers, 2);
+    assert_eq!(stats.choice_count, 1);
+    // start -> Ben -> choice -> long -> longer; the short route loops back.
+    assert_eq!(stats.longest_branch, 5);
+    assert_eq!(stats.reading_time(60), Duration::from_secs(12));
+    assert_eq!(stats.reading_time(0), Duration::ZERO);
+}
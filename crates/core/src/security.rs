@@ -1,17 +1,39 @@
 //! Security policy validation for scripts.
 
+use std::collections::HashSet;
+
 use crate::error::{VnError, VnResult};
-use crate::event::{EventCompiled, EventRaw};
+use crate::event::{CondCompiled, EventCompiled, EventRaw};
 use crate::resource::ResourceLimiter;
-use crate::script::{ScriptCompiled, ScriptRaw};
+use crate::script::{ScriptRaw, ScriptSource};
 
 /// Policy used to validate script content and compiled ranges.
 #[derive(Clone, Debug, Default)]
 pub struct SecurityPolicy {
     pub allow_empty_speaker: bool,
+    /// Commands that `ExtCall` events may run; `None` allows every command.
+    pub ext_call_allowlist: Option<HashSet<String>>,
 }
 
 impl SecurityPolicy {
+    /// Returns `true` when the policy permits running the given ext-call command.
+    pub fn allows_ext_call(&self, command: &str) -> bool {
+        self.ext_call_allowlist
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(command))
+    }
+
+    /// Fails with [`VnError::ExtCallDenied`] when the command is not allow-listed.
+    pub fn check_ext_call(&self, command: &str) -> VnResult<()> {
+        if self.allows_ext_call(command) {
+            Ok(())
+        } else {
+            Err(VnError::ExtCallDenied {
+                command: command.to_string(),
+            })
+        }
+    }
+
     /// Validates a raw script against policy and resource limits.
     pub fn validate_raw(&self, script: &ScriptRaw, limits: ResourceLimiter) -> VnResult<()> {
         if script.events.len() > limits.max_events {
@@ -34,166 +56,257 @@ impl SecurityPolicy {
         }
 
         for event in &script.events {
-            match event {
-                EventRaw::Dialogue(dialogue) => {
-                    if !self.allow_empty_speaker && dialogue.speaker.trim().is_empty() {
-                        return Err(VnError::SecurityPolicy(
-                            "speaker cannot be empty".to_string(),
-                        ));
-                    }
-                    if dialogue.text.len() > limits.max_text_length {
-                        return Err(VnError::ResourceLimit("dialogue text".to_string()));
-                    }
+            self.validate_raw_event(script, event, limits, false)?;
+        }
+
+        for (id, display) in &script.character_aliases {
+            check_character_alias(id, display, limits)?;
+        }
+
+        for (name, body) in &script.macros {
+            if name.len() > limits.max_label_length {
+                return Err(VnError::ResourceLimit("macro name".to_string()));
+            }
+            if body.len() > limits.max_events {
+                return Err(VnError::ResourceLimit(format!("macro '{name}' body")));
+            }
+            for event in body {
+                self.validate_raw_event(script, event, limits, true)?;
+            }
+        }
+
+        if script.has_expansions() {
+            // Substituted args can lengthen strings and nested macros can
+            // multiply events, so the expanded script is checked again.
+            let expanded = script.expand_macros_with_limit(limits.max_events)?;
+            self.validate_raw(&expanded, limits)?;
+        }
+        Ok(())
+    }
+
+    /// Checks one event of `script`, or of one of its macro bodies when
+    /// `in_macro` is set; targets there may still hold `$n` placeholders.
+    fn validate_raw_event(
+        &self,
+        script: &ScriptRaw,
+        event: &EventRaw,
+        limits: ResourceLimiter,
+        in_macro: bool,
+    ) -> VnResult<()> {
+        let known_label =
+            |label: &str| script.labels.contains_key(label) || (in_macro && label.contains('$'));
+        match event {
+            EventRaw::Dialogue(dialogue) => {
+                if !self.allow_empty_speaker && dialogue.speaker.trim().is_empty() {
+                    return Err(VnError::SecurityPolicy(
+                        "speaker cannot be empty".to_string(),
+                    ));
                 }
-                EventRaw::Choice(choice) => {
-                    if choice.prompt.len() > limits.max_text_length {
-                        return Err(VnError::ResourceLimit("choice prompt".to_string()));
-                    }
-                    if choice.options.is_empty() {
-                        return Err(VnError::InvalidScript(
-                            "choice must have options".to_string(),
-                        ));
+                if dialogue.text.len() > limits.max_text_length {
+                    return Err(VnError::ResourceLimit("dialogue text".to_string()));
+                }
+            }
+            EventRaw::Choice(choice) => {
+                if choice.prompt.len() > limits.max_text_length {
+                    return Err(VnError::ResourceLimit("choice prompt".to_string()));
+                }
+                if choice.options.is_empty() && choice.fallthrough.is_none() {
+                    return Err(VnError::InvalidScript(
+                        "choice must have options or a fallthrough".to_string(),
+                    ));
+                }
+                if let Some(fallthrough) = &choice.fallthrough {
+                    if fallthrough.len() > limits.max_label_length {
+                        return Err(VnError::ResourceLimit("choice fallthrough".to_string()));
                     }
-                    for option in &choice.options {
-                        if option.text.len() > limits.max_text_length {
-                            return Err(VnError::ResourceLimit("choice option".to_string()));
-                        }
-                        if option.target.len() > limits.max_label_length {
-                            return Err(VnError::ResourceLimit("choice target".to_string()));
-                        }
-                        if !script.labels.contains_key(&option.target) {
-                            return Err(VnError::InvalidScript(format!(
-                                "choice target '{}' not found",
-                                option.target
-                            )));
-                        }
+                    if !known_label(fallthrough) {
+                        return Err(VnError::InvalidScript(format!(
+                            "choice fallthrough '{fallthrough}' not found"
+                        )));
                     }
                 }
-                EventRaw::Scene(scene) => {
-                    if scene.characters.len() > limits.max_characters {
-                        return Err(VnError::ResourceLimit("character count".to_string()));
+                for option in &choice.options {
+                    if option.text.len() > limits.max_text_length {
+                        return Err(VnError::ResourceLimit("choice option".to_string()));
                     }
-                    if let Some(background) = &scene.background {
-                        if background.len() > limits.max_asset_length {
-                            return Err(VnError::ResourceLimit("background asset".to_string()));
-                        }
+                    if option.target.len() > limits.max_label_length {
+                        return Err(VnError::ResourceLimit("choice target".to_string()));
                     }
-                    if let Some(music) = &scene.music {
-                        if music.len() > limits.max_asset_length {
-                            return Err(VnError::ResourceLimit("music asset".to_string()));
-                        }
+                    if !known_label(&option.target) {
+                        return Err(VnError::InvalidScript(format!(
+                            "choice target '{}' not found",
+                            option.target
+                        )));
                     }
-                    for character in &scene.characters {
-                        if character.name.len() > limits.max_asset_length {
-                            return Err(VnError::ResourceLimit("character name".to_string()));
-                        }
-                        if let Some(expression) = &character.expression {
-                            if expression.len() > limits.max_asset_length {
-                                return Err(VnError::ResourceLimit(
-                                    "character expression".to_string(),
-                                ));
-                            }
-                        }
-                        if let Some(position) = &character.position {
-                            if position.len() > limits.max_asset_length {
-                                return Err(VnError::ResourceLimit(
-                                    "character position".to_string(),
-                                ));
-                            }
-                        }
+                }
+            }
+            EventRaw::Scene(scene) => {
+                if scene.characters.len() > limits.max_characters {
+                    return Err(VnError::ResourceLimit("character count".to_string()));
+                }
+                if let Some(background) = &scene.background {
+                    if background.len() > limits.max_asset_length {
+                        return Err(VnError::ResourceLimit("background asset".to_string()));
                     }
                 }
-                EventRaw::Patch(patch) => {
-                    if let Some(bg) = &patch.background {
-                        validate_path(bg, "background image", limits)?;
+                if let Some(music) = &scene.music {
+                    if music.len() > limits.max_asset_length {
+                        return Err(VnError::ResourceLimit("music asset".to_string()));
                     }
-                    if let Some(music) = &patch.music {
-                        validate_path(music, "music file", limits)?;
+                }
+                for character in &scene.characters {
+                    if character.name.len() > limits.max_asset_length {
+                        return Err(VnError::ResourceLimit("character name".to_string()));
                     }
-                    for character in &patch.add {
-                        validate_path(&character.name, "character name", limits)?;
-                        if let Some(expr) = &character.expression {
-                            validate_path(expr, "character expression", limits)?;
-                        }
-                        if let Some(pos) = &character.position {
-                            if pos.len() > limits.max_label_length {
-                                return Err(VnError::ResourceLimit(
-                                    "character position".to_string(),
-                                ));
-                            }
+                    if let Some(expression) = &character.expression {
+                        if expression.len() > limits.max_asset_length {
+                            return Err(VnError::ResourceLimit("character expression".to_string()));
                         }
                     }
-                    for character in &patch.update {
-                        validate_path(&character.name, "character name", limits)?;
-                        if let Some(expr) = &character.expression {
-                            validate_path(expr, "character expression", limits)?;
+                    if let Some(position) = &character.position {
+                        if position.len() > limits.max_asset_length {
+                            return Err(VnError::ResourceLimit("character position".to_string()));
                         }
-                        if let Some(pos) = &character.position {
-                            if pos.len() > limits.max_label_length {
-                                return Err(VnError::ResourceLimit(
-                                    "character position".to_string(),
-                                ));
-                            }
-                        }
-                    }
-                    for name in &patch.remove {
-                        validate_path(name, "character name", limits)?;
                     }
                 }
-                EventRaw::Jump { target } => {
-                    if target.len() > limits.max_label_length {
-                        return Err(VnError::ResourceLimit("jump target".to_string()));
-                    }
-                    if !script.labels.contains_key(target) {
-                        return Err(VnError::InvalidScript(format!(
-                            "jump target '{target}' not found"
-                        )));
+                if let Some(chapter) = &scene.chapter {
+                    if chapter.len() > limits.max_label_length {
+                        return Err(VnError::ResourceLimit("scene chapter".to_string()));
                     }
                 }
-                EventRaw::SetFlag { key, .. } => {
-                    if key.len() > limits.max_label_length {
-                        return Err(VnError::ResourceLimit("flag key".to_string()));
-                    }
+                if scene
+                    .tags
+                    .iter()
+                    .any(|tag| tag.len() > limits.max_label_length)
+                {
+                    return Err(VnError::ResourceLimit("scene tag".to_string()));
                 }
-                EventRaw::SetVar { key, .. } => {
-                    if key.len() > limits.max_label_length {
-                        return Err(VnError::ResourceLimit("var key".to_string()));
-                    }
+            }
+            EventRaw::Patch(patch) => {
+                if let Some(bg) = &patch.background {
+                    validate_path(bg, "background image", limits)?;
                 }
-                EventRaw::JumpIf { target, .. } => {
-                    if target.len() > limits.max_label_length {
-                        return Err(VnError::ResourceLimit("jump_if target".to_string()));
+                if let Some(music) = &patch.music {
+                    validate_path(music, "music file", limits)?;
+                }
+                for character in &patch.add {
+                    validate_path(&character.name, "character name", limits)?;
+                    if let Some(expr) = &character.expression {
+                        validate_path(expr, "character expression", limits)?;
                     }
-                    if !script.labels.contains_key(target) {
-                        return Err(VnError::InvalidScript(format!(
-                            "jump_if target '{target}' not found"
-                        )));
+                    if let Some(pos) = &character.position {
+                        if pos.len() > limits.max_label_length {
+                            return Err(VnError::ResourceLimit("character position".to_string()));
+                        }
                     }
                 }
-                EventRaw::ExtCall { command, args } => {
-                    if command.len() > limits.max_label_length {
-                        return Err(VnError::ResourceLimit("ext command".to_string()));
+                for character in &patch.update {
+                    validate_path(&character.name, "character name", limits)?;
+                    if let Some(expr) = &character.expression {
+                        validate_path(expr, "character expression", limits)?;
                     }
-                    for arg in args {
-                        if arg.len() > limits.max_text_length {
-                            return Err(VnError::ResourceLimit("ext arg".to_string()));
+                    if let Some(pos) = &character.position {
+                        if pos.len() > limits.max_label_length {
+                            return Err(VnError::ResourceLimit("character position".to_string()));
                         }
                     }
                 }
-                EventRaw::AudioAction(action) => {
-                    if let Some(asset) = &action.asset {
-                        validate_path(asset, "audio asset", limits)?;
+                for name in &patch.remove {
+                    validate_path(name, "character name", limits)?;
+                }
+            }
+            EventRaw::Jump { target } => {
+                if target.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("jump target".to_string()));
+                }
+                if !known_label(target) {
+                    return Err(VnError::InvalidScript(format!(
+                        "jump target '{target}' not found"
+                    )));
+                }
+            }
+            EventRaw::SetFlag { key, .. } => {
+                if key.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("flag key".to_string()));
+                }
+            }
+            EventRaw::SetVar { key, .. } => {
+                if key.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("var key".to_string()));
+                }
+            }
+            EventRaw::SetStrVar { key, value } => {
+                if key.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("string var key".to_string()));
+                }
+                if value.len() > limits.max_text_length {
+                    return Err(VnError::ResourceLimit("string var value".to_string()));
+                }
+            }
+            EventRaw::UnlockGallery { id } => {
+                if id.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("gallery id".to_string()));
+                }
+            }
+            EventRaw::Achieve { id } => {
+                if id.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("achievement id".to_string()));
+                }
+            }
+            EventRaw::JumpIf { target, .. } => {
+                if target.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("jump_if target".to_string()));
+                }
+                if !known_label(target) {
+                    return Err(VnError::InvalidScript(format!(
+                        "jump_if target '{target}' not found"
+                    )));
+                }
+            }
+            EventRaw::ExtCall { command, args } => {
+                if command.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("ext command".to_string()));
+                }
+                for arg in args {
+                    if arg.len() > limits.max_text_length {
+                        return Err(VnError::ResourceLimit("ext arg".to_string()));
                     }
                 }
-                EventRaw::Transition(_) => {}
-                EventRaw::SetCharacterPosition(pos) => {
-                    validate_path(&pos.name, "character name", limits)?;
-                    if let Some(scale) = pos.scale {
-                        if !scale.is_finite() || scale <= 0.0 {
-                            return Err(VnError::InvalidScript(
-                                "set_character_position scale must be > 0".to_string(),
-                            ));
-                        }
+            }
+            EventRaw::AudioAction(action) => {
+                if let Some(asset) = &action.asset {
+                    validate_path(asset, "audio asset", limits)?;
+                }
+            }
+            EventRaw::Transition(_) => {}
+            EventRaw::SetCharacterPosition(pos) => {
+                validate_path(&pos.name, "character name", limits)?;
+                if let Some(scale) = pos.scale {
+                    if !scale.is_finite() || scale <= 0.0 {
+                        return Err(VnError::InvalidScript(
+                            "set_character_position scale must be > 0".to_string(),
+                        ));
+                    }
+                }
+            }
+            EventRaw::InputText {
+                var_key, prompt, ..
+            } => {
+                if var_key.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("input var_key".to_string()));
+                }
+                if prompt.len() > limits.max_text_length {
+                    return Err(VnError::ResourceLimit("input prompt".to_string()));
+                }
+            }
+            EventRaw::Include { path } => validate_path(path, "include path", limits)?,
+            EventRaw::Expand { name, args } => {
+                if name.len() > limits.max_label_length {
+                    return Err(VnError::ResourceLimit("macro name".to_string()));
+                }
+                for arg in args {
+                    if arg.len() > limits.max_text_length {
+                        return Err(VnError::ResourceLimit("macro arg".to_string()));
                     }
                 }
             }
@@ -201,44 +314,64 @@ impl SecurityPolicy {
         Ok(())
     }
 
-    /// Validates compiled targets and flag ids for runtime safety.
-    pub fn validate_compiled(
+    /// Validates compiled targets, flag ids and variable ids for runtime safety.
+    pub fn validate_compiled<S: ScriptSource + ?Sized>(
         &self,
-        script: &ScriptCompiled,
-        _limits: ResourceLimiter,
+        script: &S,
+        limits: ResourceLimiter,
     ) -> VnResult<()> {
-        if script.start_ip as usize >= script.events.len() {
-            return Err(VnError::InvalidScript(
-                "compiled start_ip outside events".to_string(),
-            ));
+        if script.event_count() > limits.max_events {
+            return Err(VnError::ResourceLimit("event count".to_string()));
+        }
+        if script.start_ip() as usize >= script.event_count() {
+            return Err(VnError::corrupt_script("compiled start_ip outside events"));
+        }
+        check_compiled_symbol_tables(script)?;
+        for (id, display) in script.character_aliases() {
+            check_character_alias(id, display, limits)?;
         }
 
-        for event in &script.events {
-            match event {
+        for ip in 0..script.event_count() {
+            let event = script
+                .event(ip as u32)
+                .ok_or_else(|| VnError::corrupt_script(format!("event {ip} unreadable")))?;
+            match &*event {
                 EventCompiled::Choice(choice) => {
                     for option in &choice.options {
-                        if option.target_ip as usize >= script.events.len() {
-                            return Err(VnError::InvalidScript(format!(
-                                "choice target_ip {} outside events",
-                                option.target_ip
-                            )));
+                        check_compiled_target(script, ip, option.target_ip)?;
+                        for effect in &option.effects {
+                            check_compiled_choice_effect(script, ip, effect)?;
                         }
                     }
+                    if let Some(target_ip) = choice.fallthrough_ip {
+                        check_compiled_target(script, ip, target_ip)?;
+                    }
                 }
                 EventCompiled::Jump { target_ip } => {
-                    if *target_ip as usize >= script.events.len() {
-                        return Err(VnError::InvalidScript(format!(
-                            "jump target_ip {} outside events",
-                            target_ip
-                        )));
-                    }
+                    check_compiled_target(script, ip, *target_ip)?;
                 }
                 EventCompiled::SetFlag { flag_id, .. } => {
-                    if *flag_id >= script.flag_count {
-                        return Err(VnError::InvalidScript(format!(
-                            "flag id {} outside compiled range",
-                            flag_id
-                        )));
+                    check_compiled_flag(script, ip, *flag_id)?;
+                }
+                EventCompiled::SetVar { var_id, .. } => {
+                    check_compiled_var(script, ip, *var_id)?;
+                }
+                EventCompiled::SetStrVar { str_var_id, .. }
+                | EventCompiled::InputText { str_var_id, .. } => {
+                    check_compiled_str_var(script, ip, *str_var_id)?;
+                }
+                EventCompiled::JumpIf { cond, target_ip } => {
+                    check_compiled_target(script, ip, *target_ip)?;
+                    match cond {
+                        CondCompiled::Flag { flag_id, .. } => {
+                            check_compiled_flag(script, ip, *flag_id)?
+                        }
+                        CondCompiled::VarCmp { var_id, .. } => {
+                            check_compiled_var(script, ip, *var_id)?
+                        }
+                        CondCompiled::StrEq { str_var_id, .. } => {
+                            check_compiled_str_var(script, ip, *str_var_id)?
+                        }
                     }
                 }
                 _ => {}
@@ -253,6 +386,110 @@ impl SecurityPolicy {
     }
 }
 
+fn check_compiled_target<S: ScriptSource + ?Sized>(
+    script: &S,
+    ip: usize,
+    target_ip: u32,
+) -> VnResult<()> {
+    if target_ip as usize >= script.event_count() {
+        return Err(VnError::corrupt_script(format!(
+            "event {ip}: target_ip {target_ip} outside events"
+        )));
+    }
+    Ok(())
+}
+
+fn check_compiled_flag<S: ScriptSource + ?Sized>(
+    script: &S,
+    ip: usize,
+    flag_id: u32,
+) -> VnResult<()> {
+    if flag_id >= script.flag_count() {
+        return Err(VnError::corrupt_script(format!(
+            "event {ip}: flag id {flag_id} outside compiled range"
+        )));
+    }
+    Ok(())
+}
+
+fn check_compiled_choice_effect<S: ScriptSource + ?Sized>(
+    script: &S,
+    ip: usize,
+    effect: &EventCompiled,
+) -> VnResult<()> {
+    match effect {
+        EventCompiled::SetFlag { flag_id, .. } => check_compiled_flag(script, ip, *flag_id),
+        EventCompiled::SetVar { var_id, .. } => check_compiled_var(script, ip, *var_id),
+        other => Err(VnError::corrupt_script(format!(
+            "event {ip}: choice effect '{}' is not set_flag or set_var",
+            other.kind()
+        ))),
+    }
+}
+
+fn check_compiled_symbol_tables<S: ScriptSource + ?Sized>(script: &S) -> VnResult<()> {
+    if script.flag_names().len() != script.flag_count() as usize {
+        return Err(VnError::corrupt_script(format!(
+            "compiled script names {} flags but declares {}",
+            script.flag_names().len(),
+            script.flag_count()
+        )));
+    }
+    // String variables can only be named by events, never by choice effects,
+    // so each one needs an event of its own.
+    if script.str_var_names().len() > script.event_count() {
+        return Err(VnError::corrupt_script(format!(
+            "compiled script names {} string variables but only {} ids are possible",
+            script.str_var_names().len(),
+            script.event_count()
+        )));
+    }
+    Ok(())
+}
+
+fn check_compiled_var<S: ScriptSource + ?Sized>(
+    script: &S,
+    ip: usize,
+    var_id: u32,
+) -> VnResult<()> {
+    if var_id as usize >= script.var_names().len() {
+        return Err(VnError::corrupt_script(format!(
+            "event {ip}: var id {var_id} outside compiled range"
+        )));
+    }
+    Ok(())
+}
+
+fn check_compiled_str_var<S: ScriptSource + ?Sized>(
+    script: &S,
+    ip: usize,
+    str_var_id: u32,
+) -> VnResult<()> {
+    if str_var_id as usize >= script.str_var_names().len() {
+        return Err(VnError::corrupt_script(format!(
+            "event {ip}: string var id {str_var_id} outside compiled range"
+        )));
+    }
+    Ok(())
+}
+
+/// Applies the length limits shared by authored and runtime character aliases.
+pub(crate) fn check_character_alias(
+    id: &str,
+    display: &str,
+    limits: ResourceLimiter,
+) -> VnResult<()> {
+    if id.len() > limits.max_label_length {
+        return Err(VnError::ResourceLimit("character alias id".to_string()));
+    }
+    if display.len() > limits.max_text_length {
+        return Err(VnError::ResourceLimit(format!(
+            "character alias '{id}' display name"
+        )));
+    }
+    Ok(())
+}
+
 fn validate_path(
     path: &str,
     name: &str,
@@ -1,14 +1,24 @@
 //! Engine state storage for execution.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
-use crate::event::DialogueCompiled;
+use crate::event::{DialogueCompiled, SharedStr};
 use crate::visual::VisualState;
 
 const HISTORY_LIMIT: usize = 200;
 
+/// Output flavour for [`EngineState::transcript`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TranscriptFormat {
+    /// `Speaker: text`, one line per entry.
+    #[default]
+    Plain,
+    /// `**Speaker:** text`, one paragraph per entry with Markdown escaped.
+    Markdown,
+}
+
 /// Runtime state for the engine, including position, flags, variables, and visuals.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EngineState {
@@ -17,6 +27,22 @@ pub struct EngineState {
     pub vars: Vec<i32>,
     pub visual: VisualState,
     pub history: VecDeque<DialogueCompiled>,
+    /// Option index picked at every choice so far, in order.
+    #[serde(default)]
+    pub choice_log: Vec<usize>,
+    /// String variable values by id, kept apart from the numeric `vars`.
+    #[serde(default)]
+    pub string_vars: BTreeMap<u32, SharedStr>,
+    /// Gallery (CG) ids unlocked so far; see [`crate::Engine::unlocked_gallery`].
+    #[serde(default)]
+    pub unlocked: BTreeSet<String>,
+    /// Achievement ids recorded so far; see [`crate::Engine::achievements`].
+    #[serde(default)]
+    pub achievements: BTreeSet<String>,
+    /// Speaker-id → display-name aliases in effect, seeded from the script
+    /// and changed by [`crate::Engine::set_character_alias`].
+    #[serde(default)]
+    pub character_aliases: BTreeMap<String, String>,
 }
 
 impl EngineState {
@@ -28,6 +54,11 @@ impl EngineState {
             vars: Vec::new(),
             visual: VisualState::default(),
             history: VecDeque::with_capacity(HISTORY_LIMIT),
+            choice_log: Vec::new(),
+            string_vars: BTreeMap::new(),
+            unlocked: BTreeSet::new(),
+            achievements: BTreeSet::new(),
+            character_aliases: BTreeMap::new(),
         }
     }
 
@@ -67,6 +98,16 @@ impl EngineState {
         self.vars.get(id as usize).copied().unwrap_or(0)
     }
 
+    /// Sets a string variable value by id.
+    pub fn set_str_var(&mut self, id: u32, value: SharedStr) {
+        self.string_vars.insert(id, value);
+    }
+
+    /// Reads a string variable value by id; unset ids read as `""`.
+    pub fn get_str_var(&self, id: u32) -> &str {
+        self.string_vars.get(&id).map_or("", |value| value)
+    }
+
     /// Records a dialogue line into the history buffer.
     pub fn record_dialogue(&mut self, dialogue: &DialogueCompiled) {
         if self.history.len() >= HISTORY_LIMIT {
@@ -74,6 +115,48 @@ impl EngineState {
         }
         self.history.push_back(dialogue.clone());
     }
+
+    /// Renders the dialogue history as a readable transcript.
+    ///
+    /// Lines without a speaker are written as narration. Empty history yields
+    /// an empty string.
+    pub fn transcript(&self, format: TranscriptFormat) -> String {
+        let mut out = String::new();
+        for entry in &self.history {
+            match format {
+                TranscriptFormat::Plain => {
+                    if !entry.speaker.is_empty() {
+                        out.push_str(&entry.speaker);
+                        out.push_str(": ");
+                    }
+                    out.push_str(&entry.text);
+                    out.push('\n');
+                }
+                TranscriptFormat::Markdown => {
+                    if !out.is_empty() {
+                        out.push('\n');
+                    }
+                    if !entry.speaker.is_empty() {
+                        out.push_str("**");
+                        push_markdown_escaped(&mut out, &entry.speaker);
+                        out.push_str(":** ");
+                    }
+                    push_markdown_escaped(&mut out, &entry.text);
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+}
+
+fn push_markdown_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '*' | '_' | '`' | '#' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
 }
 
 fn bitset_len(flag_count: u32) -> usize {
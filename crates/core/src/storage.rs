@@ -5,13 +5,17 @@
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::engine::EngineMetrics;
 use crate::state::EngineState;
 use crate::version::{SAVE_BINARY_MAGIC, SAVE_FORMAT_VERSION};
 
+#[cfg(feature = "native")]
+mod slots;
+
+#[cfg(feature = "native")]
+pub use slots::{SaveSlotEntry, SaveSlotMetadata, SaveSlotStore, SaveStoreError};
+
 /// Unique identifier for a compiled script, computed as SHA-256 of its binary representation.
 pub type ScriptId = [u8; 32];
 pub const AUTH_SAVE_MAGIC: [u8; 4] = *b"VNSA";
@@ -32,12 +36,24 @@ pub struct SaveData {
     pub script_id: ScriptId,
     /// The engine state at the time of saving.
     pub state: EngineState,
+    /// Playthrough metrics, present when the engine collected them.
+    pub metrics: Option<EngineMetrics>,
 }
 
 impl SaveData {
     /// Creates a new save data bundle.
     pub fn new(script_id: ScriptId, state: EngineState) -> Self {
-        Self { script_id, state }
+        Self {
+            script_id,
+            state,
+            metrics: None,
+        }
+    }
+
+    /// Attaches playthrough metrics for post-playthrough analysis.
+    pub fn with_metrics(mut self, metrics: Option<EngineMetrics>) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     /// Serializes save data to binary format with magic bytes and version.
@@ -196,326 +212,6 @@ impl std::fmt::Display for SaveError {
 
 impl std::error::Error for SaveError {}
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct SaveSlotMetadata {
-    pub slot_id: u16,
-    pub quick: bool,
-    pub updated_unix_ms: u64,
-    pub script_id_hex: String,
-    pub position: u32,
-    pub flags_words: usize,
-    pub vars_count: usize,
-    #[serde(default)]
-    pub chapter_label: Option<String>,
-    #[serde(default)]
-    pub summary_line: Option<String>,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct SaveSlotEntry {
-    pub metadata: SaveSlotMetadata,
-    pub path: PathBuf,
-}
-
-#[derive(Debug)]
-pub struct SaveSlotStore {
-    root: PathBuf,
-}
-
-#[derive(Debug)]
-pub enum SaveStoreError {
-    Io(std::io::Error),
-    Save(SaveError),
-    RecoveryFailed {
-        primary: SaveError,
-        backup: Option<SaveError>,
-    },
-}
-
-impl std::fmt::Display for SaveStoreError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SaveStoreError::Io(err) => write!(f, "save store io error: {err}"),
-            SaveStoreError::Save(err) => write!(f, "save store serialization error: {err}"),
-            SaveStoreError::RecoveryFailed { primary, backup } => match backup {
-                Some(backup) => write!(
-                    f,
-                    "save store recovery failed (primary: {primary}, backup: {backup})"
-                ),
-                None => write!(
-                    f,
-                    "save store recovery failed (primary: {primary}, backup missing)"
-                ),
-            },
-        }
-    }
-}
-
-impl std::error::Error for SaveStoreError {}
-
-impl From<std::io::Error> for SaveStoreError {
-    fn from(value: std::io::Error) -> Self {
-        SaveStoreError::Io(value)
-    }
-}
-
-impl From<SaveError> for SaveStoreError {
-    fn from(value: SaveError) -> Self {
-        SaveStoreError::Save(value)
-    }
-}
-
-impl SaveSlotStore {
-    pub fn new(root: PathBuf) -> Self {
-        Self { root }
-    }
-
-    pub fn root(&self) -> &Path {
-        &self.root
-    }
-
-    pub fn ensure_layout(&self) -> Result<(), SaveStoreError> {
-        fs::create_dir_all(self.root.join("slots"))?;
-        fs::create_dir_all(self.root.join("meta"))?;
-        Ok(())
-    }
-
-    pub fn save_slot(
-        &self,
-        slot_id: u16,
-        save: &SaveData,
-    ) -> Result<SaveSlotEntry, SaveStoreError> {
-        self.ensure_layout()?;
-        let slot_path = self.slot_path(slot_id, false);
-        let metadata_path = self.metadata_path(slot_id, false);
-        self.atomic_write_binary(&slot_path, &save.to_authenticated_binary(AUTH_SAVE_KEY)?)?;
-        let metadata = self.build_metadata(slot_id, false, save);
-        self.atomic_write_binary(
-            &metadata_path,
-            serde_json::to_vec_pretty(&metadata)
-                .map_err(|err| SaveError::Serialization(err.to_string()))?
-                .as_slice(),
-        )?;
-        Ok(SaveSlotEntry {
-            metadata,
-            path: slot_path,
-        })
-    }
-
-    pub fn load_slot(&self, slot_id: u16) -> Result<SaveData, SaveStoreError> {
-        let slot_path = self.slot_path(slot_id, false);
-        let backup_path = backup_path(&slot_path);
-        self.load_binary_with_recovery(&slot_path, &backup_path)
-    }
-
-    pub fn remove_slot(&self, slot_id: u16) -> Result<(), SaveStoreError> {
-        let slot_path = self.slot_path(slot_id, false);
-        let metadata_path = self.metadata_path(slot_id, false);
-        if slot_path.exists() {
-            fs::remove_file(slot_path)?;
-        }
-        if metadata_path.exists() {
-            fs::remove_file(metadata_path)?;
-        }
-        Ok(())
-    }
-
-    pub fn quicksave(&self, save: &SaveData) -> Result<SaveSlotEntry, SaveStoreError> {
-        self.ensure_layout()?;
-        let slot_path = self.slot_path(0, true);
-        let metadata_path = self.metadata_path(0, true);
-        self.atomic_write_binary(&slot_path, &save.to_authenticated_binary(AUTH_SAVE_KEY)?)?;
-        let metadata = self.build_metadata(0, true, save);
-        self.atomic_write_binary(
-            &metadata_path,
-            serde_json::to_vec_pretty(&metadata)
-                .map_err(|err| SaveError::Serialization(err.to_string()))?
-                .as_slice(),
-        )?;
-        Ok(SaveSlotEntry {
-            metadata,
-            path: slot_path,
-        })
-    }
-
-    pub fn quickload(&self) -> Result<SaveData, SaveStoreError> {
-        let slot_path = self.slot_path(0, true);
-        let backup_path = backup_path(&slot_path);
-        self.load_binary_with_recovery(&slot_path, &backup_path)
-    }
-
-    pub fn list_slots(&self) -> Result<Vec<SaveSlotEntry>, SaveStoreError> {
-        self.ensure_layout()?;
-        let mut entries = Vec::new();
-
-        let meta_dir = self.root.join("meta");
-        if !meta_dir.exists() {
-            return Ok(entries);
-        }
-
-        for entry in fs::read_dir(meta_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
-                continue;
-            }
-
-            let bytes = fs::read(&path)?;
-            let metadata: SaveSlotMetadata = serde_json::from_slice(&bytes)
-                .map_err(|err| SaveError::Serialization(err.to_string()))?;
-            let slot_path = self.slot_path(metadata.slot_id, metadata.quick);
-            if slot_path.exists() {
-                entries.push(SaveSlotEntry {
-                    metadata,
-                    path: slot_path,
-                });
-            }
-        }
-
-        entries.sort_by(|a, b| b.metadata.updated_unix_ms.cmp(&a.metadata.updated_unix_ms));
-        Ok(entries)
-    }
-
-    fn build_metadata(&self, slot_id: u16, quick: bool, save: &SaveData) -> SaveSlotMetadata {
-        SaveSlotMetadata {
-            slot_id,
-            quick,
-            updated_unix_ms: now_unix_ms(),
-            script_id_hex: script_id_hex(&save.script_id),
-            position: save.state.position,
-            flags_words: save.state.flags.len(),
-            vars_count: save.state.vars.len(),
-            chapter_label: chapter_label_hint(save),
-            summary_line: summary_line_hint(save),
-        }
-    }
-
-    fn load_binary_with_recovery(
-        &self,
-        primary_path: &Path,
-        backup_path: &Path,
-    ) -> Result<SaveData, SaveStoreError> {
-        let primary_bytes = fs::read(primary_path)?;
-        match SaveData::from_any_binary(&primary_bytes, AUTH_SAVE_KEY) {
-            Ok(save) => Ok(save),
-            Err(primary_err) => match fs::read(backup_path) {
-                Ok(backup_bytes) => match SaveData::from_any_binary(&backup_bytes, AUTH_SAVE_KEY) {
-                    Ok(save) => Ok(save),
-                    Err(backup_err) => Err(SaveStoreError::RecoveryFailed {
-                        primary: primary_err,
-                        backup: Some(backup_err),
-                    }),
-                },
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                    Err(SaveStoreError::RecoveryFailed {
-                        primary: primary_err,
-                        backup: None,
-                    })
-                }
-                Err(err) => Err(SaveStoreError::Io(err)),
-            },
-        }
-    }
-
-    fn atomic_write_binary(&self, path: &Path, bytes: &[u8]) -> Result<(), SaveStoreError> {
-        let parent = path.parent().ok_or_else(|| {
-            SaveStoreError::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "target path has no parent",
-            ))
-        })?;
-        fs::create_dir_all(parent)?;
-        if path.exists() {
-            let backup = backup_path(path);
-            fs::copy(path, backup)?;
-        }
-        let tmp_path = path.with_extension("tmp");
-        fs::write(&tmp_path, bytes)?;
-        if path.exists() {
-            fs::remove_file(path)?;
-        }
-        fs::rename(&tmp_path, path)?;
-        Ok(())
-    }
-
-    fn slot_path(&self, slot_id: u16, quick: bool) -> PathBuf {
-        if quick {
-            self.root.join("slots").join("quicksave.vnsav")
-        } else {
-            self.root
-                .join("slots")
-                .join(format!("slot_{slot_id:03}.vnsav"))
-        }
-    }
-
-    fn metadata_path(&self, slot_id: u16, quick: bool) -> PathBuf {
-        if quick {
-            self.root.join("meta").join("quicksave.json")
-        } else {
-            self.root
-                .join("meta")
-                .join(format!("slot_{slot_id:03}.json"))
-        }
-    }
-}
-
-fn now_unix_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as u64)
-        .unwrap_or(0)
-}
-
-fn script_id_hex(script_id: &ScriptId) -> String {
-    let mut output = String::with_capacity(script_id.len() * 2);
-    for byte in script_id {
-        use std::fmt::Write as _;
-        let _ = write!(&mut output, "{byte:02x}");
-    }
-    output
-}
-
-fn chapter_label_hint(save: &SaveData) -> Option<String> {
-    let background = save.state.visual.background.as_ref()?;
-    let stem = Path::new(background.as_ref()).file_stem()?.to_str()?;
-    let cleaned = stem.replace(['_', '-'], " ").trim().to_string();
-    if cleaned.is_empty() {
-        None
-    } else {
-        Some(cleaned)
-    }
-}
-
-fn summary_line_hint(save: &SaveData) -> Option<String> {
-    let dialogue = save.state.history.back()?;
-    let speaker = dialogue.speaker.as_ref().trim();
-    let text = dialogue.text.as_ref().trim();
-    if text.is_empty() {
-        return None;
-    }
-    let mut line = if speaker.is_empty() {
-        text.to_string()
-    } else {
-        format!("{speaker}: {text}")
-    };
-    const MAX_CHARS: usize = 96;
-    if line.chars().count() > MAX_CHARS {
-        let mut truncated = line
-            .chars()
-            .take(MAX_CHARS.saturating_sub(3))
-            .collect::<String>();
-        truncated.push_str("...");
-        line = truncated;
-    }
-    Some(line)
-}
-
-fn backup_path(path: &Path) -> PathBuf {
-    let mut output = path.as_os_str().to_os_string();
-    output.push(".bak");
-    PathBuf::from(output)
-}
-
 fn is_authenticated_binary(input: &[u8]) -> bool {
     input.starts_with(&AUTH_SAVE_MAGIC)
 }
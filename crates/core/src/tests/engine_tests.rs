@@ -1,6 +1,8 @@
 use super::*;
+use crate::error::VnError;
+use crate::event::{CmpOp, CondCompiled, EventCompiled, EventRaw};
 use crate::resource::ResourceLimiter;
-use crate::script::ScriptRaw;
+use crate::script::{ScriptCompiled, ScriptRaw, ScriptSource};
 use crate::security::SecurityPolicy;
 use crate::{AssetId, AudioCommand};
 
@@ -104,3 +106,1639 @@ fn audio_action_fade_out_emits_stop_bgm() {
         AudioCommand::StopBgm { fade_out } if fade_out.as_millis() == 900
     ));
 }
+
+#[test]
+fn flag_and_var_names_resolve_both_ways() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "set_flag", "key": "met_alice", "value": true },
+                { "type": "set_var", "key": "trust", "value": 3 },
+                { "type": "set_flag", "key": "saw_ending", "value": false }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    let met_alice = engine.flag_id("met_alice").expect("flag id");
+    let saw_ending = engine.flag_id("saw_ending").expect("flag id");
+    assert_eq!(engine.flag_name(met_alice), Some("met_alice"));
+    assert_eq!(engine.flag_name(saw_ending), Some("saw_ending"));
+    assert_eq!(engine.var_id("trust"), Some(0));
+    assert_eq!(engine.var_name(0), Some("trust"));
+    assert_eq!(engine.flag_name(engine.flag_count()), None);
+    assert_eq!(engine.var_id("met_alice"), None);
+}
+
+#[test]
+fn goto_ip_rebuilds_background_past_scene() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "room.png", "music": "calm.ogg", "characters": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Here." },
+                { "type": "scene", "background": "street.png", "characters": [] },
+                { "type": "patch", "add": [{ "name": "Ava", "expression": "smile" }], "update": [], "remove": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Outside." }
+            ],
+            "labels": { "start": 0, "outside": 4 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    engine.goto_label("outside").unwrap();
+    let visual = engine.visual_state();
+    assert_eq!(engine.state().position, 4);
+    assert_eq!(visual.background.as_deref(), Some("street.png"));
+    assert_eq!(visual.characters.len(), 1);
+    assert_eq!(visual.characters[0].name.as_ref(), "Ava");
+
+    engine.goto_ip(1).unwrap();
+    assert_eq!(
+        engine.visual_state().background.as_deref(),
+        Some("room.png")
+    );
+    assert!(matches!(engine.goto_ip(5), Err(VnError::InvalidScript(_))));
+}
+
+#[test]
+fn goto_ip_refuses_while_choice_is_pending() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Go?", "options": [{ "text": "Yes", "target": "end" }] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0, "end": 1 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    assert!(matches!(engine.goto_ip(1), Err(VnError::InvalidScript(_))));
+    assert_eq!(engine.state().position, 0);
+
+    engine.start_at(1).unwrap();
+    assert_eq!(engine.state().position, 1);
+    assert!(engine.start_at(2).is_err());
+}
+
+#[test]
+fn choice_log_survives_save_and_replays_the_same_route() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "dialogue", "speaker": "Ava", "text": "Which way?" },
+                { "type": "choice", "prompt": "Path", "options": [
+                    { "text": "Left", "target": "left" },
+                    { "text": "Right", "target": "right" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Left." },
+                { "type": "jump", "target": "end" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Right." },
+                { "type": "choice", "prompt": "Stay?", "options": [
+                    { "text": "Yes", "target": "end" },
+                    { "text": "No", "target": "left" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0, "left": 2, "right": 4, "end": 6 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut played = build();
+    let mut recorded = Vec::new();
+    let mut picks = [1, 1].into_iter();
+    while let Ok(event) = played.current_event() {
+        recorded.push(event.to_json_string());
+        match event {
+            EventCompiled::Choice(_) => {
+                played.choose(picks.next().unwrap()).unwrap();
+            }
+            _ => {
+                played.step().unwrap();
+            }
+        }
+    }
+    assert_eq!(played.choice_log(), &[1, 1]);
+
+    let script_id = crate::compute_script_id(&played.script().to_binary().unwrap());
+    let bytes = crate::SaveData::new(script_id, played.state().clone())
+        .to_binary()
+        .unwrap();
+    let mut loaded = build();
+    loaded
+        .set_state(crate::SaveData::from_binary(&bytes).unwrap().state)
+        .unwrap();
+    assert_eq!(loaded.choice_log(), &[1, 1]);
+
+    let mut replayed = build();
+    let events: Vec<String> = replayed
+        .replay_choice_log(loaded.choice_log())
+        .unwrap()
+        .iter()
+        .map(EventCompiled::to_json_string)
+        .collect();
+    assert_eq!(events, recorded);
+    assert_eq!(replayed.choice_log(), played.choice_log());
+    assert!(matches!(
+        replayed.current_event(),
+        Err(VnError::EndOfScript)
+    ));
+}
+
+#[test]
+fn replay_steps_through_fallthrough_choices_without_using_the_log() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Ask?", "options": [], "fallthrough": "pick" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Skipped." },
+                { "type": "choice", "prompt": "Path", "options": [
+                    { "text": "Left", "target": "end" },
+                    { "text": "Right", "target": "end" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0, "pick": 2, "end": 3 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut played = build();
+    let mut recorded = Vec::new();
+    while let Ok(event) = played.current_event() {
+        recorded.push(event.to_json_string());
+        match event {
+            EventCompiled::Choice(choice) if !choice.options.is_empty() => {
+                played.choose(0).unwrap();
+            }
+            _ => {
+                played.step().unwrap();
+            }
+        }
+    }
+    assert_eq!(played.choice_log(), &[0]);
+
+    let mut replayed = build();
+    let events: Vec<String> = replayed
+        .replay_choice_log(played.choice_log())
+        .unwrap()
+        .iter()
+        .map(EventCompiled::to_json_string)
+        .collect();
+    assert_eq!(events, recorded);
+    assert_eq!(replayed.choice_log(), played.choice_log());
+
+    let mut stopped = build();
+    stopped.replay_choice_log(&[]).unwrap();
+    assert_eq!(stopped.state().position, 2);
+}
+
+#[test]
+fn record_trace_is_byte_stable_for_same_choices() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "room.png", "characters": [{ "name": "Ava" }] },
+                { "type": "set_flag", "key": "met", "value": true },
+                { "type": "choice", "prompt": "Path", "options": [
+                    { "text": "Left", "target": "left" },
+                    { "text": "Right", "target": "right" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Left." },
+                { "type": "dialogue", "speaker": "Ava", "text": "Right." }
+            ],
+            "labels": { "start": 0, "left": 3, "right": 4 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+
+    let engine = build();
+    let first = serde_json::to_vec(&engine.record_trace(&[1]).unwrap()).unwrap();
+    let second = serde_json::to_vec(&build().record_trace(&[1]).unwrap()).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(
+        engine.state().position,
+        0,
+        "recording must not move the engine"
+    );
+
+    let trace = engine.record_trace(&[1]).unwrap();
+    assert_eq!(trace.steps.len(), 4);
+    assert!(matches!(
+        &trace.steps[3].view,
+        crate::TraceUiView::Dialogue { text, .. } if text.starts_with("crc32:")
+    ));
+    assert_ne!(
+        first,
+        serde_json::to_vec(&engine.record_trace(&[0]).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn replay_ndjson_emits_one_parseable_line_per_visited_event() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "room.png" },
+                { "type": "choice", "prompt": "Path", "options": [
+                    { "text": "Left", "target": "left" },
+                    { "text": "Right", "target": "right" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Left." },
+                { "type": "dialogue", "speaker": "Ava", "text": "Right." }
+            ],
+            "labels": { "start": 0, "left": 2, "right": 3 }
+        }"#;
+    let engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    let ndjson = engine.replay_ndjson(&[0]);
+    let visited = engine.clone().replay_choice_log(&[0]).unwrap();
+    let lines: Vec<&str> = ndjson.lines().collect();
+    assert_eq!(lines.len(), visited.len());
+    assert_eq!(lines.len(), 4);
+    assert!(ndjson.ends_with('\n'));
+    for (line, event) in lines.iter().zip(&visited) {
+        let value: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(value, event.to_json_value());
+    }
+    assert_eq!(engine.state().position, 0);
+}
+
+fn visual_summary(engine: &Engine) -> (Option<String>, Vec<(String, Option<String>)>) {
+    let visual = engine.visual_state();
+    let characters = visual
+        .characters
+        .iter()
+        .map(|c| {
+            (
+                c.name.to_string(),
+                c.expression.as_deref().map(str::to_string),
+            )
+        })
+        .collect();
+    (visual.background.as_deref().map(str::to_string), characters)
+}
+
+#[test]
+fn set_state_rebuilds_visual_after_patches() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "room.png", "characters": [{ "name": "Ava" }] },
+                { "type": "patch", "add": [{ "name": "Ben", "expression": "calm" }], "update": [], "remove": [] },
+                { "type": "patch", "background": "hall.png", "add": [], "update": [{ "name": "Ava", "expression": "angry" }], "remove": [] },
+                { "type": "patch", "add": [], "update": [], "remove": ["Ben"] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Alone now." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut played = build();
+    for _ in 0..4 {
+        played.step().unwrap();
+    }
+    let mut replayed = build();
+    replayed.rebuild_visual_from(played.state().position);
+    assert_eq!(
+        visual_summary(&replayed),
+        (
+            Some("hall.png".to_string()),
+            vec![("Ava".to_string(), Some("angry".to_string()))]
+        )
+    );
+
+    let script_id = crate::compute_script_id(&played.script().to_binary().unwrap());
+    let mut without_visual = played.state().clone();
+    without_visual.visual = crate::visual::VisualState::default();
+    for snapshot in [played.state().clone(), without_visual] {
+        let bytes = crate::SaveData::new(script_id, snapshot)
+            .to_binary()
+            .unwrap();
+        let mut loaded = build();
+        loaded
+            .set_state(crate::SaveData::from_binary(&bytes).unwrap().state)
+            .unwrap();
+        assert_eq!(visual_summary(&loaded), visual_summary(&replayed));
+        assert_eq!(visual_summary(&loaded), visual_summary(&played));
+    }
+}
+
+#[test]
+fn set_state_keeps_visual_from_the_branch_taken() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Which way?", "options": [
+                    { "text": "Left", "target": "left" },
+                    { "text": "Right", "target": "right" }
+                ] },
+                { "type": "scene", "background": "left.png", "characters": [] },
+                { "type": "jump", "target": "end" },
+                { "type": "scene", "background": "right.png", "characters": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Here we are." }
+            ],
+            "labels": { "start": 0, "left": 1, "right": 3, "end": 4 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut played = build();
+    played.choose(0).unwrap();
+    played.step().unwrap();
+    played.step().unwrap();
+    assert_eq!(played.state().position, 4);
+
+    let mut loaded = build();
+    loaded.set_state(played.state().clone()).unwrap();
+
+    assert_eq!(
+        loaded.visual_state().background.as_deref(),
+        Some("left.png")
+    );
+}
+
+fn corrupt_compiled(events: Vec<EventCompiled>) -> ScriptCompiled {
+    ScriptCompiled {
+        events,
+        labels: std::collections::BTreeMap::from([("start".to_string(), 0)]),
+        start_ip: 0,
+        flag_count: 1,
+        flag_names: vec!["seen".into()],
+        var_names: Vec::new(),
+        character_aliases: std::collections::BTreeMap::new(),
+        str_var_names: Vec::new(),
+    }
+}
+
+#[test]
+fn from_compiled_rejects_flag_name_count_mismatch() {
+    let mut script = corrupt_compiled(vec![EventCompiled::SetFlag {
+        flag_id: 0,
+        value: true,
+    }]);
+    script.flag_names.clear();
+
+    let err = Engine::from_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+}
+
+#[test]
+fn from_compiled_rejects_out_of_range_var_id() {
+    let script = corrupt_compiled(vec![EventCompiled::SetVar {
+        var_id: u32::MAX,
+        value: 1,
+    }]);
+
+    let err = Engine::from_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+}
+
+#[test]
+fn from_compiled_enforces_event_limit() {
+    let script = corrupt_compiled(vec![
+        EventCompiled::SetFlag {
+            flag_id: 0,
+            value: true,
+        };
+        3
+    ]);
+
+    let err = Engine::from_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter {
+            max_events: 2,
+            ..ResourceLimiter::default()
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, VnError::ResourceLimit(_)), "{err:?}");
+}
+
+#[test]
+fn from_compiled_rejects_out_of_range_jump_if_operands() {
+    let bad_var = corrupt_compiled(vec![EventCompiled::JumpIf {
+        cond: CondCompiled::VarCmp {
+            var_id: 7,
+            op: CmpOp::Eq,
+            value: 0,
+        },
+        target_ip: 0,
+    }]);
+    let bad_flag = corrupt_compiled(vec![EventCompiled::JumpIf {
+        cond: CondCompiled::Flag {
+            flag_id: 64,
+            is_set: true,
+        },
+        target_ip: 0,
+    }]);
+    let bad_target = corrupt_compiled(vec![EventCompiled::JumpIf {
+        cond: CondCompiled::Flag {
+            flag_id: 0,
+            is_set: true,
+        },
+        target_ip: 99,
+    }]);
+
+    for script in [bad_var, bad_flag, bad_target] {
+        let err = Engine::from_compiled(
+            script,
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+    }
+}
+
+#[test]
+fn step_returns_corrupt_script_instead_of_panicking() {
+    let script = corrupt_compiled(vec![
+        EventCompiled::SetVar {
+            var_id: u32::MAX,
+            value: 1,
+        },
+        EventCompiled::SetFlag {
+            flag_id: u32::MAX,
+            value: true,
+        },
+        EventCompiled::Jump { target_ip: 42 },
+    ]);
+    let mut engine = Engine::from_validated_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    );
+
+    for _ in 0..3 {
+        let err = engine.step().unwrap_err();
+        assert!(matches!(err, VnError::CorruptScript { .. }), "{err:?}");
+        let mut state = engine.state().clone();
+        state.position += 1;
+        engine.set_state(state).unwrap();
+    }
+}
+
+fn replay<S: crate::script::ScriptSource>(
+    engine: &mut Engine<S>,
+    choices: &[usize],
+) -> Vec<(u32, EventCompiled, Option<String>)> {
+    let mut choices = choices.iter().copied();
+    let mut trace = Vec::new();
+    loop {
+        let ip = engine.state().position;
+        let event = match engine.current_event() {
+            Ok(event) => event,
+            Err(VnError::EndOfScript) => break,
+            Err(err) => panic!("unexpected error at {ip}: {err:?}"),
+        };
+        if let EventCompiled::Choice(_) = event {
+            engine.choose(choices.next().unwrap_or(0)).unwrap();
+        } else {
+            engine.step().unwrap();
+        }
+        trace.push((
+            ip,
+            event,
+            engine
+                .visual_state()
+                .background
+                .as_deref()
+                .map(str::to_string),
+        ));
+    }
+    trace
+}
+
+#[test]
+fn lazy_script_round_trips_every_compiled_table() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "set_flag", "key": "met", "value": true },
+                { "type": "set_var", "key": "trust", "value": 2 },
+                { "type": "set_str_var", "key": "nickname", "value": "Red" },
+                { "type": "dialogue", "speaker": "ava", "text": "Hi, {strvar:nickname}." }
+            ],
+            "labels": { "start": 1, "intro": 0 },
+            "character_aliases": { "ava": "Ava" }
+        }"#;
+    let compiled = ScriptRaw::from_json(json).unwrap().compile().unwrap();
+    let bytes = compiled.to_binary().unwrap();
+    // Exhaustive on purpose: a new `ScriptCompiled` field must also be added
+    // to `LazyScript`'s `ScriptTail` and checked here.
+    let ScriptCompiled {
+        events,
+        labels,
+        start_ip,
+        flag_count,
+        flag_names,
+        var_names,
+        character_aliases,
+        str_var_names,
+    } = compiled;
+    assert!(!flag_names.is_empty() && !var_names.is_empty() && !str_var_names.is_empty());
+    assert!(!character_aliases.is_empty() && start_ip != 0);
+
+    let lazy = crate::script::LazyScript::with_window(&bytes, 2).unwrap();
+    assert_eq!(lazy.event_count(), events.len());
+    for (ip, event) in events.iter().enumerate() {
+        let decoded = lazy.event(ip as u32).unwrap();
+        assert_eq!(format!("{:?}", &*decoded), format!("{event:?}"));
+    }
+    assert_eq!(lazy.labels(), &labels);
+    assert_eq!(lazy.start_ip(), start_ip);
+    assert_eq!(lazy.flag_count(), flag_count);
+    assert_eq!(lazy.flag_names(), flag_names.as_slice());
+    assert_eq!(lazy.var_names(), var_names.as_slice());
+    assert_eq!(lazy.character_aliases(), &character_aliases);
+    assert_eq!(lazy.str_var_names(), str_var_names.as_slice());
+}
+
+#[test]
+fn lazy_engine_replays_identically_to_eager() {
+    let mut events = vec![r#"{ "type": "scene", "background": "bg/room.png", "music": "bgm/a.ogg", "characters": [] }"#.to_string()];
+    for index in 0..40 {
+        events.push(format!(
+            r#"{{ "type": "dialogue", "speaker": "Ava", "text": "line {index}" }}"#
+        ));
+    }
+    events.push(r#"{ "type": "set_flag", "key": "late", "value": true }"#.to_string());
+    events.push(
+        r#"{ "type": "choice", "prompt": "Go?", "options": [
+            { "text": "Back", "target": "start" },
+            { "text": "On", "target": "outro" }
+        ] }"#
+            .to_string(),
+    );
+    events.push(r#"{ "type": "patch", "background": "bg/street.png" }"#.to_string());
+    events.push(r#"{ "type": "dialogue", "speaker": "Ava", "text": "bye" }"#.to_string());
+    let json = format!(
+        r#"{{ "script_schema_version": "1.0", "events": [{}], "labels": {{ "start": 0, "outro": 43 }} }}"#,
+        events.join(",")
+    );
+    let compiled = ScriptRaw::from_json(&json).unwrap().compile().unwrap();
+    let bytes = compiled.to_binary().unwrap();
+
+    let mut eager = Engine::from_compiled(
+        compiled,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    let lazy_script = crate::script::LazyScript::with_window(&bytes, 4).unwrap();
+    let mut lazy = Engine::from_lazy(
+        lazy_script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    assert_eq!(lazy.labels(), eager.labels());
+    assert_eq!(lazy.flag_id("late"), eager.flag_id("late"));
+    assert_eq!(
+        lazy.peek_next_asset_paths(8),
+        eager.peek_next_asset_paths(8)
+    );
+
+    let path = [0, 1];
+    let eager_trace = replay(&mut eager, &path);
+    let lazy_trace = replay(&mut lazy, &path);
+    assert_eq!(eager_trace.len(), lazy_trace.len());
+    for (eager_step, lazy_step) in eager_trace.iter().zip(&lazy_trace) {
+        assert_eq!(eager_step.0, lazy_step.0);
+        assert_eq!(eager_step.1.to_json_string(), lazy_step.1.to_json_string());
+        assert_eq!(eager_step.2, lazy_step.2);
+    }
+    assert!(lazy.source().resident_len() <= 4);
+
+    lazy.goto_label("outro").unwrap();
+    eager.goto_label("outro").unwrap();
+    assert_eq!(
+        lazy.visual_state().background,
+        eager.visual_state().background
+    );
+    assert_eq!(
+        lazy.current_event_json().unwrap(),
+        eager.current_event_json().unwrap()
+    );
+}
+
+fn auto_choice_engine(weights: [u32; 3], seed: u64) -> Engine {
+    let json = format!(
+        r#"{{
+            "script_schema_version": "1.0",
+            "events": [
+                {{ "type": "choice", "prompt": "Ambient", "auto": true, "options": [
+                    {{ "text": "A", "target": "a", "weight": {} }},
+                    {{ "text": "B", "target": "b", "weight": {} }},
+                    {{ "text": "C", "target": "c", "weight": {} }}
+                ] }},
+                {{ "type": "dialogue", "speaker": "N", "text": "a" }},
+                {{ "type": "dialogue", "speaker": "N", "text": "b" }},
+                {{ "type": "dialogue", "speaker": "N", "text": "c" }}
+            ],
+            "labels": {{ "start": 0, "a": 1, "b": 2, "c": 3 }}
+        }}"#,
+        weights[0], weights[1], weights[2]
+    );
+    let script = ScriptRaw::from_json(&json).unwrap();
+    let mut engine = Engine::new(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.seed_rng(seed);
+    engine
+}
+
+fn auto_picks(weights: [u32; 3]) -> Vec<u32> {
+    (0..300)
+        .map(|seed| {
+            let mut engine = auto_choice_engine(weights, seed);
+            engine.auto_choose_weighted().unwrap();
+            engine.state().position
+        })
+        .collect()
+}
+
+#[test]
+fn auto_choose_weighted_is_deterministic_and_follows_weights() {
+    let picks = auto_picks([1, 3, 0]);
+    assert_eq!(picks, auto_picks([1, 3, 0]));
+
+    let count = |ip: u32| picks.iter().filter(|pick| **pick == ip).count();
+    assert_eq!(count(3), 0, "zero-weight option must never be picked");
+    assert!(count(2) > count(1) * 2, "weight 3 should dominate weight 1");
+}
+
+#[test]
+fn auto_choose_weighted_falls_back_to_uniform_on_zero_total() {
+    let picks = auto_picks([0, 0, 0]);
+    assert_eq!(picks, auto_picks([0, 0, 0]));
+    for ip in 1..=3 {
+        let count = picks.iter().filter(|pick| **pick == ip).count();
+        assert!(count > 60, "option at {ip} picked {count} times out of 300");
+    }
+}
+
+#[test]
+fn auto_choose_weighted_takes_fallthrough_when_every_option_is_weighted_out() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Ambient", "auto": true, "fallthrough": "quiet", "options": [
+                    { "text": "A", "target": "a", "weight": 0 },
+                    { "text": "B", "target": "a", "weight": 0 }
+                ] },
+                { "type": "dialogue", "speaker": "N", "text": "a" },
+                { "type": "dialogue", "speaker": "N", "text": "quiet" }
+            ],
+            "labels": { "start": 0, "a": 1, "quiet": 2 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.enable_event_stream();
+    engine.auto_choose_weighted().unwrap();
+    assert_eq!(engine.state().position, 2);
+    assert!(engine.choice_log().is_empty());
+    assert!(engine
+        .drain_events()
+        .contains(&EngineEvent::ChoiceFellThrough {
+            ip: 0,
+            target_ip: 2,
+        }));
+
+    let mut replayed = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    replayed.replay_choice_log(&[]).unwrap();
+    assert!(matches!(
+        replayed.current_event(),
+        Err(VnError::EndOfScript)
+    ));
+}
+
+#[test]
+fn auto_choose_weighted_rejects_manual_choices() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Pick", "options": [
+                    { "text": "A", "target": "start" }
+                ] }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let script = ScriptRaw::from_json(json).unwrap();
+    let mut engine = Engine::new(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    assert!(matches!(
+        engine.auto_choose_weighted(),
+        Err(VnError::InvalidChoice)
+    ));
+}
+
+fn snapshot_engine() -> Engine {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "bg/room.png", "music": null, "characters": [
+                    { "name": "Ava", "expression": null, "position": null }
+                ] },
+                { "type": "set_flag", "key": "met_ava", "value": true },
+                { "type": "set_var", "key": "trust", "value": 3 },
+                { "type": "dialogue", "speaker": "Ava", "text": "Hi" },
+                { "type": "jump_if", "cond": { "kind": "flag", "key": "left", "is_set": true }, "target": "start" }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn snapshot_round_trips_through_apply_snapshot() {
+    let mut engine = snapshot_engine();
+    for _ in 0..3 {
+        engine.step().unwrap();
+    }
+    let snapshot = engine.snapshot();
+    assert_eq!(snapshot.position, 3);
+    assert_eq!(snapshot.flags.get("met_ava"), Some(&true));
+    assert_eq!(snapshot.flags.get("left"), Some(&false));
+    assert_eq!(snapshot.vars.get("trust"), Some(&3));
+    assert_eq!(snapshot.visual.background.as_deref(), Some("bg/room.png"));
+    assert_eq!(snapshot.visual.characters, vec!["Ava".to_string()]);
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let mut edited: EngineSnapshot = serde_json::from_str(&json).unwrap();
+    edited.vars.insert("trust".to_string(), 9);
+    edited.flags.insert("left".to_string(), true);
+    edited.position = 4;
+
+    let mut fresh = snapshot_engine();
+    fresh.apply_snapshot(edited.clone()).unwrap();
+    assert_eq!(fresh.state().position, 4);
+    assert_eq!(fresh.snapshot().vars, edited.vars);
+    assert_eq!(fresh.snapshot().flags, edited.flags);
+    assert_eq!(fresh.snapshot().visual, snapshot.visual);
+}
+
+#[test]
+fn apply_snapshot_rejects_invalid_snapshots_without_side_effects() {
+    let mut engine = snapshot_engine();
+    let before = engine.snapshot();
+
+    let mut out_of_range = before.clone();
+    out_of_range.position = 99;
+    out_of_range.vars.insert("trust".to_string(), 7);
+    assert!(matches!(
+        engine.apply_snapshot(out_of_range),
+        Err(VnError::InvalidScript(_))
+    ));
+
+    let mut unknown = before.clone();
+    unknown.vars.insert("missing".to_string(), 1);
+    assert!(matches!(
+        engine.apply_snapshot(unknown),
+        Err(VnError::InvalidScript(_))
+    ));
+    assert_eq!(engine.snapshot(), before);
+}
+
+#[test]
+fn batch_flag_and_var_updates_are_all_or_nothing() {
+    let mut engine = snapshot_engine();
+    let met = engine.flag_id("met_ava").unwrap();
+    let left = engine.flag_id("left").unwrap();
+    let trust = engine.var_id("trust").unwrap();
+
+    let out_of_range = engine.flag_count();
+    assert!(matches!(
+        engine.set_flags(&[(met, true), (left, true), (out_of_range, true)]),
+        Err(VnError::UnknownSymbolId { kind: "flag", id }) if id == out_of_range
+    ));
+    assert!(!engine.state().get_flag(met));
+    assert!(!engine.state().get_flag(left));
+
+    assert!(engine.set_vars(&[(trust, 5), (u32::MAX, 1)]).is_err());
+    assert_eq!(engine.state().get_var(trust), 0);
+    // Inside the event count but past the var name table.
+    let unnamed = engine.script().var_names.len() as u32;
+    assert!((unnamed as usize) < engine.script().events.len());
+    assert!(matches!(
+        engine.set_vars(&[(trust, 5), (unnamed, 1)]),
+        Err(VnError::UnknownSymbolId { kind: "variable", id }) if id == unnamed
+    ));
+    assert_eq!(engine.state().get_var(trust), 0);
+    assert!(engine.state().vars.len() <= unnamed as usize);
+
+    engine.set_flags(&[(met, true), (left, true)]).unwrap();
+    engine.set_vars(&[(trust, -2)]).unwrap();
+    assert!(engine.state().get_flag(met) && engine.state().get_flag(left));
+    assert_eq!(engine.state().get_var(trust), -2);
+}
+
+#[test]
+fn peek_next_resolves_jump_if_without_mutating_state() {
+    let mut engine = snapshot_engine();
+    let first = engine.peek_next().unwrap();
+    assert_eq!((first.ip, first.kind, first.next_ip), (0, "scene", Some(1)));
+    for _ in 0..4 {
+        engine.step().unwrap();
+    }
+
+    let before = engine.snapshot();
+    let history_len = engine.state().history.len();
+    let peeked = engine.peek_next().unwrap();
+    assert_eq!(peeked.kind, "jump_if");
+    assert_eq!(peeked.branch_taken, Some(false));
+    assert_eq!(peeked.next_ip, Some(5));
+    assert!(!peeked.is_choice);
+    assert_eq!(engine.snapshot(), before);
+    assert_eq!(engine.state().history.len(), history_len);
+
+    let left = engine.flag_id("left").unwrap();
+    engine.set_flags(&[(left, true)]).unwrap();
+    let peeked = engine.peek_next().unwrap();
+    assert_eq!(peeked.branch_taken, Some(true));
+    assert_eq!(peeked.next_ip, Some(0));
+    assert_eq!(engine.state().position, 4);
+
+    engine.step().unwrap();
+    assert_eq!(Some(engine.state().position), peeked.next_ip);
+}
+
+#[test]
+fn explain_current_resolves_jump_if_condition_and_target() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "set_var", "key": "affection", "value": 3 },
+                { "type": "jump_if", "cond": { "kind": "var_cmp", "key": "affection", "op": "ge", "value": 3 }, "target": "good_end" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Bye." },
+                { "type": "dialogue", "speaker": "Ava", "text": "Stay!" }
+            ],
+            "labels": { "start": 0, "good_end": 3 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        engine.explain_current(),
+        "SetVar affection = 3 (currently 0)"
+    );
+    engine.step().unwrap();
+
+    assert_eq!(
+        engine.explain_current(),
+        "JumpIf (var affection >= 3 → true) → will jump to IP 3 (label 'good_end')"
+    );
+    engine.step().unwrap();
+    assert_eq!(
+        engine.explain_current(),
+        "Dialogue Ava: \"Stay!\" → continues at IP 4 (end of script)"
+    );
+}
+
+#[test]
+fn event_stream_records_an_ordered_replay() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "bg/room.png", "music": null, "characters": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Hi" },
+                { "type": "choice", "prompt": "Stay?", "options": [
+                    { "text": "No", "target": "leave" },
+                    { "text": "Yes", "target": "stay" }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Bye" },
+                { "type": "set_flag", "key": "stayed", "value": true },
+                { "type": "set_flag", "key": "stayed", "value": true },
+                { "type": "dialogue", "speaker": "Ava", "text": "Great" }
+            ],
+            "labels": { "start": 0, "leave": 3, "stay": 4 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.step().unwrap();
+    assert!(engine.drain_events().is_empty(), "stream is off by default");
+
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.enable_event_stream();
+    engine.replay_choice_log(&[1]).unwrap();
+    let stayed = engine.flag_id("stayed").unwrap();
+    assert_eq!(
+        engine.drain_events(),
+        vec![
+            EngineEvent::SceneChanged { ip: 0 },
+            EngineEvent::DialogueShown { ip: 1 },
+            EngineEvent::ChoicePresented {
+                ip: 2,
+                option_count: 2
+            },
+            EngineEvent::ChoiceMade {
+                ip: 2,
+                index: 1,
+                target_ip: 4
+            },
+            EngineEvent::FlagChanged {
+                flag_id: stayed,
+                value: true
+            },
+            EngineEvent::DialogueShown { ip: 6 },
+        ]
+    );
+    assert!(engine.drain_events().is_empty());
+}
+
+#[test]
+fn stepping_emits_visual_commands() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "dialogue", "speaker": "Ava", "text": "Hi" },
+                { "type": "transition", "kind": "dissolve", "duration_ms": 300, "color": null },
+                { "type": "scene", "background": "bg/room.png", "music": null, "characters": [
+                    { "name": "Ava", "expression": "smile", "position": "left" }
+                ] },
+                { "type": "set_character_position", "name": "Ava", "x": 10, "y": 20, "scale": null },
+                { "type": "patch", "remove": ["Ava"] }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.step().unwrap();
+    assert!(engine.take_visual_commands().is_empty());
+
+    engine.step().unwrap();
+    let transition = match engine.script().events[1].clone() {
+        EventCompiled::Transition(transition) => transition,
+        other => panic!("unexpected event {other:?}"),
+    };
+    assert_eq!(
+        engine.take_visual_commands(),
+        vec![crate::VisualCommand::Transition {
+            kind: transition.kind,
+            duration_ms: 300,
+            color: None,
+        }]
+    );
+
+    for _ in 0..3 {
+        engine.step().unwrap();
+    }
+    let ava: crate::SharedStr = "Ava".into();
+    assert_eq!(
+        engine.take_visual_commands(),
+        vec![
+            crate::VisualCommand::SetBackground {
+                path: Some("bg/room.png".into()),
+            },
+            crate::VisualCommand::ShowCharacter {
+                name: ava.clone(),
+                expression: Some("smile".into()),
+                position: Some("left".into()),
+            },
+            crate::VisualCommand::MoveCharacter {
+                name: ava.clone(),
+                x: Some(10),
+                y: Some(20),
+                scale: None,
+            },
+            crate::VisualCommand::HideCharacter { name: ava },
+        ]
+    );
+}
+
+#[test]
+fn choice_option_effects_apply_before_landing_on_target() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Gift?", "options": [
+                    { "text": "Keep it", "target": "after" },
+                    { "text": "Give it", "target": "after", "effects": [
+                        { "type": "set_var", "key": "affection", "value": 1 },
+                        { "type": "set_flag", "key": "gave_gift", "value": true }
+                    ] }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Skipped." },
+                { "type": "dialogue", "speaker": "Ava", "text": "Thanks." }
+            ],
+            "labels": { "start": 0, "after": 2 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    let affection = engine.var_id("affection").unwrap();
+    let gave_gift = engine.flag_id("gave_gift").unwrap();
+
+    engine.choose(1).unwrap();
+    assert_eq!(engine.state().position, 2);
+    assert_eq!(engine.state().get_var(affection), 1);
+    assert!(engine.state().get_flag(gave_gift));
+
+    let mut script = ScriptRaw::from_json(json).unwrap();
+    let EventRaw::Choice(choice) = &mut script.events[0] else {
+        unreachable!()
+    };
+    choice.options[0].effects.push(EventRaw::Jump {
+        target: "start".to_string(),
+    });
+    let err = script.compile().unwrap_err();
+    assert!(
+        err.to_string().contains("may only be set_flag or set_var"),
+        "{err}"
+    );
+}
+
+#[test]
+fn choice_may_set_more_variables_than_the_script_has_events() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Stats?", "options": [
+                    { "text": "All", "target": "end", "effects": [
+                        { "type": "set_var", "key": "a", "value": 1 },
+                        { "type": "set_var", "key": "b", "value": 2 },
+                        { "type": "set_var", "key": "c", "value": 3 }
+                    ] }
+                ] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0, "end": 1 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    engine.choose(0).unwrap();
+    let c = engine.var_id("c").unwrap();
+    assert_eq!(engine.state().get_var(c), 3);
+}
+
+#[test]
+fn from_compiled_rejects_out_of_range_choice_effect_var_id() {
+    let mut script = corrupt_compiled(vec![
+        EventCompiled::Choice(crate::event::ChoiceCompiled {
+            prompt: "Pick".into(),
+            options: vec![crate::event::ChoiceOptionCompiled {
+                text: "Only".into(),
+                target_ip: 1,
+                weight: None,
+                effects: vec![EventCompiled::SetVar {
+                    var_id: 1,
+                    value: 1,
+                }],
+            }],
+            auto: false,
+            fallthrough_ip: None,
+        }),
+        EventCompiled::SetVar {
+            var_id: 0,
+            value: 0,
+        },
+    ]);
+    script.var_names = vec!["a".into()];
+
+    let err = Engine::from_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("var id 1"), "{err}");
+}
+
+#[test]
+fn character_aliases_change_displayed_speaker_but_not_history() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "dialogue", "speaker": "alice", "text": "Who am I?" },
+                { "type": "dialogue", "speaker": "alice", "text": "It's me." }
+            ],
+            "labels": { "start": 0 },
+            "character_aliases": { "alice": "???" }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    let speaker = |engine: &Engine| match engine.ui_state().unwrap().view {
+        crate::UiView::Dialogue { speaker, .. } => speaker,
+        other => panic!("expected dialogue, got {other:?}"),
+    };
+
+    assert_eq!(speaker(&engine), "???");
+    engine.step().unwrap();
+    engine.set_character_alias("alice", "Alice").unwrap();
+    assert_eq!(speaker(&engine), "Alice");
+    assert_eq!(engine.state().history[0].speaker.as_ref(), "alice");
+
+    assert!(engine.clear_character_alias("alice"));
+    assert_eq!(speaker(&engine), "alice");
+}
+
+#[test]
+fn runtime_character_aliases_survive_save_and_respect_limits() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "dialogue", "speaker": "alice", "text": "Who am I?" },
+                { "type": "dialogue", "speaker": "alice", "text": "It's me." }
+            ],
+            "labels": { "start": 0 },
+            "character_aliases": { "alice": "???" }
+        }"#;
+    let limits = ResourceLimiter::default();
+    let new_engine = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            limits,
+        )
+        .unwrap()
+    };
+    let mut played = new_engine();
+    played.step().unwrap();
+    played.set_character_alias("alice", "Alice").unwrap();
+
+    let long_id = "a".repeat(limits.max_label_length + 1);
+    let err = played.set_character_alias(&long_id, "Alice").unwrap_err();
+    assert!(matches!(err, VnError::ResourceLimit(_)), "{err:?}");
+    let long_display = "A".repeat(limits.max_text_length + 1);
+    let err = played
+        .set_character_alias("alice", &long_display)
+        .unwrap_err();
+    assert!(matches!(err, VnError::ResourceLimit(_)), "{err:?}");
+    assert_eq!(played.character_aliases()["alice"], "Alice");
+
+    let script_id = crate::compute_script_id(&played.script().to_binary().unwrap());
+    let bytes = crate::SaveData::new(script_id, played.state().clone())
+        .to_binary()
+        .unwrap();
+    let mut restored = new_engine();
+    assert_eq!(restored.character_aliases()["alice"], "???");
+    restored
+        .set_state(crate::SaveData::from_binary(&bytes).unwrap().state)
+        .unwrap();
+    assert_eq!(restored.character_aliases()["alice"], "Alice");
+    match restored.ui_state().unwrap().view {
+        crate::UiView::Dialogue { speaker, .. } => assert_eq!(speaker, "Alice"),
+        other => panic!("expected dialogue, got {other:?}"),
+    }
+
+    let mut tampered = played.state().clone();
+    tampered
+        .character_aliases
+        .insert("alice".to_string(), long_display);
+    let err = restored.set_state(tampered).unwrap_err();
+    assert!(matches!(err, VnError::ResourceLimit(_)), "{err:?}");
+}
+
+#[test]
+fn input_text_blocks_until_submitted_and_stores_sanitized_value() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "input_text", "var_key": "player", "prompt": "Your name?", "max_len": 5 },
+                { "type": "dialogue", "speaker": "Ava", "text": "Nice to meet you, {strvar:player}." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    engine.step().unwrap();
+    assert_eq!(engine.state().position, 0);
+    assert!(matches!(
+        engine.ui_state().unwrap().view,
+        crate::UiView::TextInput { max_len: 5, .. }
+    ));
+
+    engine.submit_text("  Sa\u{7}kura\n").unwrap();
+    assert_eq!(engine.state().position, 1);
+    assert_eq!(engine.str_var("player"), Some("Sakur"));
+    match engine.ui_state().unwrap().view {
+        crate::UiView::Dialogue { text, .. } => assert_eq!(text, "Nice to meet you, Sakur."),
+        other => panic!("expected dialogue, got {other:?}"),
+    }
+    assert!(matches!(
+        engine.submit_text("again"),
+        Err(VnError::NoPendingInput)
+    ));
+}
+
+#[test]
+fn str_eq_branches_on_string_vars_kept_apart_from_numeric_vars() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "set_var", "key": "name", "value": 7 },
+                { "type": "set_str_var", "key": "name", "value": "Ava" },
+                { "type": "jump_if", "cond": { "kind": "str_eq", "key": "name", "value": "Ava" }, "target": "ava" },
+                { "type": "dialogue", "speaker": "Narrator", "text": "Stranger." },
+                { "type": "dialogue", "speaker": "Narrator", "text": "Hello, {strvar:name}." }
+            ],
+            "labels": { "start": 0, "ava": 4 }
+        }"#;
+    let script = ScriptRaw::from_json(json).unwrap().compile().unwrap();
+    assert_eq!(script.var_names.len(), 1);
+    assert_eq!(script.str_var_names.len(), 1);
+    assert!(matches!(
+        script.events[1],
+        EventCompiled::SetStrVar { str_var_id: 0, .. }
+    ));
+
+    let mut engine = Engine::from_compiled(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    for _ in 0..3 {
+        engine.step().unwrap();
+    }
+    assert_eq!(engine.state().position, 4);
+    assert_eq!(engine.state().get_var(0), 7);
+    assert_eq!(engine.str_var("name"), Some("Ava"));
+    match engine.ui_state().unwrap().view {
+        crate::UiView::Dialogue { text, .. } => assert_eq!(text, "Hello, Ava."),
+        other => panic!("expected dialogue, got {other:?}"),
+    }
+}
+
+#[test]
+fn chapters_and_scene_tags_report_entry_ips() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "background": "bg/room.png", "chapter": "Chapter 1", "tags": ["chapter1"] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Morning." },
+                { "type": "scene", "background": "bg/street.png", "chapter": "Chapter 1", "tags": ["chapter1", "outdoor"] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Outside." },
+                { "type": "scene", "background": "bg/park.png", "chapter": "Chapter 2", "tags": ["outdoor"] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Later." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    assert_eq!(engine.scenes_with_tag("chapter1"), vec![0, 2]);
+    assert_eq!(engine.scenes_with_tag("outdoor"), vec![2, 4]);
+    assert!(engine.scenes_with_tag("missing").is_empty());
+    assert_eq!(
+        engine.chapters(),
+        vec![("Chapter 1".to_string(), 0), ("Chapter 2".to_string(), 4)]
+    );
+
+    engine.goto_ip(engine.chapters()[1].1).unwrap();
+    assert_eq!(engine.state().position, 4);
+    assert_eq!(
+        engine.visual_state().background.as_deref(),
+        Some("bg/park.png")
+    );
+}
+
+#[test]
+fn gallery_unlocks_accumulate_and_survive_save_and_load() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "dialogue", "speaker": "Ava", "text": "Look." },
+                { "type": "unlock_gallery", "id": "cg_sunset" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Pretty." },
+                { "type": "unlock_gallery", "id": "cg_night" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Bye." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut played = build();
+    played.step().unwrap();
+    assert!(played.unlocked_gallery().is_empty());
+    played.step().unwrap();
+    assert_eq!(
+        played.unlocked_gallery().iter().collect::<Vec<_>>(),
+        ["cg_sunset"]
+    );
+
+    let script_id = crate::compute_script_id(&played.script().to_binary().unwrap());
+    let bytes = crate::SaveData::new(script_id, played.state().clone())
+        .to_binary()
+        .unwrap();
+    let mut loaded = build();
+    loaded
+        .set_state(crate::SaveData::from_binary(&bytes).unwrap().state)
+        .unwrap();
+    assert!(loaded.unlocked_gallery().contains("cg_sunset"));
+
+    loaded.step().unwrap();
+    loaded.step().unwrap();
+    let early_save = played.state().clone();
+    loaded.set_state(early_save).unwrap();
+    assert_eq!(
+        loaded.unlocked_gallery().iter().collect::<Vec<_>>(),
+        ["cg_night", "cg_sunset"]
+    );
+}
+
+#[test]
+fn achieving_twice_records_once_and_emits_one_event() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "achieve", "id": "first_steps" },
+                { "type": "achieve", "id": "first_steps" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let build = || {
+        Engine::new(
+            ScriptRaw::from_json(json).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap()
+    };
+    let mut engine = build();
+    engine.enable_event_stream();
+    engine.step().unwrap();
+    engine.step().unwrap();
+
+    assert_eq!(
+        engine.achievements().iter().collect::<Vec<_>>(),
+        ["first_steps"]
+    );
+    let unlocked: Vec<EngineEvent> = engine
+        .drain_events()
+        .into_iter()
+        .filter(|event| matches!(event, EngineEvent::AchievementUnlocked { .. }))
+        .collect();
+    assert_eq!(
+        unlocked,
+        vec![EngineEvent::AchievementUnlocked {
+            ip: 0,
+            id: "first_steps".into(),
+        }]
+    );
+
+    let script_id = crate::compute_script_id(&engine.script().to_binary().unwrap());
+    let bytes = crate::SaveData::new(script_id, engine.state().clone())
+        .to_binary()
+        .unwrap();
+    let mut loaded = build();
+    loaded
+        .set_state(crate::SaveData::from_binary(&bytes).unwrap().state)
+        .unwrap();
+    assert!(loaded.achievements().contains("first_steps"));
+}
+
+#[test]
+fn numeric_vars_snapshot_reports_current_values_by_name() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "set_var", "key": "affection_ava", "value": 2 },
+                { "type": "set_var", "key": "affection_mio", "value": 1 },
+                { "type": "set_var", "key": "affection_ava", "value": 5 },
+                { "type": "jump_if", "cond": { "kind": "var_cmp", "key": "trust", "op": "gt", "value": 0 }, "target": "end" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Done." }
+            ],
+            "labels": { "start": 0, "end": 4 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    for _ in 0..3 {
+        engine.step().unwrap();
+    }
+
+    let snapshot = engine.numeric_vars_snapshot();
+    assert_eq!(
+        snapshot.into_iter().collect::<Vec<_>>(),
+        [
+            ("affection_ava".to_string(), 5),
+            ("affection_mio".to_string(), 1),
+            ("trust".to_string(), 0),
+        ]
+    );
+    assert_eq!(
+        engine.format_numeric_vars(|name, value| format!("{name}={value}")),
+        ["affection_ava=5", "affection_mio=1", "trust=0"]
+    );
+}
+
+#[test]
+fn choice_without_options_takes_its_fallthrough() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "choice", "prompt": "Ask?", "options": [], "fallthrough": "after" },
+                { "type": "dialogue", "speaker": "Ava", "text": "Skipped." },
+                { "type": "dialogue", "speaker": "Ava", "text": "After." }
+            ],
+            "labels": { "start": 0, "after": 2 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    assert_eq!(engine.peek_next().unwrap().next_ip, Some(2));
+    engine.step().unwrap();
+    assert_eq!(engine.state().position, 2);
+
+    let without = json.replace(r#", "fallthrough": "after""#, "");
+    assert!(matches!(
+        Engine::new(
+            ScriptRaw::from_json(&without).unwrap(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        ),
+        Err(VnError::InvalidScript(_))
+    ));
+}
+
+#[test]
+fn patch_raising_character_z_moves_it_to_the_end_of_the_draw_list() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "characters": [{ "name": "Ava" }, { "name": "Ben" }, { "name": "Cy" }] },
+                { "type": "patch", "add": [], "update": [{ "name": "Ava", "z": 1 }], "remove": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "On top." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    let draw_order = |engine: &Engine| {
+        engine
+            .state()
+            .visual
+            .characters
+            .iter()
+            .map(|character| character.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    engine.step().unwrap();
+    assert_eq!(draw_order(&engine), ["Ava", "Ben", "Cy"]);
+    engine.step().unwrap();
+    assert_eq!(draw_order(&engine), ["Ben", "Cy", "Ava"]);
+}
+
+#[test]
+fn flipped_character_stays_flipped_through_patch_update() {
+    let json = r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                { "type": "scene", "characters": [{ "name": "Ava", "expression": "calm", "flip_x": true }, { "name": "Ben" }] },
+                { "type": "patch", "add": [], "update": [{ "name": "Ava", "expression": "angry" }], "remove": [] },
+                { "type": "dialogue", "speaker": "Ava", "text": "Still facing left." }
+            ],
+            "labels": { "start": 0 }
+        }"#;
+    let mut engine = Engine::new(
+        ScriptRaw::from_json(json).unwrap(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    engine.step().unwrap();
+    engine.step().unwrap();
+
+    let characters = &engine.state().visual.characters;
+    assert_eq!(characters[0].name.as_ref(), "Ava");
+    assert_eq!(characters[0].expression.as_deref(), Some("angry"));
+    assert!(characters[0].flip_x);
+    assert!(!characters[1].flip_x);
+}
@@ -118,6 +118,7 @@ fn test_serialization_roundtrip() {
         .spawn(EntityKind::Character(CharacterData {
             name: "Alice".into(),
             expression: Some("happy".into()),
+            flip_x: false,
         }))
         .unwrap();
 
@@ -16,8 +16,12 @@ fn make_choice(prompt: &str, options: Vec<(&str, u32)>) -> EventCompiled {
             .map(|(text, target)| ChoiceOptionCompiled {
                 text: SharedStr::from(text),
                 target_ip: target,
+                weight: None,
+                effects: Vec::new(),
             })
             .collect(),
+        auto: false,
+        fallthrough_ip: None,
     })
 }
 
@@ -32,6 +36,10 @@ fn test_linear_script_graph() {
         labels: [("start".to_string(), 0)].into_iter().collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -59,6 +67,10 @@ fn test_branching_script_graph() {
         labels: [("start".to_string(), 0)].into_iter().collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -82,6 +94,10 @@ fn test_unreachable_detection() {
         labels: [("start".to_string(), 0)].into_iter().collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -104,6 +120,10 @@ fn test_dot_export() {
         labels: [("start".to_string(), 0)].into_iter().collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -131,6 +151,10 @@ fn test_find_by_label() {
         .collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -149,6 +173,10 @@ fn test_dialogue_preview_handles_utf8_boundaries() {
         labels: [("start".to_string(), 0)].into_iter().collect(),
         start_ip: 0,
         flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
     };
 
     let graph = StoryGraph::from_script(&script);
@@ -160,3 +188,73 @@ fn test_dialogue_preview_handles_utf8_boundaries() {
     assert!(text_preview.ends_with("..."));
     assert!(text_preview.chars().count() <= 50);
 }
+
+#[test]
+fn test_branch_lengths_use_shortest_path_through_loops() {
+    let script = ScriptCompiled {
+        events: vec![
+            make_choice("Route", vec![("A", 1), ("B", 3)]),
+            make_dialogue("A", "Short route"),
+            EventCompiled::Jump { target_ip: 6 },
+            make_dialogue("B", "Long route"),
+            EventCompiled::JumpIf {
+                cond: CondCompiled::Flag {
+                    flag_id: 0,
+                    is_set: true,
+                },
+                target_ip: 3,
+            },
+            make_dialogue("B", "Done"),
+        ],
+        labels: [("start".to_string(), 0)].into_iter().collect(),
+        start_ip: 0,
+        flag_count: 1,
+        flag_names: vec!["again".into()],
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
+    };
+
+    let report = StoryGraph::from_script(&script).branch_lengths();
+
+    assert_eq!(
+        report.ends,
+        vec![
+            BranchLength {
+                end_id: 2,
+                shortest: 3,
+                longest: 3,
+            },
+            BranchLength {
+                end_id: 5,
+                shortest: 4,
+                longest: 4,
+            },
+        ]
+    );
+    assert_eq!((report.min, report.max), (3, 4));
+    assert!((report.mean - 3.5).abs() < f32::EPSILON);
+    assert!(!report.imbalanced);
+}
+
+#[test]
+fn test_branch_lengths_flag_neglected_route() {
+    let mut events = vec![make_choice("Route", vec![("A", 1), ("B", 2)])];
+    events.push(EventCompiled::Jump { target_ip: 12 });
+    events.extend((0..10).map(|i| make_dialogue("B", &format!("Line {i}"))));
+    let script = ScriptCompiled {
+        events,
+        labels: [("start".to_string(), 0)].into_iter().collect(),
+        start_ip: 0,
+        flag_count: 0,
+        flag_names: Vec::new(),
+        var_names: Vec::new(),
+        character_aliases: BTreeMap::new(),
+        str_var_names: Vec::new(),
+    };
+
+    let report = StoryGraph::from_script(&script).branch_lengths();
+
+    assert_eq!((report.min, report.max), (2, 11));
+    assert!(report.imbalanced);
+}
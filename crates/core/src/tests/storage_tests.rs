@@ -1,4 +1,9 @@
 use super::*;
+#[cfg(feature = "native")]
+use std::fs;
+
+#[cfg(feature = "native")]
+use super::slots::now_unix_ms;
 
 fn sample_save(position: u32) -> SaveData {
     let mut state = EngineState::new(position, 8);
@@ -7,6 +12,7 @@ fn sample_save(position: u32) -> SaveData {
     SaveData::new([1u8; 32], state)
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn slot_store_roundtrip_and_list() {
     let root = std::env::temp_dir().join(format!("vn_slot_store_{}", now_unix_ms()));
@@ -37,6 +43,7 @@ fn slot_store_roundtrip_and_list() {
     let _ = fs::remove_dir_all(root);
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn quicksave_roundtrip() {
     let root = std::env::temp_dir().join(format!("vn_quicksave_store_{}", now_unix_ms()));
@@ -98,6 +105,7 @@ fn from_any_binary_accepts_authenticated_and_legacy_payloads() {
     assert!(decoded_legacy.state.get_flag(2));
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn slot_load_recovers_from_corrupted_primary() {
     let root = std::env::temp_dir().join(format!("vn_slot_recovery_{}", now_unix_ms()));
@@ -123,6 +131,7 @@ fn slot_load_recovers_from_corrupted_primary() {
     let _ = fs::remove_dir_all(root);
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn quickload_reports_recovery_failure_when_no_backup() {
     let root = std::env::temp_dir().join(format!("vn_quick_recovery_fail_{}", now_unix_ms()));
@@ -142,6 +151,7 @@ fn quickload_reports_recovery_failure_when_no_backup() {
     let _ = fs::remove_dir_all(root);
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn list_slots_accepts_legacy_metadata_without_new_fields() {
     let root = std::env::temp_dir().join(format!("vn_slot_legacy_meta_{}", now_unix_ms()));
@@ -175,6 +185,7 @@ fn list_slots_accepts_legacy_metadata_without_new_fields() {
     let _ = fs::remove_dir_all(root);
 }
 
+#[cfg(feature = "native")]
 #[test]
 fn slot_store_loads_legacy_plain_payloads() {
     let root = std::env::temp_dir().join(format!("vn_slot_legacy_save_{}", now_unix_ms()));
@@ -180,3 +180,206 @@ fn test_scrubbing_determinism() {
         "Scrubbing should be deterministic"
     );
 }
+
+fn sample_between(easing: Easing, tick: u32) -> Option<f32> {
+    let entity = EntityId::new(7);
+    let mut timeline = Timeline::new(60);
+    let track = timeline
+        .get_or_create_track(entity, PropertyType::PositionX)
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(0, 0, Easing::Linear))
+        .unwrap();
+    track.add_keyframe(Keyframe::new(100, 100, easing)).unwrap();
+    timeline.sample(entity, PropertyType::PositionX, tick)
+}
+
+#[test]
+fn test_sample_linear_keeps_fraction() {
+    assert_eq!(sample_between(Easing::Linear, 25), Some(25.0));
+    assert_eq!(sample_between(Easing::Linear, 1), Some(1.0));
+
+    let entity = EntityId::new(7);
+    let mut timeline = Timeline::new(60);
+    let track = timeline
+        .get_or_create_track(entity, PropertyType::Opacity)
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(0, 0, Easing::Linear))
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(4, 1, Easing::Linear))
+        .unwrap();
+    assert_eq!(timeline.sample(entity, PropertyType::Opacity, 2), Some(0.5));
+    // `evaluate` still truncates to whole units.
+    assert_eq!(
+        timeline.evaluate_at(2),
+        vec![(entity, PropertyType::Opacity, 0)]
+    );
+}
+
+#[test]
+fn test_sample_ease_in_and_out() {
+    assert_eq!(sample_between(Easing::EaseIn, 50), Some(25.0));
+    assert_eq!(sample_between(Easing::EaseOut, 50), Some(75.0));
+    assert_eq!(sample_between(Easing::EaseInOut, 25), Some(12.5));
+    assert_eq!(sample_between(Easing::EaseInOut, 75), Some(87.5));
+    assert_eq!(sample_between(Easing::Step, 99), Some(0.0));
+}
+
+#[test]
+fn test_sample_clamps_and_requires_track() {
+    assert_eq!(sample_between(Easing::EaseIn, 0), Some(0.0));
+    assert_eq!(sample_between(Easing::EaseIn, 500), Some(100.0));
+
+    let timeline = Timeline::new(60);
+    assert_eq!(
+        timeline.sample(EntityId::new(7), PropertyType::PositionX, 10),
+        None
+    );
+}
+
+#[test]
+fn test_easing_f32_boundaries_and_midpoints() {
+    for easing in Easing::ALL {
+        assert_eq!(easing.apply_f32(0.0), 0.0, "{easing:?} at 0");
+        assert_eq!(easing.apply_f32(1.0), 1.0, "{easing:?} at 1");
+    }
+
+    assert_eq!(Easing::Linear.apply_f32(0.5), 0.5);
+    assert_eq!(Easing::EaseInQuad.apply_f32(0.5), 0.25);
+    assert_eq!(Easing::EaseOutQuad.apply_f32(0.5), 0.75);
+    assert_eq!(Easing::EaseInOutCubic.apply_f32(0.5), 0.5);
+    assert_eq!(Easing::EaseInOutCubic.apply_f32(0.25), 0.0625);
+    assert_eq!(Easing::EaseInOutCubic.apply_f32(0.75), 0.9375);
+    assert_eq!(Easing::Step.apply_f32(0.5), 0.0);
+    assert_eq!(Easing::Step.apply_f32(0.999), 0.0);
+}
+
+#[test]
+fn test_new_easings_drive_sample() {
+    assert_eq!(sample_between(Easing::EaseInQuad, 50), Some(25.0));
+    assert_eq!(sample_between(Easing::EaseOutQuad, 50), Some(75.0));
+    assert_eq!(sample_between(Easing::EaseInOutCubic, 25), Some(6.25));
+    assert_eq!(sample_between(Easing::EaseInOutCubic, 75), Some(93.75));
+}
+
+#[test]
+fn test_set_keyframe_easing() {
+    let mut track = Track::new(EntityId::new(1), PropertyType::PositionX);
+    track
+        .add_keyframe(Keyframe::new(10, 5, Easing::Linear))
+        .unwrap();
+    assert!(track.set_keyframe_easing(10, Easing::EaseInOutCubic));
+    assert!(!track.set_keyframe_easing(11, Easing::Step));
+    assert_eq!(
+        track.keyframes().next().unwrap().easing,
+        Easing::EaseInOutCubic
+    );
+}
+
+#[test]
+fn test_sample_opacity_fades_in_within_unit_range() {
+    let entity = EntityId::new(3);
+    let mut timeline = Timeline::new(60);
+    let track = timeline
+        .get_or_create_track(entity, PropertyType::Opacity)
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(0, 0, Easing::Linear))
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(60, 1000, Easing::EaseInOut))
+        .unwrap();
+
+    let mut previous = 0.0;
+    for tick in 0..=80 {
+        let opacity = timeline.sample_opacity(entity, tick).unwrap();
+        assert!((0.0..=1.0).contains(&opacity), "tick {tick}: {opacity}");
+        assert!(opacity >= previous, "fade must not go backwards");
+        previous = opacity;
+    }
+    assert_eq!(timeline.sample_opacity(entity, 0), Some(0.0));
+    assert_eq!(timeline.sample_opacity(entity, 60), Some(1.0));
+    assert_eq!(timeline.sample_opacity(EntityId::new(4), 30), None);
+}
+
+#[test]
+fn test_sample_opacity_clamps_out_of_range_keyframes() {
+    let entity = EntityId::new(3);
+    let mut timeline = Timeline::new(60);
+    let track = timeline
+        .get_or_create_track(entity, PropertyType::Opacity)
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(0, -500, Easing::Linear))
+        .unwrap();
+    track
+        .add_keyframe(Keyframe::new(10, 1500, Easing::Linear))
+        .unwrap();
+
+    assert_eq!(timeline.sample_opacity(entity, 0), Some(0.0));
+    assert_eq!(timeline.sample_opacity(entity, 10), Some(1.0));
+
+    let mut visual = crate::visual::VisualState::default();
+    assert_eq!(visual.opacity_of("Ava"), 1.0);
+    visual.set_character_opacity("Ava", 1.7);
+    assert_eq!(visual.opacity_of("Ava"), 1.0);
+    visual.set_character_opacity("Ava", -0.2);
+    assert_eq!(visual.opacity_of("Ava"), 0.0);
+}
+
+#[test]
+fn test_compiled_timeline_round_trips_tracks() {
+    let mut timeline = Timeline::new(30);
+    let hero = EntityId::new(9);
+    let villain = EntityId::new(2);
+    let fade = timeline
+        .get_or_create_track(hero, PropertyType::Opacity)
+        .unwrap();
+    fade.add_keyframe(Keyframe::new(30, 1000, Easing::EaseOutQuad))
+        .unwrap();
+    fade.add_keyframe(Keyframe::new(0, 0, Easing::Linear))
+        .unwrap();
+    let slide = timeline
+        .get_or_create_track(villain, PropertyType::PositionX)
+        .unwrap();
+    slide
+        .add_keyframe(Keyframe::new(10, -40, Easing::EaseIn))
+        .unwrap();
+    timeline.seek(12);
+
+    let compiled = timeline.to_compiled();
+    assert_eq!(compiled.ticks_per_second, 30);
+    assert_eq!(compiled.entities, vec![hero, villain]);
+    assert_eq!(compiled.tracks.len(), 2);
+    assert_eq!(compiled.tracks[0].keyframes[0].time, 0);
+
+    let json = serde_json::to_string(&compiled).unwrap();
+    let decoded: CompiledTimeline = serde_json::from_str(&json).unwrap();
+    let reloaded = Timeline::from_compiled(&decoded).unwrap();
+    assert_eq!(reloaded.ticks_per_second, 30);
+    assert_eq!(
+        reloaded.tracks().cloned().collect::<Vec<_>>(),
+        timeline.tracks().cloned().collect::<Vec<_>>()
+    );
+    assert_eq!(reloaded.to_compiled(), compiled);
+}
+
+#[test]
+fn test_from_compiled_rejects_dangling_indices() {
+    let compiled = CompiledTimeline {
+        ticks_per_second: 60,
+        entities: vec![EntityId::new(1)],
+        properties: vec![PropertyType::Scale],
+        tracks: vec![CompiledTrack {
+            entity: 0,
+            property: 3,
+            keyframes: vec![Keyframe::new(0, 1000, Easing::Linear)],
+        }],
+    };
+    assert_eq!(
+        Timeline::from_compiled(&compiled).unwrap_err(),
+        TimelineError::InvalidCompiledIndex { index: 3 }
+    );
+}
@@ -16,6 +16,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::entity::EntityId;
 
+mod compiled;
+
+pub use compiled::{CompiledTimeline, CompiledTrack};
+
 // =============================================================================
 // Fixed-Point Math (Q16.16)
 // =============================================================================
@@ -139,16 +143,81 @@ pub enum Easing {
     EaseInOut,
     /// Instant jump to end value.
     Step,
+    /// Quadratic ease-in (t²).
+    EaseInQuad,
+    /// Quadratic ease-out (1 - (1 - t)²).
+    EaseOutQuad,
+    /// Cubic ease-in-out: 4t³ below the midpoint, mirrored above it.
+    EaseInOutCubic,
 }
 
 impl Easing {
+    /// Every easing, in the order editors list them.
+    pub const ALL: [Easing; 8] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::EaseInQuad,
+        Easing::EaseOutQuad,
+        Easing::EaseInOutCubic,
+        Easing::Step,
+    ];
+
+    /// Display name for editor UIs.
+    pub fn label(self) -> &'static str {
+        match self {
+            Easing::Linear => "Linear",
+            Easing::EaseIn => "Ease In",
+            Easing::EaseOut => "Ease Out",
+            Easing::EaseInOut => "Ease In/Out",
+            Easing::Step => "Step",
+            Easing::EaseInQuad => "Ease In (Quad)",
+            Easing::EaseOutQuad => "Ease Out (Quad)",
+            Easing::EaseInOutCubic => "Ease In/Out (Cubic)",
+        }
+    }
+
+    /// Floating-point reference curve on `t` in [0, 1] (clamped), for
+    /// previews and plotting. Runtime interpolation uses [`Easing::apply`].
+    pub fn apply_f32(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn | Easing::EaseInQuad => t * t,
+            Easing::EaseOut | Easing::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 1.0 - t;
+                    1.0 - 4.0 * u * u * u
+                }
+            }
+            Easing::Step => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
     /// Apply the easing function to a normalized time `t` in [0, 1].
     /// Returns the eased value, also in [0, 1].
     pub fn apply(self, t: Fixed) -> Fixed {
         match self {
             Easing::Linear => t,
-            Easing::EaseIn => t * t,
-            Easing::EaseOut => {
+            Easing::EaseIn | Easing::EaseInQuad => t * t,
+            Easing::EaseOut | Easing::EaseOutQuad => {
                 // 1 - (1 - t)^2 = 2t - t^2
                 let one_minus_t = Fixed::ONE - t;
                 Fixed::ONE - (one_minus_t * one_minus_t)
@@ -165,6 +234,16 @@ impl Easing {
                     Fixed::ONE - two * one_minus_t * one_minus_t
                 }
             }
+            Easing::EaseInOutCubic => {
+                let half = Fixed::from_raw(Fixed::ONE.0 / 2);
+                let four = Fixed::from_int(4);
+                if t < half {
+                    four * t * t * t
+                } else {
+                    let one_minus_t = Fixed::ONE - t;
+                    Fixed::ONE - four * one_minus_t * one_minus_t * one_minus_t
+                }
+            }
             Easing::Step => {
                 if t.0 >= Fixed::ONE.0 {
                     Fixed::ONE
@@ -256,6 +335,12 @@ pub struct Track {
 /// Maximum keyframes per track (Criterio C: Presupuesto).
 pub const MAX_KEYFRAMES_PER_TRACK: usize = 256;
 
+/// Where a time falls on a [`Track`]: past either end, or between two keyframes.
+enum Segment<'a> {
+    Hold(i32),
+    Between(&'a Keyframe, &'a Keyframe),
+}
+
 impl Track {
     /// Creates a new empty track.
     pub fn new(target: EntityId, property: PropertyType) -> Self {
@@ -310,38 +395,62 @@ impl Track {
     ///
     /// Returns `None` if the track has no keyframes.
     pub fn evaluate(&self, time: u32) -> Option<i32> {
-        if self.keyframes.is_empty() {
-            return None;
+        self.evaluate_fixed(time).map(Fixed::to_int)
+    }
+
+    /// Like [`Track::evaluate`], but keeps the fractional part of the
+    /// interpolated value (for smooth preview rendering).
+    ///
+    /// Computed in `f32` from `elapsed / duration`, so short segments don't
+    /// lose precision to the fixed-point `t` used by `evaluate`.
+    pub fn sample(&self, time: u32) -> Option<f32> {
+        match self.segment(time)? {
+            Segment::Hold(value) => Some(value as f32),
+            Segment::Between(k0, k1) => {
+                let duration = k1.time - k0.time;
+                let t = (time - k0.time) as f32 / duration as f32;
+                let eased_t = k1.easing.apply_f32(t);
+                let (v0, v1) = (k0.value as f32, k1.value as f32);
+                Some(v0 + (v1 - v0) * eased_t)
+            }
         }
+    }
+
+    fn evaluate_fixed(&self, time: u32) -> Option<Fixed> {
+        Some(match self.segment(time)? {
+            Segment::Hold(value) => Fixed::from_int(value),
+            Segment::Between(k0, k1) => self.interpolate(k0, k1, time),
+        })
+    }
+
+    /// Finds the keyframes bracketing `time`; `None` for an empty track.
+    fn segment(&self, time: u32) -> Option<Segment<'_>> {
+        let first = self.keyframes.first()?;
 
         // Before first keyframe
-        if time <= self.keyframes[0].time {
-            return Some(self.keyframes[0].value);
+        if time <= first.time {
+            return Some(Segment::Hold(first.value));
         }
 
         // After last keyframe
-        let last = self.keyframes.last().unwrap();
+        let last = self.keyframes.last()?;
         if time >= last.time {
-            return Some(last.value);
-        }
-
-        // Find the segment we're in
-        for i in 0..self.keyframes.len() - 1 {
-            let k0 = &self.keyframes[i];
-            let k1 = &self.keyframes[i + 1];
-            if time >= k0.time && time < k1.time {
-                return Some(self.interpolate(k0, k1, time));
-            }
+            return Some(Segment::Hold(last.value));
         }
 
-        Some(last.value)
+        // Find the bracketing segment
+        let next = self.keyframes.partition_point(|k| k.time <= time);
+        Some(Segment::Between(
+            &self.keyframes[next - 1],
+            &self.keyframes[next],
+        ))
     }
 
     /// Interpolates between two keyframes.
-    fn interpolate(&self, k0: &Keyframe, k1: &Keyframe, time: u32) -> i32 {
+    fn interpolate(&self, k0: &Keyframe, k1: &Keyframe, time: u32) -> Fixed {
         let duration = k1.time - k0.time;
         if duration == 0 {
-            return k1.value;
+            return Fixed::from_int(k1.value);
         }
 
         // Calculate normalized time t in [0, 1] using fixed-point
@@ -355,7 +464,19 @@ impl Track {
         // Lerp the values
         let v0 = Fixed::from_int(k0.value);
         let v1 = Fixed::from_int(k1.value);
-        Fixed::lerp(v0, v1, eased_t).to_int()
+        Fixed::lerp(v0, v1, eased_t)
+    }
+
+    /// Changes the easing of the keyframe at `time`. Returns false when no
+    /// keyframe sits at that time.
+    pub fn set_keyframe_easing(&mut self, time: u32, easing: Easing) -> bool {
+        match self.keyframes.iter_mut().find(|k| k.time == time) {
+            Some(keyframe) => {
+                keyframe.easing = easing;
+                true
+            }
+            None => false,
+        }
     }
 
     /// Returns an iterator over keyframes.
@@ -498,6 +619,21 @@ impl Timeline {
             .collect()
     }
 
+    /// Samples one entity property at `tick`, interpolating between the
+    /// bracketing keyframes with the later keyframe's [`Easing`].
+    ///
+    /// Returns `None` when no track animates that property.
+    pub fn sample(&self, entity: EntityId, property: PropertyType, tick: u32) -> Option<f32> {
+        self.find_track(entity, property)?.sample(tick)
+    }
+
+    /// Samples the [`PropertyType::Opacity`] track of `entity` as a fraction
+    /// in [0, 1]. Keyframes store 0-1000; values outside it are clamped.
+    pub fn sample_opacity(&self, entity: EntityId, tick: u32) -> Option<f32> {
+        self.sample(entity, PropertyType::Opacity, tick)
+            .map(|value| (value / 1000.0).clamp(0.0, 1.0))
+    }
+
     /// Returns the total duration of the timeline (end of last keyframe).
     pub fn duration(&self) -> u32 {
         self.tracks
@@ -552,6 +688,13 @@ pub enum TimelineError {
         help("Consider consolidating tracks or removing unused animations")
     )]
     TrackLimitExceeded,
+
+    #[error("compiled timeline references missing table entry {index}")]
+    #[diagnostic(
+        code(vn::timeline::invalid_compiled_index),
+        help("Re-export the timeline; the bundle is corrupt or hand-edited")
+    )]
+    InvalidCompiledIndex { index: u32 },
 }
 
 // =============================================================================
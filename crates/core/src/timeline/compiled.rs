@@ -0,0 +1,98 @@
+//! Export form of a [`Timeline`].
+//!
+//! Entity and property ids are interned into tables so each track stores two
+//! small indices, and tracks are ordered deterministically for diffing.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Keyframe, PropertyType, Timeline, TimelineError, Track};
+use crate::entity::EntityId;
+
+/// Runtime-ready animation bundle produced by [`Timeline::to_compiled`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledTimeline {
+    pub ticks_per_second: u32,
+    /// Interned entity ids, in first-use order.
+    pub entities: Vec<EntityId>,
+    /// Interned properties, in first-use order.
+    pub properties: Vec<PropertyType>,
+    /// Tracks sorted by entity, then property index.
+    pub tracks: Vec<CompiledTrack>,
+}
+
+/// One track of a [`CompiledTimeline`]; `entity` and `property` index the
+/// bundle's interning tables.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledTrack {
+    pub entity: u32,
+    pub property: u32,
+    /// Keyframes sorted by time.
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Timeline {
+    /// Compiles the timeline for export. Playback position is not kept.
+    pub fn to_compiled(&self) -> CompiledTimeline {
+        let mut entities = Vec::new();
+        let mut properties = Vec::new();
+        let mut tracks: Vec<CompiledTrack> = self
+            .tracks
+            .iter()
+            .map(|track| CompiledTrack {
+                entity: intern(&mut entities, track.target),
+                property: intern(&mut properties, track.property),
+                keyframes: track.keyframes.clone(),
+            })
+            .collect();
+        tracks.sort_by_key(|track| (track.entity, track.property));
+
+        CompiledTimeline {
+            ticks_per_second: self.ticks_per_second,
+            entities,
+            properties,
+            tracks,
+        }
+    }
+
+    /// Rebuilds a timeline from an exported bundle, re-checking track and
+    /// keyframe invariants.
+    ///
+    /// # Errors
+    /// Returns [`TimelineError::InvalidCompiledIndex`] when a track points
+    /// outside the interning tables, or the usual limit/ordering errors.
+    pub fn from_compiled(compiled: &CompiledTimeline) -> Result<Self, TimelineError> {
+        let mut timeline = Timeline::new(compiled.ticks_per_second);
+        for compiled_track in &compiled.tracks {
+            let target = *compiled
+                .entities
+                .get(compiled_track.entity as usize)
+                .ok_or(TimelineError::InvalidCompiledIndex {
+                    index: compiled_track.entity,
+                })?;
+            let property = *compiled
+                .properties
+                .get(compiled_track.property as usize)
+                .ok_or(TimelineError::InvalidCompiledIndex {
+                    index: compiled_track.property,
+                })?;
+            let mut track = Track::new(target, property);
+            for keyframe in &compiled_track.keyframes {
+                track.add_keyframe(keyframe.clone())?;
+            }
+            timeline.add_track(track)?;
+        }
+        Ok(timeline)
+    }
+}
+
+/// Returns the index of `value` in `table`, appending it on first use.
+fn intern<T: Copy + PartialEq>(table: &mut Vec<T>, value: T) -> u32 {
+    let index = match table.iter().position(|entry| *entry == value) {
+        Some(index) => index,
+        None => {
+            table.push(value);
+            table.len() - 1
+        }
+    };
+    index as u32
+}
@@ -76,6 +76,9 @@ impl UiView {
             EventCompiled::SetVar { var_id, value } => UiView::System {
                 message: format!("SetVar: {} = {}", var_id, value),
             },
+            EventCompiled::SetStrVar { str_var_id, value } => UiView::System {
+                message: format!("SetStrVar: {} = {:?}", str_var_id, value),
+            },
             EventCompiled::Jump { .. } => UiView::System {
                 message: "Jump".to_string(),
             },
@@ -94,10 +97,40 @@ impl UiView {
             EventCompiled::SetCharacterPosition(pos) => UiView::System {
                 message: format!("SetCharacterPosition: {} ({}, {})", pos.name, pos.x, pos.y),
             },
+            EventCompiled::InputText { str_var_id, .. } => UiView::System {
+                message: format!("InputText: {str_var_id}"),
+            },
+            EventCompiled::UnlockGallery { id } => UiView::System {
+                message: format!("UnlockGallery: {id}"),
+            },
+            EventCompiled::Achieve { id } => UiView::System {
+                message: format!("Achieve: {id}"),
+            },
+        }
+    }
+
+    /// Replaces dialogue text and choice prompt/options with fixed CRC32
+    /// digests (`crc32:xxxxxxxx`), keeping golden traces compact while still
+    /// catching any wording change. Speakers and other views are kept as is.
+    pub fn digested(self) -> Self {
+        match self {
+            UiView::Dialogue { speaker, text } => UiView::Dialogue {
+                speaker,
+                text: text_digest(&text),
+            },
+            UiView::Choice { prompt, options } => UiView::Choice {
+                prompt: text_digest(&prompt),
+                options: options.iter().map(|option| text_digest(option)).collect(),
+            },
+            other => other,
         }
     }
 }
 
+fn text_digest(text: &str) -> String {
+    format!("crc32:{:08x}", crc32fast::hash(text.as_bytes()))
+}
+
 /// Simplified engine state for deterministic comparison.
 /// Uses only contractual data, no internal handles or pointers.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -108,6 +141,9 @@ pub struct StateDigest {
     pub flags: BTreeMap<u32, bool>,
     /// Variables as a sorted map (var_id -> value).
     pub vars: BTreeMap<u32, i32>,
+    /// [`StateDigest::symbols_hash_of`] `flags` and `vars`, so golden traces
+    /// change on any flag or var write even when nothing visible does.
+    pub symbols_hash: u32,
     /// Number of dialogue entries in history.
     pub history_len: usize,
     /// Digest of the visual scene.
@@ -115,6 +151,26 @@ pub struct StateDigest {
 }
 
 impl StateDigest {
+    /// CRC32 (IEEE, via `crc32fast`) over a fixed byte encoding: `b'f'` then
+    /// the little-endian `u32` id of each set flag, then `b'v'`, the `u32` id
+    /// and the `i32` value of each non-zero var, both in ascending id order.
+    ///
+    /// The encoding is part of the golden-trace format; changing it
+    /// invalidates every committed trace.
+    pub fn symbols_hash_of(flags: &BTreeMap<u32, bool>, vars: &BTreeMap<u32, i32>) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        for (id, _) in flags.iter().filter(|(_, set)| **set) {
+            hasher.update(b"f");
+            hasher.update(&id.to_le_bytes());
+        }
+        for (id, value) in vars.iter().filter(|(_, value)| **value != 0) {
+            hasher.update(b"v");
+            hasher.update(&id.to_le_bytes());
+            hasher.update(&value.to_le_bytes());
+        }
+        hasher.finalize()
+    }
+
     pub fn from_state(state: &EngineState, flag_count: usize) -> Self {
         let mut flags = BTreeMap::new();
         // Manually iterate bits because state.flags is Vec<u64>
@@ -137,6 +193,7 @@ impl StateDigest {
 
         Self {
             position: state.position,
+            symbols_hash: Self::symbols_hash_of(&flags, &vars),
             flags,
             vars,
             history_len: state.history.len(),
@@ -159,6 +216,66 @@ impl UiTrace {
     pub fn push(&mut self, step: u32, view: UiView, state: StateDigest) {
         self.steps.push(UiTraceStep { step, view, state });
     }
+
+    /// Compares two runs step by step and reports what differs at the first
+    /// step where they diverge: the view, the visual digest, or both. A run
+    /// that ends early diverges with [`TraceDivergence::Length`]. Identical
+    /// runs return an empty list.
+    pub fn diff(&self, other: &UiTrace) -> Vec<TraceDivergence> {
+        for (left, right) in self.steps.iter().zip(&other.steps) {
+            let mut divergences = Vec::new();
+            if left.view != right.view {
+                divergences.push(TraceDivergence::View {
+                    step: left.step,
+                    left: left.view.clone(),
+                    right: right.view.clone(),
+                });
+            }
+            if left.state.visual != right.state.visual {
+                divergences.push(TraceDivergence::Visual {
+                    step: left.step,
+                    left: left.state.visual.clone(),
+                    right: right.state.visual.clone(),
+                });
+            }
+            if !divergences.is_empty() {
+                return divergences;
+            }
+        }
+        if self.steps.len() != other.steps.len() {
+            let shorter = self.steps.len().min(other.steps.len());
+            return vec![TraceDivergence::Length {
+                step: shorter as u32,
+                left_len: self.steps.len(),
+                right_len: other.steps.len(),
+            }];
+        }
+        Vec::new()
+    }
+}
+
+/// Where two [`UiTrace`]s stop matching, from [`UiTrace::diff`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceDivergence {
+    /// The user saw different content at `step`.
+    View {
+        step: u32,
+        left: UiView,
+        right: UiView,
+    },
+    /// The scene (background, music or characters) differed at `step`.
+    Visual {
+        step: u32,
+        left: VisualDigest,
+        right: VisualDigest,
+    },
+    /// One run ended at `step` while the other kept going.
+    Length {
+        step: u32,
+        left_len: usize,
+        right_len: usize,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
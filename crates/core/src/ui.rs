@@ -1,5 +1,7 @@
 //! UI mapping helpers for runtime consumers.
 
+use std::collections::BTreeMap;
+
 use crate::event::EventCompiled;
 use crate::visual::VisualState;
 
@@ -26,14 +28,32 @@ pub enum UiView {
     System {
         message: String,
     },
+    /// Text field for an `input_text` event; answer with `Engine::submit_text`.
+    TextInput {
+        prompt: String,
+        max_len: u32,
+    },
 }
 
 impl UiState {
     /// Build a UI view from the current event and visual state.
     pub fn from_event(event: &EventCompiled, visual: &VisualState) -> Self {
+        Self::from_event_with_aliases(event, visual, &BTreeMap::new())
+    }
+
+    /// Like [`UiState::from_event`], but shows dialogue speakers through
+    /// `aliases` (speaker id → display name); unknown ids are shown as-is.
+    pub fn from_event_with_aliases(
+        event: &EventCompiled,
+        visual: &VisualState,
+        aliases: &BTreeMap<String, String>,
+    ) -> Self {
         let view = match event {
             EventCompiled::Dialogue(dialogue) => UiView::Dialogue {
-                speaker: dialogue.speaker.as_ref().to_string(),
+                speaker: aliases
+                    .get(dialogue.speaker.as_ref())
+                    .map_or(dialogue.speaker.as_ref(), String::as_str)
+                    .to_string(),
                 text: dialogue.text.as_ref().to_string(),
             },
             EventCompiled::Choice(choice) => UiView::Choice {
@@ -66,6 +86,15 @@ impl UiState {
             EventCompiled::SetVar { var_id, value } => UiView::System {
                 message: format!("Var {var_id} = {value}"),
             },
+            EventCompiled::SetStrVar { str_var_id, value } => UiView::System {
+                message: format!("StrVar {str_var_id} = {value:?}"),
+            },
+            EventCompiled::UnlockGallery { id } => UiView::System {
+                message: format!("Gallery unlocked: {id}"),
+            },
+            EventCompiled::Achieve { id } => UiView::System {
+                message: format!("Achievement: {id}"),
+            },
             EventCompiled::JumpIf { target_ip, .. } => UiView::System {
                 message: format!("JumpIf to {target_ip}"),
             },
@@ -84,6 +113,12 @@ impl UiState {
                     pos.name, pos.x, pos.y, pos.scale
                 ),
             },
+            EventCompiled::InputText {
+                prompt, max_len, ..
+            } => UiView::TextInput {
+                prompt: prompt.clone(),
+                max_len: *max_len,
+            },
         };
         Self { view }
     }
@@ -10,15 +10,41 @@ pub const SCRIPT_SCHEMA_VERSION: &str = "1.0";
 /// Current binary format version for compiled scripts.
 /// Increment when the binary layout changes.
 /// v2: Migrated from bincode to postcard serialization.
-pub const COMPILED_FORMAT_VERSION: u16 = 2;
+/// v3: Added flag and variable symbol names.
+/// v4: Added auto choices and per-option weights.
+/// v5: Added per-option choice effects.
+/// v6: Added character aliases.
+/// v7: Added input text events.
+/// v8: Added string variables.
+/// v9: Added scene chapters and tags.
+/// v10: Added gallery unlock events.
+/// v11: Added achievement events.
+/// v12: Added choice fallthrough targets.
+/// v13: Added character draw layers (z).
+/// v14: Added character horizontal flip.
+pub const COMPILED_FORMAT_VERSION: u16 = 14;
 
 /// Current format version for save files.
 /// Increment when EngineState serialization changes.
 /// v3: Migrated save payload encoding from bincode to postcard.
-pub const SAVE_FORMAT_VERSION: u16 = 3;
+/// v4: Added optional engine metrics to the save payload.
+/// v5: Added the choice log to EngineState.
+/// v6: Added submitted text values to EngineState.
+/// v7: Replaced submitted text values with id-keyed string variables.
+/// v8: Added unlocked gallery ids to EngineState.
+/// v9: Added achievement ids to EngineState.
+/// v10: Added character draw layers to the visual state.
+/// v11: Added character horizontal flip to the visual state.
+/// v12: Always write character opacity, which postcard cannot skip.
+/// v13: Added active character aliases to EngineState.
+pub const SAVE_FORMAT_VERSION: u16 = 13;
 
 /// Magic bytes for compiled script binaries.
 pub const SCRIPT_BINARY_MAGIC: [u8; 4] = *b"VNSC";
 
+/// Leading tag byte of the compact postcard script encoding. Never equal to
+/// the first byte of [`SCRIPT_BINARY_MAGIC`], so loaders can tell them apart.
+pub const SCRIPT_COMPACT_TAG: u8 = 0xC5;
+
 /// Magic bytes for save files.
 pub const SAVE_BINARY_MAGIC: [u8; 4] = *b"VNSV";
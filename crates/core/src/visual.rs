@@ -1,5 +1,7 @@
 //! Visual state handling for scenes.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::event::{
@@ -7,15 +9,60 @@ use crate::event::{
     SetCharacterPositionCompiled, SharedStr,
 };
 
+/// Visual side effect emitted while stepping, drained with
+/// `Engine::take_visual_commands` so a frontend can animate changes instead
+/// of diffing [`VisualState`] itself.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VisualCommand {
+    /// A `Transition` event was stepped past; `kind` is 0 = fade, 1 = dissolve.
+    Transition {
+        kind: u8,
+        duration_ms: u32,
+        color: Option<SharedStr>,
+    },
+    /// The background changed; `None` clears it.
+    SetBackground { path: Option<SharedStr> },
+    /// A character appeared, or its expression or named position changed.
+    ShowCharacter {
+        name: SharedStr,
+        expression: Option<SharedStr>,
+        position: Option<SharedStr>,
+    },
+    /// An on-screen character's coordinates or scale changed.
+    MoveCharacter {
+        name: SharedStr,
+        x: Option<i32>,
+        y: Option<i32>,
+        scale: Option<f32>,
+    },
+    /// A character left the scene.
+    HideCharacter { name: SharedStr },
+}
+
 /// Current visual state for rendering.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct VisualState {
     pub background: Option<SharedStr>,
     pub music: Option<SharedStr>,
+    /// On-screen characters in draw order: ascending z, ties in insertion
+    /// order.
     pub characters: Vec<CharacterPlacementCompiled>,
+    /// Per-character opacity in [0, 1]; characters without an entry are
+    /// fully opaque.
+    #[serde(default)]
+    pub character_opacity: BTreeMap<String, f32>,
 }
 
 impl VisualState {
+    /// Returns `true` when nothing is on screen or playing, as in a snapshot
+    /// captured without visuals.
+    pub fn is_empty(&self) -> bool {
+        self.background.is_none()
+            && self.music.is_none()
+            && self.characters.is_empty()
+            && self.character_opacity.is_empty()
+    }
+
     /// Applies a scene update to the visual state.
     ///
     /// Note: Scene events preserve existing values when fields are None.
@@ -30,6 +77,7 @@ impl VisualState {
         if !update.characters.is_empty() {
             self.characters.clear();
             self.characters.extend_from_slice(&update.characters);
+            self.sort_characters_by_z();
         }
     }
 
@@ -62,6 +110,9 @@ impl VisualState {
                 if let Some(position) = &patch_update.position {
                     existing.position = Some(position.clone());
                 }
+                if let Some(z) = patch_update.z {
+                    existing.z = Some(z);
+                }
             }
         }
         if !patch.add.is_empty() {
@@ -77,11 +128,14 @@ impl VisualState {
                         existing.x = new_character.x;
                         existing.y = new_character.y;
                         existing.scale = new_character.scale;
+                        existing.z = new_character.z;
+                        existing.flip_x = new_character.flip_x;
                     }
                     None => self.characters.push(new_character.clone()),
                 }
             }
         }
+        self.sort_characters_by_z();
     }
 
     /// Sets a character's absolute position and scale.
@@ -94,6 +148,7 @@ impl VisualState {
             existing.x = Some(pos.x);
             existing.y = Some(pos.y);
             existing.scale = pos.scale;
+            existing.flip_x = pos.flip_x;
             return;
         }
 
@@ -104,6 +159,91 @@ impl VisualState {
             x: Some(pos.x),
             y: Some(pos.y),
             scale: pos.scale,
+            z: None,
+            flip_x: pos.flip_x,
+        });
+        self.sort_characters_by_z();
+    }
+
+    /// Stable-sorts `characters` by z so equal layers keep insertion order.
+    fn sort_characters_by_z(&mut self) {
+        self.characters
+            .sort_by_key(|character| character.z.unwrap_or(0));
+    }
+
+    /// Opacity the renderer should use for `name`, in [0, 1].
+    pub fn opacity_of(&self, name: &str) -> f32 {
+        self.character_opacity.get(name).copied().unwrap_or(1.0)
+    }
+
+    /// Sets a character's opacity, clamped to [0, 1].
+    pub fn set_character_opacity(&mut self, name: &str, opacity: f32) {
+        let opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        self.character_opacity.insert(name.to_string(), opacity);
+    }
+}
+
+/// Appends the commands that turn `before` into `after`: background first,
+/// then hidden, shown and moved characters in `after` order.
+pub(crate) fn append_visual_delta(
+    before: &VisualState,
+    after: &VisualState,
+    commands: &mut Vec<VisualCommand>,
+) {
+    if before.background.as_deref() != after.background.as_deref() {
+        commands.push(VisualCommand::SetBackground {
+            path: after.background.clone(),
         });
     }
+    let find = |state: &VisualState, name: &str| {
+        state
+            .characters
+            .iter()
+            .find(|character| character.name.as_ref() == name)
+            .cloned()
+    };
+    for gone in &before.characters {
+        if find(after, &gone.name).is_none() {
+            commands.push(VisualCommand::HideCharacter {
+                name: gone.name.clone(),
+            });
+        }
+    }
+    for character in &after.characters {
+        let previous = find(before, &character.name);
+        let shown = match &previous {
+            Some(previous) => {
+                previous.expression.as_deref() != character.expression.as_deref()
+                    || previous.position.as_deref() != character.position.as_deref()
+            }
+            None => true,
+        };
+        if shown {
+            commands.push(VisualCommand::ShowCharacter {
+                name: character.name.clone(),
+                expression: character.expression.clone(),
+                position: character.position.clone(),
+            });
+        }
+        let moved = match &previous {
+            Some(previous) => {
+                previous.x != character.x
+                    || previous.y != character.y
+                    || previous.scale != character.scale
+            }
+            None => character.x.is_some() || character.y.is_some() || character.scale.is_some(),
+        };
+        if moved {
+            commands.push(VisualCommand::MoveCharacter {
+                name: character.name.clone(),
+                x: character.x,
+                y: character.y,
+                scale: character.scale,
+            });
+        }
+    }
 }
@@ -0,0 +1,58 @@
+//! Minimal `wasm-bindgen` surface for running scripts in the browser.
+//!
+//! Only compiled for `wasm32` with the `wasm` feature, which doubles as the
+//! compile check that the core builds without filesystem helpers:
+//!
+//! ```text
+//! cargo build -p visual_novel_engine --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm
+//! ```
+//!
+//! Events cross the boundary as the same JSON produced by
+//! [`Engine::current_event_json`]; errors become `Error` strings on the JS side.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Engine, ResourceLimiter, ScriptRaw, SecurityPolicy, VnError};
+
+fn to_js(err: VnError) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+#[wasm_bindgen]
+pub struct WasmEngine {
+    inner: Engine,
+}
+
+#[wasm_bindgen]
+impl WasmEngine {
+    /// Parses and compiles `script_json` with the default security policy and limits.
+    #[wasm_bindgen(constructor)]
+    pub fn new(script_json: &str) -> Result<WasmEngine, JsValue> {
+        let script = ScriptRaw::from_json(script_json).map_err(to_js)?;
+        let inner = Engine::new(
+            script,
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .map_err(to_js)?;
+        Ok(Self { inner })
+    }
+
+    /// Advances past the current event and returns it as JSON.
+    pub fn step(&mut self) -> Result<String, JsValue> {
+        let event = self.inner.step_event().map_err(to_js)?;
+        Ok(event.to_json_string())
+    }
+
+    /// Picks `option_index` on the current choice and returns the choice event as JSON.
+    pub fn choose(&mut self, option_index: u32) -> Result<String, JsValue> {
+        let event = self.inner.choose(option_index as usize).map_err(to_js)?;
+        Ok(event.to_json_string())
+    }
+
+    /// Returns the event the engine is waiting on as JSON.
+    pub fn current_event_json(&self) -> Result<String, JsValue> {
+        self.inner.current_event_json().map_err(to_js)
+    }
+}
@@ -0,0 +1,212 @@
+//! Property tests for compiler parity: every generated, structurally valid
+//! `ScriptRaw` must play the same event sequence once compiled as the raw
+//! preview simulation does, for every choice policy.
+
+use std::collections::BTreeMap;
+
+use arbitrary::{Result, Unstructured};
+use visual_novel_engine::analysis::{
+    check_preview_runtime_parity, run_dry_run, ChoicePolicy, ChoiceStrategy,
+};
+use visual_novel_engine::{
+    CharacterPlacementRaw, ChoiceOptionRaw, ChoiceRaw, CmpOp, CondRaw, DialogueRaw, Engine,
+    EventRaw, ResourceLimiter, ScenePatchRaw, SceneTransitionRaw, SceneUpdateRaw, ScriptRaw,
+    SecurityPolicy,
+};
+
+const CASES: u64 = 256;
+const MAX_EVENTS: usize = 24;
+
+const SPEAKERS: &[&str] = &["Ava", "Ben", "Cid"];
+const FLAGS: &[&str] = &["met", "seen", "done"];
+const VARS: &[&str] = &["trust", "gold"];
+const BACKGROUNDS: &[&str] = &["bg/room.png", "bg/street.png"];
+const MUSIC: &[&str] = &["bgm/calm.ogg", "bgm/tense.ogg"];
+const TRANSITIONS: &[&str] = &["fade_black", "dissolve"];
+const OPS: &[CmpOp] = &[
+    CmpOp::Eq,
+    CmpOp::Ne,
+    CmpOp::Lt,
+    CmpOp::Le,
+    CmpOp::Gt,
+    CmpOp::Ge,
+];
+
+fn fill_deterministic(buf: &mut [u8], seed: u64) {
+    let mut state = seed.max(1);
+    for byte in buf.iter_mut() {
+        // xorshift64*
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state = state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        *byte = (state & 0xFF) as u8;
+    }
+}
+
+fn pick<'a>(u: &mut Unstructured<'_>, pool: &[&'a str]) -> Result<&'a str> {
+    u.choose(pool).copied()
+}
+
+fn maybe(u: &mut Unstructured<'_>, pool: &[&str]) -> Result<Option<String>> {
+    Ok(if u.arbitrary::<bool>()? {
+        Some(pick(u, pool)?.to_string())
+    } else {
+        None
+    })
+}
+
+/// Generates a script whose every jump and option target names an existing
+/// label, so compiling it must succeed and only semantics are under test.
+/// `__end` names a closing dialogue after the generated events, since labels
+/// must point inside the script.
+///
+/// Visual events stick to the subset both sides model identically: scenes
+/// with distinct characters and patches that only swap assets or remove.
+fn valid_script(u: &mut Unstructured<'_>) -> Result<ScriptRaw> {
+    let count = u.int_in_range(1..=MAX_EVENTS)?;
+    let mut labels = BTreeMap::from([("start".to_string(), 0usize), ("__end".to_string(), count)]);
+    for ip in 1..count {
+        if u.ratio(1, 3)? {
+            labels.insert(format!("l{ip}"), ip);
+        }
+    }
+    let targets: Vec<String> = labels.keys().cloned().collect();
+    let target = |u: &mut Unstructured<'_>| u.choose(&targets).cloned();
+
+    let mut events = Vec::with_capacity(count);
+    for _ in 0..count {
+        let event = match u.int_in_range(0..=9u8)? {
+            0 | 1 => EventRaw::Dialogue(DialogueRaw {
+                speaker: pick(u, SPEAKERS)?.to_string(),
+                text: format!("line {}", u.int_in_range(0..=99u8)?),
+            }),
+            2 => {
+                let mut options = Vec::new();
+                for index in 0..u.int_in_range(1..=3usize)? {
+                    options.push(ChoiceOptionRaw {
+                        text: format!("option {index}"),
+                        target: target(u)?,
+                        weight: None,
+                        effects: Vec::new(),
+                    });
+                }
+                EventRaw::Choice(ChoiceRaw {
+                    prompt: "Pick".to_string(),
+                    options,
+                    auto: false,
+                    fallthrough: None,
+                })
+            }
+            3 => EventRaw::Jump { target: target(u)? },
+            4 => {
+                let cond = if u.arbitrary::<bool>()? {
+                    CondRaw::Flag {
+                        key: pick(u, FLAGS)?.to_string(),
+                        is_set: u.arbitrary()?,
+                    }
+                } else {
+                    CondRaw::VarCmp {
+                        key: pick(u, VARS)?.to_string(),
+                        op: *u.choose(OPS)?,
+                        value: u.int_in_range(-3..=3)?,
+                    }
+                };
+                EventRaw::JumpIf {
+                    cond,
+                    target: target(u)?,
+                }
+            }
+            5 => EventRaw::SetFlag {
+                key: pick(u, FLAGS)?.to_string(),
+                value: u.arbitrary()?,
+            },
+            6 => EventRaw::SetVar {
+                key: pick(u, VARS)?.to_string(),
+                value: u.int_in_range(-3..=3)?,
+            },
+            7 => {
+                let cast = u.int_in_range(0..=SPEAKERS.len())?;
+                EventRaw::Scene(SceneUpdateRaw {
+                    background: maybe(u, BACKGROUNDS)?,
+                    music: maybe(u, MUSIC)?,
+                    characters: SPEAKERS[..cast]
+                        .iter()
+                        .map(|name| CharacterPlacementRaw {
+                            name: name.to_string(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    chapter: None,
+                    tags: Vec::new(),
+                })
+            }
+            8 => EventRaw::Patch(ScenePatchRaw {
+                background: maybe(u, BACKGROUNDS)?,
+                music: maybe(u, MUSIC)?,
+                remove: maybe(u, SPEAKERS)?.into_iter().collect(),
+                ..Default::default()
+            }),
+            _ => EventRaw::Transition(SceneTransitionRaw {
+                kind: pick(u, TRANSITIONS)?.to_string(),
+                duration_ms: u.int_in_range(0..=500)?,
+                color: None,
+            }),
+        };
+        events.push(event);
+    }
+    events.push(EventRaw::Dialogue(DialogueRaw {
+        speaker: SPEAKERS[0].to_string(),
+        text: "end".to_string(),
+    }));
+    Ok(ScriptRaw::new(events, labels))
+}
+
+fn policies(u: &mut Unstructured<'_>) -> Result<Vec<ChoicePolicy>> {
+    let mut path = Vec::new();
+    for _ in 0..u.int_in_range(0..=8usize)? {
+        path.push(u.int_in_range(0..=2usize)?);
+    }
+    Ok(vec![
+        ChoicePolicy::Strategy(ChoiceStrategy::First),
+        ChoicePolicy::Strategy(ChoiceStrategy::Last),
+        ChoicePolicy::Strategy(ChoiceStrategy::Alternating),
+        ChoicePolicy::Scripted(path),
+    ])
+}
+
+#[test]
+fn compiled_runtime_matches_raw_simulation_for_generated_scripts() {
+    let mut raw_data = [0u8; 4096];
+
+    for case in 0..CASES {
+        fill_deterministic(&mut raw_data, 0xC0_4D1E_u64 ^ (case << 7));
+        let mut u = Unstructured::new(&raw_data);
+        let Ok(script) = valid_script(&mut u) else {
+            continue;
+        };
+        let policies = policies(&mut u).unwrap_or_default();
+        let json = script.to_json().expect("generated script serializes");
+
+        script
+            .compile()
+            .unwrap_or_else(|err| panic!("case {case} failed to compile: {err}\n{json}"));
+        let engine = Engine::new(
+            script.clone(),
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .unwrap_or_else(|err| panic!("case {case} failed to boot: {err}\n{json}"));
+
+        for policy in &policies {
+            let outcome = run_dry_run(engine.clone(), policy);
+            let issues = check_preview_runtime_parity(&script, &outcome.report, policy);
+            assert!(
+                issues.is_empty(),
+                "case {case} [route={}] diverged: {}\n{json}",
+                policy.label(),
+                issues[0].message
+            );
+        }
+    }
+}
@@ -62,3 +62,36 @@ fn json_and_compiled_paths_match() {
 
     assert_eq!(trace_json.steps, trace_compiled.steps);
 }
+
+#[test]
+fn compiled_binary_preserves_symbol_names() {
+    let script_json = format!(
+        r#"{{
+            "script_schema_version": "{SCRIPT_SCHEMA_VERSION}",
+            "events": [
+                {{"type": "set_flag", "key": "met_alice", "value": true}},
+                {{"type": "set_var", "key": "trust", "value": 1}},
+                {{"type": "jump_if", "cond": {{"kind": "flag", "key": "saw_ending", "is_set": true}}, "target": "start"}}
+            ],
+            "labels": {{ "start": 0 }}
+        }}"#
+    );
+    let compiled = ScriptRaw::from_json(&script_json)
+        .expect("parse")
+        .compile()
+        .expect("compile");
+    let bytes = compiled.to_binary().expect("serialize");
+    let loaded = ScriptCompiled::from_binary(&bytes).expect("load");
+    assert_eq!(loaded.flag_names, compiled.flag_names);
+    assert_eq!(loaded.var_names, compiled.var_names);
+
+    let engine = Engine::from_compiled(
+        loaded,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine compiled");
+    assert_eq!(engine.flag_name(0), Some("met_alice"));
+    assert_eq!(engine.flag_name(1), Some("saw_ending"));
+    assert_eq!(engine.var_name(0), Some("trust"));
+}
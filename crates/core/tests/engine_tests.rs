@@ -1,4 +1,6 @@
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use visual_novel_engine::{
     CharacterPlacementRaw, Engine, EventCompiled, EventRaw, RenderBackend, ResourceLimiter,
@@ -17,7 +19,11 @@ fn sample_script() -> ScriptRaw {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Dialogue(visual_novel_engine::DialogueRaw {
             speaker: "Ava".to_string(),
@@ -29,12 +35,18 @@ fn sample_script() -> ScriptRaw {
                 visual_novel_engine::ChoiceOptionRaw {
                     text: "Si".to_string(),
                     target: "end".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
                 visual_novel_engine::ChoiceOptionRaw {
                     text: "No".to_string(),
                     target: "start".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 },
             ],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Dialogue(visual_novel_engine::DialogueRaw {
             speaker: "Ava".to_string(),
@@ -62,7 +74,11 @@ fn script_with_invalid_choice_target() -> ScriptRaw {
         options: vec![visual_novel_engine::ChoiceOptionRaw {
             text: "Si".to_string(),
             target: "missing".to_string(),
+            weight: None,
+            effects: Vec::new(),
         }],
+        auto: false,
+        fallthrough: None,
     })];
     let mut labels = BTreeMap::new();
     labels.insert("start".to_string(), 0);
@@ -177,6 +193,51 @@ fn engine_records_choice_history() {
     assert_eq!(history[0].target_ip, 0);
 }
 
+#[test]
+fn event_hooks_observe_each_new_current_event() {
+    let mut engine = Engine::new(
+        sample_script(),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let counted = Arc::new(AtomicUsize::new(0));
+
+    let log = Arc::clone(&seen);
+    engine.on_event(Box::new(move |event| {
+        log.lock().unwrap().push(event_signature(event));
+    }));
+    let counter = Arc::clone(&counted);
+    let counter_id = engine.on_event(Box::new(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    engine.step().unwrap();
+    engine.step().unwrap();
+    engine.step().unwrap();
+    engine.choose(1).unwrap();
+    assert!(engine.remove_event_hook(counter_id));
+    assert!(!engine.remove_event_hook(counter_id));
+    engine.step().unwrap();
+    engine.step().unwrap();
+    engine.choose(0).unwrap();
+    engine.step().unwrap();
+
+    assert_eq!(counted.load(Ordering::SeqCst), 3);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            "dialogue:Hola",
+            "choice:Ir?",
+            "scene:bg/room.png",
+            "dialogue:Hola",
+            "choice:Ir?",
+            "dialogue:Fin",
+        ]
+    );
+}
+
 #[test]
 fn json_round_trip() {
     let script = sample_script();
@@ -384,11 +445,17 @@ fn collect_raw_sequence(script: &ScriptRaw, choices: &[usize]) -> Vec<String> {
             | EventRaw::Dialogue(_)
             | EventRaw::Scene(_)
             | EventRaw::SetVar { .. }
+            | EventRaw::SetStrVar { .. }
             | EventRaw::Patch(_)
             | EventRaw::ExtCall { .. }
             | EventRaw::AudioAction(_)
             | EventRaw::Transition(_)
-            | EventRaw::SetCharacterPosition(_) => {
+            | EventRaw::SetCharacterPosition(_)
+            | EventRaw::InputText { .. }
+            | EventRaw::UnlockGallery { .. }
+            | EventRaw::Achieve { .. }
+            | EventRaw::Include { .. }
+            | EventRaw::Expand { .. } => {
                 position += 1;
             }
             EventRaw::JumpIf { .. } => {
@@ -421,12 +488,16 @@ fn event_signature(event: &EventCompiled) -> String {
         EventCompiled::Jump { target_ip } => format!("jump:{target_ip}"),
         EventCompiled::SetFlag { flag_id, value } => format!("flag:{flag_id}:{value}"),
         EventCompiled::SetVar { var_id, value } => format!("var:{var_id}:{value}"),
+        EventCompiled::SetStrVar { str_var_id, value } => format!("str_var:{str_var_id}:{value}"),
         EventCompiled::JumpIf { cond: _, target_ip } => format!("jump_if:{target_ip}"),
         EventCompiled::Patch(_) => "patch".to_string(),
         EventCompiled::ExtCall { command, .. } => format!("ext_call:{command}"),
         EventCompiled::AudioAction(action) => format!("audio:{}:{}", action.action, action.channel),
         EventCompiled::Transition(trans) => format!("transition:{}", trans.kind),
         EventCompiled::SetCharacterPosition(pos) => format!("placement:{}", pos.name),
+        EventCompiled::InputText { str_var_id, .. } => format!("input_text:{str_var_id}"),
+        EventCompiled::UnlockGallery { id } => format!("unlock_gallery:{id}"),
+        EventCompiled::Achieve { id } => format!("achieve:{id}"),
     }
 }
 
@@ -440,11 +511,17 @@ fn event_signature_raw(event: &EventRaw) -> String {
         EventRaw::Jump { target } => format!("jump:{target}"),
         EventRaw::SetFlag { key, value } => format!("flag:{key}:{value}"),
         EventRaw::SetVar { key, value } => format!("var:{key}:{value}"),
+        EventRaw::SetStrVar { key, value } => format!("str_var:{key}:{value}"),
         EventRaw::JumpIf { .. } => "jump_if".to_string(),
         EventRaw::Patch(_) => "patch".to_string(),
         EventRaw::ExtCall { command, .. } => format!("ext_call:{command}"),
         EventRaw::AudioAction(action) => format!("audio:{}:{}", action.action, action.channel),
         EventRaw::Transition(trans) => format!("transition:{}", trans.kind),
         EventRaw::SetCharacterPosition(pos) => format!("placement:{}", pos.name),
+        EventRaw::InputText { var_key, .. } => format!("input_text:{var_key}"),
+        EventRaw::UnlockGallery { id } => format!("unlock_gallery:{id}"),
+        EventRaw::Achieve { id } => format!("achieve:{id}"),
+        EventRaw::Include { path } => format!("include:{path}"),
+        EventRaw::Expand { name, .. } => format!("expand:{name}"),
     }
 }
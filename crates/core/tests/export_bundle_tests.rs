@@ -1,3 +1,5 @@
+#![cfg(feature = "native")]
+
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use visual_novel_engine::{
     AssetId, AudioActionRaw, CharacterPatchRaw, CharacterPlacementRaw, Engine, EventRaw,
@@ -46,6 +46,77 @@ fn ext_call_requires_resume_to_advance() {
     ));
 }
 
+#[test]
+fn ext_call_allowlist_denies_unlisted_commands() {
+    let script = ScriptRaw::new(
+        vec![EventRaw::ExtCall {
+            command: "minigame_start".to_string(),
+            args: Vec::new(),
+        }],
+        BTreeMap::from([("start".to_string(), 0)]),
+    );
+    let policy_for = |allowed: &str| SecurityPolicy {
+        ext_call_allowlist: Some(HashSet::from([allowed.to_string()])),
+        ..SecurityPolicy::default()
+    };
+
+    let mut denied = Engine::new(
+        script.clone(),
+        policy_for("open_shop"),
+        visual_novel_engine::ResourceLimiter::default(),
+    )
+    .unwrap();
+    let err = denied.step().expect_err("unlisted command must be denied");
+    assert!(matches!(
+        err,
+        visual_novel_engine::VnError::ExtCallDenied { ref command } if command == "minigame_start"
+    ));
+    assert!(denied.resume().is_err());
+    assert_eq!(denied.state().position, 0);
+
+    let mut allowed = Engine::new(
+        script,
+        policy_for("minigame_start"),
+        visual_novel_engine::ResourceLimiter::default(),
+    )
+    .unwrap();
+    allowed.step().unwrap();
+    allowed.resume().unwrap();
+    assert!(matches!(
+        allowed.current_event(),
+        Err(visual_novel_engine::VnError::EndOfScript)
+    ));
+}
+
+#[test]
+fn peek_next_reports_ext_call_denial_like_step() {
+    let script = ScriptRaw::new(
+        vec![EventRaw::ExtCall {
+            command: "minigame_start".to_string(),
+            args: Vec::new(),
+        }],
+        BTreeMap::from([("start".to_string(), 0)]),
+    );
+    let policy = SecurityPolicy {
+        ext_call_allowlist: Some(HashSet::from(["open_shop".to_string()])),
+        ..SecurityPolicy::default()
+    };
+    let mut engine = Engine::new(
+        script,
+        policy,
+        visual_novel_engine::ResourceLimiter::default(),
+    )
+    .unwrap();
+
+    let peeked = engine.peek_next().expect_err("peek must deny like step");
+    assert!(matches!(
+        peeked,
+        visual_novel_engine::VnError::ExtCallDenied { ref command } if command == "minigame_start"
+    ));
+    let stepped = engine.step().expect_err("step denies the command");
+    assert_eq!(peeked.to_string(), stepped.to_string());
+}
+
 #[test]
 fn peek_next_assets_collects_scene_assets() {
     let events = vec![EventRaw::Scene(SceneUpdateRaw {
@@ -58,7 +129,11 @@ fn peek_next_assets_collects_scene_assets() {
             x: None,
             y: None,
             scale: None,
+            z: None,
+            flip_x: false,
         }],
+        chapter: None,
+        tags: Vec::new(),
     })];
     let mut labels = BTreeMap::new();
     labels.insert("start".to_string(), 0);
@@ -90,7 +165,11 @@ fn peek_next_asset_paths_returns_prefetchable_paths_only() {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Patch(ScenePatchRaw {
             background: None,
@@ -102,11 +181,14 @@ fn peek_next_asset_paths_returns_prefetchable_paths_only() {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
             update: vec![CharacterPatchRaw {
                 name: "Ava".to_string(),
                 expression: Some("sprites/ava_focus.png".to_string()),
                 position: None,
+                z: None,
             }],
             remove: Vec::new(),
         }),
@@ -1,11 +1,105 @@
+use std::fs;
+use std::path::Path;
+
+use miette::Diagnostic;
+use visual_novel_engine::{
+    Engine, EventCompiled, ResourceLimiter, ScriptRaw, SecurityPolicy, VnError, VnResult,
+};
+
+const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fuzz_corpus");
+
+/// Plays at most `max_steps` events, picking `step % len` at each choice,
+/// resolving auto choices by weight, answering text inputs and resuming ext
+/// calls. Option-less choices are stepped so their fallthrough runs. Stops
+/// cleanly at the end; any other failure must surface as a `VnError`, never a
+/// panic.
+fn run_bounded(engine: &mut Engine, max_steps: usize) -> VnResult<usize> {
+    for step in 0..max_steps {
+        let event = match engine.current_event() {
+            Ok(event) => event,
+            Err(VnError::EndOfScript) => return Ok(step),
+            Err(err) => return Err(err),
+        };
+        match event {
+            EventCompiled::Choice(choice) if choice.auto => {
+                engine.auto_choose_weighted()?;
+            }
+            EventCompiled::Choice(choice) if !choice.options.is_empty() => {
+                engine.choose(step % choice.options.len())?;
+            }
+            EventCompiled::ExtCall { .. } => engine.resume()?,
+            EventCompiled::InputText { .. } => engine.submit_text("fuzz")?,
+            _ => {
+                engine.step()?;
+            }
+        }
+    }
+    Ok(max_steps)
+}
+
+/// Loads, boots and plays one script, returning the first typed error.
+fn load_and_run(script: ScriptRaw, max_steps: usize) -> VnResult<usize> {
+    let mut engine = Engine::new(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )?;
+    run_bounded(&mut engine, max_steps)
+}
+
+/// Expected result of each seed: the steps played, or the diagnostic code of
+/// the typed error it must be rejected with.
+const SEED_OUTCOMES: &[(&str, Result<usize, &str>)] = &[
+    ("empty_choice.json", Err("vn.invalid_script")),
+    ("extreme_visuals.json", Ok(6)),
+    // Fallthrough, input cut to "fuz", failed str_eq, weighted-out auto choice.
+    ("fallthrough_and_input.json", Ok(5)),
+    ("label_past_end.json", Err("vn.invalid_script")),
+    ("macro_misuse.json", Err("vn.invalid_script")),
+    ("self_loop.json", Ok(256)),
+    ("var_id_bounds.json", Ok(5)),
+];
+
+#[test]
+fn fuzz_seed_corpus_matches_expected_outcomes() {
+    let mut names: Vec<String> = fs::read_dir(Path::new(CORPUS_DIR))
+        .expect("seed corpus directory should exist")
+        .map(|entry| entry.expect("corpus entry should be readable").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    let expected: Vec<&str> = SEED_OUTCOMES.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, expected, "every seed needs an expected outcome");
+
+    for (name, expected) in SEED_OUTCOMES {
+        let json = fs::read_to_string(Path::new(CORPUS_DIR).join(name))
+            .expect("corpus script should be readable");
+        let outcome = ScriptRaw::from_json(&json).and_then(|script| load_and_run(script, 256));
+        let code = outcome
+            .as_ref()
+            .map(|steps| *steps)
+            .map_err(|err| err.code().map(|code| code.to_string()).unwrap_or_default());
+        assert_eq!(
+            code.as_ref().map_err(String::as_str),
+            expected.as_ref().map_err(|code| *code),
+            "{name}: {outcome:?}"
+        );
+    }
+}
+
 #[cfg(feature = "arbitrary")]
 mod fuzz {
+    use std::collections::BTreeMap;
+
     use arbitrary::{Arbitrary, Unstructured};
     use visual_novel_engine::{
         Engine, EventCompiled, EventRaw, ResourceLimiter, ScriptCompiled, ScriptRaw,
         SecurityPolicy, VnError,
     };
 
+    use super::load_and_run;
+
     fn fill_deterministic(buf: &mut [u8], seed: u64) {
         let mut state = seed;
         for byte in buf.iter_mut() {
@@ -60,12 +154,18 @@ mod fuzz {
             EventRaw::Jump { .. } => "jump",
             EventRaw::SetFlag { .. } => "set_flag",
             EventRaw::SetVar { .. } => "set_var",
+            EventRaw::SetStrVar { .. } => "set_str_var",
             EventRaw::JumpIf { .. } => "jump_if",
             EventRaw::Patch(_) => "patch",
             EventRaw::ExtCall { .. } => "ext_call",
             EventRaw::AudioAction(_) => "audio_action",
             EventRaw::Transition(_) => "transition",
             EventRaw::SetCharacterPosition(_) => "set_character_position",
+            EventRaw::InputText { .. } => "input_text",
+            EventRaw::UnlockGallery { .. } => "unlock_gallery",
+            EventRaw::Achieve { .. } => "achieve",
+            EventRaw::Include { .. } => "include",
+            EventRaw::Expand { .. } => "expand",
         }
     }
 
@@ -92,6 +192,8 @@ mod fuzz {
                         assert_eq!(decoded.labels, compiled.labels);
                         assert_eq!(decoded.start_ip, compiled.start_ip);
                         assert_eq!(decoded.flag_count, compiled.flag_count);
+                        assert_eq!(decoded.flag_names, compiled.flag_names);
+                        assert_eq!(decoded.var_names, compiled.var_names);
 
                         let bytes_again = compiled
                             .to_binary()
@@ -172,4 +274,54 @@ mod fuzz {
             }
         }
     }
+
+    /// Rewrites a random script so it usually compiles: `start` points at the
+    /// first event, every event gets a label, and unknown targets are sent to
+    /// one of those labels. The engine then gets past loading and actually runs.
+    fn repair_targets(script: &mut ScriptRaw) {
+        let count = script.events.len();
+        script.labels.retain(|_, ip| *ip < count);
+        if count == 0 {
+            return;
+        }
+        script.labels.insert("start".to_string(), 0);
+        for ip in 0..count {
+            script.labels.insert(format!("e{ip}"), ip);
+        }
+        let labels: BTreeMap<String, usize> = script.labels.clone();
+        let fix = |target: &mut String| {
+            if !labels.contains_key(target.as_str()) {
+                *target = format!("e{}", target.len() % count);
+            }
+        };
+        for event in &mut script.events {
+            match event {
+                EventRaw::Jump { target } | EventRaw::JumpIf { target, .. } => fix(target),
+                EventRaw::Choice(choice) => {
+                    for option in &mut choice.options {
+                        fix(&mut option.target);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_engine_runs_random_scripts_to_completion() {
+        let mut raw_data = [0u8; 1024 * 32];
+
+        for i in 0..128u64 {
+            fill_deterministic(&mut raw_data, 0xF0_22ED_u64 ^ (i << 3));
+            let mut u = Unstructured::new(&raw_data);
+            let Ok(mut script) = ScriptRaw::arbitrary(&mut u) else {
+                continue;
+            };
+            if i % 2 == 0 {
+                repair_targets(&mut script);
+            }
+            // Any outcome is acceptable as long as failures are typed errors.
+            let _ = load_and_run(script, 256);
+        }
+    }
 }
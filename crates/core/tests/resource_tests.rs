@@ -47,3 +47,69 @@ fn test_oversized_invalid_json_rejects_before_deserialize() {
     let result = ScriptRaw::from_json_with_limits(&oversized_invalid_json, limits);
     assert!(matches!(result, Err(VnError::ResourceLimit(_))));
 }
+
+#[test]
+fn budget_report_flags_near_limit_script() {
+    let limits = ResourceLimiter {
+        max_script_bytes: 100,
+        ..ResourceLimiter::default()
+    };
+    let script_json = format!(
+        r#"{{
+  "script_schema_version": "{schema}",
+  "events": [
+    {{ "type": "dialogue", "speaker": "A", "text": "{text}" }}
+  ],
+  "labels": {{ "start": 0 }}
+}}"#,
+        schema = SCRIPT_SCHEMA_VERSION,
+        text = "a".repeat(90)
+    );
+    let script = ScriptRaw::from_json_with_limits(&script_json, ResourceLimiter::default())
+        .expect("script should parse under default limits");
+
+    let report = script.budget_report(&limits);
+    assert_eq!(report.string_bytes.used, "start".len() + 1 + 90);
+    assert!(report.string_bytes.utilization_percent() > 90.0);
+    assert!(!report.string_bytes.exceeded());
+    assert_eq!(report.events.used, 1);
+    assert_eq!(report.longest_text.used, 90);
+    assert_eq!(report.label_count, 1);
+    assert!(report.max_utilization_percent() > 90.0);
+}
+
+#[test]
+fn budget_report_counts_macros_and_aliases_like_compilation() {
+    let script_json = format!(
+        r#"{{
+  "script_schema_version": "{schema}",
+  "events": [
+    {{ "type": "expand", "macro": "greet", "args": [] }}
+  ],
+  "labels": {{ "start": 0 }},
+  "macros": {{
+    "greet": [{{ "type": "dialogue", "speaker": "A", "text": "{text}" }}]
+  }},
+  "character_aliases": {{ "alice": "{alias}" }}
+}}"#,
+        schema = SCRIPT_SCHEMA_VERSION,
+        text = "a".repeat(60),
+        alias = "b".repeat(30)
+    );
+    let script = ScriptRaw::from_json_with_limits(&script_json, ResourceLimiter::default())
+        .expect("script should parse under default limits");
+
+    for max_script_bytes in [64, 100, 128, 256] {
+        let limits = ResourceLimiter {
+            max_script_bytes,
+            ..ResourceLimiter::default()
+        };
+        let report = script.budget_report(&limits);
+        let compiled = script.ensure_string_budget(max_script_bytes);
+        assert_eq!(
+            report.string_bytes.exceeded(),
+            compiled.is_err(),
+            "report and compilation disagree at {max_script_bytes} bytes"
+        );
+    }
+}
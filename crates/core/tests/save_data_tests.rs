@@ -1,4 +1,7 @@
-use visual_novel_engine::{EngineState, SaveData, SaveError, SAVE_FORMAT_VERSION};
+use visual_novel_engine::{
+    Engine, EngineState, ResourceLimiter, SaveData, SaveError, ScriptRaw, SecurityPolicy,
+    SAVE_FORMAT_VERSION,
+};
 
 #[test]
 fn save_data_roundtrip_binary_v2() {
@@ -33,3 +36,72 @@ fn save_data_rejects_old_header_version() {
         }
     );
 }
+
+#[test]
+fn engine_metrics_count_replay_and_roundtrip_through_save() {
+    let script = ScriptRaw::from_json(
+        r#"{
+  "script_schema_version": "1.0",
+  "events": [
+    { "type": "dialogue", "speaker": "A", "text": "Hola" },
+    { "type": "choice", "prompt": "Ruta", "options": [
+      { "text": "Izquierda", "target": "left" },
+      { "text": "Derecha", "target": "right" }
+    ] },
+    { "type": "dialogue", "speaker": "A", "text": "Izquierda" },
+    { "type": "set_flag", "key": "right", "value": true },
+    { "type": "dialogue", "speaker": "A", "text": "Fin" }
+  ],
+  "labels": { "start": 0, "left": 2, "right": 3 }
+}"#,
+    )
+    .expect("parse script");
+    let limits = ResourceLimiter {
+        collect_metrics: true,
+        ..ResourceLimiter::default()
+    };
+    let mut engine = Engine::new(script.clone(), SecurityPolicy::default(), limits)
+        .expect("engine with metrics");
+
+    engine.step().expect("intro dialogue");
+    engine.step().expect("choice waits");
+    engine.choose(1).expect("pick right");
+    engine.step().expect("set flag");
+    engine.step().expect("final dialogue");
+
+    let metrics = engine.metrics().expect("metrics enabled").clone();
+    assert_eq!(metrics.events_visited, 4);
+    assert_eq!(metrics.choices_made, 1);
+    assert_eq!(metrics.visits("dialogue"), 2);
+    assert_eq!(metrics.visits("choice"), 1);
+    assert_eq!(metrics.visits("set_flag"), 1);
+    assert_eq!(metrics.visits("jump"), 0);
+
+    let save = SaveData::new([3u8; 32], engine.state().clone()).with_metrics(Some(metrics));
+    let decoded = SaveData::from_binary(&save.to_binary().expect("encode save data"))
+        .expect("decode save data");
+    assert_eq!(decoded.metrics, save.metrics);
+
+    let disabled = Engine::new(
+        script,
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine without metrics");
+    assert!(disabled.metrics().is_none());
+}
+
+#[test]
+fn save_data_roundtrip_keeps_character_opacity() {
+    let mut state = EngineState::new(3, 8);
+    state.visual.set_character_opacity("Ava", 0.25);
+    state.set_var(0, 5);
+
+    let save = SaveData::new([2u8; 32], state);
+    let encoded = save.to_binary().expect("encode save data");
+    let decoded = SaveData::from_binary(&encoded).expect("decode save data");
+
+    assert_eq!(decoded.state.visual.opacity_of("Ava"), 0.25);
+    assert_eq!(decoded.state.visual.opacity_of("Ben"), 1.0);
+    assert_eq!(decoded.state.get_var(0), 5);
+}
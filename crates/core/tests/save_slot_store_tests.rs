@@ -1,3 +1,5 @@
+#![cfg(feature = "native")]
+
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -42,3 +42,287 @@ fn script_json_accepts_legacy_major_schema_version() {
     assert!(parsed.events.is_empty());
     assert_eq!(parsed.labels.get("start"), Some(&0usize));
 }
+
+/// Draft-07 validation for the keywords `schemars` emits.
+///
+/// Panics on any other keyword so a schema change cannot silently widen what
+/// this accepts.
+fn schema_errors(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    let Some(schema) = schema.as_object() else {
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: no value is allowed"));
+        }
+        return;
+    };
+    for (keyword, rule) in schema {
+        match keyword.as_str() {
+            "$ref" => {
+                let name = rule
+                    .as_str()
+                    .and_then(|reference| reference.strip_prefix("#/definitions/"))
+                    .expect("local definition reference");
+                let target = &root["definitions"][name];
+                assert!(target.is_object(), "missing definition {name}");
+                schema_errors(root, target, value, path, errors);
+            }
+            "type" => {
+                let allowed: Vec<&str> = match rule {
+                    Value::String(kind) => vec![kind.as_str()],
+                    Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+                    _ => panic!("bad type keyword at {path}"),
+                };
+                let matches = |kind: &str| match kind {
+                    "null" => value.is_null(),
+                    "boolean" => value.is_boolean(),
+                    "object" => value.is_object(),
+                    "array" => value.is_array(),
+                    "string" => value.is_string(),
+                    "number" => value.is_number(),
+                    "integer" => value.is_i64() || value.is_u64(),
+                    other => panic!("unknown type {other}"),
+                };
+                if !allowed.iter().any(|kind| matches(kind)) {
+                    errors.push(format!("{path}: expected {allowed:?}"));
+                }
+            }
+            "enum" => {
+                if !rule.as_array().expect("enum array").contains(value) {
+                    errors.push(format!("{path}: {value} not in {rule}"));
+                }
+            }
+            "oneOf" | "anyOf" => {
+                let passing = rule
+                    .as_array()
+                    .expect("schema list")
+                    .iter()
+                    .filter(|option| {
+                        let mut nested = Vec::new();
+                        schema_errors(root, option, value, path, &mut nested);
+                        nested.is_empty()
+                    })
+                    .count();
+                let ok = if keyword == "oneOf" {
+                    passing == 1
+                } else {
+                    passing >= 1
+                };
+                if !ok {
+                    errors.push(format!("{path}: {passing} {keyword} branches match"));
+                }
+            }
+            "allOf" => {
+                for option in rule.as_array().expect("schema list") {
+                    schema_errors(root, option, value, path, errors);
+                }
+            }
+            "required" => {
+                if let Some(object) = value.as_object() {
+                    for field in rule.as_array().expect("required list") {
+                        let field = field.as_str().expect("field name");
+                        if !object.contains_key(field) {
+                            errors.push(format!("{path}: missing `{field}`"));
+                        }
+                    }
+                }
+            }
+            "properties" | "additionalProperties" => {}
+            "items" => {
+                if let Some(items) = value.as_array() {
+                    for (index, item) in items.iter().enumerate() {
+                        schema_errors(root, rule, item, &format!("{path}/{index}"), errors);
+                    }
+                }
+            }
+            "minimum" | "maximum" => {
+                if let (Some(number), Some(bound)) = (value.as_f64(), rule.as_f64()) {
+                    let ok = if keyword == "minimum" {
+                        number >= bound
+                    } else {
+                        number <= bound
+                    };
+                    if !ok {
+                        errors.push(format!("{path}: {number} violates {keyword} {bound}"));
+                    }
+                }
+            }
+            "$schema" | "$id" | "title" | "description" | "default" | "format" | "definitions" => {}
+            other if other.starts_with("x-") => {}
+            other => panic!("unsupported schema keyword `{other}` at {path}"),
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (field, item) in object {
+            let item_path = format!("{path}/{field}");
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(property) => schema_errors(root, property, item, &item_path, errors),
+                None => {
+                    if let Some(extra) = schema.get("additionalProperties") {
+                        schema_errors(root, extra, item, &item_path, errors);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn matches_schema(
+    schema: &serde_json::Value,
+    script: &serde_json::Value,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    schema_errors(schema, schema, script, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[test]
+fn json_schema_carries_version_metadata() {
+    let schema = ScriptRaw::json_schema().expect("schema");
+    assert!(schema["$schema"].as_str().is_some());
+    assert_eq!(schema["x-script-schema-version"], SCRIPT_SCHEMA_VERSION);
+    assert!(schema["$id"]
+        .as_str()
+        .is_some_and(|id| id.contains(SCRIPT_SCHEMA_VERSION)));
+    assert!(schema["properties"]["script_schema_version"].is_object());
+}
+
+#[test]
+fn json_schema_accepts_sample_and_rejects_unknown_event_type() {
+    let schema = ScriptRaw::json_schema().expect("schema");
+    let good: serde_json::Value = serde_json::from_str(
+        r#"{
+            "script_schema_version": "1.0",
+            "events": [
+                {"type": "scene", "background": "bg/room.png"},
+                {"type": "dialogue", "speaker": "Ava", "text": "Hola"},
+                {"type": "jump", "target": "start"}
+            ],
+            "labels": {"start": 0}
+        }"#,
+    )
+    .expect("good json");
+    let bad: serde_json::Value = serde_json::from_str(
+        r#"{
+            "script_schema_version": "1.0",
+            "events": [{"type": "teleport", "target": "start"}],
+            "labels": {"start": 0}
+        }"#,
+    )
+    .expect("bad json");
+
+    assert_eq!(matches_schema(&schema, &good), Ok(()));
+    assert!(matches_schema(&schema, &bad).is_err());
+
+    let missing_text = serde_json::json!({
+        "events": [{"type": "dialogue", "speaker": "Ava"}],
+        "labels": {"start": 0}
+    });
+    assert!(matches_schema(&schema, &missing_text).is_err());
+    let negative_label = serde_json::json!({
+        "events": [],
+        "labels": {"start": -1}
+    });
+    assert!(matches_schema(&schema, &negative_label).is_err());
+}
+
+#[test]
+fn strict_load_names_dangling_jump_target() {
+    let script_json = r#"{
+        "script_schema_version": "1.0",
+        "events": [
+            {"type": "dialogue", "speaker": "Ava", "text": "Hola"},
+            {"type": "jump", "target": "ending_typo"}
+        ],
+        "labels": {"start": 0, "ending": 1}
+    }"#;
+
+    assert!(ScriptRaw::from_json(script_json).is_ok());
+    let err = ScriptRaw::from_json_strict(script_json).expect_err("strict should reject typo");
+    match &err {
+        VnError::UnknownLabel {
+            event_index,
+            target,
+            src,
+            span,
+        } => {
+            assert_eq!(*event_index, 1);
+            assert_eq!(target, "ending_typo");
+            assert_eq!(
+                &src[span.offset()..span.offset() + span.len()],
+                "\"ending_typo\""
+            );
+        }
+        other => panic!("expected unknown label error, got {other:?}"),
+    }
+    assert!(err.to_string().contains("ending_typo"));
+}
+
+#[test]
+fn strict_load_points_at_the_offending_event_not_the_first_mention() {
+    let script_json = r#"{
+        "script_schema_version": "1.0",
+        "events": [
+            {"type": "dialogue", "speaker": "Ava", "text": "ending_typo"},
+            {"type": "choice", "prompt": "Go?", "options": [
+                {"text": "ending_typo", "target": "ending_typo"}
+            ]}
+        ],
+        "labels": {"start": 0, "ending": 1}
+    }"#;
+
+    let err = ScriptRaw::from_json_strict(script_json).expect_err("strict should reject typo");
+    let VnError::UnknownLabel { src, span, .. } = &err else {
+        panic!("expected unknown label error, got {err:?}");
+    };
+    let expected = script_json
+        .find(r#""target": "ending_typo""#)
+        .expect("target in source")
+        + r#""target": "#.len();
+    let window_start = script_json
+        .find(src.as_str())
+        .expect("window is a slice of input");
+    assert_eq!(window_start + span.offset(), expected);
+    assert_eq!(span.len(), "\"ending_typo\"".len());
+}
+
+#[test]
+fn strict_load_checks_choice_fallthrough_label() {
+    let script_json = r#"{
+        "script_schema_version": "1.0",
+        "events": [
+            {"type": "choice", "prompt": "Ask?", "options": [], "fallthrough": "after_typo"},
+            {"type": "dialogue", "speaker": "Ava", "text": "After"}
+        ],
+        "labels": {"start": 0, "after": 1}
+    }"#;
+
+    let err = ScriptRaw::from_json_strict(script_json).expect_err("strict should reject typo");
+    assert!(
+        matches!(&err, VnError::UnknownLabel { event_index: 0, target, .. } if target == "after_typo")
+    );
+}
+
+#[test]
+fn strict_load_rejects_missing_start_label() {
+    let script_json = r#"{
+        "script_schema_version": "1.0",
+        "events": [{"type": "dialogue", "speaker": "Ava", "text": "Hola"}],
+        "labels": {"intro": 0}
+    }"#;
+
+    let err = ScriptRaw::from_json_strict(script_json).expect_err("missing start");
+    assert!(err.to_string().contains("start"));
+}
@@ -24,3 +24,39 @@ fn compiled_script_id_is_deterministic() {
     assert_eq!(bytes_one, bytes_two);
     assert_eq!(id_one, id_two);
 }
+
+#[test]
+fn canonical_json_ignores_label_insertion_order() {
+    let first = ScriptRaw::from_json(&format!(
+        r#"{{
+            "script_schema_version": "{SCRIPT_SCHEMA_VERSION}",
+            "events": [
+                {{"type": "dialogue", "speaker": "Narrator", "text": "Hi"}},
+                {{"type": "set_character_position", "name": "Ava", "x": 1, "y": 2, "scale": 0.8}}
+            ],
+            "labels": {{"start": 0, "zeta": 1, "alpha": 1}}
+        }}"#
+    ))
+    .expect("parse first");
+    let second = ScriptRaw::from_json(
+        r#"{"labels": {"alpha": 1, "start": 0, "zeta": 1},
+            "events": [
+                {"text": "Hi", "speaker": "Narrator", "type": "dialogue"},
+                {"scale": 0.8, "y": 2, "x": 1, "name": "Ava", "type": "set_character_position"}
+            ]}"#,
+    )
+    .expect("parse second");
+
+    let canonical = first.to_canonical_json().expect("canonical first");
+    assert_eq!(
+        canonical,
+        second.to_canonical_json().expect("canonical second")
+    );
+    assert!(canonical.contains("0.8"));
+    assert!(!canonical.contains("0.800000"));
+    assert_eq!(
+        first.script_checksum().expect("checksum first"),
+        second.script_checksum().expect("checksum second")
+    );
+    assert_eq!(first.script_checksum().expect("checksum").len(), 64);
+}
@@ -0,0 +1,91 @@
+mod common;
+use common::run_headless;
+use visual_novel_engine::{EngineState, StateDigest, TraceDivergence, TraceUiView};
+
+fn script_with(second_line: &str, expression: &str) -> String {
+    format!(
+        r#"{{
+        "script_schema_version": "1.0",
+        "events": [
+            {{"type": "scene", "background": "room.png", "characters": [{{"name": "Ava", "expression": "{expression}"}}]}},
+            {{"type": "dialogue", "speaker": "Ava", "text": "Hola"}},
+            {{"type": "dialogue", "speaker": "Ava", "text": "{second_line}"}},
+            {{"type": "dialogue", "speaker": "Ava", "text": "Adios"}}
+        ],
+        "labels": {{"start": 0}}
+    }}"#
+    )
+}
+
+#[test]
+fn identical_runs_diff_empty() {
+    let script = script_with("Que tal", "calm");
+    let left = run_headless(&script, 16);
+    let right = run_headless(&script, 16);
+
+    assert!(left.diff(&right).is_empty());
+}
+
+#[test]
+fn one_event_change_diffs_at_its_step() {
+    let left = run_headless(&script_with("Que tal", "calm"), 16);
+    let right = run_headless(&script_with("Buenas", "calm"), 16);
+
+    assert_eq!(
+        left.diff(&right),
+        vec![TraceDivergence::View {
+            step: 2,
+            left: TraceUiView::Dialogue {
+                speaker: "Ava".to_string(),
+                text: "Que tal".to_string(),
+            },
+            right: TraceUiView::Dialogue {
+                speaker: "Ava".to_string(),
+                text: "Buenas".to_string(),
+            },
+        }]
+    );
+}
+
+#[test]
+fn visual_and_length_changes_are_reported() {
+    let left = run_headless(&script_with("Que tal", "calm"), 16);
+    let right = run_headless(&script_with("Que tal", "angry"), 16);
+    let divergences = left.diff(&right);
+    assert_eq!(divergences.len(), 1, "{divergences:?}");
+    assert!(matches!(
+        &divergences[0],
+        TraceDivergence::Visual { step: 0, left, right }
+            if left.characters[0].expression.as_deref() == Some("calm")
+                && right.characters[0].expression.as_deref() == Some("angry")
+    ));
+
+    let truncated = run_headless(&script_with("Que tal", "calm"), 2);
+    assert_eq!(
+        left.diff(&truncated),
+        vec![TraceDivergence::Length {
+            step: 2,
+            left_len: 4,
+            right_len: 2,
+        }]
+    );
+}
+
+#[test]
+fn one_flag_changes_the_state_digest() {
+    let mut left = EngineState::new(3, 8);
+    left.set_var(1, 7);
+    let mut right = left.clone();
+    right.set_flag(5, true);
+
+    let left = StateDigest::from_state(&left, 8);
+    let right = StateDigest::from_state(&right, 8);
+
+    assert_ne!(left, right);
+    assert_ne!(left.symbols_hash, right.symbols_hash);
+    assert_eq!(left.visual, right.visual);
+    assert_eq!(
+        StateDigest::symbols_hash_of(&Default::default(), &Default::default()),
+        0
+    );
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use visual_novel_engine::{Engine, ResourceLimiter, ScriptRaw, SecurityPolicy};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CapturedSpan {
+    name: &'static str,
+    fields: BTreeMap<String, String>,
+}
+
+#[derive(Default)]
+struct FieldCollector(BTreeMap<String, String>);
+
+impl Visit for FieldCollector {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+#[derive(Default)]
+struct SpanCapture {
+    next_id: AtomicU64,
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl Subscriber for SpanCapture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut collector = FieldCollector::default();
+        span.record(&mut collector);
+        self.spans.lock().expect("span log").push(CapturedSpan {
+            name: span.metadata().name(),
+            fields: collector.0,
+        });
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn step_and_choose_emit_one_span_each_with_kind_and_ip() {
+    let capture = SpanCapture::default();
+    let spans = Arc::clone(&capture.spans);
+
+    tracing::subscriber::with_default(capture, || {
+        let script = ScriptRaw::from_json(
+            r#"{
+  "script_schema_version": "1.0",
+  "events": [
+    { "type": "dialogue", "speaker": "A", "text": "Hola" },
+    { "type": "choice", "prompt": "Ruta", "options": [
+      { "text": "Fin", "target": "end" }
+    ] },
+    { "type": "dialogue", "speaker": "A", "text": "Fin" }
+  ],
+  "labels": { "start": 0, "end": 2 }
+}"#,
+        )
+        .expect("parse script");
+        let mut engine = Engine::new(
+            script,
+            SecurityPolicy::default(),
+            ResourceLimiter::default(),
+        )
+        .expect("engine");
+        engine.step().expect("dialogue");
+        engine.choose(0).expect("choice");
+        engine.step().expect("final dialogue");
+    });
+
+    let spans = spans.lock().expect("span log");
+    let names: Vec<&str> = spans.iter().map(|span| span.name).collect();
+    assert_eq!(
+        names,
+        [
+            "script.from_json",
+            "engine.step",
+            "engine.choose",
+            "engine.step"
+        ]
+    );
+    let steps: Vec<(&str, &str)> = spans[1..]
+        .iter()
+        .map(|span| (span.fields["ip"].as_str(), span.fields["kind"].as_str()))
+        .collect();
+    assert_eq!(
+        steps,
+        [("0", "dialogue"), ("1", "choice"), ("2", "dialogue")]
+    );
+    assert_eq!(spans[2].fields["option_index"], "0");
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use visual_novel_engine::{DialogueCompiled, EngineState, TranscriptFormat};
+
+fn line(speaker: &str, text: &str) -> DialogueCompiled {
+    DialogueCompiled {
+        speaker: Arc::from(speaker),
+        text: Arc::from(text),
+    }
+}
+
+fn state_with_history() -> EngineState {
+    let mut state = EngineState::new(0, 0);
+    state.record_dialogue(&line("Ana", "Hola, *mundo*"));
+    state.record_dialogue(&line("", "Cae la noche."));
+    state.record_dialogue(&line("Luis", "Adios"));
+    state
+}
+
+#[test]
+fn plain_transcript_writes_speaker_lines() {
+    assert_eq!(
+        state_with_history().transcript(TranscriptFormat::Plain),
+        "Ana: Hola, *mundo*\nCae la noche.\nLuis: Adios\n"
+    );
+}
+
+#[test]
+fn markdown_transcript_bolds_speakers_and_escapes_text() {
+    assert_eq!(
+        state_with_history().transcript(TranscriptFormat::Markdown),
+        "**Ana:** Hola, \\*mundo\\*\n\nCae la noche.\n\n**Luis:** Adios\n"
+    );
+}
+
+#[test]
+fn empty_history_yields_empty_transcript() {
+    let state = EngineState::new(0, 0);
+    assert_eq!(state.transcript(TranscriptFormat::Plain), "");
+    assert_eq!(state.transcript(TranscriptFormat::Markdown), "");
+}
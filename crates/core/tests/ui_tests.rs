@@ -33,12 +33,18 @@ fn ui_state_maps_choice() {
             ChoiceOptionCompiled {
                 text: shared("Yes"),
                 target_ip: 1,
+                weight: None,
+                effects: Vec::new(),
             },
             ChoiceOptionCompiled {
                 text: shared("No"),
                 target_ip: 2,
+                weight: None,
+                effects: Vec::new(),
             },
         ],
+        auto: false,
+        fallthrough_ip: None,
     });
     let ui = UiState::from_event(&event, &VisualState::default());
     assert_eq!(
@@ -62,7 +68,11 @@ fn ui_state_maps_scene_description() {
             x: None,
             y: None,
             scale: None,
+            z: None,
+            flip_x: false,
         }],
+        chapter: None,
+        tags: Vec::new(),
     });
     let ui = UiState::from_event(&event, &VisualState::default());
     match ui.view {
@@ -81,6 +91,8 @@ fn ui_state_uses_existing_visual_state_for_scene() {
         background: None,
         music: None,
         characters: Vec::new(),
+        chapter: None,
+        tags: Vec::new(),
     });
     let visual = VisualState {
         background: Some(shared("bg/forest.png")),
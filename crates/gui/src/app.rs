@@ -2,17 +2,21 @@ use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
 use eframe::egui;
-use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use visual_novel_engine::{
-    compute_script_id, Engine, ResourceLimiter, ScriptId, ScriptRaw, SecurityPolicy, UiState,
-    UiView, VnError,
+    compute_script_id, BudgetReport, Engine, EventCompiled, ResourceLimiter, ScriptId, ScriptRaw,
+    SecurityPolicy, UiView, VnError,
 };
 
-use crate::assets::{AssetManager, AssetStore, SecurityMode};
+use crate::assets::{AssetManager, AssetStore, SecurityMode, DEFAULT_MAX_TEXTURES};
+use crate::editor::PlayerSessionState;
 use crate::persist::{load_state_from, save_state_to, PersistError, UserPreferences};
-use crate::widgets::{event_kind, history_bytes};
+use crate::shortcuts::{ShortcutAction, ShortcutRegistry};
+
+mod panels;
+mod watch;
+
 #[derive(Clone, Debug, Default)]
 pub struct DisplayInfo {
     pub width: f32,
@@ -29,6 +33,9 @@ pub struct VnConfig {
     pub scale_factor: Option<f32>,
     pub assets_root: Option<PathBuf>,
     pub asset_cache_budget_mb: Option<u64>,
+    /// Most textures kept uploaded at once; defaults to
+    /// [`DEFAULT_MAX_TEXTURES`].
+    pub asset_cache_max_textures: Option<usize>,
     pub security_mode: SecurityMode,
     pub manifest_path: Option<PathBuf>,
     pub require_manifest: Option<bool>,
@@ -44,6 +51,7 @@ impl Default for VnConfig {
             scale_factor: None,
             assets_root: None,
             asset_cache_budget_mb: Some(128),
+            asset_cache_max_textures: None,
             security_mode: SecurityMode::Trusted,
             manifest_path: None,
             require_manifest: None,
@@ -71,6 +79,9 @@ impl VnConfig {
 
         let asset_cache_budget_mb = self.asset_cache_budget_mb.unwrap_or(128);
         let asset_cache_budget_bytes = (asset_cache_budget_mb * 1024 * 1024) as usize;
+        let asset_cache_max_textures = self
+            .asset_cache_max_textures
+            .unwrap_or(DEFAULT_MAX_TEXTURES);
         let assets_root = self
             .assets_root
             .clone()
@@ -88,6 +99,7 @@ impl VnConfig {
             ui_scale,
             assets_root,
             asset_cache_budget_bytes,
+            asset_cache_max_textures,
             security_mode: self.security_mode,
             manifest_path: self.manifest_path.clone(),
             require_manifest,
@@ -111,11 +123,25 @@ pub struct ResolvedConfig {
     pub ui_scale: f32,
     pub assets_root: PathBuf,
     pub asset_cache_budget_bytes: usize,
+    pub asset_cache_max_textures: usize,
     pub security_mode: SecurityMode,
     pub manifest_path: Option<PathBuf>,
     pub require_manifest: bool,
 }
 
+impl ResolvedConfig {
+    /// Builds the store every player asset load goes through, with the
+    /// configured root, security mode and manifest requirement.
+    pub fn asset_store(&self) -> Result<AssetStore, crate::assets::AssetError> {
+        AssetStore::new(
+            self.assets_root.clone(),
+            self.security_mode,
+            self.manifest_path.clone(),
+            self.require_manifest,
+        )
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GuiError {
     #[error("script error: {0}")]
@@ -132,6 +158,7 @@ pub enum GuiError {
 
 pub fn run_app(script_json: String, config: Option<VnConfig>) -> Result<(), GuiError> {
     let script = ScriptRaw::from_json(&script_json)?;
+    let budget_report = script.budget_report(&ResourceLimiter::default());
     let engine = Engine::new(
         script,
         SecurityPolicy::default(),
@@ -145,27 +172,26 @@ pub fn run_app(script_json: String, config: Option<VnConfig>) -> Result<(), GuiE
     let resolved = config.resolve(None);
     let title = resolved.title.clone();
     let options = native_options(&resolved, &preferences);
-    let asset_store = AssetStore::new(
-        resolved.assets_root.clone(),
-        resolved.security_mode,
-        resolved.manifest_path.clone(),
-        resolved.require_manifest,
-    )?;
-    let assets = AssetManager::new(asset_store, resolved.asset_cache_budget_bytes);
+    let asset_store = resolved.asset_store()?;
+    let assets = AssetManager::new(asset_store, resolved.asset_cache_budget_bytes)
+        .with_capacity(resolved.asset_cache_max_textures);
 
     eframe::run_native(
         &title,
         options,
         Box::new(move |cc| {
-            Box::new(VnApp::new(
-                engine,
-                resolved,
-                preferences,
-                preferences_path,
-                script_id,
-                assets,
-                cc,
-            ))
+            Box::new(
+                VnApp::new(
+                    engine,
+                    resolved,
+                    preferences,
+                    preferences_path,
+                    script_id,
+                    assets,
+                    cc,
+                )
+                .with_budget_report(budget_report),
+            )
         }),
     )?;
     Ok(())
@@ -193,11 +219,16 @@ struct VnApp {
     show_settings: bool,
     show_history: bool,
     show_inspector: bool,
+    show_help: bool,
+    shortcuts: ShortcutRegistry,
     last_error: Option<String>,
     assets: AssetManager,
     applied_scale: f32,
     label_jump_input: String,
     script_id: ScriptId,
+    budget_report: Option<BudgetReport>,
+    playback: PlayerSessionState,
+    watches: watch::WatchList,
 }
 
 impl VnApp {
@@ -221,11 +252,16 @@ impl VnApp {
             show_settings: false,
             show_history: false,
             show_inspector: false,
+            show_help: false,
+            shortcuts: ShortcutRegistry::default(),
             last_error: None,
             assets,
             applied_scale: 0.0,
             label_jump_input: String::new(),
             script_id,
+            budget_report: None,
+            playback: PlayerSessionState::default(),
+            watches: watch::WatchList::default(),
         };
         let scale = app.config.scale_factor * app.prefs.ui_scale;
         cc.egui_ctx.set_pixels_per_point(scale.max(0.5));
@@ -233,6 +269,11 @@ impl VnApp {
         app
     }
 
+    fn with_budget_report(mut self, report: BudgetReport) -> Self {
+        self.budget_report = Some(report);
+        self
+    }
+
     fn render_scene(&mut self, ui: &mut egui::Ui) {
         let visual = self.engine.visual_state();
         ui.group(|ui| {
@@ -271,10 +312,7 @@ impl VnApp {
     }
 
     fn render_ui(&mut self, ui: &mut egui::Ui) {
-        let view = self
-            .engine
-            .current_event()
-            .map(|event| UiState::from_event(&event, self.engine.visual_state()).view);
+        let view = self.engine.ui_state().map(|ui| ui.view);
         let view = match view {
             Ok(view) => view,
             Err(err) => {
@@ -284,13 +322,18 @@ impl VnApp {
         };
         ui.group(|ui| match view {
             UiView::Dialogue { speaker, text } => {
+                let now = ui.ctx().input(|i| i.time);
                 ui.heading(speaker);
-                ui.label(text);
+                ui.label(self.playback.visible_text(&text, now));
                 if ui.button("History").clicked() {
                     self.show_history = !self.show_history;
                 }
                 if ui.button("Continue").clicked() {
-                    self.advance();
+                    if self.playback.is_text_fully_revealed(&text, now) {
+                        self.advance();
+                    } else {
+                        self.playback.reveal_current_line(&text, now);
+                    }
                 }
             }
             UiView::Choice { prompt, options } => {
@@ -322,71 +365,24 @@ impl VnApp {
                     self.advance();
                 }
             }
-        });
-    }
-
-    fn render_history(&self, ctx: &egui::Context) {
-        if !self.show_history {
-            return;
-        }
-        egui::Window::new("History").show(ctx, |ui| {
-            for entry in &self.engine.state().history {
-                ui.label(format!("{}: {}", entry.speaker, entry.text));
-                ui.separator();
+            UiView::TextInput { prompt, max_len } => {
+                ui.heading(prompt);
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.playback.text_input)
+                        .char_limit(max_len as usize),
+                );
+                if ui.button("Confirm").clicked() {
+                    self.submit_text();
+                }
             }
         });
     }
 
-    fn render_inspector(&mut self, ctx: &egui::Context) {
-        if !self.show_inspector {
-            return;
+    fn submit_text(&mut self) {
+        let value = std::mem::take(&mut self.playback.text_input);
+        if let Err(err) = self.engine.submit_text(&value) {
+            self.last_error = Some(err.to_string());
         }
-        let event_summary = match self.engine.current_event() {
-            Ok(event) => event_kind(&event),
-            Err(err) => format!("Error: {err}"),
-        };
-        let history_bytes = history_bytes(&self.engine.state().history);
-        let dt = ctx.input(|i| i.unstable_dt);
-        let fps = if dt > 0.0 { 1.0 / dt } else { 0.0 };
-        let cache_stats = self.assets.stats();
-        egui::Window::new("Inspector").show(ctx, |ui| {
-            ui.label(format!("IP: {}", self.engine.state().position));
-            ui.label(format!("Event: {event_summary}"));
-            ui.label(format!("FPS: {:.1}", fps));
-            ui.label(format!("History bytes (approx): {}", history_bytes));
-            ui.label(format!(
-                "Texture cache: {} entries, {} MB (budget {} MB)",
-                cache_stats.entries,
-                cache_stats.bytes / (1024 * 1024),
-                cache_stats.budget_bytes / (1024 * 1024)
-            ));
-            ui.label(format!(
-                "Cache hits: {}, misses: {}, evictions: {}",
-                cache_stats.hits, cache_stats.misses, cache_stats.evictions
-            ));
-            ui.separator();
-            ui.label("Flags:");
-            let flag_count = self.engine.flag_count();
-            for flag_id in 0..flag_count {
-                let mut value = self.engine.state().get_flag(flag_id);
-                if ui.checkbox(&mut value, format!("flag {flag_id}")).changed() {
-                    self.engine.set_flag(flag_id, value);
-                }
-            }
-            ui.separator();
-            ui.label("Jump to label:");
-            ui.text_edit_singleline(&mut self.label_jump_input);
-            if ui.button("Jump").clicked() {
-                if let Err(err) = self.engine.jump_to_label(&self.label_jump_input) {
-                    self.last_error = Some(err.to_string());
-                }
-            }
-            ui.separator();
-            ui.label("Available labels:");
-            for label in self.engine.labels().keys() {
-                ui.label(label);
-            }
-        });
     }
 
     fn advance(&mut self) {
@@ -414,8 +410,34 @@ impl VnApp {
         ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.prefs.fullscreen));
     }
 
+    /// Keeps the reveal/auto-advance state in sync with preferences and the
+    /// engine position, stepping when an auto-advance is due.
+    fn update_playback(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.playback.text_chars_per_second = self.prefs.text_speed;
+        self.playback.autoplay_enabled = self.prefs.auto_advance;
+        self.playback
+            .on_position_changed(self.engine.state().position, now);
+        let Ok(EventCompiled::Dialogue(dialogue)) = self.engine.current_event() else {
+            return;
+        };
+        if !self.playback.is_text_fully_revealed(&dialogue.text, now) {
+            ctx.request_repaint();
+            return;
+        }
+        if !self.prefs.auto_advance {
+            return;
+        }
+        if self.playback.autoplay_ready(&self.engine, now) {
+            self.playback.mark_auto_step(now);
+            self.advance();
+        }
+        ctx.request_repaint();
+    }
+
     fn save_state(&mut self, path: &Path) {
-        let data = visual_novel_engine::SaveData::new(self.script_id, self.engine.state().clone());
+        let data = visual_novel_engine::SaveData::new(self.script_id, self.engine.state().clone())
+            .with_metrics(self.engine.metrics().cloned());
         if let Err(err) = save_state_to(path, &data) {
             self.last_error = Some(format!("Failed to save state: {err}"));
         }
@@ -430,12 +452,25 @@ impl VnApp {
                 }
                 if let Err(err) = self.engine.set_state(data.state) {
                     self.last_error = Some(format!("Failed to load state: {err}"));
+                    return;
+                }
+                if let Some(metrics) = data.metrics {
+                    self.engine.restore_metrics(metrics);
                 }
             }
             Err(err) => self.last_error = Some(format!("Failed to load state: {err}")),
         }
     }
 
+    fn apply_shortcut(&mut self, action: ShortcutAction) {
+        match action {
+            ShortcutAction::ToggleSettings => self.show_settings = !self.show_settings,
+            ShortcutAction::ToggleHistory => self.show_history = !self.show_history,
+            ShortcutAction::ToggleInspector => self.show_inspector = !self.show_inspector,
+            ShortcutAction::ToggleHelp => self.show_help = !self.show_help,
+        }
+    }
+
     fn persist_preferences(&self) {
         if let Err(err) = self.prefs.save_to(&self.prefs_path) {
             eprintln!("Failed to save GUI preferences: {err}");
@@ -445,14 +480,14 @@ impl VnApp {
 
 impl eframe::App for VnApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            self.show_settings = !self.show_settings;
-        }
-        if ctx.input(|i| i.key_pressed(egui::Key::F12)) {
-            self.show_inspector = !self.show_inspector;
+        if !ctx.wants_keyboard_input() {
+            for action in ctx.input(|i| self.shortcuts.pressed(i)) {
+                self.apply_shortcut(action);
+            }
         }
 
         self.apply_preferences(ctx);
+        self.update_playback(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading(&self.config.title);
@@ -466,36 +501,9 @@ impl eframe::App for VnApp {
             }
         });
 
-        if self.show_settings {
-            let mut dirty = false;
-            egui::Window::new("Settings").show(ctx, |ui| {
-                dirty |= ui
-                    .checkbox(&mut self.prefs.fullscreen, "Fullscreen")
-                    .changed();
-                dirty |= ui
-                    .checkbox(&mut self.prefs.vsync, "VSync (restart required)")
-                    .changed();
-                dirty |= ui
-                    .add(egui::Slider::new(&mut self.prefs.ui_scale, 0.75..=2.0).text("UI Scale"))
-                    .changed();
-                if ui.button("Save State").clicked() {
-                    if let Some(path) = FileDialog::new().set_title("Save State").save_file() {
-                        self.save_state(&path);
-                    }
-                }
-                if ui.button("Load State").clicked() {
-                    if let Some(path) = FileDialog::new().set_title("Load State").pick_file() {
-                        self.load_state(&path);
-                    }
-                }
-            });
-
-            if dirty {
-                self.persist_preferences();
-            }
-        }
-
+        self.render_settings(ctx);
         self.render_history(ctx);
         self.render_inspector(ctx);
+        self.render_help(ctx);
     }
 }
@@ -0,0 +1,198 @@
+//! Auxiliary windows of the player: history, inspector, settings and help.
+
+use std::path::Path;
+
+use eframe::egui;
+use rfd::FileDialog;
+use visual_novel_engine::TranscriptFormat;
+
+use crate::widgets::{event_kind, history_bytes};
+
+use super::VnApp;
+
+impl VnApp {
+    pub(super) fn render_history(&mut self, ctx: &egui::Context) {
+        if !self.show_history {
+            return;
+        }
+        let mut export_clicked = false;
+        egui::Window::new("History").show(ctx, |ui| {
+            export_clicked = ui.button("Export Transcript").clicked();
+            let history = &self.engine.state().history;
+            if history.is_empty() {
+                ui.label("No dialogue yet.");
+            }
+            for entry in history {
+                ui.label(format!("{}: {}", entry.speaker, entry.text));
+                ui.separator();
+            }
+        });
+        if export_clicked {
+            if let Some(path) = FileDialog::new()
+                .set_title("Export Transcript")
+                .add_filter("Text", &["txt"])
+                .add_filter("Markdown", &["md"])
+                .set_file_name("transcript.txt")
+                .save_file()
+            {
+                self.export_transcript(&path);
+            }
+        }
+    }
+
+    /// Writes the history to `path`, as Markdown when the extension is `.md`.
+    fn export_transcript(&mut self, path: &Path) {
+        let is_markdown = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+        let format = if is_markdown {
+            TranscriptFormat::Markdown
+        } else {
+            TranscriptFormat::Plain
+        };
+        let transcript = self.engine.state().transcript(format);
+        if let Err(err) = std::fs::write(path, transcript) {
+            self.last_error = Some(format!("Failed to export transcript: {err}"));
+        }
+    }
+
+    pub(super) fn render_inspector(&mut self, ctx: &egui::Context) {
+        if !self.show_inspector {
+            return;
+        }
+        let event_summary = match self.engine.current_event() {
+            Ok(event) => event_kind(&event),
+            Err(err) => format!("Error: {err}"),
+        };
+        let history_bytes = history_bytes(&self.engine.state().history);
+        let dt = ctx.input(|i| i.unstable_dt);
+        let fps = if dt > 0.0 { 1.0 / dt } else { 0.0 };
+        let cache_stats = self.assets.stats();
+        egui::Window::new("Inspector").show(ctx, |ui| {
+            ui.label(format!("IP: {}", self.engine.state().position));
+            ui.label(format!("Event: {event_summary}"));
+            ui.label(format!("FPS: {:.1}", fps));
+            ui.label(format!("History bytes (approx): {}", history_bytes));
+            ui.label(format!(
+                "Texture cache: {} entries, {} MB (budget {} MB)",
+                cache_stats.entries,
+                cache_stats.bytes / (1024 * 1024),
+                cache_stats.budget_bytes / (1024 * 1024)
+            ));
+            ui.label(format!(
+                "Cache hits: {}, misses: {}, evictions: {}",
+                cache_stats.hits, cache_stats.misses, cache_stats.evictions
+            ));
+            if let Some(report) = &self.budget_report {
+                ui.separator();
+                ui.label("Script budget:");
+                for (name, entry) in report.entries() {
+                    let text = format!(
+                        "{name}: {} / {} ({:.1}%)",
+                        entry.used,
+                        entry.limit,
+                        entry.utilization_percent()
+                    );
+                    if entry.utilization_percent() >= 90.0 {
+                        ui.colored_label(egui::Color32::YELLOW, text);
+                    } else {
+                        ui.label(text);
+                    }
+                }
+            }
+            ui.separator();
+            ui.label("Flags:");
+            let flag_count = self.engine.flag_count();
+            for flag_id in 0..flag_count {
+                let mut value = self.engine.state().get_flag(flag_id);
+                let label = match self.engine.flag_name(flag_id) {
+                    Some(name) => name.to_string(),
+                    None => format!("flag {flag_id}"),
+                };
+                if ui.checkbox(&mut value, label).changed() {
+                    self.engine.set_flag(flag_id, value);
+                }
+            }
+            ui.separator();
+            self.render_watches(ui);
+            ui.separator();
+            ui.label("Jump to label:");
+            ui.text_edit_singleline(&mut self.label_jump_input);
+            if ui.button("Jump").clicked() {
+                if let Err(err) = self.engine.goto_label(&self.label_jump_input) {
+                    self.last_error = Some(err.to_string());
+                }
+            }
+            ui.separator();
+            ui.label("Available labels:");
+            for label in self.engine.labels().keys() {
+                ui.label(label);
+            }
+        });
+    }
+
+    pub(super) fn render_settings(&mut self, ctx: &egui::Context) {
+        if !self.show_settings {
+            return;
+        }
+        let mut dirty = false;
+        egui::Window::new("Settings").show(ctx, |ui| {
+            dirty |= ui
+                .checkbox(&mut self.prefs.fullscreen, "Fullscreen")
+                .changed();
+            dirty |= ui
+                .checkbox(&mut self.prefs.vsync, "VSync (restart required)")
+                .changed();
+            dirty |= ui
+                .add(egui::Slider::new(&mut self.prefs.ui_scale, 0.75..=2.0).text("UI Scale"))
+                .changed();
+            dirty |= ui
+                .add(
+                    egui::Slider::new(&mut self.prefs.text_speed, 0.0..=240.0)
+                        .text("Text speed (chars/s, 0 = instant)"),
+                )
+                .changed();
+            dirty |= ui
+                .checkbox(&mut self.prefs.auto_advance, "Auto-advance")
+                .changed();
+            if ui.button("Save State").clicked() {
+                if let Some(path) = FileDialog::new().set_title("Save State").save_file() {
+                    self.save_state(&path);
+                }
+            }
+            if ui.button("Load State").clicked() {
+                if let Some(path) = FileDialog::new().set_title("Load State").pick_file() {
+                    self.load_state(&path);
+                }
+            }
+        });
+
+        if dirty {
+            self.persist_preferences();
+        }
+    }
+
+    /// Lists every registered shortcut; clicking a row runs its action.
+    pub(super) fn render_help(&mut self, ctx: &egui::Context) {
+        if !self.show_help {
+            return;
+        }
+        let mut triggered = None;
+        egui::Window::new("Keyboard Shortcuts").show(ctx, |ui| {
+            egui::Grid::new("shortcut_help")
+                .striped(true)
+                .show(ui, |ui| {
+                    for shortcut in self.shortcuts.entries() {
+                        if ui.button(shortcut.key.name()).clicked() {
+                            triggered = Some(shortcut.action);
+                        }
+                        ui.label(shortcut.description);
+                        ui.end_row();
+                    }
+                });
+        });
+        if let Some(action) = triggered {
+            self.apply_shortcut(action);
+        }
+    }
+}
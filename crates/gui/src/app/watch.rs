@@ -0,0 +1,121 @@
+//! Watch expressions shown in the inspector.
+
+use eframe::egui;
+use visual_novel_engine::Engine;
+
+use super::VnApp;
+
+/// Current value of a watched flag or variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WatchValue {
+    Flag(bool),
+    Var(i32),
+}
+
+impl WatchValue {
+    /// Resolves `name` through the script symbol tables, flags first.
+    fn resolve(engine: &Engine, name: &str) -> Option<Self> {
+        if let Some(id) = engine.flag_id(name) {
+            return Some(Self::Flag(engine.state().get_flag(id)));
+        }
+        engine
+            .var_id(name)
+            .map(|id| Self::Var(engine.state().get_var(id)))
+    }
+
+    fn display(self) -> String {
+        match self {
+            Self::Flag(value) => value.to_string(),
+            Self::Var(value) => value.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct WatchEntry {
+    name: String,
+    value: Option<WatchValue>,
+    /// Value as of the previous step, used to highlight changes.
+    baseline: Option<WatchValue>,
+}
+
+impl WatchEntry {
+    fn changed(&self) -> bool {
+        self.value != self.baseline
+    }
+}
+
+/// Flag/variable names the author is watching, with their last values.
+#[derive(Clone, Debug, Default)]
+pub(super) struct WatchList {
+    entries: Vec<WatchEntry>,
+    input: String,
+    last_position: Option<u32>,
+}
+
+impl WatchList {
+    fn add(&mut self, engine: &Engine) {
+        let name = self.input.trim().to_string();
+        self.input.clear();
+        if name.is_empty() || self.entries.iter().any(|entry| entry.name == name) {
+            return;
+        }
+        let value = WatchValue::resolve(engine, &name);
+        self.entries.push(WatchEntry {
+            name,
+            value,
+            baseline: value,
+        });
+    }
+
+    /// Re-reads every watched value; a new engine position starts a new step.
+    fn refresh(&mut self, engine: &Engine) {
+        let position = engine.state().position;
+        let stepped = self.last_position != Some(position);
+        self.last_position = Some(position);
+        for entry in &mut self.entries {
+            if stepped {
+                entry.baseline = entry.value;
+            }
+            entry.value = WatchValue::resolve(engine, &entry.name);
+        }
+    }
+}
+
+impl VnApp {
+    pub(super) fn render_watches(&mut self, ui: &mut egui::Ui) {
+        self.watches.refresh(&self.engine);
+        ui.label("Watch:");
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut self.watches.input);
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                self.watches.add(&self.engine);
+            }
+        });
+        let mut remove = None;
+        for (index, entry) in self.watches.entries.iter().enumerate() {
+            ui.horizontal(|ui| {
+                match entry.value {
+                    Some(value) => {
+                        let text = format!("{} = {}", entry.name, value.display());
+                        if entry.changed() {
+                            ui.colored_label(egui::Color32::YELLOW, text);
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                    None => {
+                        ui.colored_label(egui::Color32::GRAY, format!("{}: unknown", entry.name));
+                    }
+                }
+                if ui.small_button("x").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.watches.entries.remove(index);
+        }
+    }
+}
@@ -13,6 +13,9 @@ pub struct CacheStats {
     pub budget_bytes: usize,
 }
 
+/// Texture count cap used unless [`AssetManager::with_capacity`] overrides it.
+pub const DEFAULT_MAX_TEXTURES: usize = 64;
+
 struct CachedTexture {
     texture: egui::TextureHandle,
     bytes: usize,
@@ -23,6 +26,7 @@ pub struct AssetManager {
     store: AssetStore,
     cache: HashMap<String, CachedTexture>,
     budget_bytes: usize,
+    max_textures: usize,
     current_bytes: usize,
     usage_counter: u64,
     stats: CacheStats,
@@ -38,12 +42,20 @@ impl AssetManager {
             store,
             cache: HashMap::new(),
             budget_bytes,
+            max_textures: DEFAULT_MAX_TEXTURES,
             current_bytes: 0,
             usage_counter: 0,
             stats,
         }
     }
 
+    /// Caps how many textures stay uploaded; the least recently requested
+    /// one is dropped first, on top of the byte budget.
+    pub fn with_capacity(mut self, max_textures: usize) -> Self {
+        self.max_textures = max_textures.max(1);
+        self
+    }
+
     pub fn stats(&self) -> CacheStats {
         CacheStats {
             entries: self.cache.len(),
@@ -75,7 +87,9 @@ impl AssetManager {
             });
         }
         self.stats.misses += 1;
-        while self.current_bytes + bytes > self.budget_bytes {
+        while self.current_bytes + bytes > self.budget_bytes
+            || self.cache.len() >= self.max_textures
+        {
             if !self.evict_lru() {
                 break;
             }
@@ -157,4 +171,74 @@ mod tests {
         assert_eq!(stats_after_second.hits, 1);
         assert_eq!(first.id(), second.id());
     }
+
+    #[test]
+    fn untrusted_mode_rejects_background_outside_root() {
+        let tmp = tempfile::tempdir().expect("temp dir");
+        let root = tmp.path().join("game");
+        std::fs::create_dir_all(root.join("bg")).expect("asset dir");
+        write_png(&tmp.path().join("secret.png"));
+
+        let store =
+            AssetStore::new(root, SecurityMode::Untrusted, None, false).expect("asset store");
+        let mut manager = AssetManager::new(store, 8 * 1024 * 1024);
+        let ctx = egui::Context::default();
+
+        let result = manager.texture_for_asset(&ctx, "../secret.png");
+        assert!(result.is_err(), "out-of-root path must not load");
+        let stats = manager.stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.misses, 0);
+    }
+
+    #[test]
+    fn capacity_evicts_least_recently_used_texture() {
+        let tmp = tempfile::tempdir().expect("temp dir");
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("bg")).expect("asset dir");
+        for name in ["a", "b", "c"] {
+            write_png(&root.join(format!("bg/{name}.png")));
+        }
+
+        let store = AssetStore::new(root.to_path_buf(), SecurityMode::Trusted, None, false)
+            .expect("asset store");
+        let mut manager = AssetManager::new(store, 8 * 1024 * 1024).with_capacity(2);
+        let ctx = egui::Context::default();
+        let mut load = |path: &str| {
+            manager
+                .texture_for_asset(&ctx, path)
+                .expect("lookup")
+                .expect("texture should load")
+                .id()
+        };
+
+        let first_a = load("bg/a.png");
+        load("bg/b.png");
+        load("bg/a.png");
+        load("bg/c.png");
+        let stats = manager.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.misses, 3);
+
+        // `b` was least recently used, so `a` is still a hit.
+        assert_eq!(
+            manager
+                .texture_for_asset(&ctx, "bg/a.png")
+                .unwrap()
+                .unwrap()
+                .id(),
+            first_a
+        );
+        assert_eq!(manager.stats().misses, 3);
+
+        // Re-requesting the evicted path uploads it again.
+        let _reloaded = manager
+            .texture_for_asset(&ctx, "bg/b.png")
+            .expect("reload b")
+            .expect("texture should load");
+        let stats = manager.stats();
+        assert_eq!(stats.misses, 4);
+        assert_eq!(stats.evictions, 2);
+    }
 }
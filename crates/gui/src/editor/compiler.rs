@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use crate::editor::{
@@ -6,128 +6,16 @@ use crate::editor::{
     script_sync,
     validator::{self, LintCode, LintIssue, LintSeverity, ValidationPhase},
 };
-use visual_novel_engine::{Engine, ScriptRaw, StoryGraph};
-
-const DRY_RUN_MAX_STEPS: usize = 2048;
-const DRY_RUN_EXHAUSTIVE_ROUTE_LIMIT: usize = 32;
-const DRY_RUN_EXHAUSTIVE_CHOICE_DEPTH: usize = 12;
-const REPRO_DEFAULT_RADIUS: usize = 12;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum ChoiceStrategy {
-    First,
-    Last,
-    Alternating,
-}
-
-impl ChoiceStrategy {
-    fn label(self) -> &'static str {
-        match self {
-            ChoiceStrategy::First => "first",
-            ChoiceStrategy::Last => "last",
-            ChoiceStrategy::Alternating => "alternating",
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum ChoicePolicy {
-    Strategy(ChoiceStrategy),
-    Scripted(Vec<usize>),
-}
-
-impl ChoicePolicy {
-    fn label(&self) -> String {
-        match self {
-            ChoicePolicy::Strategy(strategy) => strategy.label().to_string(),
-            ChoicePolicy::Scripted(path) => {
-                let route = path
-                    .iter()
-                    .map(std::string::ToString::to_string)
-                    .collect::<Vec<_>>()
-                    .join(".");
-                format!("scripted({route})")
-            }
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum CompilationPhase {
-    GraphSync,
-    GraphValidation,
-    ScriptCompile,
-    RuntimeInit,
-    DryRun,
-}
-
-impl CompilationPhase {
-    pub fn label(self) -> &'static str {
-        match self {
-            CompilationPhase::GraphSync => "GRAPH_SYNC",
-            CompilationPhase::GraphValidation => "GRAPH_VALIDATION",
-            CompilationPhase::ScriptCompile => "SCRIPT_COMPILE",
-            CompilationPhase::RuntimeInit => "RUNTIME_INIT",
-            CompilationPhase::DryRun => "DRY_RUN",
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct PhaseTrace {
-    pub phase: CompilationPhase,
-    pub ok: bool,
-    pub detail: String,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DryRunStopReason {
-    Finished,
-    StepLimit,
-    RuntimeError,
-}
-
-impl DryRunStopReason {
-    pub fn label(self) -> &'static str {
-        match self {
-            DryRunStopReason::Finished => "finished",
-            DryRunStopReason::StepLimit => "step_limit",
-            DryRunStopReason::RuntimeError => "runtime_error",
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct DryRunStepTrace {
-    pub step: usize,
-    pub event_ip: u32,
-    pub event_kind: String,
-    pub event_signature: String,
-    pub visual_background: Option<String>,
-    pub visual_music: Option<String>,
-    pub character_count: usize,
-}
-
-#[derive(Debug, Clone)]
-pub struct DryRunReport {
-    pub max_steps: usize,
-    pub executed_steps: usize,
-    pub stop_reason: DryRunStopReason,
-    pub stop_message: String,
-    pub failing_event_ip: Option<u32>,
-    pub steps: Vec<DryRunStepTrace>,
-}
-
-impl DryRunReport {
-    pub fn first_event_ip(&self) -> Option<u32> {
-        self.steps.first().map(|step| step.event_ip)
-    }
+use visual_novel_engine::analysis::{
+    analyze_script_with_config, run_dry_run_with_config, AnalysisIssue, AnalysisIssueKind,
+    AnalysisSeverity, ChoicePolicy, ChoiceStrategy,
+};
+use visual_novel_engine::{Engine, ScriptRaw};
 
-    pub fn minimal_repro_script(&self, script: &ScriptRaw, radius: usize) -> Option<ScriptRaw> {
-        let candidate_ip = self.failing_event_ip.or_else(|| self.first_event_ip())?;
-        build_minimal_repro_script(script, candidate_ip, radius)
-    }
-}
+pub use visual_novel_engine::analysis::{
+    CompilationPhase, DryRunConfig, DryRunReport, DryRunStepTrace, DryRunStopReason, PhaseTrace,
+    REPRO_DEFAULT_RADIUS,
+};
 
 pub struct CompilationResult {
     pub script: ScriptRaw,
@@ -135,13 +23,17 @@ pub struct CompilationResult {
     pub issues: Vec<LintIssue>,
     pub phase_trace: Vec<PhaseTrace>,
     pub dry_run_report: Option<DryRunReport>,
+    /// Compiled event ip of each node, for starting playback mid-script.
+    pub node_ips: BTreeMap<u32, u32>,
+    /// Limits the dry runs used; also sizes [`Self::minimal_repro_script`].
+    pub dry_run_config: DryRunConfig,
 }
 
 impl CompilationResult {
     pub fn minimal_repro_script(&self) -> Option<ScriptRaw> {
-        self.dry_run_report
-            .as_ref()
-            .and_then(|report| report.minimal_repro_script(&self.script, REPRO_DEFAULT_RADIUS))
+        self.dry_run_report.as_ref().and_then(|report| {
+            report.minimal_repro_script(&self.script, self.dry_run_config.repro_radius)
+        })
     }
 }
 
@@ -152,6 +44,27 @@ pub fn compile_project(graph: &NodeGraph) -> CompilationResult {
 pub fn compile_project_with_project_root(
     graph: &NodeGraph,
     project_root: Option<&Path>,
+) -> CompilationResult {
+    compile_project_with_breakpoints(graph, project_root, &BTreeSet::new())
+}
+
+/// Compiles like [`compile_project_with_project_root`]; when the primary dry
+/// run reaches one of the `breakpoints` (node ids) its report is replaced by
+/// one halted at that node with [`DryRunStopReason::Breakpoint`].
+pub fn compile_project_with_breakpoints(
+    graph: &NodeGraph,
+    project_root: Option<&Path>,
+    breakpoints: &BTreeSet<u32>,
+) -> CompilationResult {
+    compile_project_with_config(graph, project_root, breakpoints, &DryRunConfig::default())
+}
+
+/// [`compile_project_with_breakpoints`] with custom dry-run limits.
+pub fn compile_project_with_config(
+    graph: &NodeGraph,
+    project_root: Option<&Path>,
+    breakpoints: &BTreeSet<u32>,
+    config: &DryRunConfig,
 ) -> CompilationResult {
     let mut phase_trace = Vec::new();
 
@@ -173,205 +86,95 @@ pub fn compile_project_with_project_root(
         detail: format!("{} issue(s) from graph validation", issues.len()),
     });
 
-    let mut dry_run_report = None;
-    let engine_result = match script.compile() {
-        Ok(compiled) => {
+    let analysis = analyze_script_with_config(&script, config);
+    issues.extend(
+        analysis
+            .issues
+            .into_iter()
+            .map(|issue| lint_issue_from_analysis(graph, issue)),
+    );
+    phase_trace.extend(analysis.phase_trace);
+
+    let mut dry_run_report = analysis.dry_run_report;
+    let breakpoint_ips: BTreeSet<u32> = breakpoints
+        .iter()
+        .filter_map(|node_id| graph.event_ip_for_node(*node_id))
+        .collect();
+    if let (Ok(engine), false) = (&analysis.engine_result, breakpoint_ips.is_empty()) {
+        let outcome = run_dry_run_with_config(
+            engine.clone(),
+            &ChoicePolicy::Strategy(ChoiceStrategy::First),
+            &breakpoint_ips,
+            config,
+        );
+        if outcome.report.stop_reason == DryRunStopReason::Breakpoint {
             phase_trace.push(PhaseTrace {
-                phase: CompilationPhase::ScriptCompile,
+                phase: CompilationPhase::DryRun,
                 ok: true,
-                detail: "ScriptRaw compiled successfully".to_string(),
+                detail: outcome.report.stop_message.clone(),
             });
-
-            let story_graph = StoryGraph::from_script(&compiled);
-            let unreachable = story_graph.unreachable_nodes();
-            if !unreachable.is_empty() {
-                for event_ip in unreachable {
-                    let incoming = story_graph
-                        .incoming_edges(event_ip)
-                        .into_iter()
-                        .map(|edge| edge.from.to_string())
-                        .collect::<Vec<_>>();
-                    let blocked_by = if incoming.is_empty() {
-                        "no incoming compiled edges".to_string()
-                    } else {
-                        format!("incoming_event_ips={}", incoming.join(","))
-                    };
-                    let node_id = graph.node_for_event_ip(event_ip);
-                    issues.push(
-                        LintIssue::warning(
-                            node_id,
-                            ValidationPhase::DryRun,
-                            LintCode::DryRunUnreachableCompiled,
-                            format!("Dry Run detected unreachable compiled event at ip={event_ip}"),
-                        )
-                        .with_event_ip(Some(event_ip))
-                        .with_blocked_by(blocked_by),
-                    );
-                }
-            }
-
-            match Engine::from_compiled(
-                compiled.clone(),
-                visual_novel_engine::SecurityPolicy::default(),
-                visual_novel_engine::ResourceLimiter::default(),
-            ) {
-                Ok(engine) => {
-                    phase_trace.push(PhaseTrace {
-                        phase: CompilationPhase::RuntimeInit,
-                        ok: true,
-                        detail: "Engine initialized".to_string(),
-                    });
-
-                    let primary_policy = ChoicePolicy::Strategy(ChoiceStrategy::First);
-                    let outcome = run_dry_run(engine.clone(), &primary_policy);
-                    dry_run_report = Some(outcome.report.clone());
-                    issues.extend(outcome.issues);
-
-                    let parity_issues =
-                        check_preview_runtime_parity(&script, &outcome.report, &primary_policy);
-                    if let Some(report) = dry_run_report.as_mut() {
-                        report.failing_event_ip = report
-                            .failing_event_ip
-                            .or_else(|| parity_issues.iter().find_map(|issue| issue.event_ip));
-                    }
-                    issues.extend(parity_issues);
-
-                    let mut route_policies = vec![
-                        ChoicePolicy::Strategy(ChoiceStrategy::Last),
-                        ChoicePolicy::Strategy(ChoiceStrategy::Alternating),
-                    ];
-                    for path in enumerate_choice_routes(
-                        &script,
-                        DRY_RUN_MAX_STEPS,
-                        DRY_RUN_EXHAUSTIVE_ROUTE_LIMIT,
-                        DRY_RUN_EXHAUSTIVE_CHOICE_DEPTH,
-                    ) {
-                        route_policies.push(ChoicePolicy::Scripted(path));
-                    }
-
-                    let mut seen_policies = HashSet::new();
-                    seen_policies.insert(primary_policy.clone());
-                    for policy in route_policies {
-                        if !seen_policies.insert(policy.clone()) {
-                            continue;
-                        }
-
-                        match Engine::from_compiled(
-                            compiled.clone(),
-                            visual_novel_engine::SecurityPolicy::default(),
-                            visual_novel_engine::ResourceLimiter::default(),
-                        ) {
-                            Ok(route_engine) => {
-                                let route_outcome = run_dry_run(route_engine, &policy);
-                                let mut route_issues = check_preview_runtime_parity(
-                                    &script,
-                                    &route_outcome.report,
-                                    &policy,
-                                );
-                                if route_outcome.report.stop_reason
-                                    == DryRunStopReason::RuntimeError
-                                {
-                                    let route_label = policy.label();
-                                    route_issues.push(
-                                        LintIssue::error(
-                                            None,
-                                            ValidationPhase::DryRun,
-                                            LintCode::DryRunRuntimeError,
-                                            format!(
-                                                "Dry Run route '{}' runtime error: {}",
-                                                route_label, route_outcome.report.stop_message
-                                            ),
-                                        )
-                                        .with_event_ip(route_outcome.report.failing_event_ip),
-                                    );
-                                }
-
-                                if let Some(report) = dry_run_report.as_mut() {
-                                    report.failing_event_ip =
-                                        report.failing_event_ip.or_else(|| {
-                                            route_issues.iter().find_map(|issue| issue.event_ip)
-                                        });
-                                }
-                                issues.extend(route_issues);
-                            }
-                            Err(err) => {
-                                let route_label = policy.label();
-                                issues.push(LintIssue::error(
-                                    None,
-                                    ValidationPhase::Runtime,
-                                    LintCode::RuntimeInitError,
-                                    format!(
-                                        "Runtime initialization failed for route '{}': {}",
-                                        route_label, err
-                                    ),
-                                ));
-                            }
-                        }
-                    }
-
-                    let dry_run_errors = issues
-                        .iter()
-                        .filter(|i| {
-                            i.phase == ValidationPhase::DryRun && i.severity == LintSeverity::Error
-                        })
-                        .count();
-                    phase_trace.push(PhaseTrace {
-                        phase: CompilationPhase::DryRun,
-                        ok: dry_run_errors == 0,
-                        detail: format!("Dry run complete ({} dry-run error(s))", dry_run_errors),
-                    });
-
-                    Ok(engine)
-                }
-                Err(e) => {
-                    issues.push(LintIssue::error(
-                        None,
-                        ValidationPhase::Runtime,
-                        LintCode::RuntimeInitError,
-                        format!("Runtime initialization failed: {}", e),
-                    ));
-                    phase_trace.push(PhaseTrace {
-                        phase: CompilationPhase::RuntimeInit,
-                        ok: false,
-                        detail: e.to_string(),
-                    });
-                    Err(format!("Runtime Init Error: {}", e))
-                }
-            }
+            dry_run_report = Some(outcome.report);
         }
-        Err(e) => {
-            issues.push(LintIssue::error(
-                None,
-                ValidationPhase::Compile,
-                LintCode::CompileError,
-                format!("Compilation Error: {}", e),
-            ));
-            phase_trace.push(PhaseTrace {
-                phase: CompilationPhase::ScriptCompile,
-                ok: false,
-                detail: e.to_string(),
-            });
-            Err(format!("Compilation Failed: {}", e))
-        }
-    };
+    }
+
+    let node_ips = analysis
+        .engine_result
+        .as_ref()
+        .map(|engine| script_sync::node_event_ips(engine.labels()))
+        .unwrap_or_default();
 
     CompilationResult {
         script,
-        engine_result,
+        engine_result: analysis.engine_result,
+        node_ips,
         issues,
         phase_trace,
         dry_run_report,
+        dry_run_config: *config,
     }
 }
 
-mod parity;
-
-use parity::{
-    build_minimal_repro_script, check_preview_runtime_parity, enumerate_choice_routes, run_dry_run,
-};
+fn lint_issue_from_analysis(graph: &NodeGraph, issue: AnalysisIssue) -> LintIssue {
+    let (phase, code) = match issue.kind {
+        AnalysisIssueKind::CompileError => (ValidationPhase::Compile, LintCode::CompileError),
+        AnalysisIssueKind::RuntimeInitError => {
+            (ValidationPhase::Runtime, LintCode::RuntimeInitError)
+        }
+        AnalysisIssueKind::UnreachableCompiled => {
+            (ValidationPhase::DryRun, LintCode::DryRunUnreachableCompiled)
+        }
+        AnalysisIssueKind::StepLimit => (ValidationPhase::DryRun, LintCode::DryRunStepLimit),
+        AnalysisIssueKind::RuntimeError | AnalysisIssueKind::RouteRuntimeError => {
+            (ValidationPhase::DryRun, LintCode::DryRunRuntimeError)
+        }
+        AnalysisIssueKind::ParityMismatch => {
+            (ValidationPhase::DryRun, LintCode::DryRunParityMismatch)
+        }
+        AnalysisIssueKind::Finished => (ValidationPhase::DryRun, LintCode::DryRunFinished),
+        AnalysisIssueKind::DeadBranch => (ValidationPhase::DryRun, LintCode::DeadBranch),
+    };
+    // The primary dry run anchors step-limit and runtime errors on the event ip itself.
+    let node_id = match issue.kind {
+        AnalysisIssueKind::UnreachableCompiled | AnalysisIssueKind::DeadBranch => issue
+            .event_ip
+            .and_then(|event_ip| graph.node_for_event_ip(event_ip)),
+        AnalysisIssueKind::StepLimit | AnalysisIssueKind::RuntimeError => issue.event_ip,
+        _ => None,
+    };
+    let lint = match issue.severity {
+        AnalysisSeverity::Error => LintIssue::error(node_id, phase, code, issue.message),
+        AnalysisSeverity::Warning => LintIssue::warning(node_id, phase, code, issue.message),
+        AnalysisSeverity::Info => LintIssue::info(node_id, phase, code, issue.message),
+    }
+    .with_event_ip(issue.event_ip);
+    match issue.blocked_by {
+        Some(blocked_by) => lint.with_blocked_by(blocked_by),
+        None => lint,
+    }
+}
 
 #[cfg(test)]
-use parity::simulate_raw_sequence;
+use visual_novel_engine::analysis::{enumerate_choice_routes, simulate_raw_sequence};
 
 #[cfg(test)]
 #[path = "tests/compiler_tests.rs"]
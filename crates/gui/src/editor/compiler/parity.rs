@@ -1,11 +0,0 @@
-mod dry_run;
-mod repro;
-mod route_sim;
-mod signatures;
-
-pub(super) use dry_run::run_dry_run;
-pub(super) use repro::{build_minimal_repro_script, check_preview_runtime_parity};
-pub(super) use route_sim::enumerate_choice_routes;
-
-#[cfg(test)]
-pub(super) use route_sim::simulate_raw_sequence;
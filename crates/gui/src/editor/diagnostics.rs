@@ -137,6 +137,17 @@ fn catalog_for(code: LintCode) -> DiagnosticCatalogEntry {
             how_to_fix_en: "Connect the missing option to a valid target node.",
             docs_ref: "docs/phase10_production_plan.md#106-herramientas-de-autoria-avanzada",
         },
+        LintCode::ChoicePortUnconnected => DiagnosticCatalogEntry {
+            title_es: "Puerto de Choice sin conectar",
+            title_en: "Unconnected Choice port",
+            root_cause_es: "El puerto de una opcion no tiene conexion de salida.",
+            root_cause_en: "An option port has no outgoing connection.",
+            why_failed_es: "Al elegir esa opcion la historia termina sin aviso; la exportacion queda bloqueada.",
+            why_failed_en: "Picking that option silently dead-ends the story, so export is blocked.",
+            how_to_fix_es: "Conecta el puerto a un nodo destino o a un End explicito.",
+            how_to_fix_en: "Connect the port to a target node or to an explicit End node.",
+            docs_ref: "docs/phase10_production_plan.md#106-herramientas-de-autoria-avanzada",
+        },
         LintCode::ChoicePortOutOfRange => DiagnosticCatalogEntry {
             title_es: "Puerto de Choice fuera de rango",
             title_en: "Choice port out of range",
@@ -148,6 +159,17 @@ fn catalog_for(code: LintCode) -> DiagnosticCatalogEntry {
             how_to_fix_en: "Synchronize option count and ports, or remove the invalid connection.",
             docs_ref: "docs/phase10_production_plan.md#101-contratos-de-datos--migraciones-reales",
         },
+        LintCode::ChoiceNoFallthrough => DiagnosticCatalogEntry {
+            title_es: "Choice automatica sin fallthrough",
+            title_en: "Auto choice without fallthrough",
+            root_cause_es: "Algunas opciones tienen peso 0 y la choice no define fallthrough.",
+            root_cause_en: "Some options have zero weight and the choice has no fallthrough.",
+            why_failed_es: "Si todas quedan en peso 0, el motor elige al azar entre opciones desactivadas.",
+            why_failed_en: "If every option ends up at zero weight, the engine picks among switched-off options at random.",
+            how_to_fix_es: "Agrega un label de fallthrough o da peso a alguna opcion.",
+            how_to_fix_en: "Add a fallthrough label or give some option a weight.",
+            docs_ref: "docs/phase10_production_plan.md#101-contratos-de-datos--migraciones-reales",
+        },
         LintCode::AudioAssetMissing | LintCode::AudioAssetEmpty => DiagnosticCatalogEntry {
             title_es: "Audio sin asset valido",
             title_en: "Audio missing valid asset",
@@ -241,6 +263,17 @@ fn catalog_for(code: LintCode) -> DiagnosticCatalogEntry {
             how_to_fix_en: "Set a valid speaker or an explicit narrator.",
             docs_ref: "docs/phase10_production_plan.md#103-componentes-vn-esenciales-faltantes",
         },
+        LintCode::SpeakerNameInconsistent => DiagnosticCatalogEntry {
+            title_es: "Speaker con mayusculas inconsistentes",
+            title_en: "Speaker with inconsistent casing",
+            root_cause_es: "El mismo speaker aparece escrito con distintas mayusculas.",
+            root_cause_en: "The same speaker is spelled with different casing.",
+            why_failed_es: "Historial, filtros y estilos por personaje lo tratan como personas distintas.",
+            why_failed_en: "History, filters, and per-character styles treat them as different people.",
+            how_to_fix_es: "Unifica todas las variantes a la grafia mas usada.",
+            how_to_fix_en: "Normalize every variant to the most common spelling.",
+            docs_ref: "docs/phase10_production_plan.md#103-componentes-vn-esenciales-faltantes",
+        },
         LintCode::EmptyJumpTarget => DiagnosticCatalogEntry {
             title_es: "Salto sin target",
             title_en: "Jump without target",
@@ -252,6 +285,28 @@ fn catalog_for(code: LintCode) -> DiagnosticCatalogEntry {
             how_to_fix_en: "Assign a valid label (for example start or a target node label).",
             docs_ref: "docs/phase10_production_plan.md#101-contratos-de-datos--migraciones-reales",
         },
+        LintCode::FlagSetButNeverRead | LintCode::VarSetButNeverRead => DiagnosticCatalogEntry {
+            title_es: "Flag/variable escrita pero nunca leida",
+            title_en: "Flag/variable written but never read",
+            root_cause_es: "Ningun JumpIf consulta el simbolo que este nodo escribe.",
+            root_cause_en: "No JumpIf checks the symbol this node writes.",
+            why_failed_es: "El estado se guarda sin efecto en la historia; suele ser un nombre mal escrito.",
+            why_failed_en: "State is stored with no effect on the story; often a misspelled name.",
+            how_to_fix_es: "Agrega la condicion que falta o elimina la escritura.",
+            how_to_fix_en: "Add the missing condition or remove the write.",
+            docs_ref: "docs/phase10_production_plan.md#101-contratos-de-datos--migraciones-reales",
+        },
+        LintCode::FlagReadButNeverSet | LintCode::VarReadButNeverSet => DiagnosticCatalogEntry {
+            title_es: "Flag/variable leida pero nunca escrita",
+            title_en: "Flag/variable read but never written",
+            root_cause_es: "Un JumpIf consulta un simbolo que ningun nodo asigna.",
+            root_cause_en: "A JumpIf checks a symbol that no node assigns.",
+            why_failed_es: "La condicion siempre evalua el valor por defecto y una rama es inalcanzable.",
+            why_failed_en: "The condition always sees the default value, so one branch never runs.",
+            how_to_fix_es: "Asigna el simbolo antes del salto o corrige su nombre.",
+            how_to_fix_en: "Set the symbol before the branch or fix its name.",
+            docs_ref: "docs/phase10_production_plan.md#101-contratos-de-datos--migraciones-reales",
+        },
         LintCode::ContractUnsupportedExport => DiagnosticCatalogEntry {
             title_es: "Evento no exportable por contrato",
             title_en: "Contract-unsupported export event",
@@ -296,6 +351,17 @@ fn catalog_for(code: LintCode) -> DiagnosticCatalogEntry {
             how_to_fix_en: "Review earlier compile/dry-run errors and resource contracts.",
             docs_ref: "docs/phase10_production_plan.md#107-observabilidad-operativa--diagnostico-reproducible",
         },
+        LintCode::DeadBranch => DiagnosticCatalogEntry {
+            title_es: "Rama de JumpIf que nunca se ejecuta",
+            title_en: "JumpIf branch that never runs",
+            root_cause_es: "Todas las rutas fijan la flag/variable antes del salto con el mismo valor.",
+            root_cause_en: "Every path sets the flag/variable to the same value before the branch.",
+            why_failed_es: "La condicion siempre da el mismo resultado y una rama es codigo muerto.",
+            why_failed_en: "The condition always has the same result, so one branch is dead code.",
+            how_to_fix_es: "Revisa la condicion o los SetFlag/SetVar previos.",
+            how_to_fix_en: "Review the condition or the preceding SetFlag/SetVar events.",
+            docs_ref: "docs/phase10_production_plan.md#107-observabilidad-operativa--diagnostico-reproducible",
+        },
         LintCode::DryRunUnreachableCompiled
         | LintCode::DryRunStepLimit
         | LintCode::DryRunRuntimeError
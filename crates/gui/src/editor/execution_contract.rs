@@ -138,6 +138,33 @@ const EXT_CALL: EventExecutionContract = EventExecutionContract {
     fidelity: FidelityClass::RuntimeReal,
 };
 
+const INPUT_TEXT: EventExecutionContract = EventExecutionContract {
+    event_name: "InputText",
+    editor_supported: true,
+    preview_supported: true,
+    runtime_supported: true,
+    export_supported: true,
+    fidelity: FidelityClass::RuntimeReal,
+};
+
+const UNLOCK_GALLERY: EventExecutionContract = EventExecutionContract {
+    event_name: "UnlockGallery",
+    editor_supported: true,
+    preview_supported: true,
+    runtime_supported: true,
+    export_supported: true,
+    fidelity: FidelityClass::RuntimeReal,
+};
+
+const ACHIEVE: EventExecutionContract = EventExecutionContract {
+    event_name: "Achieve",
+    editor_supported: true,
+    preview_supported: true,
+    runtime_supported: true,
+    export_supported: true,
+    fidelity: FidelityClass::RuntimeReal,
+};
+
 const GENERIC_EVENT: EventExecutionContract = EventExecutionContract {
     event_name: "Generic/EventRaw",
     editor_supported: true,
@@ -147,7 +174,16 @@ const GENERIC_EVENT: EventExecutionContract = EventExecutionContract {
     fidelity: FidelityClass::PreviewOnly,
 };
 
-const CONTRACT_MATRIX: [EventExecutionContract; 14] = [
+const COMMENT_NOTE: EventExecutionContract = EventExecutionContract {
+    event_name: "Comment",
+    editor_supported: true,
+    preview_supported: false,
+    runtime_supported: false,
+    export_supported: false,
+    fidelity: FidelityClass::PreviewOnly,
+};
+
+const CONTRACT_MATRIX: [EventExecutionContract; 18] = [
     DIALOGUE,
     CHOICE,
     SCENE,
@@ -159,9 +195,13 @@ const CONTRACT_MATRIX: [EventExecutionContract; 14] = [
     TRANSITION,
     CHARACTER_PLACEMENT,
     EXT_CALL,
+    INPUT_TEXT,
+    UNLOCK_GALLERY,
+    ACHIEVE,
     GENERIC_EVENT,
     START_MARKER,
     END_MARKER,
+    COMMENT_NOTE,
 ];
 
 /// Returns the immutable compatibility matrix for editor/runtime/export.
@@ -185,7 +225,11 @@ pub fn contract_for_node(node: &StoryNode) -> EventExecutionContract {
         StoryNode::Transition { .. } => TRANSITION,
         StoryNode::CharacterPlacement { .. } => CHARACTER_PLACEMENT,
         StoryNode::Generic(EventRaw::ExtCall { .. }) => EXT_CALL,
+        StoryNode::Generic(EventRaw::InputText { .. }) => INPUT_TEXT,
+        StoryNode::Generic(EventRaw::UnlockGallery { .. }) => UNLOCK_GALLERY,
+        StoryNode::Generic(EventRaw::Achieve { .. }) => ACHIEVE,
         StoryNode::Generic(_) => GENERIC_EVENT,
+        StoryNode::Comment { .. } => COMMENT_NOTE,
     }
 }
 
@@ -196,13 +240,17 @@ pub fn contract_for_event_raw(event: &EventRaw) -> EventExecutionContract {
         EventRaw::Choice(_) => CHOICE,
         EventRaw::Scene(_) => SCENE,
         EventRaw::Jump { .. } => JUMP,
-        EventRaw::SetFlag { .. } | EventRaw::SetVar { .. } => SET_VAR,
+        EventRaw::SetFlag { .. } | EventRaw::SetVar { .. } | EventRaw::SetStrVar { .. } => SET_VAR,
         EventRaw::JumpIf { .. } => JUMP_IF,
         EventRaw::Patch(_) => SCENE_PATCH,
         EventRaw::ExtCall { .. } => EXT_CALL,
         EventRaw::AudioAction(_) => AUDIO_ACTION,
         EventRaw::Transition(_) => TRANSITION,
         EventRaw::SetCharacterPosition(_) => CHARACTER_PLACEMENT,
+        EventRaw::InputText { .. } => INPUT_TEXT,
+        EventRaw::UnlockGallery { .. } => UNLOCK_GALLERY,
+        EventRaw::Achieve { .. } => ACHIEVE,
+        EventRaw::Include { .. } | EventRaw::Expand { .. } => GENERIC_EVENT,
     }
 }
 
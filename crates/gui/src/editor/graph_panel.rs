@@ -76,6 +76,7 @@ impl<'a> GraphPanel<'a> {
                     } => {
                         format!("Placement: {} ({}, {})", name, x, y)
                     }
+                    StoryNode::Comment { text } => format!("Note: {}", truncate(text, 24)),
                 };
                 (*id, info, node.color())
             })
@@ -40,6 +40,12 @@ impl<'a> InspectorPanel<'a> {
 
             ui.separator();
 
+            ui.collapsing("References", |ui| {
+                self.render_references(ui);
+            });
+
+            ui.separator();
+
             ui.collapsing("Selected Entity", |ui| {
                 self.render_entity_info(ui);
             });
@@ -53,3 +59,4 @@ impl<'a> InspectorPanel<'a> {
 mod entity_info;
 #[path = "inspector_panel_node_editor.rs"]
 mod node_editor;
+mod references;
@@ -0,0 +1,39 @@
+use super::*;
+use crate::editor::ReferenceKind;
+
+impl<'a> InspectorPanel<'a> {
+    pub(super) fn render_references(&mut self, ui: &mut egui::Ui) {
+        let Some(label) = self
+            .selected_node
+            .and_then(|node_id| self.graph.label_for_node(node_id))
+        else {
+            ui.label("No node selected");
+            return;
+        };
+        let references = self.graph.references_to_label(&label);
+        ui.label(format!("Label: {label}"));
+        if references.is_empty() {
+            ui.label("Nothing references this node");
+            return;
+        }
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!(
+                "{} reference(s) will break if this node is deleted",
+                references.len()
+            ),
+        );
+        for (node_id, kind) in references {
+            let text = match kind {
+                ReferenceKind::Jump => format!("Node {node_id}: jump"),
+                ReferenceKind::JumpIf => format!("Node {node_id}: conditional jump"),
+                ReferenceKind::ChoiceOption { index } => {
+                    format!("Node {node_id}: choice option {}", index + 1)
+                }
+            };
+            if ui.link(text).clicked() {
+                self.graph.selected = Some(node_id);
+            }
+        }
+    }
+}
@@ -71,6 +71,10 @@ impl<'a> InspectorPanel<'a> {
                 StoryNode::End => {
                     ui.label("End Node (Termination)");
                 }
+                StoryNode::Comment { text } => {
+                    ui.label("Comment (editor only, not compiled)");
+                    standard_changed |= ui.text_edit_multiline(text).changed();
+                }
                 StoryNode::SetVariable { key, value } => {
                     ui.label("Variable Name:");
                     standard_changed |= ui.text_edit_singleline(key).changed();
@@ -126,6 +126,7 @@ pub(super) fn render_scene_node(
                 &mut character.position,
                 standard_changed,
             );
+            edit_flip_x(ui, &mut character.flip_x, standard_changed);
         });
     }
 }
@@ -143,13 +144,17 @@ pub(super) fn render_jump_if_node(
     ui.label("Condition:");
 
     let is_flag = matches!(cond, CondRaw::Flag { .. });
+    let is_var = matches!(cond, CondRaw::VarCmp { .. });
+    let is_str = matches!(cond, CondRaw::StrEq { .. });
     let mut type_changed = false;
 
     egui::ComboBox::from_label("Type")
         .selected_text(if is_flag {
             "Flag"
-        } else {
+        } else if is_var {
             "Variable Comparison"
+        } else {
+            "String Equals"
         })
         .show_ui(ui, |ui| {
             if ui.selectable_label(is_flag, "Flag").clicked() && !is_flag {
@@ -159,11 +164,7 @@ pub(super) fn render_jump_if_node(
                 };
                 type_changed = true;
             }
-            if ui
-                .selectable_label(!is_flag, "Variable Comparison")
-                .clicked()
-                && is_flag
-            {
+            if ui.selectable_label(is_var, "Variable Comparison").clicked() && !is_var {
                 *cond = CondRaw::VarCmp {
                     key: "var_name".to_string(),
                     op: CmpOp::Eq,
@@ -171,6 +172,13 @@ pub(super) fn render_jump_if_node(
                 };
                 type_changed = true;
             }
+            if ui.selectable_label(is_str, "String Equals").clicked() && !is_str {
+                *cond = CondRaw::StrEq {
+                    key: "str_var_name".to_string(),
+                    value: String::new(),
+                };
+                type_changed = true;
+            }
         });
 
     *standard_changed |= type_changed;
@@ -215,6 +223,12 @@ pub(super) fn render_jump_if_node(
                 *standard_changed |= ui.add(egui::DragValue::new(value)).changed();
             });
         }
+        CondRaw::StrEq { key, value } => {
+            ui.label("String Var Key:");
+            *standard_changed |= ui.text_edit_singleline(key).changed();
+            ui.label("Equals:");
+            *standard_changed |= ui.text_edit_singleline(value).changed();
+        }
     }
 }
 
@@ -252,6 +266,7 @@ pub(super) fn render_scene_patch_node(
                     &mut character.position,
                     standard_changed,
                 );
+                edit_flip_x(ui, &mut character.flip_x, standard_changed);
             });
         }
         if let Some(idx) = delete_add_idx {
@@ -479,5 +494,9 @@ fn render_optional_character_fields(
     edit_optional_text_inline(ui, "Pos:", position, standard_changed);
 }
 
+fn edit_flip_x(ui: &mut egui::Ui, flip_x: &mut bool, standard_changed: &mut bool) {
+    *standard_changed |= ui.checkbox(flip_x, "Flip X").changed();
+}
+
 #[allow(dead_code)]
 fn _type_anchor(_: &CharacterPatchRaw) {}
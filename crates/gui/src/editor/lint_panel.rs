@@ -1,6 +1,6 @@
 //! Validation panel for displaying lint issues.
 
-use super::quick_fix::{suggest_fixes, QuickFixCandidate, QuickFixRisk};
+use super::quick_fix::{preview_fix, suggest_fixes, QuickFixCandidate, QuickFixRisk};
 use super::{DiagnosticLanguage, LintIssue, LintSeverity, NodeGraph};
 use eframe::egui;
 
@@ -20,6 +20,10 @@ pub enum LintPanelAction {
         include_review: bool,
     },
     RevertLastFix,
+    /// Pan the node editor so `node_id` is centered.
+    FocusNode {
+        node_id: u32,
+    },
 }
 
 #[derive(Debug, Default)]
@@ -120,11 +124,23 @@ impl<'a> LintPanel<'a> {
             .count();
 
         ui.label(format!(
-            "Found {} errors, {} warnings, {} infos.",
+            "Found {} errors, {} warnings, {} infos. (F8: next error)",
             error_count, warning_count, info_count
         ));
         ui.separator();
 
+        if ui.input(|i| i.key_pressed(egui::Key::F8)) {
+            if let Some(idx) = next_error_index(self.issues, *self.selected_issue, self.graph) {
+                *self.selected_issue = Some(idx);
+                *self.selected_node = issue_target_node(&self.issues[idx], self.graph);
+                if let Some(node_id) = *self.selected_node {
+                    response
+                        .actions
+                        .push(LintPanelAction::FocusNode { node_id });
+                }
+            }
+        }
+
         egui::ScrollArea::vertical()
             .max_height(220.0)
             .show(ui, |ui| {
@@ -152,21 +168,16 @@ impl<'a> LintPanel<'a> {
 
                     let resp = ui.selectable_label(selected, text);
 
-                    if resp.clicked() {
+                    if resp.clicked() || resp.double_clicked() {
                         *self.selected_issue = Some(idx);
-                        *self.selected_node = issue
-                            .node_id
-                            .or(issue.edge_from)
-                            .or_else(|| {
-                                issue
-                                    .event_ip
-                                    .and_then(|event_ip| self.graph.node_for_event_ip(event_ip))
-                            })
-                            .or_else(|| {
-                                issue.asset_path.as_ref().and_then(|asset| {
-                                    self.graph.first_node_referencing_asset(asset)
-                                })
-                            });
+                        *self.selected_node = issue_target_node(issue, self.graph);
+                    }
+                    if resp.double_clicked() {
+                        if let Some(node_id) = *self.selected_node {
+                            response
+                                .actions
+                                .push(LintPanelAction::FocusNode { node_id });
+                        }
                     }
 
                     ui.separator();
@@ -242,7 +253,17 @@ impl<'a> LintPanel<'a> {
                 } else {
                     ui.label(egui::RichText::new("Available quick-fixes").strong());
                     for fix in fixes {
-                        render_fix_card(ui, fix, *self.language, &mut response.actions, issue_idx);
+                        render_fix_card(
+                            ui,
+                            fix,
+                            FixContext {
+                                graph: self.graph,
+                                issue,
+                                issue_idx,
+                            },
+                            *self.language,
+                            &mut response.actions,
+                        );
                     }
                 }
             }
@@ -252,13 +273,54 @@ impl<'a> LintPanel<'a> {
     }
 }
 
+/// Node an issue points at: its own node, the edge source, the node that
+/// emits its event, or the first node using its asset.
+fn issue_target_node(issue: &LintIssue, graph: &NodeGraph) -> Option<u32> {
+    issue
+        .node_id
+        .or(issue.edge_from)
+        .or_else(|| {
+            issue
+                .event_ip
+                .and_then(|event_ip| graph.node_for_event_ip(event_ip))
+        })
+        .or_else(|| {
+            issue
+                .asset_path
+                .as_ref()
+                .and_then(|asset| graph.first_node_referencing_asset(asset))
+        })
+}
+
+/// Index of the next error after `current` that maps to a node, wrapping
+/// around the list. Global errors are skipped; `None` when none qualify.
+pub(crate) fn next_error_index(
+    issues: &[LintIssue],
+    current: Option<usize>,
+    graph: &NodeGraph,
+) -> Option<usize> {
+    let len = issues.len();
+    let start = current.map_or(0, |idx| idx + 1);
+    (0..len).map(|offset| (start + offset) % len).find(|&idx| {
+        let issue = &issues[idx];
+        issue.severity == LintSeverity::Error && issue_target_node(issue, graph).is_some()
+    })
+}
+
+struct FixContext<'a> {
+    graph: &'a NodeGraph,
+    issue: &'a LintIssue,
+    issue_idx: usize,
+}
+
 fn render_fix_card(
     ui: &mut egui::Ui,
     fix: QuickFixCandidate,
+    ctx: FixContext<'_>,
     language: DiagnosticLanguage,
     out: &mut Vec<LintPanelAction>,
-    issue_idx: usize,
 ) {
+    let issue_idx = ctx.issue_idx;
     ui.group(|ui| {
         ui.label(egui::RichText::new(fix.title(language)).strong());
         ui.label(format!(
@@ -273,7 +335,24 @@ fn render_fix_card(
             QuickFixRisk::Safe => "Apply fix",
             QuickFixRisk::Review => "Apply fix (review)",
         };
-        if ui.button(label).clicked() {
+        let button = ui.button(label).on_hover_ui(|ui| {
+            // Computed only while hovered: it clones the graph.
+            match preview_fix(ctx.graph, ctx.issue, fix.fix_id) {
+                Ok(preview) if preview.is_empty() => {
+                    ui.label("Preview: no changes");
+                }
+                Ok(preview) => {
+                    ui.label(egui::RichText::new("Preview").strong());
+                    for line in preview.summary_lines() {
+                        ui.monospace(line);
+                    }
+                }
+                Err(err) => {
+                    ui.label(format!("Preview failed: {err}"));
+                }
+            }
+        });
+        if button.clicked() {
             out.push(LintPanelAction::ApplyFix {
                 issue_index: issue_idx,
                 fix_id: fix.fix_id.to_string(),
@@ -283,3 +362,7 @@ fn render_fix_card(
     });
     ui.separator();
 }
+
+#[cfg(test)]
+#[path = "tests/lint_panel_tests.rs"]
+mod tests;
@@ -56,6 +56,14 @@ pub fn render_menu_bar(ui: &mut egui::Ui, workbench: &mut EditorWorkbench) {
                 workbench.run_loaded_repro_case();
                 ui.close_menu();
             }
+            if ui.button("Export Choice Route").clicked() {
+                workbench.export_choice_route();
+                ui.close_menu();
+            }
+            if ui.button("Import Choice Route").clicked() {
+                workbench.import_choice_route();
+                ui.close_menu();
+            }
             if ui.button("Export Diagnostic Report").clicked() {
                 workbench.export_diagnostic_report();
                 ui.close_menu();
@@ -88,6 +96,7 @@ pub fn render_menu_bar(ui: &mut egui::Ui, workbench: &mut EditorWorkbench) {
             if workbench.show_validation {
                 ui.checkbox(&mut workbench.validation_collapsed, "Validation Minimizado");
             }
+            ui.checkbox(&mut workbench.show_statistics, "Statistics");
             ui.separator();
             ui.checkbox(
                 &mut workbench.node_editor_window_open,
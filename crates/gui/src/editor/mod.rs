@@ -25,6 +25,7 @@ mod player_ui;
 pub mod project_io;
 pub mod quick_fix;
 mod script_sync;
+mod statistics_panel;
 mod timeline_panel;
 mod undo;
 mod validator;
@@ -40,12 +41,15 @@ pub use graph_panel::GraphPanel;
 pub use inspector_panel::InspectorPanel;
 pub use lint_panel::LintPanel;
 pub use node_editor::NodeEditorPanel;
-pub use node_graph::NodeGraph;
+pub use node_graph::{snap_to_grid, NodeGraph, NodeGroup, ReferenceKind, SubgraphClip};
 pub use node_types::{ContextMenu, StoryNode, ToastKind, ToastState};
+pub(crate) use player_ui::PlayerSessionState;
+pub use statistics_panel::StatisticsPanel;
 pub use timeline_panel::TimelinePanel;
 pub use undo::UndoStack;
 pub use validator::{
-    validate as validate_graph, LintCode, LintIssue, LintSeverity, ValidationPhase,
+    validate as validate_graph, validate_json, LintCode, LintIssue, LintSeverity, StableIssue,
+    ValidationPhase,
 };
 pub use viewport_panel::ViewportPanel;
 pub use visual_composer::VisualComposerPanel;
@@ -10,7 +10,7 @@
 
 use eframe::egui;
 
-use super::node_graph::NodeGraph;
+use super::node_graph::{NodeGraph, SubgraphClip, GROUP_HEADER_HEIGHT, REACHABILITY_DEBOUNCE_SECS};
 use super::node_rendering;
 use super::node_types::{node_visual_height, ContextMenu, StoryNode, NODE_WIDTH};
 use super::undo::UndoStack;
@@ -37,7 +37,6 @@ impl<'a> NodeEditorPanel<'a> {
 
     #[inline]
     /// Transforms a screen-space position to graph-space.
-    #[allow(dead_code)]
     fn screen_to_graph(&self, rect: egui::Rect, pos: egui::Pos2) -> egui::Pos2 {
         ((pos - rect.min) / self.graph.zoom() - self.graph.pan()).to_pos2()
     }
@@ -57,13 +56,19 @@ impl<'a> NodeEditorPanel<'a> {
 
         painter.rect_filled(rect, 5.0, egui::Color32::from_rgb(25, 25, 35));
 
+        if let Some(node_id) = self.graph.pending_focus.take() {
+            self.graph.center_on_node(node_id, rect.size());
+        }
+
         self.render_grid(&painter, rect);
         self.handle_input(ui, &response);
+        self.render_groups(&painter, rect);
         self.render_connections(&painter, rect);
         self.render_nodes(ui, &painter, rect, &response);
         self.render_connecting_line(&painter, rect, &response);
         node_rendering::render_context_menu(self.graph, ui);
         node_rendering::render_inline_editor(self.graph, ui);
+        self.render_search_palette(ui, rect);
         self.render_status_bar(&painter, rect);
     }
 
@@ -110,6 +115,16 @@ impl<'a> NodeEditorPanel<'a> {
                     self.graph.selected = Some(id);
                     ui.close_menu();
                 }
+                if ui.button("📝 Comment").clicked() {
+                    let id = self.graph.add_node(
+                        StoryNode::Comment {
+                            text: "Note...".to_string(),
+                        },
+                        pos,
+                    );
+                    self.graph.selected = Some(id);
+                    ui.close_menu();
+                }
                 ui.separator();
                 if ui.button("▶ Start").clicked() {
                     let id = self
@@ -137,6 +152,41 @@ impl<'a> NodeEditorPanel<'a> {
             }
             ui.label(format!("Zoom: {:.0}%", self.graph.zoom() * 100.0));
 
+            let mut snap = self.graph.snap_enabled();
+            if ui.checkbox(&mut snap, "Snap").changed() {
+                self.graph.set_snap_enabled(snap);
+            }
+            let selected = self.graph.selected_ids().len();
+            if ui
+                .add_enabled(selected >= 2, egui::Button::new("Align Left"))
+                .clicked()
+            {
+                self.graph.align_left();
+            }
+            if ui
+                .add_enabled(selected >= 3, egui::Button::new("Distribute"))
+                .on_hover_text("Space selected nodes evenly from left to right")
+                .clicked()
+            {
+                self.graph.distribute_horizontally();
+            }
+            if ui
+                .add_enabled(selected >= 1, egui::Button::new("Group"))
+                .on_hover_text("Frame the selected nodes; drag the frame title to move them")
+                .clicked()
+            {
+                self.graph.group_selected("Group");
+            }
+            let selected_group = self.graph.selected.and_then(|id| self.graph.group_of(id));
+            if ui
+                .add_enabled(selected_group.is_some(), egui::Button::new("Ungroup"))
+                .clicked()
+            {
+                if let Some(group_id) = selected_group {
+                    self.graph.remove_group(group_id);
+                }
+            }
+
             ui.separator();
 
             // Undo/Redo
@@ -170,7 +220,7 @@ impl<'a> NodeEditorPanel<'a> {
     }
 
     fn render_grid(&self, painter: &egui::Painter, rect: egui::Rect) {
-        let grid_spacing = 50.0 * self.graph.zoom();
+        let grid_spacing = self.graph.grid_size() * self.graph.zoom();
         let grid_color_minor = egui::Color32::from_rgba_unmultiplied(80, 80, 100, 32);
         let grid_color_major = egui::Color32::from_rgba_unmultiplied(120, 120, 150, 70);
         let normalize_offset = |value: f32| ((value % grid_spacing) + grid_spacing) % grid_spacing;
@@ -214,36 +264,11 @@ impl<'a> NodeEditorPanel<'a> {
     }
 
     fn handle_input(&mut self, ui: &egui::Ui, response: &egui::Response) {
-        let mut is_panning = response.dragged_by(egui::PointerButton::Middle)
+        // Primary drags on empty canvas rubber-band select; pan with the
+        // middle button or Ctrl+drag.
+        let is_panning = response.dragged_by(egui::PointerButton::Middle)
             || (response.dragged() && ui.input(|i| i.modifiers.ctrl));
 
-        if response.dragged_by(egui::PointerButton::Primary)
-            && !is_panning
-            && self.graph.dragging_node.is_none()
-        {
-            // Check if we started dragging on a node
-            if let Some(_pos) = response.interact_pointer_pos() {
-                // We need the START position of the drag, not current.
-                if let Some(start_pos) = ui.input(|i| i.pointer.press_origin()) {
-                    let mut started_on_node = false;
-                    for (_, node, n_pos) in self.graph.nodes() {
-                        let screen_pos = self.graph_to_screen(response.rect, *n_pos);
-                        let size =
-                            egui::vec2(NODE_WIDTH, node_visual_height(node)) * self.graph.zoom();
-                        let rect = egui::Rect::from_min_size(screen_pos, size);
-                        if rect.contains(start_pos) {
-                            started_on_node = true;
-                            break;
-                        }
-                    }
-
-                    if !started_on_node {
-                        is_panning = true;
-                    }
-                }
-            }
-        }
-
         if is_panning {
             let delta = ui.input(|i| i.pointer.delta()) / self.graph.zoom();
             if delta.length_sq() > 0.0 {
@@ -251,23 +276,28 @@ impl<'a> NodeEditorPanel<'a> {
             }
         }
 
+        // The search palette owns the keyboard while open.
+        let keyboard_free = self.graph.palette_query.is_none();
+
         // Pan with Arrow Keys
-        let pan_speed = 5.0; // Pixels per frame approx
-        if ui.input(|i| i.key_down(egui::Key::ArrowUp)) {
-            self.graph
-                .pan_by(egui::vec2(0.0, pan_speed) / self.graph.zoom());
-        }
-        if ui.input(|i| i.key_down(egui::Key::ArrowDown)) {
-            self.graph
-                .pan_by(egui::vec2(0.0, -pan_speed) / self.graph.zoom());
-        }
-        if ui.input(|i| i.key_down(egui::Key::ArrowLeft)) {
-            self.graph
-                .pan_by(egui::vec2(pan_speed, 0.0) / self.graph.zoom());
-        }
-        if ui.input(|i| i.key_down(egui::Key::ArrowRight)) {
-            self.graph
-                .pan_by(egui::vec2(-pan_speed, 0.0) / self.graph.zoom());
+        if keyboard_free {
+            let pan_speed = 5.0; // Pixels per frame approx
+            if ui.input(|i| i.key_down(egui::Key::ArrowUp)) {
+                self.graph
+                    .pan_by(egui::vec2(0.0, pan_speed) / self.graph.zoom());
+            }
+            if ui.input(|i| i.key_down(egui::Key::ArrowDown)) {
+                self.graph
+                    .pan_by(egui::vec2(0.0, -pan_speed) / self.graph.zoom());
+            }
+            if ui.input(|i| i.key_down(egui::Key::ArrowLeft)) {
+                self.graph
+                    .pan_by(egui::vec2(pan_speed, 0.0) / self.graph.zoom());
+            }
+            if ui.input(|i| i.key_down(egui::Key::ArrowRight)) {
+                self.graph
+                    .pan_by(egui::vec2(-pan_speed, 0.0) / self.graph.zoom());
+            }
         }
 
         // Zoom with scroll wheel only when pointer is over the graph canvas.
@@ -290,6 +320,10 @@ impl<'a> NodeEditorPanel<'a> {
             self.graph.context_menu = None;
         }
 
+        if !keyboard_free {
+            return;
+        }
+
         // === Zoom Keyboard Shortcuts ===
         if ui.input(|i| i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals)) {
             self.graph.zoom_by(0.1);
@@ -307,7 +341,7 @@ impl<'a> NodeEditorPanel<'a> {
         // === Node Action Shortcuts ===
         if ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace)) {
             if let Some(id) = self.graph.selected {
-                self.graph.remove_node(id);
+                self.graph.delete_node_reconnect(id);
                 self.graph.selected = None;
             }
         }
@@ -321,6 +355,44 @@ impl<'a> NodeEditorPanel<'a> {
                 self.graph.duplicate_node(id);
             }
         }
+        self.handle_clipboard(ui);
+    }
+
+    /// Ctrl+C copies the multi-selection (or the selected node's whole branch)
+    /// as JSON; Ctrl+V pastes it.
+    fn handle_clipboard(&mut self, ui: &egui::Ui) {
+        let (copy, pasted) = ui.input(|i| {
+            let copy = i
+                .events
+                .iter()
+                .any(|event| matches!(event, egui::Event::Copy));
+            let pasted = i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            });
+            (copy, pasted)
+        });
+        if copy {
+            let selection = self.graph.selected_ids();
+            let ids = if selection.len() == 1 {
+                self.graph.branch_from(selection[0])
+            } else {
+                selection
+            };
+            if !ids.is_empty() {
+                let clip = self.graph.copy_subgraph(&ids);
+                if let Ok(json) = clip.to_json() {
+                    ui.output_mut(|o| o.copied_text = json);
+                }
+            }
+        }
+        if let Some(text) = pasted {
+            if let Ok(clip) = SubgraphClip::from_json(&text) {
+                if !clip.is_empty() {
+                    self.graph.paste_subgraph(&clip, egui::vec2(50.0, 50.0));
+                }
+            }
+        }
     }
 
     fn render_connections(&self, painter: &egui::Painter, rect: egui::Rect) {
@@ -386,6 +458,8 @@ impl<'a> NodeEditorPanel<'a> {
     }
 }
 
+mod groups;
+mod palette;
 mod render;
 #[cfg(test)]
 #[path = "tests/node_editor_tests.rs"]
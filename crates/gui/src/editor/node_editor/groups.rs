@@ -0,0 +1,37 @@
+use super::*;
+
+impl<'a> NodeEditorPanel<'a> {
+    /// Draws group frames behind the nodes, title strip on top.
+    pub(super) fn render_groups(&self, painter: &egui::Painter, rect: egui::Rect) {
+        let zoom = self.graph.zoom();
+        for group in self.graph.groups() {
+            let Some(frame) = self.graph.group_bounds(group.id) else {
+                continue;
+            };
+            let screen = egui::Rect::from_min_max(
+                self.graph_to_screen(rect, frame.min),
+                self.graph_to_screen(rect, frame.max),
+            );
+            if !rect.intersects(screen) {
+                continue;
+            }
+
+            let color = group.color32();
+            painter.rect_filled(screen, 8.0 * zoom, color.linear_multiply(0.25));
+            painter.rect_stroke(screen, 8.0 * zoom, egui::Stroke::new(1.5, color));
+
+            let header = egui::Rect::from_min_size(
+                screen.min,
+                egui::vec2(screen.width(), GROUP_HEADER_HEIGHT * zoom),
+            );
+            painter.rect_filled(header, 8.0 * zoom, color.linear_multiply(0.6));
+            painter.text(
+                header.left_center() + egui::vec2(8.0 * zoom, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &group.title,
+                egui::FontId::proportional(12.0 * zoom),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+}
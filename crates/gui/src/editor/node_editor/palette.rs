@@ -0,0 +1,73 @@
+use super::*;
+
+/// Maximum number of hits listed in the palette.
+const PALETTE_MAX_RESULTS: usize = 12;
+
+impl<'a> NodeEditorPanel<'a> {
+    /// Ctrl+F search palette; picking a hit centers the canvas on that node.
+    pub(super) fn render_search_palette(&mut self, ui: &egui::Ui, rect: egui::Rect) {
+        if ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::F)) {
+            self.graph.palette_query = match self.graph.palette_query {
+                Some(_) => None,
+                None => Some(String::new()),
+            };
+        }
+        let Some(mut query) = self.graph.palette_query.take() else {
+            return;
+        };
+
+        let hits = self.graph.search(&query);
+        let mut picked = None;
+        let mut open = true;
+        egui::Window::new("🔎 Find Node")
+            .collapsible(false)
+            .resizable(false)
+            .fixed_pos(rect.center_top() + egui::vec2(-150.0, 40.0))
+            .fixed_size(egui::vec2(300.0, 0.0))
+            .show(ui.ctx(), |ui| {
+                let response = ui.text_edit_singleline(&mut query);
+                response.request_focus();
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    picked = hits.first().copied();
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    open = false;
+                }
+
+                if !query.trim().is_empty() && hits.is_empty() {
+                    ui.weak("No matching nodes");
+                }
+                for &id in hits.iter().take(PALETTE_MAX_RESULTS) {
+                    let Some(node) = self.graph.get_node(id) else {
+                        continue;
+                    };
+                    let label = format!("{} #{} {}", node.icon(), id, result_summary(node));
+                    if ui.selectable_label(false, label).clicked() {
+                        picked = Some(id);
+                    }
+                }
+                if hits.len() > PALETTE_MAX_RESULTS {
+                    ui.weak(format!("… {} more", hits.len() - PALETTE_MAX_RESULTS));
+                }
+            });
+
+        if let Some(id) = picked {
+            self.graph.center_on_node(id, rect.size());
+            open = false;
+        }
+        if open {
+            self.graph.palette_query = Some(query);
+        }
+    }
+}
+
+fn result_summary(node: &StoryNode) -> String {
+    let text = match node {
+        StoryNode::Dialogue { speaker, text } => format!("{speaker}: {text}"),
+        StoryNode::Choice { prompt, .. } => prompt.clone(),
+        StoryNode::Jump { target } | StoryNode::JumpIf { target, .. } => format!("→ {target}"),
+        StoryNode::Comment { text } => text.clone(),
+        other => other.type_name().to_string(),
+    };
+    text.chars().take(40).collect()
+}
@@ -13,6 +13,14 @@ impl<'a> NodeEditorPanel<'a> {
         let mut double_clicked_node = None;
         let nodes: Vec<_> = self.graph.nodes().cloned().collect();
 
+        self.graph.refresh_reachability(ui.input(|i| i.time));
+        if self.graph.reachability_stale() {
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_secs_f64(
+                    REACHABILITY_DEBOUNCE_SECS,
+                ));
+        }
+
         // 1. Handle Drag Start (Nodes)
         if response.drag_started_by(egui::PointerButton::Primary) {
             if let Some(pos) = response.interact_pointer_pos() {
@@ -39,36 +47,62 @@ impl<'a> NodeEditorPanel<'a> {
                     }
                 }
 
-                // Then Node Drag
+                // Then Node Drag (the whole selection moves together)
                 for (id, _, n_pos) in nodes.iter().rev() {
                     let screen_pos = self.graph_to_screen(rect, *n_pos);
                     let height = self.get_node_height(self.graph.get_node(*id).unwrap());
                     let size = egui::vec2(NODE_WIDTH, height) * self.graph.zoom();
                     let node_rect = egui::Rect::from_min_size(screen_pos, size);
                     if node_rect.contains(pos) {
+                        if !self.graph.is_selected(*id) {
+                            self.graph.select_only(*id);
+                        }
                         self.graph.dragging_node = Some(*id);
                         break;
                     }
                 }
+
+                // Then a group's title strip
+                if self.graph.dragging_node.is_none() {
+                    self.graph.dragging_group =
+                        self.graph.group_header_at(self.screen_to_graph(rect, pos));
+                }
+
+                // Otherwise start a rubber-band selection
+                if self.graph.dragging_node.is_none()
+                    && self.graph.dragging_group.is_none()
+                    && !ui.input(|i| i.modifiers.ctrl)
+                {
+                    self.graph.box_select_origin = Some(self.screen_to_graph(rect, pos));
+                }
             }
         }
 
         // 2. Handle Dragging
         if response.dragged_by(egui::PointerButton::Primary) && self.graph.context_menu.is_none() {
-            if let Some(id) = self.graph.dragging_node {
-                let delta = ui.input(|i| i.pointer.delta()) / self.graph.zoom();
-                if let Some(node_pos) = self.graph.get_node_pos_mut(id) {
-                    if delta.length_sq() > 0.0 {
-                        *node_pos += delta;
-                        self.graph.mark_modified();
-                    }
+            let delta = ui.input(|i| i.pointer.delta()) / self.graph.zoom();
+            if delta.length_sq() > 0.0 {
+                if self.graph.dragging_node.is_some() {
+                    self.graph.move_selected(delta);
+                } else if let Some(group_id) = self.graph.dragging_group {
+                    self.graph.move_group(group_id, delta);
                 }
             }
         }
 
         // 3. Handle Drag End
         if response.drag_stopped() {
-            self.graph.dragging_node = None;
+            if self.graph.dragging_node.take().is_some() {
+                self.graph.snap_selected();
+            }
+            self.graph.dragging_group = None;
+            if let Some(origin) = self.graph.box_select_origin.take() {
+                if let Some(pos) = response.interact_pointer_pos() {
+                    let additive = ui.input(|i| i.modifiers.shift);
+                    let band = egui::Rect::from_two_pos(origin, self.screen_to_graph(rect, pos));
+                    self.graph.select_in_rect(band, additive);
+                }
+            }
             if let Some((from, _)) = self.graph.connecting_from {
                 let mut dropped_on_node = false;
                 if let Some(pos) = response.interact_pointer_pos() {
@@ -108,15 +142,18 @@ impl<'a> NodeEditorPanel<'a> {
                 continue;
             }
 
-            let is_selected = self.graph.selected == Some(*id);
+            let is_selected = self.graph.is_selected(*id);
             let is_connecting = self.graph.connecting_from.map(|(nid, _)| nid) == Some(*id);
             let is_dragging = self.graph.dragging_node == Some(*id);
+            let is_unreachable = self.graph.is_unreachable(*id);
 
             // Shape
             let bg_color = if is_selected || is_dragging {
                 node.color().linear_multiply(1.3)
             } else if is_connecting {
                 egui::Color32::YELLOW.linear_multiply(0.3)
+            } else if is_unreachable {
+                node.color().linear_multiply(0.5)
             } else {
                 node.color()
             };
@@ -124,6 +161,9 @@ impl<'a> NodeEditorPanel<'a> {
             painter.rect_filled(node_rect, 6.0 * self.graph.zoom(), bg_color);
             let border_color = if is_selected {
                 egui::Color32::from_rgb(100, 150, 255)
+            } else if is_unreachable {
+                // Dimmed body plus a red outline marks nodes the story never reaches.
+                egui::Color32::from_rgb(200, 70, 70)
             } else {
                 egui::Color32::from_rgb(80, 80, 90)
             };
@@ -132,6 +172,13 @@ impl<'a> NodeEditorPanel<'a> {
                 2.0 * self.graph.zoom(),
                 egui::Stroke::new(2.0, border_color),
             );
+            if self.graph.has_breakpoint(*id) {
+                painter.circle_filled(
+                    node_rect.right_top() + egui::vec2(-8.0, 8.0) * self.graph.zoom(),
+                    4.0 * self.graph.zoom(),
+                    egui::Color32::from_rgb(220, 40, 40),
+                );
+            }
 
             // Content
             let font_size = 13.0 * self.graph.zoom();
@@ -310,7 +357,27 @@ impl<'a> NodeEditorPanel<'a> {
         }
 
         if let Some(id) = clicked_node {
-            self.graph.selected = Some(id);
+            if ui.input(|i| i.modifiers.shift) {
+                self.graph.toggle_selection(id);
+            } else {
+                self.graph.select_only(id);
+            }
+        }
+        if let (Some(origin), Some(pointer)) = (
+            self.graph.box_select_origin,
+            response.interact_pointer_pos(),
+        ) {
+            let band = egui::Rect::from_two_pos(self.graph_to_screen(rect, origin), pointer);
+            painter.rect_filled(
+                band,
+                0.0,
+                egui::Color32::from_rgba_unmultiplied(100, 150, 255, 30),
+            );
+            painter.rect_stroke(
+                band,
+                0.0,
+                egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 150, 255)),
+            );
         }
         if let Some((id, pos)) = right_clicked_node {
             self.graph.context_menu = Some(ContextMenu {
@@ -371,6 +438,15 @@ impl<'a> NodeEditorPanel<'a> {
                 visual_novel_engine::EventRaw::ExtCall { command, .. } => {
                     format!("Ext: {}", command.chars().take(12).collect::<String>())
                 }
+                visual_novel_engine::EventRaw::InputText { var_key, .. } => {
+                    format!("Input: {}", var_key.chars().take(12).collect::<String>())
+                }
+                visual_novel_engine::EventRaw::UnlockGallery { id } => {
+                    format!("CG: {}", id.chars().take(12).collect::<String>())
+                }
+                visual_novel_engine::EventRaw::Achieve { id } => {
+                    format!("Achieve: {}", id.chars().take(12).collect::<String>())
+                }
                 _ => {
                     let json = event.to_json_value();
                     let type_name = json
@@ -391,6 +467,7 @@ impl<'a> NodeEditorPanel<'a> {
             StoryNode::CharacterPlacement { name, x, y, .. } => {
                 format!("{}: ({}, {})", name, x, y)
             }
+            StoryNode::Comment { text } => text.chars().take(24).collect(),
         }
     }
 
@@ -4,25 +4,38 @@
 //! the story flow. It handles node management and connections.
 //! Script synchronization is in the `script_sync` module.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use eframe::egui;
 use serde::{Deserialize, Serialize};
 use visual_novel_engine::{CharacterPlacementRaw, ScriptRaw};
 
 use super::node_types::{
-    node_visual_height, ContextMenu, StoryNode, NODE_HEIGHT, NODE_VERTICAL_SPACING, NODE_WIDTH,
-    ZOOM_DEFAULT, ZOOM_MAX, ZOOM_MIN,
+    node_visual_height, ContextMenu, StoryNode, GRID_SIZE_DEFAULT, NODE_HEIGHT,
+    NODE_VERTICAL_SPACING, NODE_WIDTH, ZOOM_DEFAULT, ZOOM_MAX, ZOOM_MIN,
 };
 use super::script_sync;
 
+mod align;
+mod clipboard;
 mod connections;
+mod groups;
 mod layout;
 mod mutations;
 mod navigation;
+mod reachability;
+mod references;
 mod search;
+mod selection;
 mod view;
 
+pub use align::snap_to_grid;
+pub use clipboard::SubgraphClip;
+pub use groups::NodeGroup;
+pub(crate) use groups::GROUP_HEADER_HEIGHT;
+pub(crate) use reachability::REACHABILITY_DEBOUNCE_SECS;
+pub use references::ReferenceKind;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GraphConnection {
     pub from: u32,
@@ -49,32 +62,66 @@ pub struct NodeGraph {
     /// Named anchors for fast navigation in large graphs.
     #[serde(default)]
     pub(crate) bookmarks: BTreeMap<String, u32>,
+    /// Editor-only frames around related nodes.
+    #[serde(default)]
+    pub(crate) groups: Vec<NodeGroup>,
+    /// Nodes where debug dry runs halt; editor-session only.
+    #[serde(skip)]
+    pub(crate) breakpoints: BTreeSet<u32>,
     /// Next available node ID
     next_id: u32,
     /// Currently selected node
     #[serde(skip)]
     pub selected: Option<u32>,
+    /// Multi-selection; only active while it contains `selected`.
+    #[serde(skip)]
+    pub(crate) selected_set: HashSet<u32>,
+    /// Graph-space origin of an in-progress rubber-band selection.
+    #[serde(skip)]
+    pub(crate) box_select_origin: Option<egui::Pos2>,
     /// Pan offset (world-space translation)
     pub(crate) pan: egui::Vec2,
     /// Zoom level
     pub(crate) zoom: f32,
+    /// Round dropped nodes to the nearest grid cell.
+    #[serde(default)]
+    pub(crate) snap_enabled: bool,
+    /// Grid cell size used for drawing and snapping.
+    #[serde(default = "default_grid_size")]
+    pub(crate) grid_size: f32,
     /// Node being edited inline
     #[serde(skip)]
     pub editing: Option<u32>,
     /// Node being dragged (robust interaction)
     #[serde(skip)]
     pub dragging_node: Option<u32>,
+    /// Group whose frame is being dragged
+    #[serde(skip)]
+    pub(crate) dragging_group: Option<u32>,
     /// Node being connected (Connect To mode)
     #[serde(skip)]
     pub connecting_from: Option<(u32, usize)>,
     /// Active context menu
     #[serde(skip)]
     pub context_menu: Option<ContextMenu>,
+    /// Debounced unreachable-node set for live highlighting.
+    #[serde(skip)]
+    pub(crate) reachability: reachability::ReachabilityCache,
+    /// Query of the open search palette (Ctrl+F); `None` when closed.
+    #[serde(skip)]
+    pub(crate) palette_query: Option<String>,
+    /// Node to center on the next time the editor canvas is drawn.
+    #[serde(skip)]
+    pub(crate) pending_focus: Option<u32>,
     /// Dirty flag (script modified since last save)
     #[serde(skip)]
     pub(crate) modified: bool,
 }
 
+fn default_grid_size() -> f32 {
+    GRID_SIZE_DEFAULT
+}
+
 impl Default for NodeGraph {
     fn default() -> Self {
         Self {
@@ -82,14 +129,24 @@ impl Default for NodeGraph {
             connections: Vec::new(),
             scene_profiles: BTreeMap::new(),
             bookmarks: BTreeMap::new(),
+            groups: Vec::new(),
+            breakpoints: BTreeSet::new(),
             next_id: 0,
             selected: None,
+            selected_set: HashSet::new(),
+            box_select_origin: None,
             pan: egui::Vec2::ZERO,
             zoom: ZOOM_DEFAULT,
+            snap_enabled: false,
+            grid_size: GRID_SIZE_DEFAULT,
             editing: None,
             dragging_node: None,
+            dragging_group: None,
             connecting_from: None,
             context_menu: None,
+            reachability: reachability::ReachabilityCache::default(),
+            palette_query: None,
+            pending_focus: None,
             modified: false,
         }
     }
@@ -105,7 +162,7 @@ impl NodeGraph {
         let id = self.next_id;
         self.next_id += 1;
         self.nodes.push((id, node, pos));
-        self.modified = true;
+        self.mark_modified();
         id
     }
 
@@ -114,6 +171,11 @@ impl NodeGraph {
         self.nodes.retain(|(nid, _, _)| *nid != id);
         self.connections.retain(|c| c.from != id && c.to != id);
         self.bookmarks.retain(|_, target| *target != id);
+        self.breakpoints.remove(&id);
+        self.selected_set.remove(&id);
+        for group in &mut self.groups {
+            group.members.remove(&id);
+        }
 
         if self.selected == Some(id) {
             self.selected = None;
@@ -127,7 +189,7 @@ impl NodeGraph {
             }
         }
 
-        self.modified = true;
+        self.mark_modified();
     }
 
     /// Returns the number of nodes.
@@ -156,6 +218,7 @@ impl NodeGraph {
     /// Marks the graph as modified.
     pub fn mark_modified(&mut self) {
         self.modified = true;
+        self.reachability.invalidate();
     }
 
     /// Creates a node graph from a raw script.
@@ -0,0 +1,91 @@
+use super::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Nodes and the edges between them, detached from any graph.
+///
+/// Serializes to JSON so a copied branch can travel through the OS clipboard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubgraphClip {
+    pub nodes: Vec<(u32, StoryNode, egui::Pos2)>,
+    pub connections: Vec<GraphConnection>,
+}
+
+impl SubgraphClip {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl NodeGraph {
+    /// Captures `ids` and the edges among them; edges leaving the set are dropped.
+    pub fn copy_subgraph(&self, ids: &[u32]) -> SubgraphClip {
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|(id, _, _)| ids.contains(id))
+            .cloned()
+            .collect();
+        let connections = self
+            .connections
+            .iter()
+            .filter(|conn| {
+                nodes.iter().any(|(id, _, _)| *id == conn.from)
+                    && nodes.iter().any(|(id, _, _)| *id == conn.to)
+            })
+            .cloned()
+            .collect();
+        SubgraphClip { nodes, connections }
+    }
+
+    /// Inserts a copy of `clip` shifted by `offset`, with fresh ids.
+    ///
+    /// Returns the new ids in clip order and selects the first one.
+    pub fn paste_subgraph(&mut self, clip: &SubgraphClip, offset: egui::Vec2) -> Vec<u32> {
+        let mut remap = HashMap::with_capacity(clip.nodes.len());
+        let mut new_ids = Vec::with_capacity(clip.nodes.len());
+        for (old_id, node, pos) in &clip.nodes {
+            let new_id = self.add_node(node.clone(), *pos + offset);
+            remap.insert(*old_id, new_id);
+            new_ids.push(new_id);
+        }
+        for conn in &clip.connections {
+            if let (Some(&from), Some(&to)) = (remap.get(&conn.from), remap.get(&conn.to)) {
+                self.connections.push(GraphConnection {
+                    from,
+                    from_port: conn.from_port,
+                    to,
+                });
+            }
+        }
+        if let Some(&first) = new_ids.first() {
+            self.selected = Some(first);
+        }
+        new_ids
+    }
+
+    /// `node_id` plus every node reachable from it, in breadth-first order.
+    pub fn branch_from(&self, node_id: u32) -> Vec<u32> {
+        if self.get_node(node_id).is_none() {
+            return Vec::new();
+        }
+        let mut branch = vec![node_id];
+        let mut queue = VecDeque::from([node_id]);
+        while let Some(current) = queue.pop_front() {
+            for next in self.outgoing_nodes(current) {
+                if !branch.contains(&next) {
+                    branch.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        branch
+    }
+}
@@ -40,14 +40,14 @@ impl NodeGraph {
                 from_port,
                 to,
             });
-            self.modified = true;
+            self.mark_modified();
         }
     }
 
     /// Disconnects two nodes (any port).
     pub fn disconnect(&mut self, from: u32, to: u32) {
         self.connections.retain(|c| !(c.from == from && c.to == to));
-        self.modified = true;
+        self.mark_modified();
     }
 
     /// Disconnects all outbound connections from a source node.
@@ -55,7 +55,7 @@ impl NodeGraph {
         let before = self.connections.len();
         self.connections.retain(|c| c.from != from);
         if self.connections.len() != before {
-            self.modified = true;
+            self.mark_modified();
         }
     }
 
@@ -65,7 +65,7 @@ impl NodeGraph {
         self.connections
             .retain(|c| !(c.from == from && c.from_port == from_port));
         if self.connections.len() != before {
-            self.modified = true;
+            self.mark_modified();
         }
     }
 
@@ -92,7 +92,7 @@ impl NodeGraph {
             }
         }
 
-        self.modified = true;
+        self.mark_modified();
     }
 
     pub(crate) fn ensure_choice_option(&mut self, node_id: u32, option_idx: usize) {
@@ -107,7 +107,7 @@ impl NodeGraph {
             changed = true;
         }
         if changed {
-            self.modified = true;
+            self.mark_modified();
         }
     }
 }
@@ -0,0 +1,154 @@
+use super::*;
+
+/// Padding between a group frame and its outermost members (graph space).
+pub(crate) const GROUP_PADDING: f32 = 20.0;
+/// Height of the title strip above the members; dragging it moves the group.
+pub(crate) const GROUP_HEADER_HEIGHT: f32 = 24.0;
+
+/// Labeled frame drawn behind a set of nodes. Editor-only; never compiled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeGroup {
+    pub id: u32,
+    pub title: String,
+    pub color: [u8; 3],
+    pub members: BTreeSet<u32>,
+}
+
+impl NodeGroup {
+    pub fn color32(&self) -> egui::Color32 {
+        let [r, g, b] = self.color;
+        egui::Color32::from_rgb(r, g, b)
+    }
+}
+
+impl NodeGraph {
+    /// Creates a group around the existing nodes in `members`. A node belongs
+    /// to at most one group, so members are taken from any previous group.
+    pub fn create_group(&mut self, title: impl Into<String>, members: &[u32]) -> u32 {
+        let id = self
+            .groups
+            .iter()
+            .map(|group| group.id + 1)
+            .max()
+            .unwrap_or(0);
+        self.groups.push(NodeGroup {
+            id,
+            title: title.into(),
+            color: [70, 90, 120],
+            members: BTreeSet::new(),
+        });
+        for &node_id in members {
+            self.add_to_group(id, node_id);
+        }
+        self.mark_modified();
+        id
+    }
+
+    /// Wraps the current selection in a new group; returns `None` when
+    /// nothing is selected.
+    pub fn group_selected(&mut self, title: impl Into<String>) -> Option<u32> {
+        let ids = self.selected_ids();
+        if ids.is_empty() {
+            return None;
+        }
+        Some(self.create_group(title, &ids))
+    }
+
+    /// Deletes the frame; member nodes stay where they are.
+    pub fn remove_group(&mut self, group_id: u32) -> bool {
+        let before = self.groups.len();
+        self.groups.retain(|group| group.id != group_id);
+        let removed = self.groups.len() != before;
+        if removed {
+            self.mark_modified();
+        }
+        removed
+    }
+
+    pub fn add_to_group(&mut self, group_id: u32, node_id: u32) -> bool {
+        if self.get_node(node_id).is_none() || self.group(group_id).is_none() {
+            return false;
+        }
+        for group in &mut self.groups {
+            if group.id == group_id {
+                group.members.insert(node_id);
+            } else {
+                group.members.remove(&node_id);
+            }
+        }
+        self.mark_modified();
+        true
+    }
+
+    pub fn remove_from_group(&mut self, group_id: u32, node_id: u32) -> bool {
+        let removed = self
+            .groups
+            .iter_mut()
+            .find(|group| group.id == group_id)
+            .is_some_and(|group| group.members.remove(&node_id));
+        if removed {
+            self.mark_modified();
+        }
+        removed
+    }
+
+    pub fn groups(&self) -> &[NodeGroup] {
+        &self.groups
+    }
+
+    pub fn group(&self, group_id: u32) -> Option<&NodeGroup> {
+        self.groups.iter().find(|group| group.id == group_id)
+    }
+
+    /// The group containing `node_id`, if any.
+    pub fn group_of(&self, node_id: u32) -> Option<u32> {
+        self.groups
+            .iter()
+            .find(|group| group.members.contains(&node_id))
+            .map(|group| group.id)
+    }
+
+    /// Frame rectangle in graph space: the members' bounds plus padding and
+    /// the title strip. `None` for an empty group.
+    pub fn group_bounds(&self, group_id: u32) -> Option<egui::Rect> {
+        let group = self.group(group_id)?;
+        let bounds = self
+            .nodes
+            .iter()
+            .filter(|(id, _, _)| group.members.contains(id))
+            .map(|(_, node, pos)| {
+                egui::Rect::from_min_size(*pos, egui::vec2(NODE_WIDTH, node_visual_height(node)))
+            })
+            .reduce(|acc, rect| acc.union(rect))?;
+        let mut frame = bounds.expand(GROUP_PADDING);
+        frame.min.y -= GROUP_HEADER_HEIGHT;
+        Some(frame)
+    }
+
+    /// Topmost group whose title strip contains `pos` (graph space).
+    pub fn group_header_at(&self, pos: egui::Pos2) -> Option<u32> {
+        self.groups.iter().rev().find_map(|group| {
+            let frame = self.group_bounds(group.id)?;
+            let header = egui::Rect::from_min_max(
+                frame.min,
+                egui::pos2(frame.max.x, frame.min.y + GROUP_HEADER_HEIGHT),
+            );
+            header.contains(pos).then_some(group.id)
+        })
+    }
+
+    /// Moves every member of the group by `delta` (graph space).
+    pub fn move_group(&mut self, group_id: u32, delta: egui::Vec2) {
+        let Some(members) = self.group(group_id).map(|group| group.members.clone()) else {
+            return;
+        };
+        for (id, _, pos) in &mut self.nodes {
+            if members.contains(id) {
+                *pos += delta;
+            }
+        }
+        if !members.is_empty() {
+            self.mark_modified();
+        }
+    }
+}
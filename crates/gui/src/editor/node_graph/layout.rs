@@ -104,7 +104,7 @@ impl NodeGraph {
             changed = true;
         }
         if changed {
-            self.modified = true;
+            self.mark_modified();
         }
         changed
     }
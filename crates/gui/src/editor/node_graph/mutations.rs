@@ -27,7 +27,44 @@ impl NodeGraph {
             to: target_id,
         });
 
-        self.modified = true;
+        self.mark_modified();
+    }
+
+    /// Deletes a node, joining its predecessor to its successor when the
+    /// node sits in a plain chain (exactly one inbound and one outbound edge).
+    ///
+    /// Choice nodes are never rerouted: with several option ports there is no
+    /// single successor to pick, so their edges are simply dropped. Returns
+    /// `true` when the flow was reconnected.
+    pub fn delete_node_reconnect(&mut self, id: u32) -> bool {
+        let Some(node) = self.get_node(id) else {
+            return false;
+        };
+        let is_choice = matches!(node, StoryNode::Choice { .. });
+        let inbound: Vec<(u32, usize)> = self
+            .connections
+            .iter()
+            .filter(|conn| conn.to == id)
+            .map(|conn| (conn.from, conn.from_port))
+            .collect();
+        let outbound: Vec<u32> = self
+            .connections
+            .iter()
+            .filter(|conn| conn.from == id)
+            .map(|conn| conn.to)
+            .collect();
+
+        self.remove_node(id);
+
+        match (inbound.as_slice(), outbound.as_slice()) {
+            ([(from, from_port)], [to]) if !is_choice && from != to => {
+                self.connect_port(*from, *from_port, *to);
+                self.connections.iter().any(|conn| {
+                    conn.from == *from && conn.from_port == *from_port && conn.to == *to
+                })
+            }
+            _ => false,
+        }
     }
 
     /// Inserts a new node after the target node, re-routing connections.
@@ -52,7 +89,7 @@ impl NodeGraph {
             to: new_id,
         });
 
-        self.modified = true;
+        self.mark_modified();
     }
 
     /// Converts a node to a Choice node with default options.
@@ -62,7 +99,7 @@ impl NodeGraph {
                 prompt: "Choose an option:".to_string(),
                 options: vec!["Option 1".to_string(), "Option 2".to_string()],
             };
-            self.modified = true;
+            self.mark_modified();
         }
     }
 
@@ -136,7 +173,7 @@ impl NodeGraph {
         if let Some(StoryNode::Scene { profile, .. }) = self.get_node_mut(node_id) {
             *profile = Some(profile_id);
         }
-        self.modified = true;
+        self.mark_modified();
         true
     }
 
@@ -160,7 +197,7 @@ impl NodeGraph {
         *music = scene_profile.music;
         *characters = scene_profile.characters;
         *profile = Some(profile_id.to_string());
-        self.modified = true;
+        self.mark_modified();
         true
     }
 
@@ -179,14 +216,14 @@ impl NodeGraph {
             return false;
         }
         self.bookmarks.insert(normalized, node_id);
-        self.modified = true;
+        self.mark_modified();
         true
     }
 
     /// Removes a bookmark by name.
     pub fn remove_bookmark(&mut self, name: &str) -> bool {
         if self.bookmarks.remove(name).is_some() {
-            self.modified = true;
+            self.mark_modified();
             true
         } else {
             false
@@ -202,4 +239,82 @@ impl NodeGraph {
     pub fn bookmarks(&self) -> impl Iterator<Item = (&String, &u32)> {
         self.bookmarks.iter()
     }
+
+    /// Flags or unflags a node as a dry-run breakpoint. Returns the new state.
+    pub fn toggle_breakpoint(&mut self, node_id: u32) -> bool {
+        if self.breakpoints.remove(&node_id) {
+            return false;
+        }
+        if self.get_node(node_id).is_none() {
+            return false;
+        }
+        self.breakpoints.insert(node_id)
+    }
+
+    pub fn has_breakpoint(&self, node_id: u32) -> bool {
+        self.breakpoints.contains(&node_id)
+    }
+
+    /// Node ids flagged as breakpoints, in ascending order.
+    pub fn breakpoints(&self) -> &BTreeSet<u32> {
+        &self.breakpoints
+    }
+
+    /// Maps each dialogue speaker spelling that differs only by case from a
+    /// more common one to that canonical spelling (first seen wins ties).
+    /// Speakers with a single spelling are absent.
+    pub fn speaker_casing_fixes(&self) -> BTreeMap<String, String> {
+        // Lowercased name -> spellings with line counts, in first-seen order.
+        let mut groups: BTreeMap<String, Vec<(&str, usize)>> = BTreeMap::new();
+        for (_, node, _) in &self.nodes {
+            let StoryNode::Dialogue { speaker, .. } = node else {
+                continue;
+            };
+            if speaker.trim().is_empty() {
+                continue;
+            }
+            let spellings = groups.entry(speaker.to_lowercase()).or_default();
+            match spellings.iter_mut().find(|(name, _)| *name == speaker) {
+                Some((_, count)) => *count += 1,
+                None => spellings.push((speaker, 1)),
+            }
+        }
+
+        let mut fixes = BTreeMap::new();
+        for spellings in groups.values().filter(|spellings| spellings.len() > 1) {
+            let mut canonical = spellings[0];
+            for candidate in &spellings[1..] {
+                if candidate.1 > canonical.1 {
+                    canonical = *candidate;
+                }
+            }
+            for (name, _) in spellings {
+                if *name != canonical.0 {
+                    fixes.insert(name.to_string(), canonical.0.to_string());
+                }
+            }
+        }
+        fixes
+    }
+
+    /// Renames every dialogue line spoken by exactly `from` to `to`. Returns
+    /// how many nodes changed.
+    pub fn rename_speaker(&mut self, from: &str, to: &str) -> usize {
+        if from == to {
+            return 0;
+        }
+        let mut renamed = 0;
+        for (_, node, _) in &mut self.nodes {
+            if let StoryNode::Dialogue { speaker, .. } = node {
+                if speaker == from {
+                    *speaker = to.to_string();
+                    renamed += 1;
+                }
+            }
+        }
+        if renamed > 0 {
+            self.mark_modified();
+        }
+        renamed
+    }
 }
@@ -98,7 +98,7 @@ impl NodeGraph {
             .into_iter()
             .filter(|node_id| {
                 self.get_node(*node_id)
-                    .is_some_and(|node| !node.is_marker())
+                    .is_some_and(|node| !node.is_marker() && !node.is_comment())
             })
             .collect()
     }
@@ -0,0 +1,89 @@
+use super::*;
+use visual_novel_engine::StoryGraph;
+
+/// Seconds the graph must stay unchanged before reachability is recomputed.
+pub(crate) const REACHABILITY_DEBOUNCE_SECS: f64 = 0.25;
+
+/// Cached unreachable-node set for live highlighting. `mark_modified` bumps
+/// `generation`; the set is rebuilt once edits settle for the debounce window.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ReachabilityCache {
+    unreachable: BTreeSet<u32>,
+    generation: u64,
+    computed_generation: Option<u64>,
+    seen_generation: u64,
+    last_change_time: f64,
+}
+
+impl ReachabilityCache {
+    pub(crate) fn invalidate(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub(crate) fn is_stale(&self) -> bool {
+        self.computed_generation != Some(self.generation)
+    }
+}
+
+impl NodeGraph {
+    /// Whether the cached unreachable set lags behind the latest edit.
+    pub fn reachability_stale(&self) -> bool {
+        self.reachability.is_stale()
+    }
+
+    /// Nodes flagged unreachable by the last recomputation.
+    pub fn is_unreachable(&self, node_id: u32) -> bool {
+        self.reachability.unreachable.contains(&node_id)
+    }
+
+    /// Recomputes the unreachable set when it is stale and no edit happened
+    /// in the last [`REACHABILITY_DEBOUNCE_SECS`]. `now` is in seconds.
+    /// Returns true when a recomputation ran.
+    pub fn refresh_reachability(&mut self, now: f64) -> bool {
+        let cache = &mut self.reachability;
+        if !cache.is_stale() {
+            return false;
+        }
+        if cache.seen_generation != cache.generation {
+            cache.seen_generation = cache.generation;
+            cache.last_change_time = now;
+        }
+        // The first computation runs right away; later ones wait for edits
+        // to settle.
+        if cache.computed_generation.is_some()
+            && now - cache.last_change_time < REACHABILITY_DEBOUNCE_SECS
+        {
+            return false;
+        }
+
+        let unreachable = self.compute_unreachable();
+        let cache = &mut self.reachability;
+        cache.unreachable = unreachable;
+        cache.computed_generation = Some(cache.generation);
+        true
+    }
+
+    /// Nodes that never run: those the script export drops because no path
+    /// from Start reaches them, plus events `StoryGraph` finds unreachable
+    /// once jumps are resolved. Markers and comments are never flagged.
+    fn compute_unreachable(&self) -> BTreeSet<u32> {
+        let order = self.script_order_node_ids();
+        let mut unreachable: BTreeSet<u32> = self
+            .nodes
+            .iter()
+            .filter(|(id, node, _)| !node.is_marker() && !node.is_comment() && !order.contains(id))
+            .map(|(id, _, _)| *id)
+            .collect();
+
+        if let Ok(compiled) = script_sync::to_script(self).compile() {
+            let story = StoryGraph::from_script(&compiled);
+            unreachable.extend(
+                story
+                    .unreachable_nodes()
+                    .into_iter()
+                    .filter_map(|ip| order.get(ip as usize).copied()),
+            );
+        }
+        unreachable
+    }
+}
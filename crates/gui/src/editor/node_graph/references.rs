@@ -0,0 +1,82 @@
+use super::*;
+use visual_novel_engine::EventRaw;
+
+/// How an event points at a label.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+    Jump,
+    JumpIf,
+    /// Choice option at `index` whose target is the label.
+    ChoiceOption {
+        index: usize,
+    },
+}
+
+impl NodeGraph {
+    /// Label the compiled script gives to a node, if the node exists.
+    pub fn label_for_node(&self, id: u32) -> Option<String> {
+        self.get_node(id).map(|node| match node {
+            StoryNode::Start => "start".to_string(),
+            StoryNode::End => "__end".to_string(),
+            _ => format!("node_{id}"),
+        })
+    }
+
+    /// Every jump, conditional jump and choice option that targets `name`,
+    /// as `(source node id, kind)` ordered by node id.
+    pub fn references_to_label(&self, name: &str) -> Vec<(u32, ReferenceKind)> {
+        let mut references = Vec::new();
+        for (id, node, _) in &self.nodes {
+            match node {
+                StoryNode::Jump { target } if target == name => {
+                    references.push((*id, ReferenceKind::Jump));
+                }
+                StoryNode::JumpIf { target, .. } if target == name => {
+                    references.push((*id, ReferenceKind::JumpIf));
+                }
+                StoryNode::Choice { .. } => {
+                    let mut ports = self
+                        .connections
+                        .iter()
+                        .filter(|connection| connection.from == *id)
+                        .filter(|connection| {
+                            self.label_for_node(connection.to).as_deref() == Some(name)
+                        })
+                        .map(|connection| connection.from_port)
+                        .collect::<Vec<_>>();
+                    ports.sort_unstable();
+                    references.extend(
+                        ports
+                            .into_iter()
+                            .map(|index| (*id, ReferenceKind::ChoiceOption { index })),
+                    );
+                }
+                StoryNode::Generic(event) => {
+                    references.extend(
+                        generic_references(event, name)
+                            .into_iter()
+                            .map(|kind| (*id, kind)),
+                    );
+                }
+                _ => {}
+            }
+        }
+        references.sort_by_key(|(id, _)| *id);
+        references
+    }
+}
+
+fn generic_references(event: &EventRaw, name: &str) -> Vec<ReferenceKind> {
+    match event {
+        EventRaw::Jump { target } if target == name => vec![ReferenceKind::Jump],
+        EventRaw::JumpIf { target, .. } if target == name => vec![ReferenceKind::JumpIf],
+        EventRaw::Choice(choice) => choice
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| option.target == name)
+            .map(|(index, _)| ReferenceKind::ChoiceOption { index })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
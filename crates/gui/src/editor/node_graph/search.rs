@@ -20,6 +20,28 @@ impl NodeGraph {
             })
             .collect()
     }
+
+    /// Command-palette search: node content as in [`Self::search_nodes`],
+    /// plus exact script labels (`node_12`, `start`) and bookmark names.
+    pub fn search(&self, query: &str) -> Vec<u32> {
+        let needle = query.trim().to_ascii_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let content_hits = self.search_nodes(&needle);
+        self.nodes
+            .iter()
+            .map(|(id, _, _)| *id)
+            .filter(|id| {
+                content_hits.contains(id)
+                    || self.label_for_node(*id).as_deref() == Some(needle.as_str())
+                    || self.bookmarks.iter().any(|(name, target)| {
+                        target == id && name.to_ascii_lowercase().contains(&needle)
+                    })
+            })
+            .collect()
+    }
 }
 
 fn searchable_text(node: &StoryNode) -> String {
@@ -85,6 +107,7 @@ fn searchable_text(node: &StoryNode) -> String {
         }
         StoryNode::CharacterPlacement { name, .. } => fields.push(name.to_ascii_lowercase()),
         StoryNode::Generic(event) => fields.push(event.to_json_string().to_ascii_lowercase()),
+        StoryNode::Comment { text } => fields.push(text.to_ascii_lowercase()),
         StoryNode::Start | StoryNode::End => {}
     }
     fields.join(" ")
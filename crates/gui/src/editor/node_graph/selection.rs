@@ -0,0 +1,112 @@
+use super::*;
+
+impl NodeGraph {
+    /// Selects `id` alone, dropping any multi-selection.
+    pub fn select_only(&mut self, id: u32) {
+        self.selected_set.clear();
+        self.selected_set.insert(id);
+        self.selected = Some(id);
+    }
+
+    /// Shift-click behavior: adds or removes `id` from the multi-selection.
+    ///
+    /// `selected` follows the most recently added member so the inspector
+    /// shows it; removing the primary falls back to another member.
+    pub fn toggle_selection(&mut self, id: u32) {
+        if let Some(primary) = self.selected {
+            // Promote a plain single selection into the set first.
+            if !self.selected_set.contains(&primary) {
+                self.selected_set.clear();
+                self.selected_set.insert(primary);
+            }
+        }
+        if self.selected_set.remove(&id) {
+            if self.selected == Some(id) {
+                self.selected = self.selected_set.iter().min().copied();
+            }
+        } else {
+            self.selected_set.insert(id);
+            self.selected = Some(id);
+        }
+    }
+
+    /// Replaces (or extends, when `additive`) the selection with every node
+    /// whose bounds intersect `rect`, given in graph space.
+    pub fn select_in_rect(&mut self, rect: egui::Rect, additive: bool) {
+        let hits = self.nodes_in_rect(rect);
+        if !additive {
+            self.selected_set.clear();
+            self.selected = None;
+        } else if let Some(primary) = self.selected {
+            self.selected_set.insert(primary);
+        }
+        self.selected_set.extend(hits.iter().copied());
+        if let Some(&last) = hits.last() {
+            self.selected = Some(last);
+        }
+    }
+
+    /// Ids of nodes whose graph-space bounds intersect `rect`.
+    pub fn nodes_in_rect(&self, rect: egui::Rect) -> Vec<u32> {
+        self.nodes
+            .iter()
+            .filter(|(_, node, pos)| {
+                rects_intersect(
+                    rect,
+                    egui::Rect::from_min_size(
+                        *pos,
+                        egui::vec2(NODE_WIDTH, node_visual_height(node)),
+                    ),
+                )
+            })
+            .map(|(id, _, _)| *id)
+            .collect()
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_set.clear();
+        self.selected = None;
+    }
+
+    /// Ids in the active selection, in ascending order.
+    pub fn selected_ids(&self) -> Vec<u32> {
+        match self.selected {
+            Some(primary) if self.selected_set.contains(&primary) => {
+                let mut ids: Vec<u32> = self.selected_set.iter().copied().collect();
+                ids.sort_unstable();
+                ids
+            }
+            Some(primary) => vec![primary],
+            None => Vec::new(),
+        }
+    }
+
+    pub fn is_selected(&self, id: u32) -> bool {
+        self.selected == Some(id)
+            || (self.selected_set.contains(&id)
+                && self
+                    .selected
+                    .is_some_and(|primary| self.selected_set.contains(&primary)))
+    }
+
+    /// Moves every selected node by `delta` (graph space).
+    pub fn move_selected(&mut self, delta: egui::Vec2) {
+        let ids = self.selected_ids();
+        for (id, _, pos) in &mut self.nodes {
+            if ids.contains(id) {
+                *pos += delta;
+            }
+        }
+        if !ids.is_empty() {
+            self.mark_modified();
+        }
+    }
+}
+
+/// Overlap test for rubber-band selection; a rect dragged in any direction
+/// is normalized first, and touching edges count as a hit.
+pub(crate) fn rects_intersect(a: egui::Rect, b: egui::Rect) -> bool {
+    let a = egui::Rect::from_two_pos(a.min, a.max);
+    let b = egui::Rect::from_two_pos(b.min, b.max);
+    a.min.x <= b.max.x && b.min.x <= a.max.x && a.min.y <= b.max.y && b.min.y <= a.max.y
+}
@@ -78,6 +78,24 @@ impl NodeGraph {
         );
     }
 
+    /// Pans so `node_id` sits in the middle of a viewport of `viewport_size`
+    /// screen pixels, and selects it. Returns false for unknown nodes.
+    pub fn center_on_node(&mut self, node_id: u32, viewport_size: egui::Vec2) -> bool {
+        let Some((_, node, pos)) = self.nodes.iter().find(|(id, _, _)| *id == node_id) else {
+            return false;
+        };
+        let node_center = *pos + egui::vec2(NODE_WIDTH, node_visual_height(node)) / 2.0;
+        self.pan = pan_to_center(node_center, viewport_size, self.zoom);
+        self.select_only(node_id);
+        true
+    }
+
+    /// Asks the node editor to center on `node_id` once it knows its
+    /// viewport size; see [`NodeGraph::center_on_node`].
+    pub fn request_focus(&mut self, node_id: u32) {
+        self.pending_focus = Some(node_id);
+    }
+
     /// Duplicates a node at an offset position.
     pub fn duplicate_node(&mut self, node_id: u32) {
         let Some((_, node, pos)) = self.nodes.iter().find(|(id, _, _)| *id == node_id).cloned()
@@ -90,3 +108,13 @@ impl NodeGraph {
         self.selected = Some(new_id);
     }
 }
+
+/// Pan that maps graph-space `target` onto the center of the viewport, given
+/// `screen = (graph + pan) * zoom` relative to the viewport origin.
+pub(super) fn pan_to_center(
+    target: egui::Pos2,
+    viewport_size: egui::Vec2,
+    zoom: f32,
+) -> egui::Vec2 {
+    viewport_size / (2.0 * zoom) - target.to_vec2()
+}
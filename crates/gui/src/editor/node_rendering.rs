@@ -130,11 +130,21 @@ pub fn render_context_menu(graph: &mut NodeGraph, ui: &egui::Ui) {
                     graph.context_menu = None;
                 }
 
+                let breakpoint_label = if graph.has_breakpoint(node_id) {
+                    "Remove Breakpoint"
+                } else {
+                    "Add Breakpoint"
+                };
+                if ui.button(breakpoint_label).clicked() {
+                    graph.toggle_breakpoint(node_id);
+                    graph.context_menu = None;
+                }
+
                 if ui
                     .button(egui::RichText::new("Delete").color(egui::Color32::RED))
                     .clicked()
                 {
-                    graph.remove_node(node_id);
+                    graph.delete_node_reconnect(node_id);
                     graph.context_menu = None;
                 }
             });
@@ -249,6 +259,10 @@ pub fn render_inline_editor(graph: &mut NodeGraph, ui: &egui::Ui) {
                 StoryNode::Start | StoryNode::End => {
                     ui.label("This node has no editable properties.");
                 }
+                StoryNode::Comment { text } => {
+                    ui.label("Comment (editor only)");
+                    changed |= ui.text_edit_multiline(text).changed();
+                }
                 StoryNode::Generic(event) => match event {
                     visual_novel_engine::EventRaw::ExtCall { command, args } => {
                         ui.label("External Action");
@@ -265,6 +279,41 @@ pub fn render_inline_editor(graph: &mut NodeGraph, ui: &egui::Ui) {
                             changed = true;
                         }
                     }
+                    visual_novel_engine::EventRaw::InputText {
+                        var_key,
+                        prompt,
+                        max_len,
+                    } => {
+                        ui.label("Text Input");
+                        ui.horizontal(|ui| {
+                            ui.label("Prompt:");
+                            changed |= ui.text_edit_singleline(prompt).changed();
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Store in:");
+                            changed |= ui.text_edit_singleline(var_key).changed();
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Max length:");
+                            changed |= ui
+                                .add(egui::DragValue::new(max_len).clamp_range(1..=256))
+                                .changed();
+                        });
+                    }
+                    visual_novel_engine::EventRaw::UnlockGallery { id } => {
+                        ui.label("Gallery Unlock");
+                        ui.horizontal(|ui| {
+                            ui.label("CG id:");
+                            changed |= ui.text_edit_singleline(id).changed();
+                        });
+                    }
+                    visual_novel_engine::EventRaw::Achieve { id } => {
+                        ui.label("Achievement");
+                        ui.horizontal(|ui| {
+                            ui.label("Achievement id:");
+                            changed |= ui.text_edit_singleline(id).changed();
+                        });
+                    }
                     _ => {
                         ui.label("This node type cannot be edited locally.");
                         ui.label("Use the Inspector to view details.");
@@ -19,6 +19,8 @@ pub const ZOOM_MIN: f32 = 0.25;
 pub const ZOOM_MAX: f32 = 4.0;
 /// Default zoom level (100%)
 pub const ZOOM_DEFAULT: f32 = 1.0;
+/// Default grid cell size in pixels
+pub const GRID_SIZE_DEFAULT: f32 = 50.0;
 /// Node visual width in pixels
 pub const NODE_WIDTH: f32 = 140.0;
 /// Node visual height in pixels
@@ -39,6 +41,7 @@ pub const NODE_VERTICAL_SPACING: f32 = 90.0;
 /// - `Jump`: An unconditional jump to a label
 /// - `Start`: Entry point marker (not a real event)
 /// - `End`: Terminal marker (not a real event)
+/// - `Comment`: Editor-only annotation (never compiled)
 ///
 /// # Invariant
 /// Start and End nodes are markers only; they don't generate script events.
@@ -98,6 +101,8 @@ pub enum StoryNode {
     },
     /// Generic node for unhandled events (preserves data).
     Generic(visual_novel_engine::EventRaw),
+    /// Free-floating note for authors; never compiled into the script.
+    Comment { text: String },
 }
 
 impl Default for StoryNode {
@@ -130,7 +135,13 @@ impl StoryNode {
             StoryNode::Transition { .. } => "Transition",
             StoryNode::CharacterPlacement { .. } => "Placement",
             StoryNode::Generic(visual_novel_engine::EventRaw::ExtCall { .. }) => "ExtCall",
+            StoryNode::Generic(visual_novel_engine::EventRaw::InputText { .. }) => "Text Input",
+            StoryNode::Generic(visual_novel_engine::EventRaw::UnlockGallery { .. }) => {
+                "Gallery Unlock"
+            }
+            StoryNode::Generic(visual_novel_engine::EventRaw::Achieve { .. }) => "Achievement",
             StoryNode::Generic(_) => "Generic Event",
+            StoryNode::Comment { .. } => "Comment",
         }
     }
 
@@ -154,7 +165,11 @@ impl StoryNode {
             StoryNode::Transition { .. } => "⏳",
             StoryNode::CharacterPlacement { .. } => "🧍",
             StoryNode::Generic(visual_novel_engine::EventRaw::ExtCall { .. }) => "🧩",
+            StoryNode::Generic(visual_novel_engine::EventRaw::InputText { .. }) => "⌨",
+            StoryNode::Generic(visual_novel_engine::EventRaw::UnlockGallery { .. }) => "🖼",
+            StoryNode::Generic(visual_novel_engine::EventRaw::Achieve { .. }) => "🏆",
             StoryNode::Generic(_) => "📦",
+            StoryNode::Comment { .. } => "📝",
         }
     }
 
@@ -181,6 +196,7 @@ impl StoryNode {
                 egui::Color32::from_rgb(90, 85, 110)
             }
             StoryNode::Generic(_) => egui::Color32::from_rgb(80, 80, 80), // Gray for generic
+            StoryNode::Comment { .. } => egui::Color32::from_rgb(110, 100, 45), // Sticky-note yellow
         }
     }
 
@@ -190,22 +206,28 @@ impl StoryNode {
         matches!(self, StoryNode::Start | StoryNode::End)
     }
 
+    /// Returns whether this node is an editor-only comment.
+    #[inline]
+    pub fn is_comment(&self) -> bool {
+        matches!(self, StoryNode::Comment { .. })
+    }
+
     /// Returns whether this node can have outgoing connections.
     ///
     /// # Invariant
-    /// End nodes should not have outgoing connections.
+    /// End and Comment nodes should not have outgoing connections.
     #[inline]
     pub fn can_connect_from(&self) -> bool {
-        !matches!(self, StoryNode::End)
+        !matches!(self, StoryNode::End | StoryNode::Comment { .. })
     }
 
     /// Returns whether this node can receive incoming connections.
     ///
     /// # Invariant
-    /// Start nodes should not have incoming connections.
+    /// Start and Comment nodes should not have incoming connections.
     #[inline]
     pub fn can_connect_to(&self) -> bool {
-        !matches!(self, StoryNode::Start)
+        !matches!(self, StoryNode::Start | StoryNode::Comment { .. })
     }
 }
 
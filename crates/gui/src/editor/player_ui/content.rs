@@ -64,6 +64,7 @@ pub(super) fn render_transition(
 pub(super) fn render_dialogue(
     ui: &mut egui::Ui,
     ctx: &egui::Context,
+    engine: &Engine,
     player: &mut PlayerSessionState,
     speaker: &str,
     text: &str,
@@ -111,7 +112,7 @@ pub(super) fn render_dialogue(
 
     if !text_complete {
         ctx.request_repaint_after(Duration::from_millis(16));
-    } else if player.autoplay_ready(now_sec) {
+    } else if player.autoplay_ready(engine, now_sec) {
         player.mark_auto_step(now_sec);
         should_advance = true;
     }
@@ -159,8 +160,43 @@ pub(super) fn render_choice(
     }
 }
 
+pub(super) fn render_text_input(
+    ui: &mut egui::Ui,
+    engine: &mut Engine,
+    player: &mut PlayerSessionState,
+    prompt: &str,
+    max_len: u32,
+    audio_commands: &mut Vec<AudioCommand>,
+) {
+    egui::Frame::none()
+        .fill(egui::Color32::from_rgb(60, 70, 90))
+        .rounding(8.0)
+        .inner_margin(egui::Margin::symmetric(16.0, 12.0))
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(prompt).size(18.0).strong());
+        });
+
+    ui.add_space(15.0);
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut player.text_input)
+            .char_limit(max_len as usize)
+            .desired_width(240.0),
+    );
+    let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+    ui.add_space(10.0);
+    if ui.button("Confirm").clicked() || entered {
+        info!("Text submitted for input prompt: {}", prompt);
+        if engine.submit_text(&player.text_input).is_ok() {
+            player.text_input.clear();
+            audio_commands.extend(engine.take_audio_commands());
+            queue_scene_audio_if_current(engine, audio_commands);
+        }
+    }
+}
+
 pub(super) fn render_scene(
     ui: &mut egui::Ui,
+    engine: &Engine,
     player: &mut PlayerSessionState,
     background: Option<&str>,
     now_sec: f64,
@@ -180,7 +216,7 @@ pub(super) fn render_scene(
     if ui.button("Continue").clicked() {
         return true;
     }
-    if player.autoplay_ready(now_sec) {
+    if player.autoplay_ready(engine, now_sec) {
         player.mark_auto_step(now_sec);
         return true;
     }
@@ -22,6 +22,22 @@ pub(super) fn render_header_bar(
                     *toast = Some(ToastState::success("Story restarted"));
                 }
             }
+            let pause_label = if engine.is_paused() {
+                "Unpause"
+            } else {
+                "Pause"
+            };
+            if ui
+                .button(pause_label)
+                .on_hover_text("Suspend auto-advance; manual stepping still works")
+                .clicked()
+            {
+                if engine.is_paused() {
+                    engine.unpause();
+                } else {
+                    engine.pause();
+                }
+            }
         });
     });
 }
@@ -97,11 +97,15 @@ fn render_event_ui(
                         player_locale,
                         localization_catalog,
                     );
-                    let localized_text =
-                        localize_inline_value(d.text.as_ref(), player_locale, localization_catalog);
+                    let localized_text = engine.interpolate(&localize_inline_value(
+                        d.text.as_ref(),
+                        player_locale,
+                        localization_catalog,
+                    ));
                     if content::render_dialogue(
                         ui,
                         ctx,
+                        engine,
                         player,
                         &localized_speaker,
                         &localized_text,
@@ -112,6 +116,12 @@ fn render_event_ui(
                         }
                     }
                 }
+                EventCompiled::Choice(c) if c.options.is_empty() => {
+                    ui.label("Processing...");
+                    if let Ok((cmd, _)) = engine.step() {
+                        audio_commands.extend(cmd);
+                    }
+                }
                 EventCompiled::Choice(c) => {
                     let localized_prompt = localize_inline_value(
                         c.prompt.as_ref(),
@@ -142,6 +152,7 @@ fn render_event_ui(
                 EventCompiled::Scene(s) => {
                     if content::render_scene(
                         ui,
+                        engine,
                         player,
                         s.background.as_ref().map(|s| s.as_ref()),
                         now_sec,
@@ -161,6 +172,20 @@ fn render_event_ui(
                         &mut audio_commands,
                     );
                 }
+                EventCompiled::InputText {
+                    prompt, max_len, ..
+                } => {
+                    let localized_prompt =
+                        localize_inline_value(&prompt, player_locale, localization_catalog);
+                    content::render_text_input(
+                        ui,
+                        engine,
+                        player,
+                        &localized_prompt,
+                        max_len,
+                        &mut audio_commands,
+                    );
+                }
                 EventCompiled::ExtCall { .. } => {
                     ui.label("Processing custom action...");
                     let _ = engine.resume();
@@ -169,6 +194,9 @@ fn render_event_ui(
                 EventCompiled::Jump { .. }
                 | EventCompiled::SetFlag { .. }
                 | EventCompiled::SetVar { .. }
+                | EventCompiled::SetStrVar { .. }
+                | EventCompiled::UnlockGallery { .. }
+                | EventCompiled::Achieve { .. }
                 | EventCompiled::JumpIf { .. }
                 | EventCompiled::Patch(_)
                 | EventCompiled::AudioAction(_)
@@ -24,6 +24,8 @@ pub struct PlayerSessionState {
     pub voice_muted: bool,
     pub last_audio_event: Option<String>,
     pub last_audio_error: Option<String>,
+    /// Draft typed into the current `input_text` field.
+    pub text_input: String,
     current_ip: Option<u32>,
     line_started_at_sec: f64,
     last_auto_step_at_sec: Option<f64>,
@@ -46,6 +48,7 @@ impl Default for PlayerSessionState {
             voice_muted: false,
             last_audio_event: None,
             last_audio_error: None,
+            text_input: String::new(),
             current_ip: None,
             line_started_at_sec: 0.0,
             last_auto_step_at_sec: None,
@@ -80,10 +83,10 @@ impl PlayerSessionState {
     }
 
     pub(crate) fn visible_text<'a>(&self, text: &'a str, now_sec: f64) -> &'a str {
-        if text.is_empty() {
+        if text.is_empty() || self.text_chars_per_second <= 0.0 {
             return text;
         }
-        let cps = self.text_chars_per_second.max(1.0) as f64;
+        let cps = self.text_chars_per_second as f64;
         let elapsed = (now_sec - self.line_started_at_sec).max(0.0);
         let visible_chars = (elapsed * cps).floor() as usize;
         let total_chars = text.chars().count();
@@ -104,12 +107,17 @@ impl PlayerSessionState {
             SkipMode::ReadOnly => {
                 matches!(event, EventCompiled::Dialogue(_)) && engine.is_current_dialogue_read()
             }
-            SkipMode::All => !matches!(event, EventCompiled::Choice(_)),
+            SkipMode::All => {
+                !matches!(
+                    event,
+                    EventCompiled::Choice(choice) if !choice.options.is_empty()
+                ) && !matches!(event, EventCompiled::InputText { .. })
+            }
         }
     }
 
-    pub(crate) fn autoplay_ready(&self, now_sec: f64) -> bool {
-        if !self.autoplay_enabled {
+    pub(crate) fn autoplay_ready(&self, engine: &Engine, now_sec: f64) -> bool {
+        if !self.autoplay_enabled || engine.is_paused() {
             return false;
         }
         match self.last_auto_step_at_sec {
@@ -1,5 +1,6 @@
 use crate::editor::errors::EditorError;
-use crate::editor::{node_graph::NodeGraph, script_sync};
+use crate::editor::{node_graph::NodeGraph, script_sync, StoryNode};
+use serde::{Deserialize, Serialize};
 use std::path::{Component, Path, PathBuf};
 use visual_novel_engine::{
     manifest::{ManifestMigrationReport, ProjectManifest},
@@ -17,6 +18,19 @@ pub struct LoadedScript {
     pub was_imported: bool,
 }
 
+/// Editor-only comment persisted next to the script (scripts carry no notes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CommentNote {
+    text: String,
+    x: f32,
+    y: f32,
+}
+
+/// Path of the comment sidecar for a script, e.g. `main.json` -> `main.comments.json`.
+fn comments_sidecar_path(script_path: &Path) -> PathBuf {
+    script_path.with_extension("comments.json")
+}
+
 pub(crate) fn resolve_existing_project_path(
     root: &Path,
     requested: &Path,
@@ -100,7 +114,8 @@ pub fn load_script(path: PathBuf) -> Result<LoadedScript, EditorError> {
     let script = ScriptRaw::from_json(&content)
         .map_err(|e| EditorError::CompileError(format!("Parse error: {}", e)))?;
 
-    let graph = script_sync::from_script(&script);
+    let mut graph = script_sync::from_script(&script);
+    load_comments(&comments_sidecar_path(&path), &mut graph)?;
     Ok(LoadedScript {
         graph,
         was_imported: false,
@@ -110,14 +125,58 @@ pub fn load_script(path: PathBuf) -> Result<LoadedScript, EditorError> {
 pub fn save_script(path: &std::path::Path, graph: &NodeGraph) -> Result<(), EditorError> {
     let script = script_sync::to_script(graph);
     let json = script
-        .to_json()
+        .to_canonical_json()
         .map_err(|e| EditorError::CompileError(format!("Serialization error: {}", e)))?;
 
     std::fs::write(path, json).map_err(EditorError::IoError)?;
+    save_comments(&comments_sidecar_path(path), graph)?;
 
     Ok(())
 }
 
+fn load_comments(sidecar: &Path, graph: &mut NodeGraph) -> Result<(), EditorError> {
+    if !sidecar.is_file() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(sidecar).map_err(EditorError::IoError)?;
+    let notes: Vec<CommentNote> = serde_json::from_str(&content)
+        .map_err(|e| EditorError::CompileError(format!("Comment sidecar parse error: {}", e)))?;
+    for note in notes {
+        graph.add_node(
+            StoryNode::Comment { text: note.text },
+            egui::pos2(note.x, note.y),
+        );
+    }
+    graph.clear_modified();
+    Ok(())
+}
+
+fn save_comments(sidecar: &Path, graph: &NodeGraph) -> Result<(), EditorError> {
+    let notes: Vec<CommentNote> = graph
+        .nodes()
+        .filter_map(|(_, node, pos)| match node {
+            StoryNode::Comment { text } => Some(CommentNote {
+                text: text.clone(),
+                x: pos.x,
+                y: pos.y,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    if notes.is_empty() {
+        // Drop a stale sidecar so deleted comments don't come back on reload.
+        if sidecar.exists() {
+            std::fs::remove_file(sidecar).map_err(EditorError::IoError)?;
+        }
+        return Ok(());
+    }
+
+    let json = serde_json::to_string_pretty(&notes)
+        .map_err(|e| EditorError::CompileError(format!("Serialization error: {}", e)))?;
+    std::fs::write(sidecar, json).map_err(EditorError::IoError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +272,45 @@ entry_point = "../outside.json"
             Err(other) => panic!("unexpected error: {other}"),
         }
     }
+
+    #[test]
+    fn comments_round_trip_through_sidecar_but_not_script() {
+        let dir = tempdir().expect("tempdir");
+        let script_path = dir.path().join("main.json");
+
+        let mut graph = NodeGraph::new();
+        let start = graph.add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+        let dialogue = graph.add_node(StoryNode::default(), egui::pos2(0.0, 100.0));
+        let end = graph.add_node(StoryNode::End, egui::pos2(0.0, 200.0));
+        graph.connect(start, dialogue);
+        graph.connect(dialogue, end);
+        graph.add_node(
+            StoryNode::Comment {
+                text: "TODO: rewrite intro".to_string(),
+            },
+            egui::pos2(300.0, 40.0),
+        );
+
+        save_script(&script_path, &graph).expect("save");
+        let script_json = fs::read_to_string(&script_path).expect("read script");
+        assert!(!script_json.contains("TODO: rewrite intro"));
+
+        let loaded = load_script(script_path.clone()).expect("load");
+        let comments: Vec<_> = loaded
+            .graph
+            .nodes()
+            .filter(|(_, node, _)| node.is_comment())
+            .collect();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].2, egui::pos2(300.0, 40.0));
+        assert!(!loaded.graph.is_modified());
+
+        // Removing the last comment drops the sidecar.
+        save_script(
+            &script_path,
+            &script_sync::from_script(&loaded.graph.to_script()),
+        )
+        .expect("save without comments");
+        assert!(!comments_sidecar_path(&script_path).exists());
+    }
 }
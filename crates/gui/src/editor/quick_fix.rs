@@ -1,4 +1,4 @@
-use crate::editor::{DiagnosticLanguage, LintIssue, NodeGraph};
+use crate::editor::{validate_graph, DiagnosticLanguage, LintIssue, NodeGraph};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QuickFixRisk {
@@ -52,6 +52,9 @@ impl QuickFixCandidate {
 }
 
 mod catalog;
+mod preview;
+
+pub use preview::{preview_fix, FixPreview};
 
 pub fn suggest_fixes(issue: &LintIssue, graph: &NodeGraph) -> Vec<QuickFixCandidate> {
     catalog::suggest_fixes(issue, graph)
@@ -61,6 +64,66 @@ pub fn apply_fix(graph: &mut NodeGraph, issue: &LintIssue, fix_id: &str) -> Resu
     catalog::apply_fix(graph, issue, fix_id)
 }
 
+/// Upper bound on fixes applied by one [`apply_all_safe`] call, guarding
+/// against fixes that keep re-triggering each other.
+const MAX_SAFE_FIXES: usize = 128;
+
+/// Outcome of [`apply_all_safe`].
+#[derive(Debug, Clone, Default)]
+pub struct FixReport {
+    /// `(diagnostic_id, fix_id)` of each applied fix, in application order.
+    pub applied: Vec<(String, &'static str)>,
+    /// Issues still reported once no safe fix applies.
+    pub remaining: Vec<LintIssue>,
+}
+
+/// First issue, in list order, with a safe fix that actually changes `graph`.
+pub fn next_safe_fix(graph: &NodeGraph, issues: &[LintIssue]) -> Option<(LintIssue, &'static str)> {
+    issues.iter().find_map(|issue| {
+        suggest_fixes(issue, graph)
+            .into_iter()
+            .filter(|candidate| candidate.risk == QuickFixRisk::Safe)
+            .find(|candidate| {
+                let mut probe = graph.clone();
+                apply_fix(&mut probe, issue, candidate.fix_id) == Ok(true)
+            })
+            .map(|candidate| (issue.clone(), candidate.fix_id))
+    })
+}
+
+/// Applies every safe fix, re-validating after each one because a fix can
+/// change which others still apply. Review-risk fixes are never applied.
+pub fn apply_all_safe(graph: &mut NodeGraph, issues: &[LintIssue]) -> FixReport {
+    apply_all_safe_with(graph, issues, validate_graph)
+}
+
+/// [`apply_all_safe`] with a caller-provided validator, e.g. one that probes
+/// assets against the project root.
+pub fn apply_all_safe_with<V>(
+    graph: &mut NodeGraph,
+    issues: &[LintIssue],
+    revalidate: V,
+) -> FixReport
+where
+    V: Fn(&NodeGraph) -> Vec<LintIssue>,
+{
+    let mut report = FixReport {
+        applied: Vec::new(),
+        remaining: issues.to_vec(),
+    };
+    while report.applied.len() < MAX_SAFE_FIXES {
+        let Some((issue, fix_id)) = next_safe_fix(graph, &report.remaining) else {
+            break;
+        };
+        if apply_fix(graph, &issue, fix_id) != Ok(true) {
+            break;
+        }
+        report.applied.push((issue.diagnostic_id(), fix_id));
+        report.remaining = revalidate(graph);
+    }
+    report
+}
+
 #[cfg(test)]
 #[path = "tests/quick_fix_tests.rs"]
 mod tests;
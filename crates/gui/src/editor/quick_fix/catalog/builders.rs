@@ -110,6 +110,20 @@ pub(crate) fn fix_fill_speaker() -> QuickFixCandidate {
     })
 }
 
+pub(crate) fn fix_speaker_casing() -> QuickFixCandidate {
+    candidate(CandidateSpec {
+        fix_id: "dialogue_normalize_speaker_casing",
+        title_es: "Unificar mayusculas del speaker",
+        title_en: "Normalize speaker casing",
+        preconditions_es: "El speaker aparece con varias grafias que solo difieren en mayusculas.",
+        preconditions_en: "Speaker appears in several spellings that differ only by case.",
+        postconditions_es: "Todos los dialogos usan la grafia mas frecuente.",
+        postconditions_en: "Every dialogue uses the most frequent spelling.",
+        risk: QuickFixRisk::Safe,
+        structural: false,
+    })
+}
+
 pub(crate) fn fix_fill_jump_target() -> QuickFixCandidate {
     candidate(CandidateSpec {
         fix_id: "jump_set_start_target",
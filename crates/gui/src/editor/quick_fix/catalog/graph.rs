@@ -53,6 +53,16 @@ pub(crate) fn apply_empty_speaker(
     apply_fill_speaker(graph, require_node_id(issue, "dialogue_fill_speaker")?)
 }
 
+pub(crate) fn apply_speaker_casing(
+    graph: &mut NodeGraph,
+    issue: &LintIssue,
+) -> Result<bool, String> {
+    apply_normalize_speaker_casing(
+        graph,
+        require_node_id(issue, "dialogue_normalize_speaker_casing")?,
+    )
+}
+
 pub(crate) fn apply_empty_jump_target(
     graph: &mut NodeGraph,
     issue: &LintIssue,
@@ -183,6 +193,25 @@ fn apply_fill_speaker(graph: &mut NodeGraph, node_id: u32) -> Result<bool, Strin
     Ok(true)
 }
 
+/// Renames every casing variant of the node's speaker, graph-wide, to the
+/// most common spelling.
+fn apply_normalize_speaker_casing(graph: &mut NodeGraph, node_id: u32) -> Result<bool, String> {
+    let Some(StoryNode::Dialogue { speaker, .. }) = graph.get_node(node_id) else {
+        return Err(format!("node_id {node_id} is not Dialogue"));
+    };
+    let fixes = graph.speaker_casing_fixes();
+    let Some(canonical) = fixes.get(speaker).cloned() else {
+        return Ok(false);
+    };
+    let mut renamed = 0;
+    for (variant, target) in &fixes {
+        if *target == canonical {
+            renamed += graph.rename_speaker(variant, &canonical);
+        }
+    }
+    Ok(renamed > 0)
+}
+
 fn apply_set_jump_target_start(graph: &mut NodeGraph, node_id: u32) -> Result<bool, String> {
     if !graph
         .nodes()
@@ -81,6 +81,12 @@ fn quick_fix_rules() -> &'static [QuickFixRule] {
             matches: predicates::matches_empty_speaker,
             apply: graph::apply_empty_speaker,
         },
+        QuickFixRule {
+            fix_id: "dialogue_normalize_speaker_casing",
+            build: builders::fix_speaker_casing,
+            matches: predicates::matches_speaker_casing,
+            apply: graph::apply_speaker_casing,
+        },
         QuickFixRule {
             fix_id: "jump_set_start_target",
             build: builders::fix_fill_jump_target,
@@ -89,6 +89,15 @@ pub(crate) fn matches_empty_speaker(issue: &LintIssue, graph: &NodeGraph) -> boo
     matches_issue_on_node(issue, graph, LintCode::EmptySpeakerName, node_is_dialogue)
 }
 
+pub(crate) fn matches_speaker_casing(issue: &LintIssue, graph: &NodeGraph) -> bool {
+    matches_issue_on_node(
+        issue,
+        graph,
+        LintCode::SpeakerNameInconsistent,
+        node_is_dialogue,
+    )
+}
+
 pub(crate) fn matches_empty_jump_target(issue: &LintIssue, graph: &NodeGraph) -> bool {
     matches_issue_on_node(issue, graph, LintCode::EmptyJumpTarget, node_is_jump_like)
 }
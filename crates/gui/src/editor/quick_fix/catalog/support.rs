@@ -1,5 +1,7 @@
 use crate::editor::{LintIssue, NodeGraph, StoryNode};
 
+use vnengine_assets::is_unsafe_asset_path;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum AssetField {
     SceneBackground,
@@ -15,20 +17,6 @@ pub(crate) fn require_node_id(issue: &LintIssue, fix_id: &str) -> Result<u32, St
         .ok_or_else(|| format!("fix '{fix_id}' requires node_id"))
 }
 
-pub(crate) fn is_unsafe_asset_path(value: &str) -> bool {
-    let path = value.trim();
-    if path.is_empty() {
-        return false;
-    }
-    let bytes = path.as_bytes();
-    path.contains("..")
-        || path.starts_with('/')
-        || path.starts_with('\\')
-        || path.starts_with("http://")
-        || path.starts_with("https://")
-        || (bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic())
-}
-
 pub(crate) fn clearable_asset_field(
     graph: &NodeGraph,
     issue: &LintIssue,
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use eframe::egui;
+use serde_json::Value;
+
+use super::apply_fix;
+use crate::editor::{LintIssue, NodeGraph, StoryNode};
+
+/// What a quick fix would change, computed on a scratch copy of the graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixPreview {
+    pub nodes_added: Vec<u32>,
+    pub nodes_removed: Vec<u32>,
+    /// `(node_id, field)` per changed field; `kind` when the node type changed.
+    pub fields_changed: Vec<(u32, String)>,
+    pub connections_added: usize,
+    pub connections_removed: usize,
+}
+
+impl FixPreview {
+    pub fn is_empty(&self) -> bool {
+        self.nodes_added.is_empty()
+            && self.nodes_removed.is_empty()
+            && self.fields_changed.is_empty()
+            && self.connections_added == 0
+            && self.connections_removed == 0
+    }
+
+    /// One line per kind of change, for tooltips.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.nodes_added.is_empty() {
+            lines.push(format!("+ nodes {:?}", self.nodes_added));
+        }
+        if !self.nodes_removed.is_empty() {
+            lines.push(format!("- nodes {:?}", self.nodes_removed));
+        }
+        for (node_id, field) in &self.fields_changed {
+            lines.push(format!("~ node {node_id}.{field}"));
+        }
+        if self.connections_added > 0 || self.connections_removed > 0 {
+            lines.push(format!(
+                "connections +{} -{}",
+                self.connections_added, self.connections_removed
+            ));
+        }
+        lines
+    }
+}
+
+/// Applies `fix_id` to a clone of `graph` and reports the difference. The
+/// real graph is never touched.
+pub fn preview_fix(
+    graph: &NodeGraph,
+    issue: &LintIssue,
+    fix_id: &str,
+) -> Result<FixPreview, String> {
+    let mut after = graph.clone();
+    apply_fix(&mut after, issue, fix_id)?;
+    Ok(diff_graphs(graph, &after))
+}
+
+fn diff_graphs(before: &NodeGraph, after: &NodeGraph) -> FixPreview {
+    let before_nodes: BTreeMap<u32, (&StoryNode, egui::Pos2)> = before
+        .nodes()
+        .map(|(id, node, pos)| (*id, (node, *pos)))
+        .collect();
+    let after_nodes: BTreeMap<u32, (&StoryNode, egui::Pos2)> = after
+        .nodes()
+        .map(|(id, node, pos)| (*id, (node, *pos)))
+        .collect();
+
+    let mut preview = FixPreview::default();
+    for (id, (node, pos)) in &after_nodes {
+        let Some((old_node, old_pos)) = before_nodes.get(id) else {
+            preview.nodes_added.push(*id);
+            continue;
+        };
+        if old_node != node {
+            for field in changed_fields(old_node, node) {
+                preview.fields_changed.push((*id, field));
+            }
+        }
+        if old_pos != pos {
+            preview.fields_changed.push((*id, "position".to_string()));
+        }
+    }
+    preview.nodes_removed = before_nodes
+        .keys()
+        .filter(|id| !after_nodes.contains_key(id))
+        .copied()
+        .collect();
+
+    let before_conns: Vec<_> = before.connections().collect();
+    let after_conns: Vec<_> = after.connections().collect();
+    preview.connections_added = after_conns
+        .iter()
+        .filter(|conn| !before_conns.contains(conn))
+        .count();
+    preview.connections_removed = before_conns
+        .iter()
+        .filter(|conn| !after_conns.contains(conn))
+        .count();
+    preview
+}
+
+/// Field names that differ between two nodes, via their serialized form.
+fn changed_fields(before: &StoryNode, after: &StoryNode) -> Vec<String> {
+    let (Ok(before), Ok(after)) = (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return vec!["kind".to_string()];
+    };
+    let (Some((before_kind, Value::Object(before))), Some((after_kind, Value::Object(after)))) =
+        (single_entry(&before), single_entry(&after))
+    else {
+        return vec!["kind".to_string()];
+    };
+    if before_kind != after_kind {
+        return vec!["kind".to_string()];
+    }
+    let mut fields: Vec<String> = after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    fields.extend(
+        before
+            .keys()
+            .filter(|key| !after.contains_key(*key))
+            .cloned(),
+    );
+    fields.sort();
+    fields
+}
+
+/// The `(variant, payload)` of an externally tagged enum value.
+fn single_entry(value: &Value) -> Option<(&String, &Value)> {
+    match value {
+        Value::Object(map) if map.len() == 1 => map.iter().next(),
+        _ => None,
+    }
+}
@@ -45,11 +45,14 @@ pub fn from_script(script: &ScriptRaw) -> NodeGraph {
                 speaker: d.speaker.clone(),
                 text: d.text.clone(),
             },
-            EventRaw::Choice(c) => StoryNode::Choice {
+            // Choice nodes only hold prompt and option texts; anything more
+            // (fallthrough, auto, weights, effects) falls back to Generic.
+            EventRaw::Choice(c) if is_plain_choice(c) => StoryNode::Choice {
                 prompt: c.prompt.clone(),
                 options: c.options.iter().map(|o| o.text.clone()).collect(),
             },
-            EventRaw::Scene(s) => StoryNode::Scene {
+            // Chapters and tags have no Scene node fields; those fall back to Generic.
+            EventRaw::Scene(s) if s.chapter.is_none() && s.tags.is_empty() => StoryNode::Scene {
                 profile: None,
                 background: s.background.clone(),
                 music: s.music.clone(),
@@ -83,7 +86,8 @@ pub fn from_script(script: &ScriptRaw) -> NodeGraph {
                 duration_ms: trans.duration_ms,
                 color: trans.color.clone(),
             },
-            EventRaw::SetCharacterPosition(pos) => StoryNode::CharacterPlacement {
+            // Placement nodes have no flip field; flipped ones fall back to Generic.
+            EventRaw::SetCharacterPosition(pos) if !pos.flip_x => StoryNode::CharacterPlacement {
                 name: pos.name.clone(),
                 x: pos.x,
                 y: pos.y,
@@ -127,7 +131,7 @@ pub fn from_script(script: &ScriptRaw) -> NodeGraph {
                     }
                 }
             }
-            EventRaw::Choice(c) => {
+            EventRaw::Choice(c) if is_plain_choice(c) => {
                 for (opt_idx, option) in c.options.iter().enumerate() {
                     if let Some(&target_idx) = label_to_index.get(option.target.as_str()) {
                         if target_idx == script.events.len() {
@@ -183,6 +187,16 @@ pub fn from_script(script: &ScriptRaw) -> NodeGraph {
     graph
 }
 
+/// Whether `choice` fits a [`StoryNode::Choice`] without losing data.
+fn is_plain_choice(choice: &ChoiceRaw) -> bool {
+    !choice.auto
+        && choice.fallthrough.is_none()
+        && choice
+            .options
+            .iter()
+            .all(|option| option.weight.is_none() && option.effects.is_empty())
+}
+
 /// Converts a NodeGraph to a raw script.
 pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
     let mut events = Vec::new();
@@ -234,6 +248,8 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
                         ChoiceOptionRaw {
                             text: text.clone(),
                             target,
+                            weight: None,
+                            effects: Vec::new(),
                         }
                     })
                     .collect();
@@ -241,6 +257,8 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
                 events.push(EventRaw::Choice(ChoiceRaw {
                     prompt: prompt.clone(),
                     options: choice_options,
+                    auto: false,
+                    fallthrough: None,
                 }));
             }
             StoryNode::Jump { target } => {
@@ -273,6 +291,8 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
                     background: background.clone(),
                     music: music.clone(),
                     characters: characters.clone(),
+                    chapter: None,
+                    tags: Vec::new(),
                 }));
             }
             StoryNode::AudioAction {
@@ -312,6 +332,7 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
                         x: *x,
                         y: *y,
                         scale: *scale,
+                        flip_x: false,
                     },
                 ));
             }
@@ -319,8 +340,8 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
                 // Pass through the generic event
                 events.push(event.clone());
             }
-            StoryNode::Start | StoryNode::End => {
-                // Skip start/end markers
+            StoryNode::Start | StoryNode::End | StoryNode::Comment { .. } => {
+                // Skip start/end markers and editor-only comments
             }
         }
     }
@@ -343,6 +364,21 @@ pub fn to_script(graph: &NodeGraph) -> ScriptRaw {
     ScriptRaw::new(events, labels)
 }
 
+/// Maps node ids to event ips using the `node_<id>` labels written by
+/// [`to_script`].
+///
+/// Reading the labels of the compiled script keeps the mapping valid even
+/// when compilation moves events, e.g. while expanding macros.
+pub fn node_event_ips(labels: &BTreeMap<String, u32>) -> BTreeMap<u32, u32> {
+    labels
+        .iter()
+        .filter_map(|(label, ip)| {
+            let node_id = label.strip_prefix("node_")?.parse::<u32>().ok()?;
+            Some((node_id, *ip))
+        })
+        .collect()
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -396,7 +432,11 @@ mod tests {
                 x: Some(10),
                 y: Some(20),
                 scale: Some(1.2),
+                z: None,
+                flip_x: false,
             }],
+            chapter: None,
+            tags: Vec::new(),
         })];
 
         let original = ScriptRaw::new(events, labels);
@@ -446,7 +486,11 @@ mod tests {
                 options: vec![ChoiceOptionRaw {
                     text: "Fin".to_string(),
                     target: "__end".to_string(),
+                    weight: None,
+                    effects: Vec::new(),
                 }],
+                auto: false,
+                fallthrough: None,
             })],
             labels,
         );
@@ -463,4 +507,57 @@ mod tests {
             "roundtrip script should remain compilable when targeting __end"
         );
     }
+
+    #[test]
+    fn test_choice_with_effects_weight_or_auto_stays_generic() {
+        let option = ChoiceOptionRaw {
+            text: "Smile".to_string(),
+            target: "__end".to_string(),
+            weight: None,
+            effects: Vec::new(),
+        };
+        let variants = [
+            ChoiceRaw {
+                prompt: "Mood".to_string(),
+                options: vec![ChoiceOptionRaw {
+                    effects: vec![EventRaw::SetVar {
+                        key: "affection".to_string(),
+                        value: 1,
+                    }],
+                    ..option.clone()
+                }],
+                auto: false,
+                fallthrough: None,
+            },
+            ChoiceRaw {
+                prompt: "Mood".to_string(),
+                options: vec![ChoiceOptionRaw {
+                    weight: Some(3),
+                    ..option.clone()
+                }],
+                auto: false,
+                fallthrough: None,
+            },
+            ChoiceRaw {
+                prompt: "Mood".to_string(),
+                options: vec![option.clone()],
+                auto: true,
+                fallthrough: None,
+            },
+        ];
+
+        for choice in variants {
+            let script = ScriptRaw::new(
+                vec![EventRaw::Choice(choice.clone())],
+                BTreeMap::from([("start".to_string(), 0), ("__end".to_string(), 1)]),
+            );
+            let graph = from_script(&script);
+            assert!(graph
+                .nodes()
+                .any(|(_, node, _)| matches!(node, StoryNode::Generic(EventRaw::Choice(_)))));
+
+            let roundtrip = to_script(&graph);
+            assert_eq!(roundtrip.events.first(), Some(&EventRaw::Choice(choice)));
+        }
+    }
 }
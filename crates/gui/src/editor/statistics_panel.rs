@@ -0,0 +1,84 @@
+//! Statistics window with writing metrics for the current script.
+
+use eframe::egui;
+use visual_novel_engine::{BranchLengthReport, ContentStats};
+
+/// Window widget showing [`ContentStats`], route balance and a reading-time
+/// estimate.
+pub struct StatisticsPanel<'a> {
+    stats: &'a ContentStats,
+    branches: Option<&'a BranchLengthReport>,
+    words_per_minute: &'a mut u32,
+}
+
+impl<'a> StatisticsPanel<'a> {
+    /// `branches` is `None` when the script does not compile.
+    pub fn new(
+        stats: &'a ContentStats,
+        branches: Option<&'a BranchLengthReport>,
+        words_per_minute: &'a mut u32,
+    ) -> Self {
+        Self {
+            stats,
+            branches,
+            words_per_minute,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("script_statistics")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Dialogue words");
+                ui.label(self.stats.dialogue_words.to_string());
+                ui.end_row();
+                ui.label("Dialogue lines");
+                ui.label(self.stats.dialogue_lines.to_string());
+                ui.end_row();
+                ui.label("Speakers");
+                ui.label(self.stats.unique_speakers.to_string());
+                ui.end_row();
+                ui.label("Choices");
+                ui.label(self.stats.choice_count.to_string());
+                ui.end_row();
+                ui.label("Longest branch");
+                ui.label(format!("{} events", self.stats.longest_branch));
+                ui.end_row();
+                if let Some(report) = self.branches.filter(|report| !report.ends.is_empty()) {
+                    ui.label("Endings");
+                    ui.label(report.ends.len().to_string());
+                    ui.end_row();
+                    ui.label("Route length");
+                    ui.label(format!(
+                        "min {} / max {} / mean {:.1} events",
+                        report.min, report.max, report.mean
+                    ));
+                    ui.end_row();
+                }
+            });
+        if self.branches.is_some_and(|report| report.imbalanced) {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "Routes are unbalanced: the longest is several times the shortest.",
+            );
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Reading speed:");
+            ui.add(
+                egui::DragValue::new(self.words_per_minute)
+                    .clamp_range(50..=1000)
+                    .suffix(" wpm"),
+            );
+        });
+        let seconds = self.stats.reading_time(*self.words_per_minute).as_secs();
+        ui.label(format!(
+            "Estimated reading time: {}h {:02}m {:02}s",
+            seconds / 3600,
+            (seconds / 60) % 60,
+            seconds % 60
+        ));
+    }
+}
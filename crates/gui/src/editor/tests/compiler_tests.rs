@@ -81,6 +81,8 @@ fn build_scene_bootstrap_graph() -> NodeGraph {
                 x: Some(0),
                 y: Some(0),
                 scale: Some(1.0),
+                z: None,
+                flip_x: false,
             }],
         },
         p(0.0, 100.0),
@@ -129,6 +131,51 @@ fn compile_project_reports_dry_run_completion() {
         .any(|issue| issue.code == LintCode::DryRunFinished));
 }
 
+#[test]
+fn compile_project_with_config_applies_dry_run_step_limit() {
+    let graph = build_linear_graph();
+    let config = DryRunConfig {
+        max_steps: 1,
+        ..DryRunConfig::default()
+    };
+    let result = compile_project_with_config(&graph, None, &BTreeSet::new(), &config);
+
+    assert_eq!(result.dry_run_config, config);
+    let report = result.dry_run_report.expect("dry run report");
+    assert_eq!(report.stop_reason, DryRunStopReason::StepLimit);
+    assert!(result
+        .issues
+        .iter()
+        .any(|issue| issue.code == LintCode::DryRunStepLimit));
+}
+
+#[test]
+fn compile_project_ignores_comment_nodes() {
+    let mut graph = build_linear_graph();
+    let comment = graph.add_node(
+        StoryNode::Comment {
+            text: "Remember to recast Ava".to_string(),
+        },
+        p(300.0, 0.0),
+    );
+    let result = compile_project(&graph);
+
+    assert!(result.engine_result.is_ok());
+    let json = result.script.to_json().expect("script json");
+    assert!(!json.contains("Remember to recast Ava"));
+    assert_eq!(
+        json,
+        build_linear_graph()
+            .to_script()
+            .to_json()
+            .expect("baseline json")
+    );
+    assert!(result
+        .issues
+        .iter()
+        .all(|issue| issue.node_id != Some(comment)));
+}
+
 #[test]
 fn preview_runtime_sequence_matches_raw_sequence_for_default_route() {
     let graph = build_branching_graph();
@@ -324,3 +371,61 @@ fn snapshot_replay_determinism() {
         .collect();
     assert_eq!(left, right);
 }
+
+#[test]
+fn dry_run_halts_at_breakpoint_node() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+    let mut previous = start;
+    let mut dialogue_ids = Vec::new();
+    for (idx, text) in ["Uno", "Dos", "Tres"].into_iter().enumerate() {
+        let id = graph.add_node(
+            StoryNode::Dialogue {
+                speaker: "Ava".to_string(),
+                text: text.to_string(),
+            },
+            p(0.0, 100.0 * (idx as f32 + 1.0)),
+        );
+        graph.connect(previous, id);
+        dialogue_ids.push(id);
+        previous = id;
+    }
+    let end = graph.add_node(StoryNode::End, p(0.0, 400.0));
+    graph.connect(previous, end);
+
+    let mid = dialogue_ids[1];
+    assert!(graph.toggle_breakpoint(mid));
+    let expected_ip = graph.event_ip_for_node(mid).expect("mid ip");
+
+    let result = compile_project_with_breakpoints(&graph, None, graph.breakpoints());
+    let report = result.dry_run_report.expect("dry run report");
+    assert_eq!(report.stop_reason, DryRunStopReason::Breakpoint);
+    assert_eq!(
+        report.steps.last().map(|step| step.event_ip),
+        Some(expected_ip)
+    );
+    let state = report.breakpoint_state.expect("breakpoint state");
+    assert_eq!(state.position, expected_ip);
+    assert_eq!(
+        state.history.back().map(|line| line.text.as_ref()),
+        Some("Uno")
+    );
+
+    assert!(!graph.toggle_breakpoint(mid));
+    let result = compile_project_with_breakpoints(&graph, None, graph.breakpoints());
+    assert_eq!(
+        result.dry_run_report.expect("dry run report").stop_reason,
+        DryRunStopReason::Finished
+    );
+}
+
+#[test]
+fn branch_lengths_report_balanced_routes_for_branching_graph() {
+    let graph = build_branching_graph();
+    let compiled = graph.to_script().compile().expect("compile");
+    let report = visual_novel_engine::StoryGraph::from_script(&compiled).branch_lengths();
+
+    assert!(!report.ends.is_empty());
+    assert_eq!(report.min, report.max);
+    assert!(!report.imbalanced);
+}
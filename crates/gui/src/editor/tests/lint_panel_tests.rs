@@ -0,0 +1,49 @@
+use super::*;
+use crate::editor::{LintCode, ValidationPhase};
+
+fn error(node_id: Option<u32>) -> LintIssue {
+    LintIssue::error(
+        node_id,
+        ValidationPhase::Graph,
+        LintCode::MissingStart,
+        "error",
+    )
+}
+
+fn warning(node_id: Option<u32>) -> LintIssue {
+    LintIssue::warning(
+        node_id,
+        ValidationPhase::Graph,
+        LintCode::MissingStart,
+        "warning",
+    )
+}
+
+#[test]
+fn next_error_cycles_errors_in_order_and_skips_global_issues() {
+    let graph = NodeGraph::new();
+    let issues = vec![
+        warning(Some(1)),
+        error(Some(2)),
+        error(None),
+        warning(Some(3)),
+        error(Some(4)),
+        error(None).with_edge(Some(5), Some(6)),
+    ];
+
+    let mut visited = Vec::new();
+    let mut current = None;
+    for _ in 0..4 {
+        current = next_error_index(&issues, current, &graph);
+        visited.push(current);
+    }
+    assert_eq!(visited, vec![Some(1), Some(4), Some(5), Some(1)]);
+}
+
+#[test]
+fn next_error_is_none_without_node_bound_errors() {
+    let graph = NodeGraph::new();
+    let issues = vec![warning(Some(1)), error(None)];
+    assert_eq!(next_error_index(&issues, None, &graph), None);
+    assert_eq!(next_error_index(&[], Some(3), &graph), None);
+}
@@ -305,6 +305,8 @@ fn test_node_for_event_ip_and_asset_reference_navigation() {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
         },
         pos(0.0, 100.0),
@@ -498,3 +500,353 @@ fn auto_layout_hierarchical_avoids_overlap_for_mixed_heights() {
         }
     }
 }
+
+#[test]
+fn references_to_label_lists_jumps_and_choice_options() {
+    let mut graph = NodeGraph::new();
+    let target = graph.add_node(StoryNode::default(), pos(0.0, 0.0));
+    let label = graph.label_for_node(target).expect("label");
+    let jump = graph.add_node(
+        StoryNode::Jump {
+            target: label.clone(),
+        },
+        pos(0.0, 100.0),
+    );
+    let jump_if = graph.add_node(
+        StoryNode::JumpIf {
+            target: label.clone(),
+            cond: visual_novel_engine::CondRaw::Flag {
+                key: "seen".to_string(),
+                is_set: true,
+            },
+        },
+        pos(0.0, 200.0),
+    );
+    let choice = graph.add_node(
+        StoryNode::Choice {
+            prompt: "Where?".to_string(),
+            options: vec!["Stay".to_string(), "Back".to_string()],
+        },
+        pos(0.0, 300.0),
+    );
+    let other = graph.add_node(StoryNode::default(), pos(200.0, 300.0));
+    graph.connect_port(choice, 0, other);
+    graph.connect_port(choice, 1, target);
+    graph.add_node(
+        StoryNode::Jump {
+            target: "elsewhere".to_string(),
+        },
+        pos(0.0, 400.0),
+    );
+
+    assert_eq!(
+        graph.references_to_label(&label),
+        vec![
+            (jump, ReferenceKind::Jump),
+            (jump_if, ReferenceKind::JumpIf),
+            (choice, ReferenceKind::ChoiceOption { index: 1 }),
+        ]
+    );
+    assert!(graph.references_to_label("node_999").is_empty());
+}
+
+#[test]
+fn delete_node_reconnect_joins_chain_neighbours() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, pos(0.0, 0.0));
+    let middle = graph.add_node(StoryNode::default(), pos(0.0, 100.0));
+    let end = graph.add_node(StoryNode::End, pos(0.0, 200.0));
+    graph.connect(start, middle);
+    graph.connect(middle, end);
+    graph.clear_modified();
+
+    assert!(graph.delete_node_reconnect(middle));
+    assert!(graph.get_node(middle).is_none());
+    assert!(graph
+        .connections()
+        .any(|conn| conn.from == start && conn.to == end));
+    assert_eq!(graph.connection_count(), 1);
+    assert!(graph.is_modified());
+}
+
+#[test]
+fn delete_node_reconnect_drops_choice_edges() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, pos(0.0, 0.0));
+    let choice = graph.add_node(
+        StoryNode::Choice {
+            prompt: "Which way?".to_string(),
+            options: vec!["Left".to_string(), "Right".to_string()],
+        },
+        pos(0.0, 100.0),
+    );
+    let left = graph.add_node(StoryNode::default(), pos(-100.0, 200.0));
+    let right = graph.add_node(StoryNode::default(), pos(100.0, 200.0));
+    graph.connect(start, choice);
+    graph.connect_port(choice, 0, left);
+    graph.connect_port(choice, 1, right);
+
+    assert!(!graph.delete_node_reconnect(choice));
+    assert!(graph.get_node(choice).is_none());
+    assert_eq!(graph.connection_count(), 0);
+}
+
+#[test]
+fn paste_subgraph_creates_distinct_connected_pair() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, pos(0.0, 0.0));
+    let first = graph.add_node(StoryNode::default(), pos(0.0, 100.0));
+    let second = graph.add_node(StoryNode::End, pos(0.0, 200.0));
+    graph.connect(start, first);
+    graph.connect(first, second);
+
+    let clip = graph.copy_subgraph(&[first, second]);
+    assert_eq!(
+        clip.connections.len(),
+        1,
+        "external edge from start is dropped"
+    );
+    let json = clip.to_json().expect("serialize clip");
+    let clip = SubgraphClip::from_json(&json).expect("parse clip");
+
+    let pasted = graph.paste_subgraph(&clip, egui::vec2(300.0, 0.0));
+    assert_eq!(pasted.len(), 2);
+    assert!(pasted.iter().all(|id| *id != first && *id != second));
+    assert!(graph
+        .connections()
+        .any(|conn| conn.from == pasted[0] && conn.to == pasted[1]));
+    assert_eq!(graph.len(), 5);
+    assert_eq!(graph.connection_count(), 3);
+    let (_, _, pasted_pos) = graph
+        .nodes()
+        .find(|(id, _, _)| *id == pasted[0])
+        .expect("pasted node");
+    assert_eq!(*pasted_pos, pos(300.0, 100.0));
+}
+
+#[test]
+fn rects_intersect_handles_reversed_and_touching_bands() {
+    use super::selection::rects_intersect;
+    let node = egui::Rect::from_min_size(pos(100.0, 100.0), egui::vec2(50.0, 40.0));
+    let band = |a: (f32, f32), b: (f32, f32)| egui::Rect {
+        min: pos(a.0, a.1),
+        max: pos(b.0, b.1),
+    };
+
+    assert!(rects_intersect(band((0.0, 0.0), (120.0, 120.0)), node));
+    assert!(rects_intersect(band((120.0, 120.0), (0.0, 0.0)), node));
+    assert!(rects_intersect(band((150.0, 140.0), (200.0, 200.0)), node));
+    assert!(!rects_intersect(band((151.0, 0.0), (300.0, 300.0)), node));
+    assert!(!rects_intersect(band((0.0, 0.0), (99.0, 300.0)), node));
+}
+
+#[test]
+fn box_and_shift_selection_track_primary_node() {
+    let mut graph = NodeGraph::new();
+    let a = graph.add_node(StoryNode::default(), pos(0.0, 0.0));
+    let b = graph.add_node(StoryNode::default(), pos(0.0, 300.0));
+    let far = graph.add_node(StoryNode::default(), pos(2000.0, 2000.0));
+
+    graph.select_in_rect(
+        egui::Rect::from_two_pos(pos(-10.0, -10.0), pos(10.0, 310.0)),
+        false,
+    );
+    assert_eq!(graph.selected_ids(), vec![a, b]);
+    assert_eq!(graph.selected, Some(b));
+
+    graph.toggle_selection(far);
+    assert_eq!(graph.selected_ids(), vec![a, b, far]);
+    assert_eq!(graph.selected, Some(far));
+
+    graph.move_selected(egui::vec2(10.0, 0.0));
+    let (_, _, a_pos) = graph.nodes().find(|(id, _, _)| *id == a).unwrap();
+    assert_eq!(*a_pos, pos(10.0, 0.0));
+
+    graph.toggle_selection(far);
+    assert!(!graph.is_selected(far));
+    assert!(graph.is_selected(a) && graph.is_selected(b));
+
+    graph.select_only(a);
+    assert_eq!(graph.selected_ids(), vec![a]);
+}
+
+#[test]
+fn snap_to_grid_rounds_to_nearest_cell() {
+    assert_eq!(snap_to_grid(pos(74.0, 26.0), 50.0), pos(50.0, 50.0));
+    assert_eq!(snap_to_grid(pos(-26.0, 124.9), 50.0), pos(-50.0, 100.0));
+    assert_eq!(snap_to_grid(pos(13.0, 7.0), 0.0), pos(13.0, 7.0));
+}
+
+#[test]
+fn snap_selected_only_applies_when_enabled() {
+    let mut graph = NodeGraph::new();
+    let node = graph.add_node(StoryNode::default(), pos(61.0, 39.0));
+    graph.select_only(node);
+
+    graph.snap_selected();
+    assert_eq!(graph.nodes().next().unwrap().2, pos(61.0, 39.0));
+
+    graph.set_snap_enabled(true);
+    graph.snap_selected();
+    assert_eq!(graph.nodes().next().unwrap().2, pos(50.0, 50.0));
+}
+
+#[test]
+fn align_and_distribute_selected_nodes() {
+    let mut graph = NodeGraph::new();
+    let a = graph.add_node(StoryNode::default(), pos(0.0, 0.0));
+    let b = graph.add_node(StoryNode::default(), pos(70.0, 100.0));
+    let c = graph.add_node(StoryNode::default(), pos(300.0, 200.0));
+    let x_of = |graph: &NodeGraph, id: u32| {
+        graph
+            .nodes()
+            .find(|(node_id, _, _)| *node_id == id)
+            .map(|(_, _, p)| p.x)
+            .unwrap()
+    };
+    graph.select_only(a);
+    graph.toggle_selection(b);
+    graph.toggle_selection(c);
+
+    graph.distribute_horizontally();
+    assert_eq!(
+        (x_of(&graph, a), x_of(&graph, b), x_of(&graph, c)),
+        (0.0, 150.0, 300.0)
+    );
+
+    graph.align_left();
+    assert!([a, b, c].iter().all(|id| x_of(&graph, *id) == 0.0));
+    assert!(graph.is_modified());
+}
+
+#[test]
+fn group_membership_is_exclusive_and_follows_node_removal() {
+    let mut graph = NodeGraph::new();
+    let a = graph.add_node(StoryNode::default(), pos(0.0, 0.0));
+    let b = graph.add_node(StoryNode::default(), pos(200.0, 0.0));
+    let first = graph.create_group("Act 1", &[a, b]);
+    let second = graph.create_group("Act 2", &[]);
+
+    assert!(graph.add_to_group(second, b));
+    assert_eq!(graph.group_of(a), Some(first));
+    assert_eq!(graph.group_of(b), Some(second));
+    assert!(!graph.add_to_group(second, 99));
+
+    assert!(graph.remove_from_group(first, a));
+    assert!(!graph.remove_from_group(first, a));
+    assert_eq!(graph.group_of(a), None);
+
+    graph.remove_node(b);
+    assert!(graph.group(second).unwrap().members.is_empty());
+    assert_eq!(graph.group_bounds(second), None);
+}
+
+#[test]
+fn dragging_group_header_moves_members_together() {
+    let mut graph = NodeGraph::new();
+    let a = graph.add_node(StoryNode::default(), pos(0.0, 0.0));
+    let b = graph.add_node(StoryNode::default(), pos(200.0, 100.0));
+    let outsider = graph.add_node(StoryNode::default(), pos(600.0, 600.0));
+    let group = graph.create_group("Intro", &[a, b]);
+
+    let frame = graph.group_bounds(group).unwrap();
+    assert!(frame.contains(pos(0.0, 0.0)) && frame.contains(pos(340.0, 170.0)));
+    let header = frame.min + egui::vec2(10.0, 5.0);
+    assert_eq!(graph.group_header_at(header), Some(group));
+    assert_eq!(graph.group_header_at(pos(100.0, 50.0)), None);
+
+    graph.clear_modified();
+    graph.move_group(group, egui::vec2(15.0, -5.0));
+    let pos_of = |id: u32| graph.nodes().find(|(n, _, _)| *n == id).unwrap().2;
+    assert_eq!(pos_of(a), pos(15.0, -5.0));
+    assert_eq!(pos_of(b), pos(215.0, 95.0));
+    assert_eq!(pos_of(outsider), pos(600.0, 600.0));
+    assert!(graph.is_modified());
+
+    // Groups are editor-only and never reach the script.
+    let mut plain = graph.clone();
+    plain.remove_group(group);
+    assert_eq!(
+        graph.to_script().to_json().unwrap(),
+        plain.to_script().to_json().unwrap()
+    );
+}
+
+#[test]
+fn palette_search_matches_content_labels_and_bookmarks() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, pos(0.0, 0.0));
+    let key_line = graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "Ava".to_string(),
+            text: "I found the KEY under the rug".to_string(),
+        },
+        pos(0.0, 100.0),
+    );
+    let choice = graph.add_node(
+        StoryNode::Choice {
+            prompt: "Open the vault?".to_string(),
+            options: vec!["Yes".to_string()],
+        },
+        pos(0.0, 200.0),
+    );
+    assert!(graph.set_bookmark("finale", choice));
+
+    assert_eq!(graph.search("  key "), vec![key_line]);
+    assert_eq!(graph.search("vault"), vec![choice]);
+    assert_eq!(graph.search("FINALE"), vec![choice]);
+    assert_eq!(graph.search(&format!("node_{key_line}")), vec![key_line]);
+    assert_eq!(graph.search("start"), vec![start]);
+    assert!(graph.search("   ").is_empty());
+}
+
+#[test]
+fn center_on_node_puts_node_in_viewport_middle() {
+    let viewport = egui::vec2(800.0, 600.0);
+    assert_eq!(
+        view::pan_to_center(pos(100.0, 50.0), viewport, 2.0),
+        egui::vec2(100.0, 100.0)
+    );
+
+    let mut graph = NodeGraph::new();
+    let far = graph.add_node(StoryNode::default(), pos(1000.0, 2000.0));
+    graph.set_zoom(0.5);
+    assert!(graph.center_on_node(far, viewport));
+    assert!(!graph.center_on_node(far + 1, viewport));
+
+    let node_center = pos(1000.0, 2000.0) + egui::vec2(NODE_WIDTH, NODE_HEIGHT) / 2.0;
+    let screen = (node_center.to_vec2() + graph.pan()) * graph.zoom();
+    assert_eq!(screen, viewport / 2.0);
+    assert_eq!(graph.selected, Some(far));
+}
+
+#[test]
+fn reachability_cache_invalidates_on_modification_and_debounces() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, pos(0.0, 0.0));
+    let line = graph.add_node(StoryNode::default(), pos(0.0, 100.0));
+    let orphan = graph.add_node(StoryNode::default(), pos(300.0, 100.0));
+    let end = graph.add_node(StoryNode::End, pos(0.0, 200.0));
+    graph.connect(start, line);
+    graph.connect(line, end);
+
+    // First computation is immediate.
+    assert!(graph.reachability_stale());
+    assert!(graph.refresh_reachability(0.0));
+    assert!(!graph.reachability_stale());
+    assert!(graph.is_unreachable(orphan));
+    assert!(!graph.is_unreachable(line));
+    assert!(!graph.is_unreachable(start) && !graph.is_unreachable(end));
+    assert!(!graph.refresh_reachability(5.0));
+
+    // An edit invalidates the cache; the old set is kept until edits settle.
+    graph.connect(line, orphan);
+    graph.connect(orphan, end);
+    assert!(graph.reachability_stale());
+    assert!(!graph.refresh_reachability(10.0));
+    assert!(!graph.refresh_reachability(10.1));
+    assert!(graph.is_unreachable(orphan));
+    assert!(graph.refresh_reachability(10.0 + REACHABILITY_DEBOUNCE_SECS));
+    assert!(!graph.reachability_stale());
+    assert!(!graph.is_unreachable(orphan));
+}
@@ -34,6 +34,16 @@ fn text_reveal_is_utf8_safe() {
     assert!(line.starts_with(second));
 }
 
+#[test]
+fn zero_text_speed_reveals_line_instantly() {
+    let mut state = PlayerSessionState::default();
+    state.text_chars_per_second = 0.0;
+    state.on_position_changed(0, 0.0);
+
+    assert_eq!(state.visible_text("Hola mundo", 0.0), "Hola mundo");
+    assert!(state.is_text_fully_revealed("Hola mundo", 0.0));
+}
+
 #[test]
 fn skip_read_only_only_skips_seen_dialogue() {
     let mut state = PlayerSessionState::default();
@@ -56,10 +66,31 @@ fn autoplay_delay_is_respected() {
     state.autoplay_enabled = true;
     state.autoplay_delay_ms = 1000;
 
-    assert!(state.autoplay_ready(0.0));
+    let engine = one_dialogue_engine();
+
+    assert!(state.autoplay_ready(&engine, 0.0));
     state.mark_auto_step(0.2);
-    assert!(!state.autoplay_ready(0.9));
-    assert!(state.autoplay_ready(1.3));
+    assert!(!state.autoplay_ready(&engine, 0.9));
+    assert!(state.autoplay_ready(&engine, 1.3));
+}
+
+#[test]
+fn paused_engine_ignores_autoplay_ticks_but_allows_manual_steps() {
+    let mut state = PlayerSessionState::default();
+    state.autoplay_enabled = true;
+    state.autoplay_delay_ms = 100;
+    let mut engine = one_dialogue_engine();
+
+    engine.pause();
+    assert!(engine.is_paused());
+    assert!(!state.autoplay_ready(&engine, 0.0));
+    assert!(!state.autoplay_ready(&engine, 60.0));
+
+    engine.step().expect("manual step while paused");
+    assert_eq!(engine.state().position, 1);
+
+    engine.unpause();
+    assert!(state.autoplay_ready(&engine, 60.0));
 }
 
 #[test]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::editor::validator::validate_with_asset_probe;
 use crate::editor::{validate_graph, LintCode, StoryNode, ValidationPhase};
 
 fn p(x: f32, y: f32) -> egui::Pos2 {
@@ -326,6 +327,8 @@ fn autofix_catalog_coverage() {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             }],
         },
         p(220.0, 240.0),
@@ -493,3 +496,168 @@ fn autofix_catalog_coverage() {
         coverage * 100.0
     );
 }
+
+#[test]
+fn apply_all_safe_cleans_safe_issues_and_keeps_review_ones() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+    let audio = graph.add_node(
+        StoryNode::AudioAction {
+            channel: "bgm".to_string(),
+            action: "play".to_string(),
+            asset: Some("audio/theme.ogg".to_string()),
+            volume: Some(4.0),
+            fade_duration_ms: None,
+            loop_playback: None,
+        },
+        p(0.0, 100.0),
+    );
+    let dialogue = graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "  ".to_string(),
+            text: "Hello".to_string(),
+        },
+        p(0.0, 200.0),
+    );
+    graph.connect(start, audio);
+    graph.connect(audio, dialogue);
+
+    let validate_all = |graph: &NodeGraph| validate_with_asset_probe(graph, |_| true);
+    let issues = validate_all(&graph);
+    assert!(issues
+        .iter()
+        .any(|issue| issue.code == LintCode::InvalidAudioVolume));
+    assert!(issues
+        .iter()
+        .any(|issue| issue.code == LintCode::EmptySpeakerName));
+
+    let report = apply_all_safe_with(&mut graph, &issues, validate_all);
+
+    let fix_ids: Vec<&str> = report.applied.iter().map(|(_, fix_id)| *fix_id).collect();
+    assert!(fix_ids.contains(&"audio_clamp_volume"));
+    assert!(fix_ids.contains(&"dialogue_fill_speaker"));
+    assert!(next_safe_fix(&graph, &report.remaining).is_none());
+    assert!(report
+        .remaining
+        .iter()
+        .any(|issue| issue.code == LintCode::DeadEnd && issue.node_id == Some(dialogue)));
+    assert!(!report
+        .remaining
+        .iter()
+        .any(|issue| issue.code == LintCode::InvalidAudioVolume
+            || issue.code == LintCode::EmptySpeakerName));
+}
+
+#[test]
+fn preview_fix_reports_changes_without_touching_graph() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+    let choice = graph.add_node(
+        StoryNode::Choice {
+            prompt: "Pick".to_string(),
+            options: vec!["A".to_string(), "B".to_string()],
+        },
+        p(0.0, 100.0),
+    );
+    graph.connect(start, choice);
+    let issue = LintIssue::warning(
+        Some(choice),
+        ValidationPhase::Graph,
+        LintCode::ChoiceOptionUnlinked,
+        "Choice option 1 has no outgoing connection",
+    );
+    let before = graph.clone();
+
+    let preview =
+        preview_fix(&graph, &issue, "choice_link_unlinked_to_end").expect("preview should succeed");
+    assert!(!preview.is_empty());
+    assert!(preview.connections_added > 0);
+    assert_eq!(preview.nodes_removed, Vec::<u32>::new());
+
+    assert_eq!(
+        graph.nodes().collect::<Vec<_>>(),
+        before.nodes().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        graph.connections().collect::<Vec<_>>(),
+        before.connections().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn preview_fix_lists_changed_fields() {
+    let mut graph = NodeGraph::new();
+    let node_id = graph.add_node(
+        StoryNode::Dialogue {
+            speaker: " ".to_string(),
+            text: "Hello".to_string(),
+        },
+        p(0.0, 0.0),
+    );
+    let issue = LintIssue::warning(
+        Some(node_id),
+        ValidationPhase::Graph,
+        LintCode::EmptySpeakerName,
+        "Speaker is empty",
+    );
+
+    let preview =
+        preview_fix(&graph, &issue, "dialogue_fill_speaker").expect("preview should succeed");
+    assert_eq!(
+        preview.fields_changed,
+        vec![(node_id, "speaker".to_string())]
+    );
+    assert!(matches!(
+        graph.get_node(node_id),
+        Some(StoryNode::Dialogue { speaker, .. }) if speaker == " "
+    ));
+}
+
+#[test]
+fn speaker_casing_fix_normalizes_all_variants_to_most_common() {
+    let mut graph = NodeGraph::new();
+    let ids: Vec<u32> = ["Ava", "ava", "Ava", "AVA", "Ben"]
+        .iter()
+        .enumerate()
+        .map(|(idx, speaker)| {
+            graph.add_node(
+                StoryNode::Dialogue {
+                    speaker: speaker.to_string(),
+                    text: "line".to_string(),
+                },
+                p(0.0, idx as f32 * 100.0),
+            )
+        })
+        .collect();
+
+    let flagged: Vec<LintIssue> = validate_graph(&graph)
+        .into_iter()
+        .filter(|issue| issue.code == LintCode::SpeakerNameInconsistent)
+        .collect();
+    assert_eq!(
+        flagged
+            .iter()
+            .filter_map(|issue| issue.node_id)
+            .collect::<Vec<_>>(),
+        vec![ids[1], ids[3]]
+    );
+    assert!(flagged
+        .iter()
+        .all(|issue| issue.severity == crate::editor::LintSeverity::Info));
+    assert!(flagged[0].message.contains("prefer 'Ava'"));
+
+    let changed = apply_fix(&mut graph, &flagged[0], "dialogue_normalize_speaker_casing")
+        .expect("casing fix applies");
+    assert!(changed);
+    let speakers: Vec<&str> = graph
+        .nodes()
+        .filter_map(|(_, node, _)| match node {
+            StoryNode::Dialogue { speaker, .. } => Some(speaker.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(speakers, vec!["Ava", "Ava", "Ava", "Ava", "Ben"]);
+    assert!(validate_graph(&graph)
+        .iter()
+        .all(|issue| issue.code != LintCode::SpeakerNameInconsistent));
+}
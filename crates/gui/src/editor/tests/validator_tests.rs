@@ -49,6 +49,41 @@ fn validate_reports_choice_unlinked_with_explicit_code() {
     assert_eq!(choice_issue.edge_to, None);
 }
 
+#[test]
+fn unconnected_choice_port_is_a_single_blocking_error() {
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+    let choice = graph.add_node(
+        StoryNode::Choice {
+            prompt: "Door?".to_string(),
+            options: vec!["Open".to_string(), "Leave".to_string()],
+        },
+        p(0.0, 100.0),
+    );
+    let end = graph.add_node(StoryNode::End, p(0.0, 200.0));
+    graph.connect(start, choice);
+    graph.connect_port(choice, 0, end);
+
+    let issues = validate(&graph);
+    let blocking: Vec<_> = issues
+        .iter()
+        .filter(|i| i.severity == LintSeverity::Error)
+        .collect();
+    assert_eq!(blocking.len(), 1);
+    assert_eq!(blocking[0].code, LintCode::ChoicePortUnconnected);
+    assert_eq!(blocking[0].node_id, Some(choice));
+    assert!(blocking[0].message.contains("port 2"));
+    // The quick-fixable warning is still reported alongside it.
+    assert!(issues
+        .iter()
+        .any(|i| i.code == LintCode::ChoiceOptionUnlinked && i.severity == LintSeverity::Warning));
+
+    graph.connect_port(choice, 1, end);
+    assert!(validate(&graph)
+        .iter()
+        .all(|i| i.code != LintCode::ChoicePortUnconnected));
+}
+
 #[test]
 fn validate_reports_unsafe_asset_paths_and_transition_duration() {
     let mut graph = NodeGraph::new();
@@ -219,6 +254,37 @@ fn validate_reports_scene_patch_and_generic_limits() {
         .any(|i| i.code == LintCode::ContractUnsupportedExport));
 }
 
+#[test]
+fn validate_warns_auto_choice_with_zero_weight_options_and_no_fallthrough() {
+    let choice = |fallthrough: Option<&str>| {
+        let mut graph = NodeGraph::new();
+        let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+        let node = graph.add_node(
+            StoryNode::Generic(visual_novel_engine::EventRaw::Choice(
+                visual_novel_engine::ChoiceRaw {
+                    prompt: "Ambient".to_string(),
+                    options: vec![visual_novel_engine::ChoiceOptionRaw {
+                        text: "Rain".to_string(),
+                        target: "start".to_string(),
+                        weight: Some(0),
+                        effects: Vec::new(),
+                    }],
+                    auto: true,
+                    fallthrough: fallthrough.map(str::to_string),
+                },
+            )),
+            p(0.0, 100.0),
+        );
+        graph.connect(start, node);
+        validate_with_asset_probe(&graph, |_asset| true)
+            .iter()
+            .any(|i| i.code == LintCode::ChoiceNoFallthrough)
+    };
+
+    assert!(choice(None));
+    assert!(!choice(Some("start")));
+}
+
 #[test]
 fn extcall_generic_is_exportable_and_preserves_trace_context() {
     let dir = tempdir().expect("tempdir");
@@ -400,3 +466,134 @@ fn validate_with_project_root_resolves_relative_assets_from_loaded_project() {
         "asset should resolve against project_root, not process current_dir"
     );
 }
+
+/// No catch-all arm: a new variant fails to compile here until it is added
+/// to `LintCode::ALL`, which the uniqueness test below walks.
+fn variant_index(code: LintCode) -> usize {
+    match code {
+        LintCode::MissingStart
+        | LintCode::MultipleStart
+        | LintCode::UnreachableNode
+        | LintCode::PotentialLoop
+        | LintCode::DeadEnd
+        | LintCode::ChoiceNoOptions
+        | LintCode::ChoiceOptionUnlinked
+        | LintCode::ChoicePortUnconnected
+        | LintCode::ChoicePortOutOfRange
+        | LintCode::ChoiceNoFallthrough
+        | LintCode::AudioAssetMissing
+        | LintCode::AudioAssetEmpty
+        | LintCode::AssetReferenceMissing
+        | LintCode::SceneBackgroundEmpty
+        | LintCode::UnsafeAssetPath
+        | LintCode::InvalidAudioChannel
+        | LintCode::InvalidAudioAction
+        | LintCode::InvalidAudioVolume
+        | LintCode::InvalidAudioFade
+        | LintCode::InvalidCharacterScale
+        | LintCode::InvalidTransitionDuration
+        | LintCode::InvalidTransitionKind
+        | LintCode::EmptyCharacterName
+        | LintCode::EmptySpeakerName
+        | LintCode::SpeakerNameInconsistent
+        | LintCode::EmptyJumpTarget
+        | LintCode::FlagSetButNeverRead
+        | LintCode::FlagReadButNeverSet
+        | LintCode::VarSetButNeverRead
+        | LintCode::VarReadButNeverSet
+        | LintCode::ContractUnsupportedExport
+        | LintCode::GenericEventUnchecked
+        | LintCode::CompileError
+        | LintCode::RuntimeInitError
+        | LintCode::DryRunUnreachableCompiled
+        | LintCode::DeadBranch
+        | LintCode::DryRunStepLimit
+        | LintCode::DryRunRuntimeError
+        | LintCode::DryRunParityMismatch
+        | LintCode::DryRunFinished => LintCode::ALL
+            .iter()
+            .position(|listed| *listed == code)
+            .expect("every LintCode must be listed in LintCode::ALL"),
+    }
+}
+
+#[test]
+fn every_lint_code_has_unique_nonempty_stable_str() {
+    let mut seen = std::collections::BTreeSet::new();
+    for (idx, code) in LintCode::ALL.iter().enumerate() {
+        assert_eq!(variant_index(*code), idx, "{code:?} listed twice");
+        let stable = code.as_stable_str();
+        assert!(!stable.is_empty(), "{code:?} has an empty stable id");
+        assert!(
+            stable
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+            "{stable} is not kebab-case"
+        );
+        assert!(seen.insert(stable), "duplicate stable id {stable}");
+    }
+    assert_eq!(
+        LintCode::ChoiceNoOptions.as_stable_str(),
+        "choice-no-options"
+    );
+}
+
+#[test]
+fn validate_json_reports_stable_codes() {
+    let script = visual_novel_engine::ScriptRaw::new(Vec::new(), std::collections::BTreeMap::new());
+    let issues = validate_json(&script);
+    assert!(issues
+        .iter()
+        .any(|issue| issue.code == "missing-start" && issue.severity == "error"));
+    let json = serde_json::to_string(&issues).expect("serialize");
+    assert!(json.contains("\"code\":\"missing-start\""));
+}
+
+#[test]
+fn flag_set_but_never_read_and_read_but_never_set_are_reported() {
+    use visual_novel_engine::{CondRaw, EventRaw};
+
+    let mut graph = NodeGraph::new();
+    let start = graph.add_node(StoryNode::Start, p(0.0, 0.0));
+    let set_visited = graph.add_node(
+        StoryNode::Generic(EventRaw::SetFlag {
+            key: "visited".to_string(),
+            value: true,
+        }),
+        p(0.0, 100.0),
+    );
+    let branch = graph.add_node(
+        StoryNode::JumpIf {
+            target: "start".to_string(),
+            cond: CondRaw::Flag {
+                key: "met_ava".to_string(),
+                is_set: true,
+            },
+        },
+        p(0.0, 200.0),
+    );
+    let end = graph.add_node(StoryNode::End, p(0.0, 300.0));
+    graph.connect(start, set_visited);
+    graph.connect(set_visited, branch);
+    graph.connect(branch, end);
+
+    let issues = validate(&graph);
+    let unread = issues
+        .iter()
+        .find(|issue| issue.code == LintCode::FlagSetButNeverRead)
+        .expect("expected FlagSetButNeverRead");
+    assert_eq!(unread.node_id, Some(set_visited));
+    assert_eq!(unread.severity, LintSeverity::Info);
+    assert!(unread.message.contains("'visited'"));
+
+    let unset = issues
+        .iter()
+        .find(|issue| issue.code == LintCode::FlagReadButNeverSet)
+        .expect("expected FlagReadButNeverSet");
+    assert_eq!(unset.node_id, Some(branch));
+    assert!(unset.message.contains("'met_ava'"));
+    assert!(issues.iter().all(|issue| !matches!(
+        issue.code,
+        LintCode::VarSetButNeverRead | LintCode::VarReadButNeverSet
+    )));
+}
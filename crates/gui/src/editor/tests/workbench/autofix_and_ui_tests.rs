@@ -259,3 +259,83 @@ fn sync_graph_reopens_validation_panel_on_errors() {
         .iter()
         .any(|issue| issue.severity == LintSeverity::Error));
 }
+
+fn graph_shape(
+    graph: &NodeGraph,
+) -> (
+    Vec<(u32, StoryNode, egui::Pos2)>,
+    Vec<crate::editor::node_graph::GraphConnection>,
+) {
+    (
+        graph.nodes().cloned().collect(),
+        graph.connections().cloned().collect(),
+    )
+}
+
+#[test]
+fn structural_fix_is_reverted_by_a_single_undo() {
+    let mut workbench = EditorWorkbench::new(VnConfig::default());
+    let start = workbench
+        .node_graph
+        .add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+    let choice = workbench.node_graph.add_node(
+        StoryNode::Choice {
+            prompt: "Pick".to_string(),
+            options: vec!["A".to_string(), "B".to_string()],
+        },
+        egui::pos2(0.0, 100.0),
+    );
+    workbench.node_graph.connect(start, choice);
+    workbench.node_graph.clear_modified();
+    let _ = workbench.run_dry_validation();
+    let before = graph_shape(&workbench.node_graph);
+
+    let idx = workbench
+        .validation_issues
+        .iter()
+        .position(|issue| {
+            issue.code == LintCode::ChoiceOptionUnlinked && issue.node_id == Some(choice)
+        })
+        .expect("expected ChoiceOptionUnlinked");
+    workbench
+        .apply_issue_fix(idx, "choice_link_unlinked_to_end")
+        .expect("fix must apply");
+    assert_ne!(graph_shape(&workbench.node_graph), before);
+    assert!(!workbench.node_graph.is_modified());
+
+    assert!(workbench.undo_graph_change());
+    assert_eq!(graph_shape(&workbench.node_graph), before);
+}
+
+#[test]
+fn apply_all_safe_fixes_is_one_undo_step() {
+    let mut workbench = EditorWorkbench::new(VnConfig::default());
+    let start = workbench
+        .node_graph
+        .add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+    let first = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "".to_string(),
+            text: "Hola".to_string(),
+        },
+        egui::pos2(0.0, 100.0),
+    );
+    let second = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: " ".to_string(),
+            text: "Adios".to_string(),
+        },
+        egui::pos2(0.0, 200.0),
+    );
+    workbench.node_graph.connect(start, first);
+    workbench.node_graph.connect(first, second);
+    let _ = workbench.run_dry_validation();
+    let before = graph_shape(&workbench.node_graph);
+    let undo_depth = workbench.undo_stack.undo_count();
+
+    assert!(workbench.apply_all_safe_fixes() >= 2);
+    assert_eq!(workbench.undo_stack.undo_count(), undo_depth + 1);
+
+    assert!(workbench.undo_graph_change());
+    assert_eq!(graph_shape(&workbench.node_graph), before);
+}
@@ -191,6 +191,8 @@ fn sync_graph_to_script_builds_non_empty_scene_preview_from_visual_events() {
                 x: Some(640),
                 y: Some(480),
                 scale: Some(1.0),
+                z: None,
+                flip_x: false,
             }],
         },
         egui::pos2(0.0, 160.0),
@@ -325,6 +327,8 @@ fn scene_preview_builds_owner_map_for_core_entities() {
                 x: Some(500),
                 y: Some(350),
                 scale: Some(1.0),
+                z: None,
+                flip_x: false,
             }],
         },
         egui::pos2(0.0, 100.0),
@@ -384,6 +388,8 @@ fn composer_mutation_updates_node_character_position() {
                 x: Some(100),
                 y: Some(120),
                 scale: Some(1.0),
+                z: None,
+                flip_x: false,
             }],
         },
         egui::pos2(0.0, 0.0),
@@ -468,3 +474,146 @@ fn fallback_entity_owner_map_prefers_scene_music_owner_over_audio_action_overrid
         "scene node should remain canonical owner for shared scene music entity"
     );
 }
+
+#[test]
+fn play_from_selected_node_shows_that_dialogue_first() {
+    let config = VnConfig::default();
+    let mut workbench = EditorWorkbench::new(config);
+
+    let start = workbench
+        .node_graph
+        .add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+    let first = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "Ava".to_string(),
+            text: "Primera".to_string(),
+        },
+        egui::pos2(0.0, 100.0),
+    );
+    let middle = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "Ava".to_string(),
+            text: "Segunda".to_string(),
+        },
+        egui::pos2(0.0, 200.0),
+    );
+    let end = workbench
+        .node_graph
+        .add_node(StoryNode::End, egui::pos2(0.0, 300.0));
+    workbench.node_graph.connect(start, first);
+    workbench.node_graph.connect(first, middle);
+    workbench.node_graph.connect(middle, end);
+    workbench
+        .sync_graph_to_script()
+        .expect("dialogue chain should compile");
+
+    assert!(workbench.prepare_player_mode_from(middle));
+    let event = workbench
+        .engine
+        .as_ref()
+        .and_then(|engine| engine.current_event().ok())
+        .expect("player should expose a current event");
+    assert!(matches!(
+        event,
+        visual_novel_engine::EventCompiled::Dialogue(dialogue)
+            if dialogue.text.as_ref() == "Segunda"
+    ));
+
+    assert!(!workbench.prepare_player_mode_from(end));
+}
+
+#[test]
+fn prepare_player_mode_from_node_past_a_starting_choice() {
+    let config = VnConfig::default();
+    let mut workbench = EditorWorkbench::new(config);
+
+    let start = workbench
+        .node_graph
+        .add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+    let choice = workbench.node_graph.add_node(
+        StoryNode::Choice {
+            prompt: "Ruta".to_string(),
+            options: vec!["A".to_string(), "B".to_string()],
+        },
+        egui::pos2(0.0, 100.0),
+    );
+    let branch_a = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "A".to_string(),
+            text: "Ruta A".to_string(),
+        },
+        egui::pos2(-120.0, 200.0),
+    );
+    let branch_b = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "B".to_string(),
+            text: "Ruta B".to_string(),
+        },
+        egui::pos2(120.0, 200.0),
+    );
+    workbench.node_graph.connect(start, choice);
+    workbench.node_graph.connect_port(choice, 0, branch_a);
+    workbench.node_graph.connect_port(choice, 1, branch_b);
+    workbench
+        .sync_graph_to_script()
+        .expect("branching graph should compile");
+
+    assert!(workbench.prepare_player_mode_from(branch_b));
+    let event = workbench
+        .engine
+        .as_ref()
+        .and_then(|engine| engine.current_event().ok())
+        .expect("player should expose a current event");
+    assert!(matches!(
+        event,
+        visual_novel_engine::EventCompiled::Dialogue(dialogue)
+            if dialogue.text.as_ref() == "Ruta B"
+    ));
+}
+
+#[test]
+fn choice_route_json_replays_recorded_choices() {
+    let config = VnConfig::default();
+    let mut workbench = EditorWorkbench::new(config);
+
+    let start = workbench
+        .node_graph
+        .add_node(StoryNode::Start, egui::pos2(0.0, 0.0));
+    let choice = workbench.node_graph.add_node(
+        StoryNode::Choice {
+            prompt: "Ruta".to_string(),
+            options: vec!["A".to_string(), "B".to_string()],
+        },
+        egui::pos2(0.0, 100.0),
+    );
+    let branch_a = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "A".to_string(),
+            text: "Ruta A".to_string(),
+        },
+        egui::pos2(-120.0, 200.0),
+    );
+    let branch_b = workbench.node_graph.add_node(
+        StoryNode::Dialogue {
+            speaker: "B".to_string(),
+            text: "Ruta B".to_string(),
+        },
+        egui::pos2(120.0, 200.0),
+    );
+    workbench.node_graph.connect(start, choice);
+    workbench.node_graph.connect_port(choice, 0, branch_a);
+    workbench.node_graph.connect_port(choice, 1, branch_b);
+    workbench
+        .sync_graph_to_script()
+        .expect("branching graph should compile");
+
+    let executed = workbench
+        .apply_choice_route_json(r#"{ "choices": [1] }"#)
+        .expect("route should replay");
+
+    assert_eq!(executed, 2);
+    let engine = workbench.engine.as_ref().expect("player engine");
+    assert_eq!(engine.choice_log(), &[1]);
+    assert!(engine.current_event().is_err(), "route B ends the script");
+    assert!(workbench.apply_choice_route_json("not json").is_err());
+}
@@ -3,7 +3,7 @@
 //! Displays and allows editing of animation keyframes.
 
 use eframe::egui;
-use visual_novel_engine::Timeline;
+use visual_novel_engine::{Easing, Timeline};
 
 /// Timeline panel widget.
 pub struct TimelinePanel<'a> {
@@ -86,18 +86,39 @@ impl<'a> TimelinePanel<'a> {
         // Track list
         ui.label(format!("Tracks: {}", self.timeline.track_count()));
 
+        let mut easing_edits = Vec::new();
         egui::ScrollArea::vertical()
             .max_height(80.0)
             .show(ui, |ui| {
                 for (idx, track) in self.timeline.tracks().enumerate() {
-                    ui.horizontal(|ui| {
-                        ui.label(format!(
-                            "Track {}: Entity {:?} - {:?}",
-                            idx,
-                            track.target.raw(),
-                            track.property
-                        ));
-                        ui.label(format!("({} keyframes)", track.len()));
+                    let header = format!(
+                        "Track {}: Entity {:?} - {:?} ({} keyframes)",
+                        idx,
+                        track.target.raw(),
+                        track.property,
+                        track.len()
+                    );
+                    ui.collapsing(header, |ui| {
+                        for keyframe in track.keyframes() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("t={} v={}", keyframe.time, keyframe.value));
+                                let mut easing = keyframe.easing;
+                                egui::ComboBox::from_id_source(("kf_easing", idx, keyframe.time))
+                                    .selected_text(easing.label())
+                                    .show_ui(ui, |ui| {
+                                        for option in Easing::ALL {
+                                            ui.selectable_value(
+                                                &mut easing,
+                                                option,
+                                                option.label(),
+                                            );
+                                        }
+                                    });
+                                if easing != keyframe.easing {
+                                    easing_edits.push((idx, keyframe.time, easing));
+                                }
+                            });
+                        }
                     });
                 }
 
@@ -105,5 +126,11 @@ impl<'a> TimelinePanel<'a> {
                     ui.label("No tracks. Add keyframes to create animations.");
                 }
             });
+
+        for (idx, time, easing) in easing_edits {
+            if let Some(track) = self.timeline.get_track_mut(idx) {
+                track.set_keyframe_easing(time, easing);
+            }
+        }
     }
 }
@@ -1,7 +1,10 @@
 use crate::editor::execution_contract;
 use crate::editor::node_graph::NodeGraph;
 use crate::editor::node_types::StoryNode;
+use crate::editor::script_sync;
+use serde::Serialize;
 use std::path::Path;
+use visual_novel_engine::ScriptRaw;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LintSeverity {
@@ -38,7 +41,9 @@ pub enum LintCode {
     DeadEnd,
     ChoiceNoOptions,
     ChoiceOptionUnlinked,
+    ChoicePortUnconnected,
     ChoicePortOutOfRange,
+    ChoiceNoFallthrough,
     AudioAssetMissing,
     AudioAssetEmpty,
     AssetReferenceMissing,
@@ -53,12 +58,18 @@ pub enum LintCode {
     InvalidTransitionKind,
     EmptyCharacterName,
     EmptySpeakerName,
+    SpeakerNameInconsistent,
     EmptyJumpTarget,
+    FlagSetButNeverRead,
+    FlagReadButNeverSet,
+    VarSetButNeverRead,
+    VarReadButNeverSet,
     ContractUnsupportedExport,
     GenericEventUnchecked,
     CompileError,
     RuntimeInitError,
     DryRunUnreachableCompiled,
+    DeadBranch,
     DryRunStepLimit,
     DryRunRuntimeError,
     DryRunParityMismatch,
@@ -66,6 +77,97 @@ pub enum LintCode {
 }
 
 impl LintCode {
+    /// Every code, in declaration order.
+    pub const ALL: &'static [LintCode] = &[
+        LintCode::MissingStart,
+        LintCode::MultipleStart,
+        LintCode::UnreachableNode,
+        LintCode::PotentialLoop,
+        LintCode::DeadEnd,
+        LintCode::ChoiceNoOptions,
+        LintCode::ChoiceOptionUnlinked,
+        LintCode::ChoicePortUnconnected,
+        LintCode::ChoicePortOutOfRange,
+        LintCode::ChoiceNoFallthrough,
+        LintCode::AudioAssetMissing,
+        LintCode::AudioAssetEmpty,
+        LintCode::AssetReferenceMissing,
+        LintCode::SceneBackgroundEmpty,
+        LintCode::UnsafeAssetPath,
+        LintCode::InvalidAudioChannel,
+        LintCode::InvalidAudioAction,
+        LintCode::InvalidAudioVolume,
+        LintCode::InvalidAudioFade,
+        LintCode::InvalidCharacterScale,
+        LintCode::InvalidTransitionDuration,
+        LintCode::InvalidTransitionKind,
+        LintCode::EmptyCharacterName,
+        LintCode::EmptySpeakerName,
+        LintCode::SpeakerNameInconsistent,
+        LintCode::EmptyJumpTarget,
+        LintCode::FlagSetButNeverRead,
+        LintCode::FlagReadButNeverSet,
+        LintCode::VarSetButNeverRead,
+        LintCode::VarReadButNeverSet,
+        LintCode::ContractUnsupportedExport,
+        LintCode::GenericEventUnchecked,
+        LintCode::CompileError,
+        LintCode::RuntimeInitError,
+        LintCode::DryRunUnreachableCompiled,
+        LintCode::DeadBranch,
+        LintCode::DryRunStepLimit,
+        LintCode::DryRunRuntimeError,
+        LintCode::DryRunParityMismatch,
+        LintCode::DryRunFinished,
+    ];
+
+    /// Kebab-case id for CI allow/deny lists. Unlike [`LintCode::label`],
+    /// these strings never change once released; new codes get new ids.
+    pub fn as_stable_str(self) -> &'static str {
+        match self {
+            LintCode::MissingStart => "missing-start",
+            LintCode::MultipleStart => "multiple-start",
+            LintCode::UnreachableNode => "unreachable-node",
+            LintCode::PotentialLoop => "potential-loop",
+            LintCode::DeadEnd => "dead-end",
+            LintCode::ChoiceNoOptions => "choice-no-options",
+            LintCode::ChoiceOptionUnlinked => "choice-option-unlinked",
+            LintCode::ChoicePortUnconnected => "choice-port-unconnected",
+            LintCode::ChoicePortOutOfRange => "choice-port-out-of-range",
+            LintCode::ChoiceNoFallthrough => "choice-no-fallthrough",
+            LintCode::AudioAssetMissing => "audio-asset-missing",
+            LintCode::AudioAssetEmpty => "audio-asset-empty",
+            LintCode::AssetReferenceMissing => "asset-reference-missing",
+            LintCode::SceneBackgroundEmpty => "scene-background-empty",
+            LintCode::UnsafeAssetPath => "unsafe-asset-path",
+            LintCode::InvalidAudioChannel => "invalid-audio-channel",
+            LintCode::InvalidAudioAction => "invalid-audio-action",
+            LintCode::InvalidAudioVolume => "invalid-audio-volume",
+            LintCode::InvalidAudioFade => "invalid-audio-fade",
+            LintCode::InvalidCharacterScale => "invalid-character-scale",
+            LintCode::InvalidTransitionDuration => "invalid-transition-duration",
+            LintCode::InvalidTransitionKind => "invalid-transition-kind",
+            LintCode::EmptyCharacterName => "empty-character-name",
+            LintCode::EmptySpeakerName => "empty-speaker-name",
+            LintCode::SpeakerNameInconsistent => "speaker-name-inconsistent",
+            LintCode::EmptyJumpTarget => "empty-jump-target",
+            LintCode::FlagSetButNeverRead => "flag-set-but-never-read",
+            LintCode::FlagReadButNeverSet => "flag-read-but-never-set",
+            LintCode::VarSetButNeverRead => "var-set-but-never-read",
+            LintCode::VarReadButNeverSet => "var-read-but-never-set",
+            LintCode::ContractUnsupportedExport => "contract-unsupported-export",
+            LintCode::GenericEventUnchecked => "generic-event-unchecked",
+            LintCode::CompileError => "compile-error",
+            LintCode::RuntimeInitError => "runtime-init-error",
+            LintCode::DryRunUnreachableCompiled => "dry-run-unreachable-compiled",
+            LintCode::DeadBranch => "dead-branch",
+            LintCode::DryRunStepLimit => "dry-run-step-limit",
+            LintCode::DryRunRuntimeError => "dry-run-runtime-error",
+            LintCode::DryRunParityMismatch => "dry-run-parity-mismatch",
+            LintCode::DryRunFinished => "dry-run-finished",
+        }
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             LintCode::MissingStart => "VAL_START_MISSING",
@@ -75,7 +177,9 @@ impl LintCode {
             LintCode::DeadEnd => "VAL_DEAD_END",
             LintCode::ChoiceNoOptions => "VAL_CHOICE_EMPTY",
             LintCode::ChoiceOptionUnlinked => "VAL_CHOICE_UNLINKED",
+            LintCode::ChoicePortUnconnected => "VAL_CHOICE_PORT_UNCONNECTED",
             LintCode::ChoicePortOutOfRange => "VAL_CHOICE_PORT_OOB",
+            LintCode::ChoiceNoFallthrough => "VAL_CHOICE_NO_FALLTHROUGH",
             LintCode::AudioAssetMissing => "VAL_AUDIO_MISSING",
             LintCode::AudioAssetEmpty => "VAL_AUDIO_EMPTY",
             LintCode::AssetReferenceMissing => "VAL_ASSET_NOT_FOUND",
@@ -90,12 +194,18 @@ impl LintCode {
             LintCode::InvalidTransitionKind => "VAL_TRANSITION_KIND_INVALID",
             LintCode::EmptyCharacterName => "VAL_CHARACTER_NAME_EMPTY",
             LintCode::EmptySpeakerName => "VAL_SPEAKER_EMPTY",
+            LintCode::SpeakerNameInconsistent => "VAL_SPEAKER_CASE_MISMATCH",
             LintCode::EmptyJumpTarget => "VAL_JUMP_EMPTY",
+            LintCode::FlagSetButNeverRead => "VAL_FLAG_UNREAD",
+            LintCode::FlagReadButNeverSet => "VAL_FLAG_UNSET",
+            LintCode::VarSetButNeverRead => "VAL_VAR_UNREAD",
+            LintCode::VarReadButNeverSet => "VAL_VAR_UNSET",
             LintCode::ContractUnsupportedExport => "VAL_CONTRACT_EXPORT_UNSUPPORTED",
             LintCode::GenericEventUnchecked => "VAL_GENERIC_UNCHECKED",
             LintCode::CompileError => "CMP_SCRIPT_ERROR",
             LintCode::RuntimeInitError => "CMP_RUNTIME_INIT",
             LintCode::DryRunUnreachableCompiled => "DRY_UNREACHABLE",
+            LintCode::DeadBranch => "DRY_DEAD_BRANCH",
             LintCode::DryRunStepLimit => "DRY_STEP_LIMIT",
             LintCode::DryRunRuntimeError => "DRY_RUNTIME_ERROR",
             LintCode::DryRunParityMismatch => "DRY_PARITY_MISMATCH",
@@ -207,6 +317,43 @@ impl LintIssue {
     }
 }
 
+/// Serializable view of a [`LintIssue`] keyed by its stable code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StableIssue {
+    pub code: &'static str,
+    pub severity: &'static str,
+    pub phase: &'static str,
+    pub node_id: Option<u32>,
+    pub event_ip: Option<u32>,
+    pub message: String,
+}
+
+impl From<&LintIssue> for StableIssue {
+    fn from(issue: &LintIssue) -> Self {
+        Self {
+            code: issue.code.as_stable_str(),
+            severity: match issue.severity {
+                LintSeverity::Error => "error",
+                LintSeverity::Warning => "warning",
+                LintSeverity::Info => "info",
+            },
+            phase: issue.phase.label(),
+            node_id: issue.node_id,
+            event_ip: issue.event_ip,
+            message: issue.message.clone(),
+        }
+    }
+}
+
+/// Lints `script` through its node graph and returns issues with stable
+/// codes, so a pipeline can fail only on the codes it cares about.
+pub fn validate_json(script: &ScriptRaw) -> Vec<StableIssue> {
+    validate(&script_sync::from_script(script))
+        .iter()
+        .map(StableIssue::from)
+        .collect()
+}
+
 pub fn validate(graph: &NodeGraph) -> Vec<LintIssue> {
     validate_with_asset_probe(graph, helpers::default_asset_exists)
 }
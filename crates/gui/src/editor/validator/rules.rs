@@ -6,7 +6,8 @@ use super::helpers::{
     is_valid_audio_channel, is_valid_transition_kind, should_probe_asset_exists, visit_node,
 };
 use super::*;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use visual_novel_engine::{CondRaw, EventRaw};
 
 pub(super) fn validate_with_asset_probe_impl<F>(
     graph: &NodeGraph,
@@ -50,8 +51,8 @@ where
         visit_node(graph, *id, &mut visited);
     }
 
-    for (id, _, _) in &graph.nodes {
-        if !visited.contains(id) {
+    for (id, node, _) in &graph.nodes {
+        if !visited.contains(id) && !node.is_comment() {
             let (edge_from, blocked_by) = unreachable_blocker_context(graph, *id, &visited);
             let mut issue = LintIssue::warning(
                 Some(*id),
@@ -77,6 +78,9 @@ where
     }
 
     for (id, node, _) in &graph.nodes {
+        if node.is_comment() {
+            continue;
+        }
         let contract = execution_contract::contract_for_node(node);
         if !node.is_marker() && !contract.export_supported {
             let event_name = non_exportable_event_name(node, contract.event_name);
@@ -255,6 +259,18 @@ where
                 }
             }
             StoryNode::Generic(_) => {
+                if let StoryNode::Generic(EventRaw::Choice(choice)) = node {
+                    let has_zero_weight =
+                        choice.options.iter().any(|option| option.weight == Some(0));
+                    if choice.auto && has_zero_weight && choice.fallthrough.is_none() {
+                        issues.push(LintIssue::warning(
+                            Some(*id),
+                            ValidationPhase::Graph,
+                            LintCode::ChoiceNoFallthrough,
+                            "Auto choice switches options off with zero weight but has no fallthrough",
+                        ));
+                    }
+                }
                 let mut issue = LintIssue::warning(
                     Some(*id),
                     ValidationPhase::Graph,
@@ -353,6 +369,18 @@ where
                             )
                             .with_edge(Some(*id), None),
                         );
+                        issues.push(
+                            LintIssue::error(
+                                Some(*id),
+                                ValidationPhase::Graph,
+                                LintCode::ChoicePortUnconnected,
+                                format!(
+                                    "Choice port {} is unconnected; export is blocked",
+                                    idx + 1
+                                ),
+                            )
+                            .with_edge(Some(*id), None),
+                        );
                     }
                 }
 
@@ -490,7 +518,7 @@ where
                     ));
                 }
             }
-            StoryNode::Start | StoryNode::End => {}
+            StoryNode::Start | StoryNode::End | StoryNode::Comment { .. } => {}
         }
 
         if !matches!(node, StoryNode::End) && !has_outgoing(graph, *id) {
@@ -503,5 +531,96 @@ where
         }
     }
 
+    let speaker_fixes = graph.speaker_casing_fixes();
+    for (id, node, _) in &graph.nodes {
+        let StoryNode::Dialogue { speaker, .. } = node else {
+            continue;
+        };
+        if let Some(canonical) = speaker_fixes.get(speaker) {
+            issues.push(LintIssue::info(
+                Some(*id),
+                ValidationPhase::Graph,
+                LintCode::SpeakerNameInconsistent,
+                format!("Speaker '{speaker}' is also spelled '{canonical}'; prefer '{canonical}'"),
+            ));
+        }
+    }
+
+    symbol_usage_issues(graph, &mut issues);
+
     issues
 }
+
+/// Writes and reads of one symbol namespace, keyed by name, each holding the
+/// first node (in graph order) that touches it.
+#[derive(Default)]
+struct SymbolUsage {
+    writes: BTreeMap<String, u32>,
+    reads: BTreeMap<String, u32>,
+}
+
+impl SymbolUsage {
+    fn write(&mut self, key: &str, node_id: u32) {
+        self.writes.entry(key.to_string()).or_insert(node_id);
+    }
+
+    fn read(&mut self, key: &str, node_id: u32) {
+        self.reads.entry(key.to_string()).or_insert(node_id);
+    }
+}
+
+fn symbol_usage_issues(graph: &NodeGraph, issues: &mut Vec<LintIssue>) {
+    let mut flags = SymbolUsage::default();
+    let mut vars = SymbolUsage::default();
+    for (id, node, _) in &graph.nodes {
+        match node {
+            StoryNode::SetVariable { key, .. }
+            | StoryNode::Generic(EventRaw::SetVar { key, .. }) => vars.write(key, *id),
+            StoryNode::Generic(EventRaw::SetFlag { key, .. }) => flags.write(key, *id),
+            StoryNode::JumpIf { cond, .. } | StoryNode::Generic(EventRaw::JumpIf { cond, .. }) => {
+                match cond {
+                    CondRaw::Flag { key, .. } => flags.read(key, *id),
+                    CondRaw::VarCmp { key, .. } => vars.read(key, *id),
+                    CondRaw::StrEq { .. } => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (usage, kind, unread, unset) in [
+        (
+            &flags,
+            "Flag",
+            LintCode::FlagSetButNeverRead,
+            LintCode::FlagReadButNeverSet,
+        ),
+        (
+            &vars,
+            "Variable",
+            LintCode::VarSetButNeverRead,
+            LintCode::VarReadButNeverSet,
+        ),
+    ] {
+        for (key, node_id) in &usage.writes {
+            if !usage.reads.contains_key(key) {
+                issues.push(LintIssue::info(
+                    Some(*node_id),
+                    ValidationPhase::Graph,
+                    unread,
+                    format!("{kind} '{key}' is set but never read by any branch"),
+                ));
+            }
+        }
+        for (key, node_id) in &usage.reads {
+            if !usage.writes.contains_key(key) {
+                issues.push(LintIssue::warning(
+                    Some(*node_id),
+                    ValidationPhase::Graph,
+                    unset,
+                    format!("{kind} '{key}' is read but never set"),
+                ));
+            }
+        }
+    }
+}
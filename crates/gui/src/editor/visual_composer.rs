@@ -197,12 +197,18 @@ impl<'a> VisualComposerPanel<'a> {
                         if let Some(texture_id) =
                             self.resolve_image_texture(ui.ctx(), expression.as_ref())
                         {
+                            // Swapping the u bounds mirrors the sprite.
+                            let (u_min, u_max) = if character.flip_x {
+                                (1.0, 0.0)
+                            } else {
+                                (0.0, 1.0)
+                            };
                             ui.painter().image(
                                 texture_id,
                                 rect,
                                 egui::Rect::from_min_max(
-                                    egui::pos2(0.0, 0.0),
-                                    egui::pos2(1.0, 1.0),
+                                    egui::pos2(u_min, 0.0),
+                                    egui::pos2(u_max, 1.0),
                                 ),
                                 egui::Color32::WHITE,
                             );
@@ -363,7 +369,7 @@ impl<'a> VisualComposerPanel<'a> {
         &mut self,
         project_root: &Path,
         asset_path: &str,
-    ) -> Result<vnengine_assets::LoadedImage, ()> {
+    ) -> Result<std::sync::Arc<vnengine_assets::LoadedImage>, ()> {
         let store = match vnengine_assets::AssetStore::new(
             project_root.to_path_buf(),
             vnengine_assets::SecurityMode::Trusted,
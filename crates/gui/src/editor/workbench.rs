@@ -12,6 +12,7 @@ use crate::editor::{
     node_editor::NodeEditorPanel,
     node_graph::NodeGraph,
     node_types::ToastState,
+    statistics_panel::StatisticsPanel,
     timeline_panel::TimelinePanel,
     undo::UndoStack,
     EditorMode,
@@ -86,6 +87,8 @@ pub struct EditorWorkbench {
     pub show_validation: bool,
     pub validation_collapsed: bool,
     pub show_save_confirm: bool,
+    pub show_statistics: bool,
+    pub reading_wpm: u32,
 
     // Selection
     pub selected_node: Option<u32>,
@@ -105,6 +108,8 @@ pub struct EditorWorkbench {
 
     // Engine Instance (for Player Mode)
     pub engine: Option<Engine>,
+    /// Node id to event ip in `engine`'s script, for "Play from here".
+    pub player_node_ips: std::collections::BTreeMap<u32, u32>,
     pub player_audio_backend: Option<Box<dyn visual_novel_runtime::Audio>>,
     pub player_audio_root: Option<std::path::PathBuf>,
 
@@ -202,6 +207,8 @@ impl EditorWorkbench {
             show_validation: false,
             validation_collapsed: false,
             show_save_confirm: false,
+            show_statistics: false,
+            reading_wpm: visual_novel_engine::DEFAULT_READING_WPM,
             selected_node: None,
             selected_entity: None,
             scene: visual_novel_engine::SceneState::default(),
@@ -213,6 +220,7 @@ impl EditorWorkbench {
             is_playing: false,
             player_state: crate::editor::player_ui::PlayerSessionState::default(),
             engine: None,
+            player_node_ips: std::collections::BTreeMap::new(),
             player_audio_backend: None,
             player_audio_root: None,
             validation_issues: Vec::new(),
@@ -261,6 +269,17 @@ impl EditorWorkbench {
         }
     }
 
+    /// Restores the graph saved by the last undoable change and re-syncs the
+    /// script. Returns false when there is nothing to undo.
+    pub fn undo_graph_change(&mut self) -> bool {
+        let Some(previous) = self.undo_stack.undo(self.node_graph.clone()) else {
+            return false;
+        };
+        self.node_graph = previous;
+        let _ = self.sync_graph_to_script();
+        true
+    }
+
     fn layout_prefs_path() -> std::path::PathBuf {
         if let Some(project_dirs) = ProjectDirs::from("com", "vnengine", "editor") {
             project_dirs.config_dir().join("layout.json")
@@ -340,6 +359,20 @@ impl EditorWorkbench {
                 {
                     self.mode = EditorMode::Player;
                 }
+                let selected_ip = self
+                    .selected_node
+                    .and_then(|node_id| self.player_node_ips.get(&node_id).copied());
+                if ui
+                    .add_enabled(selected_ip.is_some(), egui::Button::new("Play from here"))
+                    .on_disabled_hover_text("Select a node that compiles to an event")
+                    .clicked()
+                {
+                    if let Some(node_id) = self.selected_node {
+                        if self.prepare_player_mode_from(node_id) {
+                            self.mode = EditorMode::Player;
+                        }
+                    }
+                }
 
                 ui.separator();
                 if ui.button("Validar (Dry Run)").clicked() {
@@ -473,6 +506,7 @@ mod player_mode_ops;
 mod project_ops;
 mod quick_fix_ops;
 mod report_ops;
+mod route_ops;
 #[cfg(test)]
 #[path = "tests/workbench_tests.rs"]
 mod tests;
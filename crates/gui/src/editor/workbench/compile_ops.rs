@@ -87,9 +87,10 @@ impl EditorWorkbench {
     }
 
     pub fn run_dry_validation(&mut self) -> bool {
-        let result = crate::editor::compiler::compile_project_with_project_root(
+        let result = crate::editor::compiler::compile_project_with_breakpoints(
             &self.node_graph,
             self.project_root.as_deref(),
+            self.node_graph.breakpoints(),
         );
         let has_errors = self.apply_compilation_state(
             &result.script,
@@ -103,11 +104,22 @@ impl EditorWorkbench {
             return false;
         }
 
+        let breakpoint_message = result
+            .dry_run_report
+            .as_ref()
+            .filter(|report| {
+                report.stop_reason == crate::editor::compiler::DryRunStopReason::Breakpoint
+            })
+            .map(|report| report.stop_message.clone());
         match result.engine_result {
             Ok(engine) => {
                 self.engine = Some(engine);
+                self.player_node_ips = result.node_ips;
                 self.refresh_scene_from_engine_preview();
-                self.toast = Some(ToastState::success("Dry Run completed"));
+                self.toast = Some(match breakpoint_message {
+                    Some(message) => ToastState::new(message, crate::editor::ToastKind::Info),
+                    None => ToastState::success("Dry Run completed"),
+                });
                 true
             }
             Err(e) => {
@@ -132,8 +144,27 @@ impl EditorWorkbench {
         ok
     }
 
+    /// Runs the dry validation and refuses to export while any blocking
+    /// (error-severity) issue remains.
+    fn validate_for_export(&mut self, action: &str) -> bool {
+        let ok = self.run_dry_validation();
+        let blocking = self
+            .validation_issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Error)
+            .count();
+        if blocking > 0 {
+            self.show_validation = true;
+            self.toast = Some(ToastState::error(format!(
+                "{action} blocked by {blocking} error(s)"
+            )));
+            return false;
+        }
+        ok
+    }
+
     pub fn export_compiled_project(&mut self) {
-        if !self.run_dry_validation() {
+        if !self.validate_for_export("Export") {
             return;
         }
 
@@ -164,7 +195,7 @@ impl EditorWorkbench {
             .save_file();
 
         if let Some(path) = path {
-            match std::fs::write(&path, bytes) {
+            match std::fs::write(&path, bytes).and_then(|_| self.write_timeline_sidecar(&path)) {
                 Ok(_) => {
                     self.toast = Some(ToastState::success("Exported .vnproject successfully"));
                 }
@@ -177,7 +208,25 @@ impl EditorWorkbench {
         }
     }
 
+    /// Writes the compiled timeline beside an exported `.vnproject` (as
+    /// `<name>.timeline.json`), or removes a stale one when nothing is animated.
+    fn write_timeline_sidecar(&self, project_path: &std::path::Path) -> std::io::Result<()> {
+        let sidecar = project_path.with_extension("timeline.json");
+        if self.timeline.track_count() == 0 {
+            return match std::fs::remove_file(&sidecar) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        let json = serde_json::to_string_pretty(&self.timeline.to_compiled())
+            .map_err(std::io::Error::other)?;
+        std::fs::write(sidecar, json)
+    }
+
     pub fn package_bundle_native(&mut self) {
+        if !self.validate_for_export("Package") {
+            return;
+        }
         let project_root = self.project_root.clone().or_else(|| {
             self.pending_save_path
                 .as_ref()
@@ -497,6 +546,7 @@ impl EditorWorkbench {
         match result.engine_result {
             Ok(engine) => {
                 self.engine = Some(engine);
+                self.player_node_ips = result.node_ips;
                 self.refresh_scene_from_engine_preview();
                 Ok(())
             }
@@ -527,5 +577,9 @@ fn map_dry_run_stop_reason(
         crate::editor::compiler::DryRunStopReason::RuntimeError => {
             visual_novel_engine::ReproStopReason::RuntimeError
         }
+        // A breakpoint halts before the end, like a step limit.
+        crate::editor::compiler::DryRunStopReason::Breakpoint => {
+            visual_novel_engine::ReproStopReason::StepLimit
+        }
     }
 }
@@ -162,6 +162,8 @@ impl EditorWorkbench {
                                 x: Some(x),
                                 y: Some(y),
                                 scale,
+                                z: None,
+                                flip_x: false,
                             });
                             true
                         }
@@ -187,6 +189,8 @@ impl EditorWorkbench {
                                 x: Some(x),
                                 y: Some(y),
                                 scale,
+                                z: None,
+                                flip_x: false,
                             });
                             true
                         }
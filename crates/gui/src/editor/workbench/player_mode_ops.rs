@@ -145,12 +145,34 @@ impl EditorWorkbench {
     }
 
     pub(super) fn prepare_player_mode(&mut self) -> bool {
+        self.prepare_player_mode_at(None)
+    }
+
+    /// Prepares the Player like [`Self::prepare_player_mode`] but starts
+    /// playback at `node_id`, with the scene rebuilt as if played up to it.
+    pub(crate) fn prepare_player_mode_from(&mut self, node_id: u32) -> bool {
+        self.prepare_player_mode_at(Some(node_id))
+    }
+
+    fn prepare_player_mode_at(&mut self, start_node: Option<u32>) -> bool {
         if self.engine.is_none() && self.sync_graph_to_script().is_err() {
             self.toast = Some(ToastState::error(
                 "No se pudo preparar el Player: corrige errores del grafo/importacion",
             ));
             return false;
         }
+        let start_ip = match start_node {
+            Some(node_id) => match self.player_node_ips.get(&node_id) {
+                Some(ip) => Some(*ip),
+                None => {
+                    self.toast = Some(ToastState::error(format!(
+                        "Player no puede iniciar en el nodo {node_id}: no genera ningun evento"
+                    )));
+                    return false;
+                }
+            },
+            None => None,
+        };
         {
             let Some(engine) = self.engine.as_mut() else {
                 self.toast = Some(ToastState::error(
@@ -165,6 +187,14 @@ impl EditorWorkbench {
                 )));
                 return false;
             }
+            if let Some(ip) = start_ip {
+                if let Err(err) = engine.start_at(ip) {
+                    self.toast = Some(ToastState::error(format!(
+                        "Player no pudo iniciar en el evento {ip}: {err}"
+                    )));
+                    return false;
+                }
+            }
             engine.clear_session_history();
         }
         self.player_state.reset_for_restart(0.0);
@@ -191,6 +221,7 @@ impl EditorWorkbench {
             &self.node_graph,
         );
         self.scene = snapshot.scene;
+        Self::apply_timeline_opacity(&mut self.scene, &self.timeline, self.current_time as u32);
         self.composer_entity_owners = snapshot.owners;
         if self.selected_entity.is_some_and(|id| {
             self.scene
@@ -221,9 +252,14 @@ impl EditorWorkbench {
             };
             let advanced_ok = match &event {
                 visual_novel_engine::EventCompiled::ExtCall { .. } => preview.resume().is_ok(),
+                visual_novel_engine::EventCompiled::InputText { .. } => {
+                    preview.submit_text("").is_ok()
+                }
                 visual_novel_engine::EventCompiled::Choice(choice) => {
-                    if target_ip.is_none() || choice.options.is_empty() {
+                    if target_ip.is_none() {
                         false
+                    } else if choice.options.is_empty() {
+                        preview.step().is_ok()
                     } else {
                         preview.choose(0).is_ok()
                     }
@@ -236,6 +272,9 @@ impl EditorWorkbench {
                 | visual_novel_engine::EventCompiled::Jump { .. }
                 | visual_novel_engine::EventCompiled::SetFlag { .. }
                 | visual_novel_engine::EventCompiled::SetVar { .. }
+                | visual_novel_engine::EventCompiled::SetStrVar { .. }
+                | visual_novel_engine::EventCompiled::UnlockGallery { .. }
+                | visual_novel_engine::EventCompiled::Achieve { .. }
                 | visual_novel_engine::EventCompiled::JumpIf { .. }
                 | visual_novel_engine::EventCompiled::AudioAction(_) => preview.step().is_ok(),
             };
@@ -258,6 +297,23 @@ impl EditorWorkbench {
         }
         preview.visual_state().clone()
     }
+    /// Fades character entities by their timeline opacity track, on top of
+    /// the opacity the script gave them.
+    fn apply_timeline_opacity(
+        scene: &mut visual_novel_engine::SceneState,
+        timeline: &visual_novel_engine::Timeline,
+        tick: u32,
+    ) {
+        for entity in scene.iter_mut() {
+            if !matches!(entity.kind, visual_novel_engine::EntityKind::Character(_)) {
+                continue;
+            }
+            if let Some(opacity) = timeline.sample_opacity(entity.id, tick) {
+                entity.transform.opacity =
+                    (entity.transform.opacity as f32 * opacity).round() as u32;
+            }
+        }
+    }
     fn scene_from_visual_state(
         visual: &visual_novel_engine::VisualState,
         audio_hint: AudioPreviewHint,
@@ -294,11 +350,14 @@ impl EditorWorkbench {
             transform.z_order = index as i32;
             let scale = character.scale.unwrap_or(1.0).clamp(0.1, 4.0);
             transform.scale = (scale * 1000.0) as u32;
+            transform.opacity =
+                (visual.opacity_of(character.name.as_ref()) * 1000.0).round() as u32;
             if let Some(entity_id) = scene.spawn_with_transform(
                 transform,
                 visual_novel_engine::EntityKind::Character(visual_novel_engine::CharacterData {
                     name: character.name.clone(),
                     expression: character.expression.clone(),
+                    flip_x: character.flip_x,
                 }),
             ) {
                 let owner = owner_hints
@@ -1,5 +1,7 @@
 use super::*;
-use crate::editor::quick_fix::{apply_fix, suggest_fixes, QuickFixCandidate, QuickFixRisk};
+use crate::editor::quick_fix::{
+    apply_fix, next_safe_fix, suggest_fixes, QuickFixCandidate, QuickFixRisk,
+};
 use std::hash::{Hash, Hasher};
 use visual_novel_engine::ScriptRaw;
 
@@ -13,39 +15,24 @@ impl EditorWorkbench {
         self.apply_issue_fix_for_issue(&issue, fix_id)
     }
 
+    /// Applies safe fixes until none is left; the whole batch is one undo step.
     pub fn apply_all_safe_fixes(&mut self) -> usize {
+        let before_graph = self.node_graph.clone();
         let mut applied = 0usize;
-        let mut guard = 0usize;
-
-        while guard < 128 {
-            guard += 1;
-            let issues_snapshot = self.validation_issues.clone();
-            let mut applied_this_round = false;
-
-            for issue in issues_snapshot {
-                let candidates = suggest_fixes(&issue, &self.node_graph);
-                let Some(candidate) = candidates
-                    .iter()
-                    .find(|candidate| candidate.risk == QuickFixRisk::Safe)
-                else {
-                    continue;
-                };
-
-                if self
-                    .apply_issue_fix_for_issue(&issue, candidate.fix_id)
-                    .is_ok()
-                {
-                    applied += 1;
-                    applied_this_round = true;
-                    break;
-                }
-            }
-
-            if !applied_this_round {
+        // Each fix re-syncs and re-validates, so the next pick sees fresh issues.
+        while applied < 128 {
+            let Some((issue, fix_id)) = next_safe_fix(&self.node_graph, &self.validation_issues)
+            else {
+                break;
+            };
+            if self.apply_issue_fix_unrecorded(&issue, fix_id).is_err() {
                 break;
             }
+            applied += 1;
+        }
+        if applied > 0 {
+            self.record_fix_undo(before_graph);
         }
-
         applied
     }
 
@@ -95,13 +82,17 @@ impl EditorWorkbench {
             return Err("no pending auto-fix batch".to_string());
         };
 
+        let before_graph = self.node_graph.clone();
         let mut result = AutoFixBatchResult::default();
         for op in pending.operations {
-            match self.apply_issue_fix_for_issue(&op.issue, &op.fix_id) {
+            match self.apply_issue_fix_unrecorded(&op.issue, &op.fix_id) {
                 Ok(()) => result.applied += 1,
                 Err(_) => result.skipped += 1,
             }
         }
+        if result.applied > 0 {
+            self.record_fix_undo(before_graph);
+        }
 
         Ok(result)
     }
@@ -170,6 +161,25 @@ impl EditorWorkbench {
     }
 
     fn apply_issue_fix_for_issue(&mut self, issue: &LintIssue, fix_id: &str) -> Result<(), String> {
+        let before_graph = self.node_graph.clone();
+        self.apply_issue_fix_unrecorded(issue, fix_id)?;
+        self.record_fix_undo(before_graph);
+        Ok(())
+    }
+
+    /// Pushes the pre-fix graph as a single undo step. The dirty flag is
+    /// cleared so the frame loop does not also push the post-fix graph,
+    /// which would take two undos to revert the fix.
+    fn record_fix_undo(&mut self, before_graph: NodeGraph) {
+        self.undo_stack.push(before_graph);
+        self.node_graph.clear_modified();
+    }
+
+    fn apply_issue_fix_unrecorded(
+        &mut self,
+        issue: &LintIssue,
+        fix_id: &str,
+    ) -> Result<(), String> {
         let before_graph = self.node_graph.clone();
         let before_crc32 = crc32_graph(&before_graph);
 
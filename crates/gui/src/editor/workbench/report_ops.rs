@@ -67,6 +67,7 @@ impl EditorWorkbench {
                             "visual_background": step.visual_background,
                             "visual_music": step.visual_music,
                             "character_count": step.character_count,
+                            "symbols_digest": step.symbols_digest,
                         })
                     })
                     .collect::<Vec<_>>(),
@@ -286,7 +287,9 @@ fn parse_lint_code(value: &str) -> Result<LintCode, String> {
         "VAL_DEAD_END" => Ok(LintCode::DeadEnd),
         "VAL_CHOICE_EMPTY" => Ok(LintCode::ChoiceNoOptions),
         "VAL_CHOICE_UNLINKED" => Ok(LintCode::ChoiceOptionUnlinked),
+        "VAL_CHOICE_PORT_UNCONNECTED" => Ok(LintCode::ChoicePortUnconnected),
         "VAL_CHOICE_PORT_OOB" => Ok(LintCode::ChoicePortOutOfRange),
+        "VAL_CHOICE_NO_FALLTHROUGH" => Ok(LintCode::ChoiceNoFallthrough),
         "VAL_AUDIO_MISSING" => Ok(LintCode::AudioAssetMissing),
         "VAL_AUDIO_EMPTY" => Ok(LintCode::AudioAssetEmpty),
         "VAL_ASSET_NOT_FOUND" => Ok(LintCode::AssetReferenceMissing),
@@ -301,12 +304,18 @@ fn parse_lint_code(value: &str) -> Result<LintCode, String> {
         "VAL_TRANSITION_KIND_INVALID" => Ok(LintCode::InvalidTransitionKind),
         "VAL_CHARACTER_NAME_EMPTY" => Ok(LintCode::EmptyCharacterName),
         "VAL_SPEAKER_EMPTY" => Ok(LintCode::EmptySpeakerName),
+        "VAL_SPEAKER_CASE_MISMATCH" => Ok(LintCode::SpeakerNameInconsistent),
         "VAL_JUMP_EMPTY" => Ok(LintCode::EmptyJumpTarget),
+        "VAL_FLAG_UNREAD" => Ok(LintCode::FlagSetButNeverRead),
+        "VAL_FLAG_UNSET" => Ok(LintCode::FlagReadButNeverSet),
+        "VAL_VAR_UNREAD" => Ok(LintCode::VarSetButNeverRead),
+        "VAL_VAR_UNSET" => Ok(LintCode::VarReadButNeverSet),
         "VAL_CONTRACT_EXPORT_UNSUPPORTED" => Ok(LintCode::ContractUnsupportedExport),
         "VAL_GENERIC_UNCHECKED" => Ok(LintCode::GenericEventUnchecked),
         "CMP_SCRIPT_ERROR" => Ok(LintCode::CompileError),
         "CMP_RUNTIME_INIT" => Ok(LintCode::RuntimeInitError),
         "DRY_UNREACHABLE" => Ok(LintCode::DryRunUnreachableCompiled),
+        "DRY_DEAD_BRANCH" => Ok(LintCode::DeadBranch),
         "DRY_STEP_LIMIT" => Ok(LintCode::DryRunStepLimit),
         "DRY_RUNTIME_ERROR" => Ok(LintCode::DryRunRuntimeError),
         "DRY_PARITY_MISMATCH" => Ok(LintCode::DryRunParityMismatch),
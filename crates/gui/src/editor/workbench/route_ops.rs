@@ -0,0 +1,99 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// Choice route file: the option index picked at each choice, from `start`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ChoiceRouteFile {
+    choices: Vec<usize>,
+}
+
+impl EditorWorkbench {
+    pub fn export_choice_route(&mut self) {
+        let Some(engine) = self.engine.as_ref() else {
+            self.toast = Some(ToastState::error(
+                "No hay sesion de Player para exportar la ruta",
+            ));
+            return;
+        };
+        let route = ChoiceRouteFile {
+            choices: engine.choice_log().to_vec(),
+        };
+        let Ok(payload) = serde_json::to_string_pretty(&route) else {
+            self.toast = Some(ToastState::error("Failed to serialize choice route"));
+            return;
+        };
+
+        let path = rfd::FileDialog::new()
+            .add_filter("Choice Route JSON", &["json"])
+            .set_file_name("choice_route.json")
+            .save_file();
+
+        if let Some(path) = path {
+            match std::fs::write(&path, payload) {
+                Ok(_) => {
+                    self.toast = Some(ToastState::success(format!(
+                        "Choice route exported ({} choice(s))",
+                        route.choices.len()
+                    )));
+                }
+                Err(err) => {
+                    self.toast = Some(ToastState::error(format!(
+                        "Choice route export failed: {err}"
+                    )));
+                }
+            }
+        } else {
+            self.toast = Some(ToastState::warning("Choice route export cancelled"));
+        }
+    }
+
+    pub fn import_choice_route(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Choice Route JSON", &["json"])
+            .pick_file()
+        else {
+            self.toast = Some(ToastState::warning("Choice route import cancelled"));
+            return;
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(payload) => match self.apply_choice_route_json(&payload) {
+                Ok(steps) => {
+                    self.mode = EditorMode::Player;
+                    self.toast = Some(ToastState::success(format!(
+                        "Choice route replayed ({steps} event(s))"
+                    )));
+                }
+                Err(err) => {
+                    self.toast = Some(ToastState::error(format!(
+                        "Choice route import failed: {err}"
+                    )));
+                }
+            },
+            Err(err) => {
+                self.toast = Some(ToastState::error(format!(
+                    "Failed to read choice route: {err}"
+                )));
+            }
+        }
+    }
+
+    /// Restarts the Player at `start` and replays the route in `payload`.
+    /// Returns how many events were executed.
+    pub fn apply_choice_route_json(&mut self, payload: &str) -> Result<usize, String> {
+        let route: ChoiceRouteFile =
+            serde_json::from_str(payload).map_err(|err| format!("invalid route file: {err}"))?;
+        if !self.prepare_player_mode() {
+            return Err("Player could not be prepared".to_string());
+        }
+        let engine = self
+            .engine
+            .as_mut()
+            .ok_or_else(|| "Player has no engine".to_string())?;
+        let events = engine
+            .replay_choice_log(&route.choices)
+            .map_err(|err| err.to_string())?;
+        self.refresh_scene_from_engine_preview();
+        Ok(events.len())
+    }
+}
@@ -154,6 +154,11 @@ impl EditorWorkbench {
                                     )));
                                 }
                             },
+                            crate::editor::lint_panel::LintPanelAction::FocusNode {
+                                node_id,
+                            } => {
+                                self.node_graph.request_focus(node_id);
+                            }
                             crate::editor::lint_panel::LintPanelAction::RevertLastFix => {
                                 if self.revert_last_fix() {
                                     self.toast =
@@ -389,6 +394,29 @@ impl EditorWorkbench {
             let _ = self.sync_graph_to_script();
         }
 
+        if self.show_statistics {
+            let synced;
+            let script = match &self.current_script {
+                Some(script) => script,
+                None => {
+                    synced = self.node_graph.to_script();
+                    &synced
+                }
+            };
+            let stats = script.content_stats();
+            let branches = script.compile().ok().map(|compiled| {
+                visual_novel_engine::StoryGraph::from_script(&compiled).branch_lengths()
+            });
+            let mut open = self.show_statistics;
+            egui::Window::new("Statistics")
+                .open(&mut open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    StatisticsPanel::new(&stats, branches.as_ref(), &mut self.reading_wpm).ui(ui);
+                });
+            self.show_statistics = open;
+        }
+
         // 6. Floating/Detached Node Editor
         if self.node_editor_window_open && self.show_graph {
             let mut embedded_open = self.node_editor_window_open;
@@ -2,6 +2,7 @@ mod app;
 mod assets;
 pub mod editor;
 mod persist;
+mod shortcuts;
 mod widgets;
 
 pub use app::{run_app, DisplayInfo, GuiError, ResolvedConfig, VnConfig};
@@ -10,3 +11,4 @@ pub use assets::{
 };
 pub use editor::{run_editor, EditorMode, EditorWorkbench};
 pub use persist::{load_state_from, save_state_to, PersistError, UserPreferences};
+pub use shortcuts::{Shortcut, ShortcutAction, ShortcutRegistry};
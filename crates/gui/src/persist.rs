@@ -6,10 +6,14 @@ use thiserror::Error;
 use visual_novel_engine::{SaveData, SaveError, AUTH_SAVE_KEY};
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
 pub struct UserPreferences {
     pub fullscreen: bool,
     pub ui_scale: f32,
     pub vsync: bool,
+    /// Typewriter reveal speed in characters per second; `0` shows lines at once.
+    pub text_speed: f32,
+    pub auto_advance: bool,
 }
 
 impl Default for UserPreferences {
@@ -18,6 +22,8 @@ impl Default for UserPreferences {
             fullscreen: false,
             ui_scale: 1.0,
             vsync: true,
+            text_speed: 45.0,
+            auto_advance: false,
         }
     }
 }
@@ -0,0 +1,100 @@
+//! Keyboard shortcuts of the player window.
+
+use eframe::egui;
+
+/// Something the player window can do in response to a shortcut.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShortcutAction {
+    ToggleSettings,
+    ToggleHistory,
+    ToggleInspector,
+    ToggleHelp,
+}
+
+/// A single key binding with the text shown in the help window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    pub key: egui::Key,
+    pub action: ShortcutAction,
+    pub description: &'static str,
+}
+
+/// Single source of truth for player key bindings.
+///
+/// `VnApp::update` dispatches through it and the F1 help window lists it, so
+/// the two cannot drift apart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutRegistry {
+    entries: Vec<Shortcut>,
+}
+
+impl Default for ShortcutRegistry {
+    fn default() -> Self {
+        Self::new(vec![
+            Shortcut {
+                key: egui::Key::F1,
+                action: ShortcutAction::ToggleHelp,
+                description: "Show or hide this help",
+            },
+            Shortcut {
+                key: egui::Key::Escape,
+                action: ShortcutAction::ToggleSettings,
+                description: "Open or close settings",
+            },
+            Shortcut {
+                key: egui::Key::H,
+                action: ShortcutAction::ToggleHistory,
+                description: "Show or hide dialogue history",
+            },
+            Shortcut {
+                key: egui::Key::F12,
+                action: ShortcutAction::ToggleInspector,
+                description: "Show or hide the debug inspector",
+            },
+        ])
+    }
+}
+
+impl ShortcutRegistry {
+    pub fn new(entries: Vec<Shortcut>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[Shortcut] {
+        &self.entries
+    }
+
+    pub fn action_for(&self, key: egui::Key) -> Option<ShortcutAction> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.action)
+    }
+
+    pub fn key_for(&self, action: ShortcutAction) -> Option<egui::Key> {
+        self.entries
+            .iter()
+            .find(|entry| entry.action == action)
+            .map(|entry| entry.key)
+    }
+
+    /// Rebinds `action` to `key`, dropping any other binding of that key.
+    pub fn bind(&mut self, key: egui::Key, action: ShortcutAction, description: &'static str) {
+        self.entries
+            .retain(|entry| entry.key != key && entry.action != action);
+        self.entries.push(Shortcut {
+            key,
+            action,
+            description,
+        });
+    }
+
+    /// Actions whose keys were pressed this frame, in registry order.
+    pub fn pressed(&self, input: &egui::InputState) -> Vec<ShortcutAction> {
+        self.entries
+            .iter()
+            .filter(|entry| input.key_pressed(entry.key))
+            .map(|entry| entry.action)
+            .collect()
+    }
+}
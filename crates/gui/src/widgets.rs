@@ -17,11 +17,15 @@ pub fn event_kind(event: &EventCompiled) -> String {
         EventCompiled::Jump { .. } => "Jump".to_string(),
         EventCompiled::SetFlag { .. } => "SetFlag".to_string(),
         EventCompiled::SetVar { .. } => "SetVar".to_string(),
+        EventCompiled::SetStrVar { .. } => "SetStrVar".to_string(),
         EventCompiled::JumpIf { .. } => "JumpIf".to_string(),
         EventCompiled::Patch(_) => "Patch".to_string(),
         EventCompiled::ExtCall { .. } => "ExtCall".to_string(),
         EventCompiled::AudioAction(_) => "Audio".to_string(),
         EventCompiled::Transition(_) => "Transition".to_string(),
         EventCompiled::SetCharacterPosition(_) => "Placement".to_string(),
+        EventCompiled::InputText { .. } => "InputText".to_string(),
+        EventCompiled::UnlockGallery { .. } => "UnlockGallery".to_string(),
+        EventCompiled::Achieve { .. } => "Achieve".to_string(),
     }
 }
@@ -2,7 +2,10 @@ use std::fs;
 
 use tempfile::tempdir;
 use visual_novel_engine::{EngineState, SaveData};
-use visual_novel_gui::{load_state_from, save_state_to, DisplayInfo, UserPreferences, VnConfig};
+use visual_novel_gui::{
+    load_state_from, save_state_to, AssetError, DisplayInfo, SecurityMode, UserPreferences,
+    VnConfig,
+};
 
 #[test]
 fn resolves_defaults_for_small_display() {
@@ -33,6 +36,8 @@ fn saves_and_loads_preferences() {
         fullscreen: true,
         ui_scale: 1.4,
         vsync: false,
+        text_speed: 0.0,
+        auto_advance: true,
     };
 
     prefs.save_to(&path).expect("save prefs");
@@ -43,6 +48,26 @@ fn saves_and_loads_preferences() {
     assert!(stored.contains("\"fullscreen\": true"));
 }
 
+#[test]
+fn loads_preferences_written_before_text_speed_existed() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("prefs.json");
+    fs::write(
+        &path,
+        r#"{ "fullscreen": true, "ui_scale": 1.25, "vsync": false }"#,
+    )
+    .expect("write legacy prefs");
+
+    let loaded = UserPreferences::load_from(&path).expect("load legacy prefs");
+
+    let defaults = UserPreferences::default();
+    assert!(loaded.fullscreen);
+    assert_eq!(loaded.ui_scale, 1.25);
+    assert!(!loaded.vsync);
+    assert_eq!(loaded.text_speed, defaults.text_speed);
+    assert_eq!(loaded.auto_advance, defaults.auto_advance);
+}
+
 #[test]
 fn saves_and_loads_state() {
     let dir = tempdir().expect("tempdir");
@@ -52,6 +77,7 @@ fn saves_and_loads_state() {
     let data = SaveData {
         script_id: [7u8; 32],
         state,
+        metrics: None,
     };
 
     save_state_to(&path, &data).expect("save state");
@@ -75,6 +101,7 @@ fn loads_legacy_plain_state_files() {
     let data = SaveData {
         script_id: [9u8; 32],
         state,
+        metrics: None,
     };
 
     fs::write(&path, data.to_binary().expect("plain save")).expect("write plain state");
@@ -82,3 +109,24 @@ fn loads_legacy_plain_state_files() {
     assert_eq!(loaded.script_id, [9u8; 32]);
     assert_eq!(loaded.state.position, 7);
 }
+
+#[test]
+fn untrusted_config_without_manifest_refuses_asset_loads() {
+    let dir = tempdir().expect("tempdir");
+    fs::create_dir_all(dir.path().join("bg")).expect("asset dir");
+    fs::write(dir.path().join("bg/room.bin"), [1u8, 2, 3]).expect("write asset");
+
+    let config = VnConfig {
+        assets_root: Some(dir.path().to_path_buf()),
+        security_mode: SecurityMode::Untrusted,
+        ..VnConfig::default()
+    };
+    let resolved = config.resolve(None);
+    assert!(resolved.require_manifest);
+
+    let store = resolved
+        .asset_store()
+        .expect("store builds without manifest");
+    let err = store.load_bytes("bg/room.bin").expect_err("load must fail");
+    assert!(matches!(err, AssetError::ManifestMissing), "{err:?}");
+}
@@ -0,0 +1,51 @@
+use eframe::egui::Key;
+use visual_novel_gui::{ShortcutAction, ShortcutRegistry};
+
+#[test]
+fn default_registry_lists_player_shortcuts() {
+    let registry = ShortcutRegistry::default();
+    let bindings: Vec<(Key, ShortcutAction)> = registry
+        .entries()
+        .iter()
+        .map(|shortcut| (shortcut.key, shortcut.action))
+        .collect();
+
+    assert_eq!(
+        bindings,
+        [
+            (Key::F1, ShortcutAction::ToggleHelp),
+            (Key::Escape, ShortcutAction::ToggleSettings),
+            (Key::H, ShortcutAction::ToggleHistory),
+            (Key::F12, ShortcutAction::ToggleInspector),
+        ]
+    );
+    assert!(registry
+        .entries()
+        .iter()
+        .all(|shortcut| !shortcut.description.is_empty()));
+}
+
+#[test]
+fn lookups_work_in_both_directions() {
+    let registry = ShortcutRegistry::default();
+    assert_eq!(
+        registry.action_for(Key::F12),
+        Some(ShortcutAction::ToggleInspector)
+    );
+    assert_eq!(registry.action_for(Key::Q), None);
+    assert_eq!(registry.key_for(ShortcutAction::ToggleHelp), Some(Key::F1));
+}
+
+#[test]
+fn rebinding_replaces_previous_key_and_action() {
+    let mut registry = ShortcutRegistry::default();
+    registry.bind(Key::F1, ShortcutAction::ToggleHistory, "History");
+
+    assert_eq!(
+        registry.action_for(Key::F1),
+        Some(ShortcutAction::ToggleHistory)
+    );
+    assert_eq!(registry.action_for(Key::H), None);
+    assert_eq!(registry.key_for(ShortcutAction::ToggleHelp), None);
+    assert_eq!(registry.entries().len(), 3);
+}
@@ -3,10 +3,12 @@ use serde::Serialize;
 use std::collections::BTreeMap;
 use visual_novel_engine::{
     AudioActionRaw, CharacterPatchRaw, CharacterPlacementRaw, ChoiceOptionRaw, ChoiceRaw, CmpOp,
-    CondRaw, DialogueRaw, EventRaw, ScenePatchRaw, SceneTransitionRaw, SceneUpdateRaw,
+    CondRaw, DialogueRaw, EventRaw, ScenePatchRaw, SceneTransitionRaw, SceneUpdateRaw, ScriptRaw,
     SetCharacterPositionRaw, SCRIPT_SCHEMA_VERSION,
 };
 
+use super::types::vn_error_to_py;
+
 #[pyclass(name = "ScriptBuilder")]
 pub struct PyScriptBuilder {
     events: Vec<EventRaw>,
@@ -14,20 +16,10 @@ pub struct PyScriptBuilder {
 }
 
 #[derive(Serialize)]
-struct StableScript {
-    script_schema_version: String,
-    events: Vec<EventRaw>,
-    labels: BTreeMap<String, usize>,
-}
-
-impl StableScript {
-    fn from_parts(events: &[EventRaw], labels: &BTreeMap<String, usize>) -> Self {
-        Self {
-            script_schema_version: SCRIPT_SCHEMA_VERSION.to_string(),
-            events: events.to_vec(),
-            labels: labels.clone(),
-        }
-    }
+struct StableScript<'a> {
+    script_schema_version: &'static str,
+    events: &'a [EventRaw],
+    labels: &'a BTreeMap<String, usize>,
 }
 
 fn parse_cmp_op(op: &str) -> PyResult<CmpOp> {
@@ -68,11 +60,18 @@ impl PyScriptBuilder {
     fn choice(&mut self, prompt: &str, options: Vec<(String, String)>) {
         let options = options
             .into_iter()
-            .map(|(text, target)| ChoiceOptionRaw { text, target })
+            .map(|(text, target)| ChoiceOptionRaw {
+                text,
+                target,
+                weight: None,
+                effects: Vec::new(),
+            })
             .collect();
         self.events.push(EventRaw::Choice(ChoiceRaw {
             prompt: prompt.to_string(),
             options,
+            auto: false,
+            fallthrough: None,
         }));
     }
 
@@ -92,12 +91,16 @@ impl PyScriptBuilder {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             })
             .collect();
         self.events.push(EventRaw::Scene(SceneUpdateRaw {
             background,
             music,
             characters,
+            chapter: None,
+            tags: Vec::new(),
         }));
     }
 
@@ -162,6 +165,8 @@ impl PyScriptBuilder {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             })
             .collect();
         let update = update
@@ -170,6 +175,7 @@ impl PyScriptBuilder {
                 name,
                 expression,
                 position,
+                z: None,
             })
             .collect();
         self.events.push(EventRaw::Patch(ScenePatchRaw {
@@ -218,6 +224,7 @@ impl PyScriptBuilder {
                 x,
                 y,
                 scale,
+                flip_x: false,
             }));
     }
 
@@ -229,9 +236,27 @@ impl PyScriptBuilder {
     }
 
     fn build_json(&self) -> PyResult<String> {
-        let script = StableScript::from_parts(&self.events, &self.labels);
+        let script = StableScript {
+            script_schema_version: SCRIPT_SCHEMA_VERSION,
+            events: &self.events,
+            labels: &self.labels,
+        };
         serde_json::to_string(&script).map_err(|err| {
             pyo3::exceptions::PyValueError::new_err(format!("Failed to serialize script: {err}"))
         })
     }
+
+    fn build_canonical_json(&self) -> PyResult<String> {
+        self.script().to_canonical_json().map_err(vn_error_to_py)
+    }
+
+    fn script_checksum(&self) -> PyResult<String> {
+        self.script().script_checksum().map_err(vn_error_to_py)
+    }
+}
+
+impl PyScriptBuilder {
+    fn script(&self) -> ScriptRaw {
+        ScriptRaw::new(self.events.clone(), self.labels.clone())
+    }
 }
@@ -24,6 +24,7 @@ pub fn event_to_python(event: &EventCompiled, py: Python<'_>) -> PyResult<PyObje
                 options.append(option_dict)?;
             }
             dict.set_item("options", options)?;
+            dict.set_item("fallthrough_ip", choice.fallthrough_ip)?;
         }
         EventCompiled::Scene(scene) => {
             dict.set_item("type", "scene")?;
@@ -38,9 +39,17 @@ pub fn event_to_python(event: &EventCompiled, py: Python<'_>) -> PyResult<PyObje
                 character_dict.set_item("x", character.x)?;
                 character_dict.set_item("y", character.y)?;
                 character_dict.set_item("scale", character.scale)?;
+                character_dict.set_item("z", character.z)?;
+                character_dict.set_item("flip_x", character.flip_x)?;
                 characters.append(character_dict)?;
             }
             dict.set_item("characters", characters)?;
+            dict.set_item("chapter", scene.chapter.as_deref())?;
+            let tags = PyList::empty(py);
+            for tag in &scene.tags {
+                tags.append(tag.as_ref())?;
+            }
+            dict.set_item("tags", tags)?;
         }
         EventCompiled::Jump { target_ip } => {
             dict.set_item("type", "jump")?;
@@ -58,6 +67,19 @@ pub fn event_to_python(event: &EventCompiled, py: Python<'_>) -> PyResult<PyObje
             dict.set_item("var_id", *var_id)?;
             dict.set_item("value", *value)?;
         }
+        EventCompiled::UnlockGallery { id } => {
+            dict.set_item("type", "unlock_gallery")?;
+            dict.set_item("id", id.as_ref())?;
+        }
+        EventCompiled::Achieve { id } => {
+            dict.set_item("type", "achieve")?;
+            dict.set_item("id", id.as_ref())?;
+        }
+        EventCompiled::SetStrVar { str_var_id, value } => {
+            dict.set_item("type", "set_str_var")?;
+            dict.set_item("str_var_id", *str_var_id)?;
+            dict.set_item("value", value.as_ref())?;
+        }
         EventCompiled::JumpIf { target_ip, .. } => {
             dict.set_item("type", "jump_if")?;
             dict.set_item("target_ip", *target_ip)?;
@@ -100,6 +122,17 @@ pub fn event_to_python(event: &EventCompiled, py: Python<'_>) -> PyResult<PyObje
             dict.set_item("x", pos.x)?;
             dict.set_item("y", pos.y)?;
             dict.set_item("scale", pos.scale)?;
+            dict.set_item("flip_x", pos.flip_x)?;
+        }
+        EventCompiled::InputText {
+            str_var_id,
+            prompt,
+            max_len,
+        } => {
+            dict.set_item("type", "input_text")?;
+            dict.set_item("str_var_id", str_var_id)?;
+            dict.set_item("prompt", prompt)?;
+            dict.set_item("max_len", max_len)?;
         }
     }
     Ok(dict.into())
@@ -118,6 +151,8 @@ pub fn characters_to_python(
         character_dict.set_item("x", character.x)?;
         character_dict.set_item("y", character.y)?;
         character_dict.set_item("scale", character.scale)?;
+        character_dict.set_item("z", character.z)?;
+        character_dict.set_item("flip_x", character.flip_x)?;
         list.append(character_dict)?;
     }
     Ok(list.into())
@@ -171,6 +206,11 @@ pub fn ui_state_to_python(ui: &UiState, py: Python<'_>) -> PyResult<PyObject> {
             dict.set_item("type", "system")?;
             dict.set_item("message", message)?;
         }
+        UiView::TextInput { prompt, max_len } => {
+            dict.set_item("type", "text_input")?;
+            dict.set_item("prompt", prompt)?;
+            dict.set_item("max_len", max_len)?;
+        }
     }
     Ok(dict.into())
 }
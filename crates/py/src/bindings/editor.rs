@@ -4,7 +4,11 @@
 
 use pyo3::prelude::*;
 
-use visual_novel_gui::editor::validate_graph;
+use visual_novel_engine::ScriptRaw;
+use visual_novel_gui::editor::compiler::compile_project;
+use visual_novel_gui::editor::{validate_graph, NodeGraph};
+
+use super::types::vn_error_to_py;
 
 #[path = "editor_diagnostics.rs"]
 mod diagnostics;
@@ -22,7 +26,7 @@ pub use story_node::PyStoryNode;
 #[cfg(test)]
 use support::{apply_autofix_pass, select_fix_candidate};
 #[cfg(test)]
-use visual_novel_gui::editor::{LintIssue, NodeGraph, StoryNode};
+use visual_novel_gui::editor::{LintIssue, StoryNode};
 
 #[pyfunction]
 pub fn py_validate_graph(graph: &PyNodeGraph) -> Vec<PyLintIssue> {
@@ -32,6 +36,19 @@ pub fn py_validate_graph(graph: &PyNodeGraph) -> Vec<PyLintIssue> {
         .collect()
 }
 
+/// Parses a script and runs the full editor compile pipeline over it
+/// (graph lint, compile, runtime init and dry run).
+#[pyfunction]
+pub fn validate_script_json(script_json: &str) -> PyResult<Vec<PyLintIssue>> {
+    let script = ScriptRaw::from_json(script_json).map_err(vn_error_to_py)?;
+    let graph = NodeGraph::from_script(&script);
+    Ok(compile_project(&graph)
+        .issues
+        .into_iter()
+        .map(PyLintIssue::from)
+        .collect())
+}
+
 /// Registers editor classes with the Python module.
 pub fn register_editor_classes(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyStoryNode>()?;
@@ -40,6 +57,7 @@ pub fn register_editor_classes(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyLintSeverity>()?;
     m.add_class::<PyLintIssue>()?;
     m.add_function(wrap_pyfunction!(py_validate_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_script_json, m)?)?;
     Ok(())
 }
 
@@ -98,6 +98,13 @@ impl PyLintSeverity {
     }
 }
 
+#[cfg(test)]
+impl PyLintSeverity {
+    pub(crate) fn is_error(&self) -> bool {
+        self.inner == LintSeverity::Error
+    }
+}
+
 /// A validation issue found in the graph.
 #[pyclass(name = "LintIssue")]
 #[derive(Clone)]
@@ -129,8 +129,10 @@ impl PyNodeGraph {
     }
 
     fn save(&self, path: &str) -> PyResult<()> {
-        let script = self.inner.to_script();
-        let json = serde_json::to_string_pretty(&script)
+        let json = self
+            .inner
+            .to_script()
+            .to_canonical_json()
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
         std::fs::write(path, json).map_err(|e| PyValueError::new_err(e.to_string()))
     }
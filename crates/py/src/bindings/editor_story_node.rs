@@ -52,6 +52,8 @@ impl PyStoryNode {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             })
             .collect();
 
@@ -121,6 +123,8 @@ impl PyStoryNode {
                 x: None,
                 y: None,
                 scale: None,
+                z: None,
+                flip_x: false,
             })
             .collect();
         let update = update
@@ -129,6 +133,7 @@ impl PyStoryNode {
                 name,
                 expression,
                 position,
+                z: None,
             })
             .collect();
 
@@ -208,6 +213,13 @@ impl PyStoryNode {
         }
     }
 
+    #[staticmethod]
+    fn comment(text: String) -> Self {
+        Self {
+            inner: StoryNode::Comment { text },
+        }
+    }
+
     #[getter]
     fn node_type(&self) -> String {
         self.inner.type_name().to_string()
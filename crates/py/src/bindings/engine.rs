@@ -1,18 +1,21 @@
 use super::audio::PyAudio;
 use super::conversion::{event_to_python, ui_state_to_python};
+use super::editor::PyLintIssue;
 use super::types::{vn_error_to_py, PyResourceConfig};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyDictMethods, PyList, PyListMethods};
 use std::collections::BTreeSet;
 use visual_novel_engine::{
     AudioCommand, Engine as CoreEngine, EventCompiled, ResourceLimiter, ScriptRaw, SecurityPolicy,
-    UiState,
 };
+use visual_novel_gui::editor::compiler::compile_project;
+use visual_novel_gui::editor::NodeGraph;
 
 #[pyclass(name = "Engine")]
 #[derive(Debug)]
 pub struct PyEngine {
     pub(crate) inner: CoreEngine,
+    script: ScriptRaw,
     resource_limits: ResourceLimiter,
     max_texture_memory: usize,
     prefetch_depth: usize,
@@ -37,10 +40,11 @@ impl PyEngine {
         let resource_limits = ResourceLimiter::default();
         let script = ScriptRaw::from_json_with_limits(script_json, resource_limits)
             .map_err(vn_error_to_py)?;
-        let inner = CoreEngine::new(script, SecurityPolicy::default(), resource_limits)
+        let inner = CoreEngine::new(script.clone(), SecurityPolicy::default(), resource_limits)
             .map_err(vn_error_to_py)?;
         Ok(Self {
             inner,
+            script,
             resource_limits,
             max_texture_memory: 512 * 1024 * 1024,
             prefetch_depth: 0,
@@ -103,12 +107,16 @@ impl PyEngine {
             "jump",
             "set_flag",
             "set_var",
+            "set_str_var",
             "jump_if",
             "patch",
             "ext_call",
             "audio_action",
             "transition",
             "set_character_position",
+            "input_text",
+            "unlock_gallery",
+            "achieve",
         ]
     }
 
@@ -147,8 +155,7 @@ impl PyEngine {
     }
 
     fn ui_state<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
-        let event = self.inner.current_event().map_err(vn_error_to_py)?;
-        let ui = UiState::from_event(&event, self.inner.visual_state());
+        let ui = self.inner.ui_state().map_err(vn_error_to_py)?;
         ui_state_to_python(&ui, py)
     }
 
@@ -210,6 +217,9 @@ impl PyEngine {
     fn set_resources(&mut self, config: PyResourceConfig) {
         self.max_texture_memory = config.max_texture_memory;
         self.resource_limits.max_script_bytes = config.max_script_bytes;
+        if let Some(commands) = config.ext_call_allowlist {
+            self.allowed_ext_call_commands = commands.into_iter().collect();
+        }
     }
 
     fn get_memory_usage<'py>(&self, py: Python<'py>) -> PyResult<PyObject> {
@@ -261,6 +271,20 @@ impl PyEngine {
         Ok(())
     }
 
+    fn submit_text(&mut self, value: &str) -> PyResult<()> {
+        self.inner.submit_text(value).map_err(vn_error_to_py)
+    }
+
+    /// Runs the editor compile pipeline (graph lint, compile, dry run) over the loaded script.
+    fn validate(&self) -> Vec<PyLintIssue> {
+        let graph = NodeGraph::from_script(&self.script);
+        compile_project(&graph)
+            .issues
+            .into_iter()
+            .map(PyLintIssue::from)
+            .collect()
+    }
+
     fn audio(slf: PyRef<'_, Self>) -> PyResult<Py<PyAudio>> {
         let py = slf.py();
         let engine: Py<PyEngine> = slf.into();
@@ -271,6 +295,7 @@ impl PyEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bindings::editor::validate_script_json;
     use pyo3::ffi::c_str;
     use pyo3::types::PyModule;
 
@@ -286,6 +311,39 @@ mod tests {
         PyEngine::new(script_json).expect("engine should build")
     }
 
+    #[test]
+    fn validate_reports_no_errors_for_linear_script() {
+        let script_json = r#"{
+  "script_schema_version": "1.0",
+  "events": [
+    { "type": "dialogue", "speaker": "Narrator", "text": "Hello" },
+    { "type": "dialogue", "speaker": "Narrator", "text": "Bye" }
+  ],
+  "labels": { "start": 0 }
+}"#;
+        let engine = PyEngine::new(script_json).expect("engine should build");
+        let issues = engine.validate();
+        assert!(
+            issues.iter().all(|issue| !issue.severity.is_error()),
+            "linear script should validate cleanly: {:?}",
+            issues.iter().map(|issue| &issue.code).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_script_json_rejects_broken_jump_target() {
+        let script_json = r#"{
+  "script_schema_version": "1.0",
+  "events": [
+    { "type": "dialogue", "speaker": "Narrator", "text": "Hello" },
+    { "type": "jump", "target": "missing" }
+  ],
+  "labels": { "start": 0 }
+}"#;
+        let issues = validate_script_json(script_json).expect("script should parse");
+        assert!(issues.iter().any(|issue| issue.severity.is_error()));
+    }
+
     #[test]
     fn ext_call_callbacks_are_denied_by_default() {
         pyo3::prepare_freethreaded_python();
@@ -368,4 +426,37 @@ def handler(command, args):
             assert_eq!(engine.last_ext_call_error(), None);
         });
     }
+
+    #[test]
+    fn resource_config_allowlist_denies_unlisted_ext_calls() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut denied = make_ext_call_engine();
+            denied.set_resources(PyResourceConfig::new(
+                None,
+                None,
+                Some(vec!["open_shop".to_string()]),
+            ));
+            denied.step(py).expect("denied ext-call should still step");
+            assert!(
+                denied
+                    .last_ext_call_error()
+                    .as_deref()
+                    .is_some_and(|message| message.contains("minigame_start")),
+                "configured allowlist denial should be recorded"
+            );
+
+            let mut allowed = make_ext_call_engine();
+            allowed.set_resources(PyResourceConfig::new(
+                None,
+                None,
+                Some(vec!["minigame_start".to_string()]),
+            ));
+            allowed.step(py).expect("allow-listed command should step");
+            assert_eq!(allowed.last_ext_call_error(), None);
+            allowed
+                .resume()
+                .expect("allow-listed command should resume");
+        });
+    }
 }
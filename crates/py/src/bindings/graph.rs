@@ -185,6 +185,10 @@ impl PyStoryGraph {
                         "character_placement".to_string(),
                         format!("Name: {}, x: {}, y: {}, scale: {:?}", name, x, y, scale),
                     ),
+                    visual_novel_engine::NodeType::InputText { str_var_id, prompt } => (
+                        "input_text".to_string(),
+                        format!("Prompt: {}, StrVar: {}", prompt, str_var_id),
+                    ),
                 };
                 PyGraphNode {
                     id: n.id,
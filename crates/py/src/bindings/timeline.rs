@@ -14,6 +14,9 @@ fn parse_easing(s: &str) -> Easing {
         "ease_out" | "easeout" => Easing::EaseOut,
         "ease_in_out" | "easeinout" => Easing::EaseInOut,
         "step" => Easing::Step,
+        "ease_in_quad" | "easeinquad" => Easing::EaseInQuad,
+        "ease_out_quad" | "easeoutquad" => Easing::EaseOutQuad,
+        "ease_in_out_cubic" | "easeinoutcubic" => Easing::EaseInOutCubic,
         _ => Easing::Linear,
     }
 }
@@ -26,6 +29,9 @@ fn easing_to_string(e: Easing) -> &'static str {
         Easing::EaseOut => "ease_out",
         Easing::EaseInOut => "ease_in_out",
         Easing::Step => "step",
+        Easing::EaseInQuad => "ease_in_quad",
+        Easing::EaseOutQuad => "ease_out_quad",
+        Easing::EaseInOutCubic => "ease_in_out_cubic",
     }
 }
 
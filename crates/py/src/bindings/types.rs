@@ -14,17 +14,24 @@ pub struct PyResourceConfig {
     pub max_texture_memory: usize,
     #[pyo3(get, set)]
     pub max_script_bytes: usize,
+    #[pyo3(get, set)]
+    pub ext_call_allowlist: Option<Vec<String>>,
 }
 
 #[pymethods]
 impl PyResourceConfig {
     #[new]
-    #[pyo3(signature = (max_texture_memory=None, max_script_bytes=None))]
-    fn new(max_texture_memory: Option<usize>, max_script_bytes: Option<usize>) -> Self {
+    #[pyo3(signature = (max_texture_memory=None, max_script_bytes=None, ext_call_allowlist=None))]
+    pub fn new(
+        max_texture_memory: Option<usize>,
+        max_script_bytes: Option<usize>,
+        ext_call_allowlist: Option<Vec<String>>,
+    ) -> Self {
         Self {
             max_texture_memory: max_texture_memory.unwrap_or(512 * 1024 * 1024),
             max_script_bytes: max_script_bytes
                 .unwrap_or(ResourceLimiter::default().max_script_bytes),
+            ext_call_allowlist,
         }
     }
 }
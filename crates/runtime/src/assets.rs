@@ -4,12 +4,20 @@ use std::sync::Arc;
 /// Asset store trait for runtime resource loading.
 pub trait AssetStore {
     fn load_bytes(&self, id: &str) -> Result<Vec<u8>, String>;
+
+    /// Drops any cached copy of `id` so the next load rereads it. Stores
+    /// without a cache can ignore this.
+    fn invalidate(&self, _id: &str) {}
 }
 
 impl<T: AssetStore + ?Sized> AssetStore for Arc<T> {
     fn load_bytes(&self, id: &str) -> Result<Vec<u8>, String> {
         (**self).load_bytes(id)
     }
+
+    fn invalidate(&self, id: &str) {
+        (**self).invalidate(id);
+    }
 }
 
 /// In-memory asset store mock for testing.
@@ -38,4 +46,8 @@ impl AssetStore for vnengine_assets::AssetStore {
     fn load_bytes(&self, id: &str) -> Result<Vec<u8>, String> {
         vnengine_assets::AssetStore::load_bytes(self, id).map_err(|e| e.to_string())
     }
+
+    fn invalidate(&self, id: &str) {
+        vnengine_assets::AssetStore::invalidate(self, id);
+    }
 }
@@ -29,6 +29,9 @@ pub trait Audio {
         self.play_sfx_with_volume(id, volume);
     }
     fn stop_voice(&mut self) {}
+    /// Drops any cached copy of `id` so the next playback rereads it.
+    /// Backends without a cache can ignore this.
+    fn invalidate(&mut self, _id: &str) {}
 }
 
 impl<T: Audio + ?Sized> Audio for Box<T> {
@@ -59,6 +62,9 @@ impl<T: Audio + ?Sized> Audio for Box<T> {
     fn stop_voice(&mut self) {
         (**self).stop_voice();
     }
+    fn invalidate(&mut self, id: &str) {
+        (**self).invalidate(id);
+    }
 }
 
 /// Audio backend implementation using `rodio`.
@@ -257,6 +263,10 @@ impl Audio for RodioBackend {
             sink.stop();
         }
     }
+
+    fn invalidate(&mut self, id: &str) {
+        self.audio_cache.remove(&id.to_string());
+    }
 }
 
 /// No-op audio backend for environments where sound output is disabled/unavailable.
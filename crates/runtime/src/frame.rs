@@ -0,0 +1,125 @@
+//! Software rasterization of the runtime UI into caller-provided frames.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::render::software::LETTERBOX_COLOR;
+use crate::render::{BuiltinSoftwareDrawer, SoftwareDrawStrategy, Viewport, VirtualCanvas};
+use crate::{AssetStore, Audio, Input, RuntimeApp};
+
+impl<I, A, S> RuntimeApp<I, A, S>
+where
+    I: Input,
+    A: Audio,
+    S: AssetStore,
+{
+    /// Renders into a fixed `width`×`height` canvas that [`RuntimeApp::render_frame`]
+    /// integer-scales to the window, keeping pixel art crisp.
+    pub fn with_design_resolution(mut self, width: u32, height: u32) -> Self {
+        self.canvas = Some(VirtualCanvas::new(width, height));
+        self
+    }
+
+    /// Design resolution set by [`RuntimeApp::with_design_resolution`], if any.
+    pub fn design_resolution(&self) -> Option<(u32, u32)> {
+        self.canvas.as_ref().map(VirtualCanvas::size)
+    }
+
+    /// Draws the visible UI into an RGBA `frame` of `size` pixels.
+    ///
+    /// With a design resolution the UI is drawn into the internal canvas and
+    /// nearest-neighbor upscaled; otherwise it is letterboxed at any scale.
+    pub fn render_frame(&mut self, frame: &mut [u8], size: (u32, u32)) {
+        let ui = self.visible_ui();
+        match &mut self.canvas {
+            Some(canvas) => {
+                let design = canvas.size();
+                let viewport = Viewport::new(design, design, 1.0);
+                BuiltinSoftwareDrawer.draw(canvas.pixels_mut(), &viewport, &ui);
+                canvas.blit_integer_scaled(frame, size, LETTERBOX_COLOR);
+            }
+            None => {
+                let viewport = Viewport::new(Viewport::DEFAULT_DESIGN_SIZE, size, 1.0);
+                BuiltinSoftwareDrawer.draw(frame, &viewport, &ui);
+            }
+        }
+        self.last_frame.store(frame, size);
+    }
+
+    /// Rasterizes the visible UI at `size` and keeps it as the last frame.
+    ///
+    /// For backends that draw on the GPU and never hand out a CPU frame through
+    /// [`crate::render::RenderBackend::frame_mut`], so captures still work.
+    pub fn capture_offscreen(&mut self, size: (u32, u32)) {
+        let mut frame = vec![0u8; size.0 as usize * size.1 as usize * 4];
+        self.render_frame(&mut frame, size);
+    }
+
+    /// Renders the visible UI offscreen and downscales it to a `width`×`height`
+    /// RGBA8 buffer, e.g. for save-slot previews. Independent of the window.
+    pub fn render_thumbnail(&self, width: u32, height: u32) -> Vec<u8> {
+        let design = self
+            .design_resolution()
+            .unwrap_or(Viewport::DEFAULT_DESIGN_SIZE);
+        let viewport = Viewport::new(design, design, 1.0);
+        let mut full = vec![0u8; design.0 as usize * design.1 as usize * 4];
+        BuiltinSoftwareDrawer.draw(&mut full, &viewport, &self.visible_ui());
+        let Some(full) = image::RgbaImage::from_raw(design.0, design.1, full) else {
+            return Vec::new();
+        };
+        image::imageops::thumbnail(&full, width.max(1), height.max(1)).into_raw()
+    }
+
+    /// RGBA8 copy of the last frame produced by [`RuntimeApp::render_frame`].
+    ///
+    /// Empty until the first frame has been rendered.
+    pub fn capture_frame(&self) -> Vec<u8> {
+        self.last_frame.pixels.clone()
+    }
+
+    /// Size of the buffer returned by [`RuntimeApp::capture_frame`].
+    pub fn capture_size(&self) -> (u32, u32) {
+        self.last_frame.size
+    }
+
+    /// Encodes the last rendered frame as PNG at `path`.
+    pub fn save_screenshot(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let (width, height) = self.last_frame.size;
+        if self.last_frame.pixels.is_empty() {
+            return Err("no frame has been rendered yet".to_string());
+        }
+        image::save_buffer_with_format(
+            path,
+            &self.last_frame.pixels,
+            width,
+            height,
+            image::ColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Last frame handed to the window, kept so captures never touch the live surface.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LastFrame {
+    pixels: Vec<u8>,
+    size: (u32, u32),
+}
+
+impl LastFrame {
+    fn store(&mut self, frame: &[u8], size: (u32, u32)) {
+        self.pixels.clear();
+        self.pixels.extend_from_slice(frame);
+        self.size = size;
+    }
+}
+
+/// Timestamped PNG path inside `dir`, e.g. `screenshots/screenshot-1700000000123.png`.
+pub fn screenshot_path(dir: impl AsRef<Path>, now: SystemTime) -> PathBuf {
+    let millis = now
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    dir.as_ref().join(format!("screenshot-{millis}.png"))
+}
@@ -0,0 +1,115 @@
+//! Polling watcher that reports edited asset files for cache invalidation.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Watches files under an assets root by modification time.
+///
+/// A change is reported once the file's mtime has stayed the same for the
+/// debounce window, so an editor saving in several writes yields one reload.
+/// Symlinked directories are not followed, keeping the scan inside the root.
+#[derive(Debug)]
+pub struct AssetWatcher {
+    root: PathBuf,
+    debounce: Duration,
+    known: HashMap<String, SystemTime>,
+    pending: HashMap<String, (SystemTime, Instant)>,
+    last_scan: Option<Instant>,
+}
+
+impl AssetWatcher {
+    pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+    /// Snapshots current modification times; existing files are not reported.
+    pub fn new(root: impl Into<PathBuf>, debounce: Duration) -> Self {
+        let root = root.into();
+        let known = scan_mtimes(&root);
+        Self {
+            root,
+            debounce,
+            known,
+            pending: HashMap::new(),
+            last_scan: None,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Rescans the root (at most once per debounce window) and returns the
+    /// asset keys, relative to the root with `/` separators, whose changes
+    /// have settled since the last call.
+    pub fn poll(&mut self, now: Instant) -> Vec<String> {
+        let scan_due = self
+            .last_scan
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.debounce);
+        if scan_due {
+            self.last_scan = Some(now);
+            for (key, mtime) in scan_mtimes(&self.root) {
+                if self.known.get(&key) == Some(&mtime) {
+                    continue;
+                }
+                match self.pending.get(&key) {
+                    Some((pending_mtime, _)) if *pending_mtime == mtime => {}
+                    // New or written again: restart the debounce window.
+                    _ => {
+                        self.pending.insert(key, (mtime, now));
+                    }
+                }
+            }
+        }
+
+        let mut settled: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, seen))| now.saturating_duration_since(*seen) >= self.debounce)
+            .map(|(key, _)| key.clone())
+            .collect();
+        settled.sort();
+        for key in &settled {
+            if let Some((mtime, _)) = self.pending.remove(key) {
+                self.known.insert(key.clone(), mtime);
+            }
+        }
+        settled
+    }
+}
+
+fn scan_mtimes(root: &Path) -> HashMap<String, SystemTime> {
+    let mut mtimes = HashMap::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) else {
+                continue;
+            };
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            let key = rel
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            mtimes.insert(key, modified);
+        }
+    }
+    mtimes
+}
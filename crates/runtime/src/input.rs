@@ -11,6 +11,10 @@ pub enum InputAction {
     Quit,
     Back,
     Menu,
+    /// Switches the window between borderless fullscreen and windowed mode.
+    ToggleFullscreen,
+    /// Saves the last presented frame as a PNG screenshot.
+    Screenshot,
 }
 
 /// Input trait that maps window events into engine actions.
@@ -37,6 +41,8 @@ impl Default for ConfigurableInput {
         map.insert(KeyCode::Space, InputAction::Advance);
         map.insert(KeyCode::Enter, InputAction::Advance);
         map.insert(KeyCode::Escape, InputAction::Quit);
+        map.insert(KeyCode::F11, InputAction::ToggleFullscreen);
+        map.insert(KeyCode::F2, InputAction::Screenshot);
 
         map.insert(KeyCode::Digit1, InputAction::Choose(0));
         map.insert(KeyCode::Digit2, InputAction::Choose(1));
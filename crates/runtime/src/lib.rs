@@ -2,30 +2,42 @@
 
 pub mod assets;
 pub mod audio;
+mod frame;
+mod hot_reload;
 pub mod input;
 mod loader;
 pub mod render;
+pub mod timing;
 
+pub use frame::screenshot_path;
+pub use hot_reload::AssetWatcher;
 pub use loader::{AsyncLoader, LoadRequest, LoadResult};
 
 use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
 // use pixels::{Pixels, SurfaceTexture}; // Removed unused imports
 // Logic moved to software.rs
 use visual_novel_engine::{
-    AudioCommand, Engine, EventCompiled, RenderOutput, TextRenderer, UiState, VisualState,
+    AudioCommand, Engine, EventCompiled, RenderOutput, TextRenderer, UiState, UiView,
+    VisualCommand, VisualState,
 };
 use winit::{
     dpi::LogicalSize,
     event::{Event, WindowEvent},
-    event_loop::EventLoop,
-    window::WindowBuilder,
+    event_loop::{ControlFlow, EventLoop},
+    window::{Fullscreen, Window, WindowBuilder},
 };
 
 pub use self::assets::{AssetStore, MemoryAssetStore};
 pub use self::audio::{Audio, RodioBackend, SilentAudio};
+use self::frame::LastFrame;
 pub use self::input::{ConfigurableInput, Input, InputAction};
-use self::render::{BuiltinSoftwareDrawer, RenderBackend, SoftwareBackend, WgpuBackend};
+use self::render::{
+    BuiltinSoftwareDrawer, RenderBackend, SoftwareBackend, Viewport, VirtualCanvas, WgpuBackend,
+};
+pub use self::timing::FixedTimestep;
+use self::timing::{TextReveal, TransitionTimer};
 
 // AssetStore and MemoryAssetStore moved to assets.rs
 
@@ -33,12 +45,20 @@ use self::render::{BuiltinSoftwareDrawer, RenderBackend, SoftwareBackend, WgpuBa
 pub struct RuntimeApp<I, A, S> {
     engine: Engine,
     visual: VisualState,
+    visual_commands: Vec<VisualCommand>,
     input: I,
     audio: A,
     assets: S,
     ui: UiState,
     last_bgm_path: Option<String>,
     prefetch_depth: usize,
+    tick_rate_hz: u32,
+    reveal: TextReveal,
+    transition: TransitionTimer,
+    base_title: String,
+    canvas: Option<VirtualCanvas>,
+    last_frame: LastFrame,
+    hot_reload: Option<AssetWatcher>,
 }
 
 impl<I, A, S> RuntimeApp<I, A, S>
@@ -48,6 +68,7 @@ where
     S: AssetStore,
 {
     const DEFAULT_PREFETCH_DEPTH: usize = 3;
+    const DEFAULT_TITLE: &'static str = "VN Runtime";
 
     pub fn new(
         engine: Engine,
@@ -57,17 +78,26 @@ where
     ) -> visual_novel_engine::VnResult<Self> {
         let event = engine.current_event()?;
         let visual = Self::derive_visual(&engine, &event);
-        let ui = UiState::from_event(&event, &visual);
+        let ui = Self::derive_ui(&engine, &event, &visual);
         let mut app = Self {
             engine,
             visual,
+            visual_commands: Vec::new(),
             input,
             audio,
             assets,
             ui,
             last_bgm_path: None,
             prefetch_depth: Self::DEFAULT_PREFETCH_DEPTH,
+            tick_rate_hz: FixedTimestep::DEFAULT_TICK_RATE_HZ,
+            reveal: TextReveal::new(0.0),
+            transition: TransitionTimer::default(),
+            base_title: Self::DEFAULT_TITLE.to_string(),
+            canvas: None,
+            last_frame: LastFrame::default(),
+            hot_reload: None,
         };
+        app.restart_timed_state(&event);
         let audio_commands = app.engine.take_audio_commands();
         app.apply_audio_commands(&audio_commands);
         app.prefetch_upcoming_assets();
@@ -108,6 +138,38 @@ where
         &self.ui
     }
 
+    /// Visual changes made by the last advance, for renderers that animate
+    /// them instead of snapping to the new [`VisualState`].
+    pub fn visual_commands(&self) -> &[VisualCommand] {
+        &self.visual_commands
+    }
+
+    pub fn base_title(&self) -> &str {
+        &self.base_title
+    }
+
+    /// Sets the prefix used by [`RuntimeApp::window_title`], usually the game name.
+    pub fn set_base_title(&mut self, title: impl Into<String>) {
+        self.base_title = title.into();
+    }
+
+    /// Window title for the current view, e.g. `"My Game — Alice"`.
+    pub fn window_title(&self) -> String {
+        let detail = match &self.ui.view {
+            UiView::Dialogue { speaker, .. } if !speaker.is_empty() => speaker.as_str(),
+            UiView::Dialogue { .. } | UiView::System { .. } => return self.base_title.clone(),
+            UiView::TextInput { prompt, .. } if !prompt.is_empty() => prompt.as_str(),
+            UiView::TextInput { .. } => "Input",
+            UiView::Choice { prompt, .. } if !prompt.is_empty() => prompt.as_str(),
+            UiView::Choice { .. } => "Choice",
+            UiView::Scene { .. } => match self.visual.background.as_deref() {
+                Some(background) => scene_title(background),
+                None => "Scene",
+            },
+        };
+        format!("{} — {}", self.base_title, detail)
+    }
+
     pub fn prefetch_depth(&self) -> usize {
         self.prefetch_depth
     }
@@ -121,6 +183,9 @@ where
         match action {
             InputAction::None => {}
             InputAction::Quit => return Ok(false),
+            InputAction::Advance if !self.reveal.is_complete() => {
+                self.reveal.complete();
+            }
             InputAction::Advance => {
                 let audio_commands = step_or_resume(&mut self.engine)?;
                 self.refresh_state()?;
@@ -137,14 +202,29 @@ where
             InputAction::Back | InputAction::Menu => {
                 // Action recognized but currently non-mutating in runtime mode.
             }
+            InputAction::ToggleFullscreen | InputAction::Screenshot => {
+                // Window-level concerns; handled by `run_winit`.
+            }
         }
         Ok(true)
     }
 
+    /// Answers the pending `input_text` prompt with text typed by the host
+    /// (e.g. from its own text field or IME) and moves on.
+    pub fn submit_text(&mut self, value: &str) -> visual_novel_engine::VnResult<()> {
+        self.engine.submit_text(value)?;
+        self.refresh_state()?;
+        self.apply_audio_for_current_scene();
+        self.prefetch_upcoming_assets();
+        Ok(())
+    }
+
     fn refresh_state(&mut self) -> visual_novel_engine::VnResult<()> {
         let event = self.engine.current_event()?;
+        self.visual_commands = self.engine.take_visual_commands();
         self.visual = Self::derive_visual(&self.engine, &event);
-        self.ui = UiState::from_event(&event, &self.visual);
+        self.ui = Self::derive_ui(&self.engine, &event, &self.visual);
+        self.restart_timed_state(&event);
         Ok(())
     }
 
@@ -168,6 +248,15 @@ where
         visual
     }
 
+    /// UI for `event` with speaker aliases and `{strvar:key}` placeholders applied.
+    fn derive_ui(engine: &Engine, event: &EventCompiled, visual: &VisualState) -> UiState {
+        let mut ui = UiState::from_event_with_aliases(event, visual, engine.character_aliases());
+        if let UiView::Dialogue { text, .. } = &mut ui.view {
+            *text = engine.interpolate(text);
+        }
+        ui
+    }
+
     fn apply_audio_commands(&mut self, commands: &[AudioCommand]) {
         for command in commands {
             match command {
@@ -218,6 +307,41 @@ where
     pub fn assets(&self) -> &S {
         &self.assets
     }
+
+    /// Watches `assets_root` so edited files are dropped from the asset cache
+    /// and reloaded on next use; see [`RuntimeApp::poll_hot_reload`].
+    pub fn with_hot_reload(mut self, assets_root: impl Into<std::path::PathBuf>) -> Self {
+        self.hot_reload = Some(AssetWatcher::new(
+            assets_root,
+            AssetWatcher::DEFAULT_DEBOUNCE,
+        ));
+        self
+    }
+
+    pub fn hot_reload_enabled(&self) -> bool {
+        self.hot_reload.is_some()
+    }
+
+    /// Invalidates assets, and the audio backend's copies of them, whose
+    /// files changed. Returns `true` when any did, meaning the next frame
+    /// should be redrawn.
+    pub fn poll_hot_reload(&mut self, now: Instant) -> bool {
+        let Some(watcher) = &mut self.hot_reload else {
+            return false;
+        };
+        let changed = watcher.poll(now);
+        for key in &changed {
+            self.assets.invalidate(key);
+            self.audio.invalidate(key);
+        }
+        !changed.is_empty()
+    }
+}
+
+/// Background file stem, so `"bg/room_night.png"` titles as `"room_night"`.
+fn scene_title(background: &str) -> &str {
+    let name = background.rsplit(['/', '\\']).next().unwrap_or(background);
+    name.rsplit_once('.').map_or(name, |(stem, _)| stem)
 }
 
 fn step_or_resume(engine: &mut Engine) -> visual_novel_engine::VnResult<Vec<AudioCommand>> {
@@ -230,6 +354,32 @@ fn step_or_resume(engine: &mut Engine) -> visual_novel_engine::VnResult<Vec<Audi
     }
 }
 
+fn toggle_fullscreen(window: &Window) {
+    if window.fullscreen().is_some() {
+        window.set_fullscreen(None);
+    } else {
+        window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+    }
+}
+
+const SCREENSHOT_DIR: &str = "screenshots";
+
+fn save_screenshot<I, A, S>(app: &RuntimeApp<I, A, S>)
+where
+    I: Input,
+    A: Audio,
+    S: AssetStore,
+{
+    let path = screenshot_path(SCREENSHOT_DIR, SystemTime::now());
+    let result = std::fs::create_dir_all(SCREENSHOT_DIR)
+        .map_err(|e| e.to_string())
+        .and_then(|_| app.save_screenshot(&path));
+    match result {
+        Ok(()) => eprintln!("Screenshot saved to {}", path.display()),
+        Err(e) => eprintln!("Screenshot failed: {}", e),
+    }
+}
+
 /// Run the runtime loop using winit and a rendering backend (hybrid: wgpu or software).
 pub fn run_winit<I, A, S>(mut app: RuntimeApp<I, A, S>) -> !
 where
@@ -241,14 +391,22 @@ where
     #[allow(deprecated)]
     let window = Arc::new(
         WindowBuilder::new()
-            .with_title("VN Runtime")
-            .with_inner_size(LogicalSize::new(960.0, 540.0))
+            .with_title(app.window_title())
+            .with_inner_size(LogicalSize::new(
+                Viewport::DEFAULT_DESIGN_SIZE.0,
+                Viewport::DEFAULT_DESIGN_SIZE.1,
+            ))
             .with_min_inner_size(LogicalSize::new(640.0, 360.0))
             .build(&event_loop)
             .expect("failed to build runtime window"),
     );
 
     let size = window.inner_size();
+    let mut viewport = Viewport::new(
+        Viewport::DEFAULT_DESIGN_SIZE,
+        (size.width, size.height),
+        window.scale_factor(),
+    );
 
     // Initialize Backend with Fallback
     let mut backend: Box<dyn RenderBackend> =
@@ -264,13 +422,16 @@ where
                 );
                 Box::new(SoftwareBackend::new(
                     window.clone(),
-                    size.width,
-                    size.height,
+                    viewport,
                     Box::new(BuiltinSoftwareDrawer),
                 ))
             }
         };
 
+    let mut current_title = app.window_title();
+    let mut timestep = FixedTimestep::new(app.tick_rate_hz());
+    let mut last_frame = Instant::now();
+
     event_loop
         .run(move |event, elwt| {
             match event {
@@ -279,18 +440,53 @@ where
                         elwt.exit();
                     }
                     WindowEvent::Resized(size) => {
+                        viewport.resize(size.width, size.height);
                         backend.resize(size.width, size.height);
+                        window.request_redraw();
+                    }
+                    WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                        // A `Resized` with the new physical size follows.
+                        viewport.set_scale_factor(scale_factor);
                     }
                     WindowEvent::RedrawRequested => {
-                        if let Err(e) = backend.render(app.ui()) {
+                        let drew = match backend.frame_mut() {
+                            Some((frame, size)) => {
+                                app.render_frame(frame, size);
+                                true
+                            }
+                            None => false,
+                        };
+                        let result = if drew {
+                            backend.present()
+                        } else {
+                            backend.render(&app.visible_ui())
+                        };
+                        if let Err(e) = result {
                             eprintln!("Render error: {}", e);
                             elwt.exit();
                         }
                     }
                     _ => {
                         let action = app.input.handle_window_event(&event);
+                        if action == InputAction::ToggleFullscreen {
+                            toggle_fullscreen(&window);
+                            return;
+                        }
+                        if action == InputAction::Screenshot {
+                            if backend.frame_mut().is_none() {
+                                let size = window.inner_size();
+                                app.capture_offscreen((size.width, size.height));
+                            }
+                            save_screenshot(&app);
+                            return;
+                        }
                         match app.handle_action(action) {
                             Ok(true) => {
+                                let title = app.window_title();
+                                if title != current_title {
+                                    window.set_title(&title);
+                                    current_title = title;
+                                }
                                 window.request_redraw();
                             }
                             Ok(false) => {
@@ -303,7 +499,29 @@ where
                     }
                 },
                 Event::AboutToWait => {
-                    // window.request_redraw();
+                    let now = Instant::now();
+                    let ticks = timestep.advance(now - last_frame);
+                    last_frame = now;
+                    let mut changed = false;
+                    for _ in 0..ticks {
+                        changed |= app.tick(timestep.step());
+                    }
+                    changed |= app.poll_hot_reload(now);
+                    if changed {
+                        window.request_redraw();
+                    }
+                    if app.has_pending_animation() {
+                        elwt.set_control_flow(ControlFlow::WaitUntil(now + timestep.step()));
+                    } else if app.hot_reload_enabled() {
+                        timestep.reset();
+                        elwt.set_control_flow(ControlFlow::WaitUntil(
+                            now + AssetWatcher::DEFAULT_DEBOUNCE,
+                        ));
+                    } else {
+                        // Nothing to animate: sleep until the next input event.
+                        timestep.reset();
+                        elwt.set_control_flow(ControlFlow::Wait);
+                    }
                 }
                 _ => {}
             }
@@ -7,4 +7,16 @@ pub trait RenderBackend {
 
     /// Renders the current UI state to the target.
     fn render(&mut self, ui: &UiState) -> Result<(), String>;
+
+    /// CPU-side RGBA frame and its size, for callers that rasterize themselves.
+    ///
+    /// Hardware backends return `None`; use [`RenderBackend::render`] instead.
+    fn frame_mut(&mut self) -> Option<(&mut [u8], (u32, u32))> {
+        None
+    }
+
+    /// Presents the frame previously filled through [`RenderBackend::frame_mut`].
+    fn present(&mut self) -> Result<(), String> {
+        Ok(())
+    }
 }
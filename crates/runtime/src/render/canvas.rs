@@ -0,0 +1,83 @@
+//! Fixed-resolution RGBA canvas with pixel-perfect integer upscaling.
+
+use super::viewport::LetterboxRect;
+
+/// Largest whole-number scale at which `design` still fits inside `surface`.
+///
+/// Never returns less than `1`; a surface smaller than the design clips instead.
+pub fn integer_scale(design: (u32, u32), surface: (u32, u32)) -> u32 {
+    let by_width = surface.0 / design.0.max(1);
+    let by_height = surface.1 / design.1.max(1);
+    by_width.min(by_height).max(1)
+}
+
+/// Centered rect covered by `design` upscaled by [`integer_scale`].
+///
+/// Offsets saturate at zero when the design is larger than the surface.
+pub fn integer_letterbox(design: (u32, u32), surface: (u32, u32)) -> LetterboxRect {
+    let scale = integer_scale(design, surface);
+    let width = design.0 * scale;
+    let height = design.1 * scale;
+    LetterboxRect {
+        x: surface.0.saturating_sub(width) / 2,
+        y: surface.1.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Offscreen RGBA8 buffer at a fixed design resolution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VirtualCanvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl VirtualCanvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    /// Nearest-neighbor upscales the canvas into `frame`, filling the bars
+    /// around it with `bar_color`.
+    pub fn blit_integer_scaled(&self, frame: &mut [u8], size: (u32, u32), bar_color: [u8; 4]) {
+        let (frame_w, frame_h) = size;
+        for chunk in frame.chunks_exact_mut(4) {
+            chunk.copy_from_slice(&bar_color);
+        }
+        let rect = integer_letterbox(self.size(), size);
+        let scale = integer_scale(self.size(), size);
+        let max_x = (rect.x + rect.width).min(frame_w);
+        let max_y = (rect.y + rect.height).min(frame_h);
+        for row in rect.y..max_y {
+            let src_row = ((row - rect.y) / scale) as usize;
+            for col in rect.x..max_x {
+                let src_col = ((col - rect.x) / scale) as usize;
+                let src = (src_row * self.width as usize + src_col) * 4;
+                let dst = (row as usize * frame_w as usize + col as usize) * 4;
+                if dst + 4 <= frame.len() {
+                    frame[dst..dst + 4].copy_from_slice(&self.pixels[src..src + 4]);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,11 @@
 pub mod backend;
+pub mod canvas;
 pub mod hardware;
 pub mod software;
+pub mod viewport;
 
 pub use backend::RenderBackend;
+pub use canvas::{integer_letterbox, integer_scale, VirtualCanvas};
 pub use hardware::WgpuBackend;
 pub use software::{BuiltinSoftwareDrawer, SoftwareBackend, SoftwareDrawStrategy};
+pub use viewport::{letterbox_rect, LetterboxRect, Viewport};
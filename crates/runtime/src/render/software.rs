@@ -4,28 +4,37 @@ use visual_novel_engine::{UiState, UiView};
 use winit::window::Window;
 
 use super::backend::RenderBackend;
+use super::viewport::{LetterboxRect, Viewport};
 
 /// Trait for the actual drawing logic acting on a framebuffer.
+///
+/// `frame` has the viewport's physical size; layout should be done in design
+/// coordinates and mapped through [`Viewport::design_to_physical`].
 pub trait SoftwareDrawStrategy {
-    fn draw(&self, frame: &mut [u8], size: (u32, u32), ui: &UiState);
+    fn draw(&self, frame: &mut [u8], viewport: &Viewport, ui: &UiState);
 }
 
 /// Backend that uses `pixels` (software rasterization) to display the frame.
 pub struct SoftwareBackend<'a> {
     pixels: Pixels<'a>,
     strategy: Box<dyn SoftwareDrawStrategy>,
+    viewport: Viewport,
 }
 
 impl<'a> SoftwareBackend<'a> {
     pub fn new(
         window: Arc<Window>,
-        width: u32,
-        height: u32,
+        viewport: Viewport,
         strategy: Box<dyn SoftwareDrawStrategy>,
     ) -> Self {
+        let (width, height) = viewport.physical_size();
         let surface = SurfaceTexture::new(width, height, window);
         let pixels = Pixels::new(width, height, surface).expect("failed to create pixel surface");
-        Self { pixels, strategy }
+        Self {
+            pixels,
+            strategy,
+            viewport,
+        }
     }
 }
 
@@ -33,15 +42,24 @@ impl<'a> RenderBackend for SoftwareBackend<'a> {
     fn resize(&mut self, width: u32, height: u32) {
         let _ = self.pixels.resize_surface(width, height);
         let _ = self.pixels.resize_buffer(width, height);
+        self.viewport.resize(width, height);
     }
 
     fn render(&mut self, ui: &UiState) -> Result<(), String> {
-        let extent = self.pixels.context().texture_extent;
         let frame = self.pixels.frame_mut();
-        self.strategy.draw(frame, (extent.width, extent.height), ui);
+        self.strategy.draw(frame, &self.viewport, ui);
 
         self.pixels.render().map_err(|e| e.to_string())
     }
+
+    fn frame_mut(&mut self) -> Option<(&mut [u8], (u32, u32))> {
+        let size = self.viewport.physical_size();
+        Some((self.pixels.frame_mut(), size))
+    }
+
+    fn present(&mut self) -> Result<(), String> {
+        self.pixels.render().map_err(|e| e.to_string())
+    }
 }
 
 /// Default implementation of software drawing.
@@ -49,23 +67,30 @@ impl<'a> RenderBackend for SoftwareBackend<'a> {
 pub struct BuiltinSoftwareDrawer;
 
 impl SoftwareDrawStrategy for BuiltinSoftwareDrawer {
-    fn draw(&self, frame: &mut [u8], size: (u32, u32), ui: &UiState) {
-        let (width, height) = size;
+    fn draw(&self, frame: &mut [u8], viewport: &Viewport, ui: &UiState) {
+        let (width, height) = viewport.design_size();
         let background = match &ui.view {
             UiView::Dialogue { .. } => [32, 32, 64, 255],
             UiView::Choice { .. } => [24, 48, 48, 255],
             UiView::Scene { .. } => [48, 24, 48, 255],
             UiView::System { .. } => [48, 48, 48, 255],
+            UiView::TextInput { .. } => [24, 32, 64, 255],
         };
-        clear(frame, background);
+        clear(frame, LETTERBOX_COLOR);
+        fill(
+            frame,
+            viewport.physical_size().0,
+            viewport.letterbox(),
+            background,
+        );
 
         let dialog_height = height / 3;
         let dialog_y = height.saturating_sub(dialog_height + 16);
         match &ui.view {
-            UiView::Dialogue { .. } | UiView::Choice { .. } => {
+            UiView::Dialogue { .. } | UiView::Choice { .. } | UiView::TextInput { .. } => {
                 draw_rect(
                     frame,
-                    (width, height),
+                    viewport,
                     RectSpec {
                         x: 16,
                         y: dialog_y,
@@ -78,7 +103,7 @@ impl SoftwareDrawStrategy for BuiltinSoftwareDrawer {
             UiView::Scene { .. } => {
                 draw_rect(
                     frame,
-                    (width, height),
+                    viewport,
                     RectSpec {
                         x: 16,
                         y: 16,
@@ -91,7 +116,7 @@ impl SoftwareDrawStrategy for BuiltinSoftwareDrawer {
             UiView::System { .. } => {
                 draw_rect(
                     frame,
-                    (width, height),
+                    viewport,
                     RectSpec {
                         x: 16,
                         y: 16,
@@ -109,7 +134,7 @@ impl SoftwareDrawStrategy for BuiltinSoftwareDrawer {
             for _ in options {
                 draw_rect(
                     frame,
-                    (width, height),
+                    viewport,
                     RectSpec {
                         x: 32,
                         y,
@@ -124,6 +149,8 @@ impl SoftwareDrawStrategy for BuiltinSoftwareDrawer {
     }
 }
 
+pub(crate) const LETTERBOX_COLOR: [u8; 4] = [0, 0, 0, 255];
+
 fn clear(frame: &mut [u8], color: [u8; 4]) {
     for chunk in frame.chunks_exact_mut(4) {
         chunk.copy_from_slice(&color);
@@ -138,15 +165,21 @@ struct RectSpec {
     color: [u8; 4],
 }
 
-fn draw_rect(frame: &mut [u8], size: (u32, u32), rect: RectSpec) {
-    let (width, height) = size;
-    let max_x = (rect.x + rect.width).min(width);
-    let max_y = (rect.y + rect.height).min(height);
-    for row in rect.y..max_y {
-        for col in rect.x..max_x {
-            let idx = ((row * width + col) * 4) as usize;
+/// Draws a rect given in design coordinates, clipped to the letterboxed area.
+fn draw_rect(frame: &mut [u8], viewport: &Viewport, rect: RectSpec) {
+    let (design_w, design_h) = viewport.design_size();
+    let width = rect.width.min(design_w.saturating_sub(rect.x));
+    let height = rect.height.min(design_h.saturating_sub(rect.y));
+    let target = viewport.design_to_physical(rect.x, rect.y, width, height);
+    fill(frame, viewport.physical_size().0, target, rect.color);
+}
+
+fn fill(frame: &mut [u8], stride: u32, rect: LetterboxRect, color: [u8; 4]) {
+    for row in rect.y..rect.y + rect.height {
+        for col in rect.x..rect.x + rect.width.min(stride.saturating_sub(rect.x)) {
+            let idx = ((row * stride + col) * 4) as usize;
             if idx + 4 <= frame.len() {
-                frame[idx..idx + 4].copy_from_slice(&rect.color);
+                frame[idx..idx + 4].copy_from_slice(&color);
             }
         }
     }
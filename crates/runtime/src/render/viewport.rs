@@ -0,0 +1,132 @@
+//! Mapping between the fixed design resolution and the window surface.
+
+/// Rectangle in physical surface pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LetterboxRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Largest rect with the aspect ratio of `design` that fits centered in `surface`.
+///
+/// The leftover space on either side (or above and below) is the letterbox.
+pub fn letterbox_rect(design: (u32, u32), surface: (u32, u32)) -> LetterboxRect {
+    let (design_w, design_h) = (design.0.max(1) as u64, design.1.max(1) as u64);
+    let (surface_w, surface_h) = (surface.0 as u64, surface.1 as u64);
+    let (width, height) = if surface_w * design_h <= surface_h * design_w {
+        (surface_w, surface_w * design_h / design_w)
+    } else {
+        (surface_h * design_w / design_h, surface_h)
+    };
+    LetterboxRect {
+        x: ((surface_w - width) / 2) as u32,
+        y: ((surface_h - height) / 2) as u32,
+        width: width as u32,
+        height: height as u32,
+    }
+}
+
+/// Window geometry: design resolution, physical surface size and DPI scale.
+///
+/// Layout and hit-testing work in design coordinates; only the final blit
+/// touches physical pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    design: (u32, u32),
+    physical: (u32, u32),
+    scale_factor: f64,
+}
+
+impl Viewport {
+    pub const DEFAULT_DESIGN_SIZE: (u32, u32) = (960, 540);
+
+    pub fn new(design: (u32, u32), physical: (u32, u32), scale_factor: f64) -> Self {
+        Self {
+            design: (design.0.max(1), design.1.max(1)),
+            physical,
+            scale_factor: if scale_factor > 0.0 {
+                scale_factor
+            } else {
+                1.0
+            },
+        }
+    }
+
+    pub fn design_size(&self) -> (u32, u32) {
+        self.design
+    }
+
+    pub fn physical_size(&self) -> (u32, u32) {
+        self.physical
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Window size in logical (DPI-independent) units.
+    pub fn logical_size(&self) -> (f64, f64) {
+        (
+            self.physical.0 as f64 / self.scale_factor,
+            self.physical.1 as f64 / self.scale_factor,
+        )
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.physical = (width, height);
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if scale_factor > 0.0 {
+            self.scale_factor = scale_factor;
+        }
+    }
+
+    pub fn letterbox(&self) -> LetterboxRect {
+        letterbox_rect(self.design, self.physical)
+    }
+
+    /// Maps a physical cursor position to design coordinates, or `None` when it
+    /// falls on the letterbox bars.
+    pub fn physical_to_design(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let rect = self.letterbox();
+        if rect.width == 0 || rect.height == 0 {
+            return None;
+        }
+        let local_x = x - rect.x as f64;
+        let local_y = y - rect.y as f64;
+        if local_x < 0.0
+            || local_y < 0.0
+            || local_x >= rect.width as f64
+            || local_y >= rect.height as f64
+        {
+            return None;
+        }
+        Some((
+            local_x * self.design.0 as f64 / rect.width as f64,
+            local_y * self.design.1 as f64 / rect.height as f64,
+        ))
+    }
+
+    /// Maps a rect in design coordinates onto the physical surface.
+    pub fn design_to_physical(&self, x: u32, y: u32, width: u32, height: u32) -> LetterboxRect {
+        let rect = self.letterbox();
+        let scale = |value: u32, physical: u32, design: u32| {
+            (value as u64 * physical as u64 / design as u64) as u32
+        };
+        LetterboxRect {
+            x: rect.x + scale(x, rect.width, self.design.0),
+            y: rect.y + scale(y, rect.height, self.design.1),
+            width: scale(width, rect.width, self.design.0),
+            height: scale(height, rect.height, self.design.1),
+        }
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DESIGN_SIZE, Self::DEFAULT_DESIGN_SIZE, 1.0)
+    }
+}
@@ -0,0 +1,257 @@
+//! Fixed-timestep clock and time-driven presentation state for the runtime loop.
+
+use std::time::Duration;
+
+use visual_novel_engine::{EventCompiled, UiState, UiView};
+
+use crate::{AssetStore, Audio, Input, RuntimeApp};
+
+/// Accumulates wall-clock time and hands it out in fixed-size ticks.
+#[derive(Clone, Debug)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub const DEFAULT_TICK_RATE_HZ: u32 = 60;
+    /// Upper bound on ticks returned by a single [`FixedTimestep::advance`] so a
+    /// long stall (debugger, window drag) cannot trigger a catch-up spiral.
+    pub const MAX_TICKS_PER_ADVANCE: u32 = 8;
+
+    pub fn new(tick_rate_hz: u32) -> Self {
+        Self {
+            step: Duration::from_secs(1) / tick_rate_hz.max(1),
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Adds `elapsed` wall time and returns how many fixed ticks are now due.
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        let mut ticks = 0;
+        while self.accumulator >= self.step && ticks < Self::MAX_TICKS_PER_ADVANCE {
+            self.accumulator -= self.step;
+            ticks += 1;
+        }
+        if ticks == Self::MAX_TICKS_PER_ADVANCE {
+            self.accumulator = Duration::ZERO;
+        }
+        ticks
+    }
+
+    /// Drops any partially accumulated time, e.g. after the loop was idle.
+    pub fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TICK_RATE_HZ)
+    }
+}
+
+/// Typewriter reveal progress for the current dialogue line.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TextReveal {
+    chars_per_second: f32,
+    total_chars: usize,
+    elapsed: Duration,
+}
+
+impl TextReveal {
+    /// Slowest non-zero typewriter speed. Slower values would push the reveal
+    /// time of a line past what `Duration` can represent.
+    pub(crate) const MIN_CHARS_PER_SECOND: f32 = 1.0;
+
+    pub(crate) fn new(chars_per_second: f32) -> Self {
+        Self {
+            chars_per_second: Self::sanitize_speed(chars_per_second),
+            ..Self::default()
+        }
+    }
+
+    fn sanitize_speed(chars_per_second: f32) -> f32 {
+        if chars_per_second > 0.0 {
+            chars_per_second.max(Self::MIN_CHARS_PER_SECOND)
+        } else {
+            0.0
+        }
+    }
+
+    pub(crate) fn chars_per_second(&self) -> f32 {
+        self.chars_per_second
+    }
+
+    /// Changes the speed without changing how much of the current line is
+    /// visible, so a fully shown line stays complete.
+    pub(crate) fn set_chars_per_second(&mut self, chars_per_second: f32) {
+        let visible = self.visible_chars();
+        self.chars_per_second = Self::sanitize_speed(chars_per_second);
+        if self.chars_per_second > 0.0 {
+            self.elapsed = Duration::from_secs_f32(visible as f32 / self.chars_per_second);
+        }
+    }
+
+    /// Starts revealing a new line of `total_chars` characters.
+    pub(crate) fn restart(&mut self, total_chars: usize) {
+        self.total_chars = total_chars;
+        self.elapsed = Duration::ZERO;
+    }
+
+    pub(crate) fn visible_chars(&self) -> usize {
+        if self.chars_per_second <= 0.0 {
+            return self.total_chars;
+        }
+        // The epsilon absorbs rounding when `elapsed` was derived from a char
+        // count (see `complete`), which would otherwise land one char short.
+        let revealed =
+            (self.elapsed.as_secs_f64() * f64::from(self.chars_per_second) + 1e-6) as usize;
+        revealed.min(self.total_chars)
+    }
+
+    pub(crate) fn is_complete(&self) -> bool {
+        self.visible_chars() >= self.total_chars
+    }
+
+    pub(crate) fn complete(&mut self) {
+        if self.chars_per_second > 0.0 {
+            self.elapsed = Duration::from_secs_f32(self.total_chars as f32 / self.chars_per_second);
+        }
+    }
+
+    /// Advances the reveal; returns `true` when more characters became visible.
+    pub(crate) fn tick(&mut self, dt: Duration) -> bool {
+        if self.is_complete() {
+            return false;
+        }
+        let before = self.visible_chars();
+        self.elapsed += dt;
+        self.visible_chars() != before
+    }
+}
+
+/// Remaining time of the scene transition currently on screen.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TransitionTimer {
+    total: Duration,
+    remaining: Duration,
+}
+
+impl TransitionTimer {
+    pub(crate) fn start(&mut self, duration: Duration) {
+        self.total = duration;
+        self.remaining = duration;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        !self.remaining.is_zero()
+    }
+
+    /// Fraction of the transition already played, in `0.0..=1.0`.
+    pub(crate) fn progress(&self) -> f32 {
+        if self.total.is_zero() {
+            return 1.0;
+        }
+        1.0 - self.remaining.as_secs_f32() / self.total.as_secs_f32()
+    }
+
+    /// Advances the timer; returns `true` when it was running.
+    pub(crate) fn tick(&mut self, dt: Duration) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+        self.remaining = self.remaining.saturating_sub(dt);
+        true
+    }
+}
+
+impl<I, A, S> RuntimeApp<I, A, S>
+where
+    I: Input,
+    A: Audio,
+    S: AssetStore,
+{
+    /// UI state as it should be drawn right now, with dialogue text cut to
+    /// the characters revealed so far.
+    pub fn visible_ui(&self) -> UiState {
+        let mut ui = self.ui.clone();
+        if let UiView::Dialogue { text, .. } = &mut ui.view {
+            let visible = self.reveal.visible_chars();
+            if let Some((cut, _)) = text.char_indices().nth(visible) {
+                text.truncate(cut);
+            }
+        }
+        ui
+    }
+
+    pub fn tick_rate_hz(&self) -> u32 {
+        self.tick_rate_hz
+    }
+
+    /// Sets how many fixed ticks per second [`crate::run_winit`] feeds into [`RuntimeApp::tick`].
+    pub fn set_tick_rate_hz(&mut self, hz: u32) {
+        self.tick_rate_hz = hz.max(1);
+    }
+
+    /// Typewriter speed for dialogue; `0.0` shows each line at once.
+    pub fn text_speed(&self) -> f32 {
+        self.reveal.chars_per_second()
+    }
+
+    /// Non-positive speeds disable the typewriter; positive speeds below one
+    /// character per second are raised to that minimum.
+    pub fn set_text_speed(&mut self, chars_per_second: f32) {
+        self.reveal.set_chars_per_second(chars_per_second);
+    }
+
+    /// Fraction of the current transition already played, `1.0` when none is running.
+    pub fn transition_progress(&self) -> f32 {
+        self.transition.progress()
+    }
+
+    /// Returns `true` while a reveal or transition still needs ticks to finish.
+    pub fn has_pending_animation(&self) -> bool {
+        !self.engine.is_paused() && (!self.reveal.is_complete() || self.transition.is_running())
+    }
+
+    /// Advances time-driven presentation state by `dt`.
+    ///
+    /// Returns `true` when something visible changed and a redraw is needed.
+    /// Nothing advances while the engine is paused.
+    pub fn tick(&mut self, dt: Duration) -> bool {
+        if self.engine.is_paused() {
+            return false;
+        }
+        let revealed = self.reveal.tick(dt);
+        let transitioned = self.transition.tick(dt);
+        revealed || transitioned
+    }
+
+    pub(crate) fn restart_timed_state(&mut self, event: &EventCompiled) {
+        match event {
+            EventCompiled::Dialogue(dialogue) => {
+                self.reveal.restart(dialogue.text.chars().count());
+                self.transition.clear();
+            }
+            EventCompiled::Transition(transition) => {
+                self.reveal.restart(0);
+                self.transition
+                    .start(Duration::from_millis(u64::from(transition.duration_ms)));
+            }
+            _ => {
+                self.reveal.restart(0);
+                self.transition.clear();
+            }
+        }
+    }
+}
@@ -88,12 +88,18 @@ fn audio_updates_when_choice_jumps_to_scene() {
             options: vec![ChoiceOptionRaw {
                 text: "Go".to_string(),
                 target: "scene".to_string(),
+                weight: None,
+                effects: Vec::new(),
             }],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Scene(SceneUpdateRaw {
             background: None,
             music: Some("music/theme.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
     ];
     let labels = BTreeMap::from([("start".to_string(), 0), ("scene".to_string(), 1)]);
@@ -124,18 +130,26 @@ fn audio_switches_music_for_scene_jump() {
             background: None,
             music: Some("music/old.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Choice(ChoiceRaw {
             prompt: "Pick".to_string(),
             options: vec![ChoiceOptionRaw {
                 text: "Go".to_string(),
                 target: "next_scene".to_string(),
+                weight: None,
+                effects: Vec::new(),
             }],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Scene(SceneUpdateRaw {
             background: None,
             music: Some("music/new.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
     ];
     let labels = BTreeMap::from([("start".to_string(), 0), ("next_scene".to_string(), 2)]);
@@ -174,18 +188,26 @@ fn audio_does_not_replay_same_music_when_choice_targets_same_track() {
             background: None,
             music: Some("music/theme.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Choice(ChoiceRaw {
             prompt: "Pick".to_string(),
             options: vec![ChoiceOptionRaw {
                 text: "Continue".to_string(),
                 target: "next_scene".to_string(),
+                weight: None,
+                effects: Vec::new(),
             }],
+            auto: false,
+            fallthrough: None,
         }),
         EventRaw::Scene(SceneUpdateRaw {
             background: None,
             music: Some("music/theme.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
     ];
     let labels = BTreeMap::from([("start".to_string(), 0), ("next_scene".to_string(), 2)]);
@@ -217,6 +239,8 @@ fn runtime_prefetch_tracks_upcoming_paths() {
             background: Some("bg/start.png".to_string()),
             music: Some("music/intro.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
         EventRaw::Dialogue(DialogueRaw {
             speaker: "Narrator".to_string(),
@@ -234,6 +258,8 @@ fn runtime_prefetch_tracks_upcoming_paths() {
             background: Some("bg/next.png".to_string()),
             music: Some("music/next.ogg".to_string()),
             characters: Vec::new(),
+            chapter: None,
+            tags: Vec::new(),
         }),
     ];
     let labels = BTreeMap::from([("start".to_string(), 0)]);
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use visual_novel_engine::{
+    DialogueRaw, Engine, EventRaw, ResourceLimiter, ScriptRaw, SecurityPolicy,
+};
+use vnengine_runtime::render::{integer_letterbox, integer_scale, LetterboxRect, VirtualCanvas};
+use vnengine_runtime::{screenshot_path, AssetStore, Audio, Input, InputAction, RuntimeApp};
+
+#[derive(Default)]
+struct NullInput;
+
+impl Input for NullInput {
+    fn handle_window_event(&mut self, _event: &winit::event::WindowEvent) -> InputAction {
+        InputAction::None
+    }
+}
+
+#[derive(Default)]
+struct NullAssets;
+
+impl AssetStore for NullAssets {
+    fn load_bytes(&self, _id: &str) -> Result<Vec<u8>, String> {
+        Err("NullAssets".to_string())
+    }
+}
+
+#[derive(Default)]
+struct SilentAudio;
+
+impl Audio for SilentAudio {
+    fn play_music(&mut self, _id: &str) {}
+    fn stop_music(&mut self) {}
+    fn play_sfx(&mut self, _id: &str) {}
+}
+
+#[test]
+fn integer_scale_picks_largest_fitting_factor() {
+    assert_eq!(integer_scale((320, 180), (320, 180)), 1);
+    assert_eq!(integer_scale((320, 180), (1280, 720)), 4);
+    assert_eq!(integer_scale((320, 180), (1920, 1080)), 6);
+    assert_eq!(integer_scale((320, 180), (1024, 768)), 3);
+    assert_eq!(integer_scale((320, 180), (2560, 720)), 4);
+    assert_eq!(integer_scale((320, 180), (200, 100)), 1);
+}
+
+#[test]
+fn integer_letterbox_centers_scaled_canvas() {
+    assert_eq!(
+        integer_letterbox((320, 180), (1024, 768)),
+        LetterboxRect {
+            x: 32,
+            y: 114,
+            width: 960,
+            height: 540
+        }
+    );
+    assert_eq!(
+        integer_letterbox((320, 180), (2560, 720)),
+        LetterboxRect {
+            x: 640,
+            y: 0,
+            width: 1280,
+            height: 720
+        }
+    );
+    let clipped = integer_letterbox((320, 180), (200, 100));
+    assert_eq!((clipped.x, clipped.y), (0, 0));
+}
+
+#[test]
+fn blit_repeats_each_source_pixel_without_filtering() {
+    let mut canvas = VirtualCanvas::new(2, 1);
+    canvas.pixels_mut()[..4].copy_from_slice(&[255, 0, 0, 255]);
+    canvas.pixels_mut()[4..].copy_from_slice(&[0, 0, 255, 255]);
+
+    let mut frame = vec![7u8; 5 * 4 * 4];
+    canvas.blit_integer_scaled(&mut frame, (5, 4), [0, 0, 0, 255]);
+
+    let pixel = |x: usize, y: usize| &frame[(y * 5 + x) * 4..(y * 5 + x) * 4 + 4];
+    assert_eq!(pixel(0, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel(4, 3), [0, 0, 0, 255]);
+    assert_eq!(pixel(4, 1), [0, 0, 0, 255]);
+    assert_eq!(pixel(0, 1), [255, 0, 0, 255]);
+    assert_eq!(pixel(1, 1), [255, 0, 0, 255]);
+    assert_eq!(pixel(2, 1), [0, 0, 255, 255]);
+    assert_eq!(pixel(3, 1), [0, 0, 255, 255]);
+}
+
+fn dialogue_app() -> RuntimeApp<NullInput, SilentAudio, NullAssets> {
+    let events = vec![EventRaw::Dialogue(DialogueRaw {
+        speaker: "Narrator".to_string(),
+        text: "Hola".to_string(),
+    })];
+    let labels = BTreeMap::from([("start".to_string(), 0)]);
+    let engine = Engine::new(
+        ScriptRaw::new(events, labels),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine");
+    RuntimeApp::new(engine, NullInput, SilentAudio, NullAssets).expect("runtime")
+}
+
+#[test]
+fn render_frame_uses_design_resolution_canvas() {
+    let mut app = dialogue_app().with_design_resolution(320, 180);
+    assert_eq!(app.design_resolution(), Some((320, 180)));
+
+    let size = (1024, 768);
+    let mut frame = vec![0u8; 1024 * 768 * 4];
+    app.render_frame(&mut frame, size);
+
+    let pixel = |x: usize, y: usize| &frame[(y * 1024 + x) * 4..(y * 1024 + x) * 4 + 4];
+    assert_eq!(pixel(0, 0), [0, 0, 0, 255]);
+    assert_ne!(pixel(512, 384), [0, 0, 0, 255]);
+    // Each design pixel covers a 3x3 block.
+    assert_eq!(pixel(32, 114), pixel(34, 116));
+}
+
+#[test]
+fn capture_frame_returns_last_rendered_buffer() {
+    let mut app = dialogue_app();
+    assert!(app.capture_frame().is_empty());
+    assert!(app.save_screenshot("unused.png").is_err());
+
+    let mut frame = vec![0u8; 64 * 36 * 4];
+    app.render_frame(&mut frame, (64, 36));
+    let captured = app.capture_frame();
+    assert_eq!(captured.len(), 64 * 36 * 4);
+    assert_eq!(captured, frame);
+    assert_eq!(app.capture_size(), (64, 36));
+
+    let dir = std::env::temp_dir().join("vnengine_runtime_capture_test");
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let path = screenshot_path(&dir, UNIX_EPOCH + Duration::from_millis(1234));
+    assert_eq!(path, dir.join("screenshot-1234.png"));
+    app.save_screenshot(&path).expect("save screenshot");
+    let decoded = image::open(&path).expect("decode png").to_rgba8();
+    assert_eq!(decoded.dimensions(), (64, 36));
+    assert_eq!(decoded.into_raw(), captured);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn capture_offscreen_fills_last_frame_without_a_cpu_backend() {
+    let mut app = dialogue_app();
+    app.capture_offscreen((64, 36));
+    assert_eq!(app.capture_size(), (64, 36));
+
+    let mut frame = vec![0u8; 64 * 36 * 4];
+    dialogue_app().render_frame(&mut frame, (64, 36));
+    assert_eq!(app.capture_frame(), frame);
+}
+
+#[test]
+fn thumbnail_has_requested_size_and_dialogue_layout() {
+    let app = dialogue_app();
+    let thumbnail = app.render_thumbnail(160, 90);
+    assert_eq!(thumbnail.len(), 160 * 90 * 4);
+
+    let first = &thumbnail[..4];
+    assert!(thumbnail.chunks_exact(4).any(|pixel| pixel != first));
+    // Rendering a thumbnail does not count as a presented frame.
+    assert!(app.capture_frame().is_empty());
+}
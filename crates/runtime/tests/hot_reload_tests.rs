@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use visual_novel_engine::{
+    AudioActionRaw, DialogueRaw, Engine, EventRaw, LruCache, ResourceLimiter, ScriptRaw,
+    SecurityPolicy,
+};
+use vnengine_assets::SecurityMode;
+use vnengine_runtime::{AssetStore, AssetWatcher, Audio, Input, InputAction, RuntimeApp};
+
+struct NullInput;
+
+impl Input for NullInput {
+    fn handle_window_event(&mut self, _event: &winit::event::WindowEvent) -> InputAction {
+        InputAction::None
+    }
+}
+
+/// Caches decoded bytes the way `RodioBackend` does and records what each
+/// SFX played.
+struct CachingAudio {
+    assets: Arc<vnengine_assets::AssetStore>,
+    cache: LruCache<String>,
+    played: Rc<RefCell<Vec<Vec<u8>>>>,
+}
+
+impl Audio for CachingAudio {
+    fn play_music(&mut self, _id: &str) {}
+
+    fn stop_music(&mut self) {}
+
+    fn play_sfx(&mut self, id: &str) {
+        let key = id.to_string();
+        let bytes = match self.cache.get(&key) {
+            Some(bytes) => bytes.clone(),
+            None => {
+                let bytes = AssetStore::load_bytes(&self.assets, id).expect("sfx bytes");
+                self.cache.insert(key, bytes.clone());
+                bytes
+            }
+        };
+        self.played.borrow_mut().push(bytes);
+    }
+
+    fn invalidate(&mut self, id: &str) {
+        self.cache.remove(&id.to_string());
+    }
+}
+
+fn rewrite_with_later_mtime(path: &Path, bytes: &[u8]) {
+    let mut file = File::create(path).expect("rewrite asset");
+    file.write_all(bytes).expect("write new bytes");
+    file.set_modified(SystemTime::now() + Duration::from_secs(5))
+        .expect("bump mtime");
+}
+
+#[test]
+fn modified_asset_is_invalidated_after_debounce() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_runtime_hot_reload_{unique}"));
+    fs::create_dir_all(root.join("bg")).expect("asset dir");
+    let file_path = root.join("bg/room.txt");
+    fs::write(&file_path, b"old").expect("write asset");
+
+    let store = vnengine_assets::AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+        .expect("asset store should initialize");
+    assert_eq!(
+        AssetStore::load_bytes(&store, "bg/room.txt").unwrap(),
+        b"old"
+    );
+
+    let debounce = Duration::from_millis(100);
+    let start = Instant::now();
+    let mut watcher = AssetWatcher::new(&root, debounce);
+    assert!(watcher.poll(start).is_empty());
+
+    rewrite_with_later_mtime(&file_path, b"new");
+
+    // The change is seen but held until the debounce window passes.
+    assert!(watcher.poll(start + debounce).is_empty());
+    let changed = watcher.poll(start + debounce * 2);
+    assert_eq!(changed, vec!["bg/room.txt".to_string()]);
+    assert!(watcher.poll(start + debounce * 3).is_empty());
+
+    // The cache still serves the stale copy until invalidated.
+    assert_eq!(
+        AssetStore::load_bytes(&store, "bg/room.txt").unwrap(),
+        b"old"
+    );
+    for key in &changed {
+        AssetStore::invalidate(&store, key);
+    }
+    store.reset_cache_stats();
+    assert_eq!(
+        AssetStore::load_bytes(&store, "bg/room.txt").unwrap(),
+        b"new"
+    );
+    assert_eq!(store.cache_stats().misses, 1);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn poll_hot_reload_invalidates_cached_audio() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("vn_runtime_hot_reload_audio_{unique}"));
+    fs::create_dir_all(root.join("sfx")).expect("asset dir");
+    let file_path = root.join("sfx/bell.ogg");
+    fs::write(&file_path, b"old").expect("write asset");
+
+    let assets = Arc::new(
+        vnengine_assets::AssetStore::new(root.clone(), SecurityMode::Trusted, None, false)
+            .expect("asset store should initialize"),
+    );
+    let played = Rc::new(RefCell::new(Vec::new()));
+    let audio = CachingAudio {
+        assets: Arc::clone(&assets),
+        cache: LruCache::new(1024),
+        played: Rc::clone(&played),
+    };
+    let bell = || {
+        EventRaw::AudioAction(AudioActionRaw {
+            channel: "sfx".to_string(),
+            action: "play".to_string(),
+            asset: Some("sfx/bell.ogg".to_string()),
+            volume: None,
+            fade_duration_ms: None,
+            loop_playback: None,
+        })
+    };
+    let engine = Engine::new(
+        ScriptRaw::new(
+            vec![
+                bell(),
+                EventRaw::Dialogue(DialogueRaw {
+                    speaker: "Narrator".to_string(),
+                    text: "Ring".to_string(),
+                }),
+                bell(),
+                EventRaw::Dialogue(DialogueRaw {
+                    speaker: "Narrator".to_string(),
+                    text: "Again".to_string(),
+                }),
+            ],
+            BTreeMap::from([("start".to_string(), 0)]),
+        ),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine");
+    let mut app = RuntimeApp::new(engine, NullInput, audio, assets)
+        .expect("runtime")
+        .with_hot_reload(&root);
+    app.handle_action(InputAction::Advance).expect("first bell");
+
+    rewrite_with_later_mtime(&file_path, b"new");
+    let start = Instant::now();
+    assert!(!app.poll_hot_reload(start));
+    assert!(app.poll_hot_reload(start + AssetWatcher::DEFAULT_DEBOUNCE));
+
+    app.handle_action(InputAction::Advance).expect("dialogue");
+    app.handle_action(InputAction::Advance)
+        .expect("second bell");
+    assert_eq!(*played.borrow(), vec![b"old".to_vec(), b"new".to_vec()]);
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use visual_novel_engine::{
+    DialogueRaw, Engine, EventRaw, ResourceLimiter, SceneTransitionRaw, ScriptRaw, SecurityPolicy,
+    UiView,
+};
+use vnengine_runtime::{AssetStore, Audio, FixedTimestep, Input, InputAction, RuntimeApp};
+
+#[derive(Default)]
+struct NullInput;
+
+impl Input for NullInput {
+    fn handle_window_event(&mut self, _event: &winit::event::WindowEvent) -> InputAction {
+        InputAction::None
+    }
+}
+
+#[derive(Default)]
+struct NullAssets;
+
+impl AssetStore for NullAssets {
+    fn load_bytes(&self, _id: &str) -> Result<Vec<u8>, String> {
+        Err("NullAssets".to_string())
+    }
+}
+
+#[derive(Default)]
+struct SilentAudio;
+
+impl Audio for SilentAudio {
+    fn play_music(&mut self, _id: &str) {}
+    fn stop_music(&mut self) {}
+    fn play_sfx(&mut self, _id: &str) {}
+}
+
+fn dialogue(text: &str) -> EventRaw {
+    EventRaw::Dialogue(DialogueRaw {
+        speaker: "Narrator".to_string(),
+        text: text.to_string(),
+    })
+}
+
+fn app_for(events: Vec<EventRaw>) -> RuntimeApp<NullInput, SilentAudio, NullAssets> {
+    let labels = BTreeMap::from([("start".to_string(), 0)]);
+    let engine = Engine::new(
+        ScriptRaw::new(events, labels),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine");
+    RuntimeApp::new(engine, NullInput, SilentAudio, NullAssets).expect("runtime")
+}
+
+fn visible_text(app: &RuntimeApp<NullInput, SilentAudio, NullAssets>) -> String {
+    match app.visible_ui().view {
+        UiView::Dialogue { text, .. } => text,
+        other => panic!("expected dialogue, got {other:?}"),
+    }
+}
+
+#[test]
+fn fixed_timestep_emits_whole_ticks_and_caps_catch_up() {
+    let mut timestep = FixedTimestep::new(50);
+    assert_eq!(timestep.step(), Duration::from_millis(20));
+    assert_eq!(timestep.advance(Duration::from_millis(15)), 0);
+    assert_eq!(timestep.advance(Duration::from_millis(30)), 2);
+    assert_eq!(
+        timestep.advance(Duration::from_secs(10)),
+        FixedTimestep::MAX_TICKS_PER_ADVANCE
+    );
+    assert_eq!(timestep.advance(Duration::from_millis(19)), 0);
+}
+
+#[test]
+fn tick_reveals_dialogue_and_reports_changes() {
+    let mut app = app_for(vec![dialogue("Hola"), dialogue("Fin")]);
+    assert_eq!(visible_text(&app), "Hola");
+    assert!(!app.has_pending_animation());
+
+    app.set_text_speed(10.0);
+    app.handle_action(InputAction::Advance).expect("advance");
+    assert_eq!(visible_text(&app), "");
+    assert!(app.has_pending_animation());
+
+    assert!(!app.tick(Duration::from_millis(50)));
+    assert!(app.tick(Duration::from_millis(100)));
+    assert_eq!(visible_text(&app), "F");
+    assert!(app.tick(Duration::from_millis(300)));
+    assert_eq!(visible_text(&app), "Fin");
+    assert!(!app.has_pending_animation());
+    assert!(!app.tick(Duration::from_millis(100)));
+}
+
+#[test]
+fn advance_completes_reveal_before_stepping() {
+    let mut app = app_for(vec![dialogue("Hola"), dialogue("Adios"), dialogue("Fin")]);
+    app.set_text_speed(10.0);
+    app.handle_action(InputAction::Advance).expect("advance");
+    assert_eq!(visible_text(&app), "");
+
+    app.handle_action(InputAction::Advance)
+        .expect("complete reveal");
+    assert_eq!(visible_text(&app), "Adios");
+    assert_eq!(app.engine().state().position, 1);
+
+    app.handle_action(InputAction::Advance).expect("advance");
+    assert_eq!(app.engine().state().position, 2);
+}
+
+#[test]
+fn tiny_text_speed_is_clamped_to_a_finite_reveal() {
+    let mut app = app_for(vec![dialogue("Hola"), dialogue("Adios"), dialogue("Fin")]);
+    app.set_text_speed(1e-30);
+    assert_eq!(app.text_speed(), 1.0);
+    assert_eq!(visible_text(&app), "Hola");
+
+    app.handle_action(InputAction::Advance).expect("advance");
+    assert_eq!(visible_text(&app), "");
+    assert!(app.tick(Duration::from_secs(1)));
+    assert_eq!(visible_text(&app), "A");
+
+    app.handle_action(InputAction::Advance)
+        .expect("complete reveal");
+    assert_eq!(visible_text(&app), "Adios");
+    assert_eq!(app.engine().state().position, 1);
+
+    app.set_text_speed(-5.0);
+    assert_eq!(app.text_speed(), 0.0);
+}
+
+#[test]
+fn tick_runs_transition_timer_then_goes_idle() {
+    let mut app = app_for(vec![
+        dialogue("Antes"),
+        EventRaw::Transition(SceneTransitionRaw {
+            kind: "fade_black".to_string(),
+            duration_ms: 200,
+            color: None,
+        }),
+        dialogue("Despues"),
+    ]);
+    app.handle_action(InputAction::Advance).expect("advance");
+    assert!(app.has_pending_animation());
+    assert_eq!(app.transition_progress(), 0.0);
+
+    assert!(app.tick(Duration::from_millis(100)));
+    assert!((app.transition_progress() - 0.5).abs() < 1e-6);
+    assert!(app.tick(Duration::from_millis(150)));
+    assert_eq!(app.transition_progress(), 1.0);
+    assert!(!app.has_pending_animation());
+    assert!(!app.tick(Duration::from_millis(16)));
+}
@@ -0,0 +1,74 @@
+use vnengine_runtime::render::{letterbox_rect, LetterboxRect, Viewport};
+
+#[test]
+fn letterbox_matches_surface_with_same_aspect() {
+    assert_eq!(
+        letterbox_rect((960, 540), (1920, 1080)),
+        LetterboxRect {
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080
+        }
+    );
+}
+
+#[test]
+fn letterbox_adds_bars_above_and_below_on_tall_surfaces() {
+    assert_eq!(
+        letterbox_rect((960, 540), (1024, 768)),
+        LetterboxRect {
+            x: 0,
+            y: 96,
+            width: 1024,
+            height: 576
+        }
+    );
+}
+
+#[test]
+fn letterbox_adds_side_bars_on_wide_surfaces() {
+    assert_eq!(
+        letterbox_rect((960, 540), (2560, 1080)),
+        LetterboxRect {
+            x: 320,
+            y: 0,
+            width: 1920,
+            height: 1080
+        }
+    );
+}
+
+#[test]
+fn letterbox_handles_empty_surface() {
+    let rect = letterbox_rect((960, 540), (0, 0));
+    assert_eq!((rect.width, rect.height), (0, 0));
+}
+
+#[test]
+fn viewport_maps_cursor_to_design_coordinates() {
+    let viewport = Viewport::new((960, 540), (2560, 1080), 2.0);
+    assert_eq!(viewport.logical_size(), (1280.0, 540.0));
+    assert_eq!(viewport.physical_to_design(320.0, 0.0), Some((0.0, 0.0)));
+    assert_eq!(
+        viewport.physical_to_design(1280.0, 540.0),
+        Some((480.0, 270.0))
+    );
+    assert_eq!(viewport.physical_to_design(100.0, 500.0), None);
+    assert_eq!(viewport.physical_to_design(2300.0, 500.0), None);
+}
+
+#[test]
+fn viewport_scales_design_rects_into_letterbox() {
+    let mut viewport = Viewport::default();
+    viewport.resize(1024, 768);
+    assert_eq!(
+        viewport.design_to_physical(480, 270, 480, 270),
+        LetterboxRect {
+            x: 512,
+            y: 96 + 288,
+            width: 512,
+            height: 288
+        }
+    );
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use visual_novel_engine::{
+    ChoiceOptionRaw, ChoiceRaw, DialogueRaw, Engine, EventRaw, ResourceLimiter, SceneUpdateRaw,
+    ScriptRaw, SecurityPolicy, UiView,
+};
+use vnengine_runtime::{AssetStore, Audio, Input, InputAction, RuntimeApp};
+
+#[derive(Default)]
+struct NullInput;
+
+impl Input for NullInput {
+    fn handle_window_event(&mut self, _event: &winit::event::WindowEvent) -> InputAction {
+        InputAction::None
+    }
+}
+
+#[derive(Default)]
+struct NullAssets;
+
+impl AssetStore for NullAssets {
+    fn load_bytes(&self, _id: &str) -> Result<Vec<u8>, String> {
+        Err("NullAssets".to_string())
+    }
+}
+
+#[derive(Default)]
+struct SilentAudio;
+
+impl Audio for SilentAudio {
+    fn play_music(&mut self, _id: &str) {}
+    fn stop_music(&mut self) {}
+    fn play_sfx(&mut self, _id: &str) {}
+}
+
+fn title_for(event: EventRaw) -> (UiView, String) {
+    let events = vec![
+        event,
+        EventRaw::Jump {
+            target: "start".to_string(),
+        },
+    ];
+    let labels = BTreeMap::from([("start".to_string(), 0)]);
+    let engine = Engine::new(
+        ScriptRaw::new(events, labels),
+        SecurityPolicy::default(),
+        ResourceLimiter::default(),
+    )
+    .expect("engine");
+    let mut app = RuntimeApp::new(engine, NullInput, SilentAudio, NullAssets).expect("runtime");
+    assert_eq!(app.window_title().split(" — ").next(), Some("VN Runtime"));
+    app.set_base_title("Mi Novela");
+    (app.ui().view.clone(), app.window_title())
+}
+
+#[test]
+fn dialogue_title_shows_speaker() {
+    let (view, title) = title_for(EventRaw::Dialogue(DialogueRaw {
+        speaker: "Alicia".to_string(),
+        text: "Hola".to_string(),
+    }));
+    assert!(matches!(view, UiView::Dialogue { .. }));
+    assert_eq!(title, "Mi Novela — Alicia");
+}
+
+#[test]
+fn choice_title_shows_prompt() {
+    let (view, title) = title_for(EventRaw::Choice(ChoiceRaw {
+        prompt: "¿Seguir?".to_string(),
+        options: vec![ChoiceOptionRaw {
+            text: "Si".to_string(),
+            target: "start".to_string(),
+            weight: None,
+            effects: Vec::new(),
+        }],
+        auto: false,
+        fallthrough: None,
+    }));
+    assert!(matches!(view, UiView::Choice { .. }));
+    assert_eq!(title, "Mi Novela — ¿Seguir?");
+}
+
+#[test]
+fn scene_title_shows_background_stem() {
+    let (view, title) = title_for(EventRaw::Scene(SceneUpdateRaw {
+        background: Some("bg/room_night.png".to_string()),
+        music: None,
+        characters: Vec::new(),
+        chapter: None,
+        tags: Vec::new(),
+    }));
+    assert!(matches!(view, UiView::Scene { .. }));
+    assert_eq!(title, "Mi Novela — room_night");
+}
+
+#[test]
+fn system_title_is_base_title() {
+    let (view, title) = title_for(EventRaw::SetFlag {
+        key: "seen".to_string(),
+        value: true,
+    });
+    assert!(matches!(view, UiView::System { .. }));
+    assert_eq!(title, "Mi Novela");
+}
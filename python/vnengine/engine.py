@@ -140,6 +140,11 @@ class Engine:
             return method()
         return []
 
+    def validate(self) -> Any:
+        """Run the editor linter and dry run over the loaded script."""
+
+        return call_native_method(self._engine, "validate", "validation bindings")
+
     def last_audio_commands(self) -> Any:
         """Return the audio commands emitted by the last `step()` call."""
 
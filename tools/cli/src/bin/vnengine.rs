@@ -30,6 +30,11 @@ enum Command {
         #[arg(short, long)]
         output: PathBuf,
     },
+    /// Write the JSON Schema for script files.
+    Schema {
+        #[arg(short, long, default_value = "script.schema.json")]
+        output: PathBuf,
+    },
     /// Produce an execution trace for a script JSON file.
     Trace {
         script: PathBuf,
@@ -209,6 +214,7 @@ fn main() -> Result<()> {
     match cli.command {
         Command::Validate { script } => validate_script(&script),
         Command::Compile { script, output } => compile_script(&script, &output),
+        Command::Schema { output } => write_script_schema(&output),
         Command::Trace {
             script,
             steps,
@@ -291,6 +297,15 @@ fn compile_script(path: &Path, output: &Path) -> Result<()> {
     Ok(())
 }
 
+fn write_script_schema(output: &Path) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&ScriptRaw::json_schema()?)?;
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, schema).with_context(|| format!("write {}", output.display()))?;
+    Ok(())
+}
+
 fn trace_script(path: &Path, steps: usize, output: &Path) -> Result<()> {
     let raw = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
     let script = ScriptRaw::from_json(&raw).context("parse script")?;
@@ -312,12 +327,15 @@ fn trace_script(path: &Path, steps: usize, output: &Path) -> Result<()> {
         );
         trace.push(step as u32, view, state);
         match &event {
-            visual_novel_engine::EventCompiled::Choice(_) => {
+            visual_novel_engine::EventCompiled::Choice(choice) if !choice.options.is_empty() => {
                 let _ = engine.choose(0);
             }
             visual_novel_engine::EventCompiled::ExtCall { .. } => {
                 let _ = engine.resume();
             }
+            visual_novel_engine::EventCompiled::InputText { .. } => {
+                let _ = engine.submit_text("");
+            }
             _ => {
                 let _ = engine.step();
             }